@@ -60,8 +60,8 @@
 //! don't define any explicitly.
 
 use crate::common::output;
-use crate::common::report_error;
 use crate::common::report_failure;
+use crate::common::report_usage_error;
 use crate::common::syntax::parse_arguments;
 use crate::common::syntax::Mode;
 use crate::common::to_single_message;
@@ -94,7 +94,7 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> Result {
             result
         }
 
-        Err(e) => report_error(env, &e).await,
+        Err(e) => report_usage_error(env, &e).await,
     }
 }
 
@@ -80,9 +80,10 @@
 //! [owned]: yash_env::job::Job::is_owned
 //! [expected state]: yash_env::job::Job::expected_state
 
-use crate::common::report_error;
 use crate::common::report_failure;
+use crate::common::report_output_error;
 use crate::common::report_simple_failure;
+use crate::common::report_usage_error;
 use crate::common::syntax::parse_arguments;
 use crate::common::syntax::Mode;
 use crate::common::to_single_message;
@@ -114,6 +115,9 @@ pub(crate) enum ResumeError {
     Unowned,
     #[error("target job is not job-controlled")]
     Unmonitored,
+    /// Error printing the job name to the standard output
+    #[error("error printing results to stdout: {0}")]
+    Output(Errno),
     #[error("system error: {0}")]
     SystemError(#[from] Errno),
 }
@@ -166,7 +170,8 @@ async fn resume_job_by_index(env: &mut Env, index: usize) -> Result<(), ResumeEr
     }
 
     let line = format!("[{}] {}\n", index + 1, job.name);
-    env.system.write_all(Fd::STDOUT, line.as_bytes()).await?;
+    let result = env.system.write_all(Fd::STDOUT, line.as_bytes()).await;
+    result.map_err(ResumeError::Output)?;
     drop(line);
 
     if job.state.is_alive() {
@@ -203,7 +208,7 @@ async fn resume_job_by_id(env: &mut Env, job_id: &str) -> Result<(), OperandErro
 pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
     let (options, operands) = match parse_arguments(&[], Mode::with_env(env), args) {
         Ok(result) => result,
-        Err(error) => return report_error(env, &error).await,
+        Err(error) => return report_usage_error(env, &error).await,
     };
     debug_assert_eq!(options, []);
 
@@ -211,6 +216,7 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
         if let Some(index) = env.jobs.current_job() {
             match resume_job_by_index(env, index).await {
                 Ok(()) => crate::Result::default(),
+                Err(ResumeError::Output(errno)) => report_output_error(env, errno).await,
                 Err(error) => report_simple_failure(env, &error.to_string()).await,
             }
         } else {
@@ -218,16 +224,24 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
         }
     } else {
         let mut errors = Vec::new();
+        let mut output_error = None;
         for operand in operands {
             match resume_job_by_id(env, &operand.value).await {
                 Ok(()) => {}
+                Err(OperandErrorKind::CannotResume(ResumeError::Output(errno))) => {
+                    output_error = Some(errno)
+                }
                 Err(error) => errors.push(OperandError(operand, error)),
             }
         }
-        match to_single_message(&{ errors }) {
-            None => crate::Result::default(),
-            Some(message) => report_failure(env, message).await,
+        let mut result = crate::Result::default();
+        if let Some(errno) = output_error {
+            result = report_output_error(env, errno).await;
         }
+        if let Some(message) = to_single_message(&{ errors }) {
+            result = report_failure(env, message).await;
+        }
+        result
     }
 }
 
@@ -472,6 +486,25 @@ mod tests {
         });
     }
 
+    #[test]
+    fn main_fails_on_output_error() {
+        let mut system = VirtualSystem::new();
+        system.current_process_mut().close_fd(Fd::STDOUT);
+        let mut env = Env::with_system(Box::new(system.clone()));
+        let mut job = Job::new(Pid(123));
+        job.job_controlled = true;
+        job.state = ProcessState::exited(ExitStatus::SUCCESS);
+        let index = env.jobs.add(job);
+        env.jobs.set_current_job(index).unwrap();
+
+        let result = main(&mut env, vec![]).now_or_never().unwrap();
+        assert_eq!(result, crate::Result::from(ExitStatus::FAILURE));
+
+        assert_stderr(&system.state, |stderr| {
+            assert!(stderr.contains("error printing results"), "{stderr:?}");
+        });
+    }
+
     #[test]
     fn main_with_operands_resumes_specified_jobs() {
         let system = VirtualSystem::new();
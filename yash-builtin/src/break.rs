@@ -74,8 +74,8 @@
 //! Part of the break built-in implementation is shared with the
 //! continue built-in implementation.
 
-use crate::common::report_error;
 use crate::common::report_simple_failure;
+use crate::common::report_usage_error;
 use yash_env::builtin::Result;
 use yash_env::semantics::Field;
 use yash_env::Env;
@@ -93,6 +93,6 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> Result {
             Ok(result) => result,
             Err(e) => report_simple_failure(env, &format!("cannot break: {e}")).await,
         },
-        Err(e) => report_error(env, &e).await,
+        Err(e) => report_usage_error(env, &e).await,
     }
 }
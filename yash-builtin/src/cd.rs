@@ -115,11 +115,12 @@
 //! In these cases, the working directory remains changed, the `$PWD` variable
 //! is left empty, and the exit status depends on the `-e` option.
 //!
-//! The built-in may also fail in the following cases, but the working directory
-//! will remain changed and the exit status will be zero:
+//! If the new working directory cannot be written to the standard output, the
+//! working directory remains changed and the exit status is one. No error
+//! message is printed if the standard output is a broken pipe.
 //!
-//! - The new working directory cannot be written to the standard output.
-//! - `$PWD` or `$OLDPWD` is read-only.
+//! If `$PWD` or `$OLDPWD` is read-only, the built-in prints an error message,
+//! but the working directory remains changed and the exit status is zero.
 //!
 //! # Exit Status
 //!
@@ -131,6 +132,7 @@
 //!     - If the new working directory pathname cannot be determined:
 //!       - If the `-e` option is effective, the exit status is one.
 //!       - Otherwise, the exit status is zero.
+//!   - If the new working directory cannot be printed, the exit status is one.
 //! - If the working directory cannot be changed because of an error in the
 //!   underlying `chdir` system call, the exit status is two.
 //! - If the `-L` option is effective and canonicalization fails because of a
@@ -311,12 +313,14 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> Result {
         ),
     };
 
-    print::print_path(env, &new_pwd, &origin).await;
+    let print_result = print::print_path(env, &new_pwd, &origin).await;
 
     assign::set_oldpwd(env, pwd).await;
     assign::set_pwd(env, new_pwd).await;
 
-    result
+    // Both failures leave the working directory changed, so the combined
+    // result reflects either of them.
+    result.max(print_result)
 }
 
 #[cfg(test)]
@@ -17,13 +17,10 @@
 //! Part of the cd built-in that prints the new working directory
 
 use super::target::Origin;
-use crate::common::arrange_message_and_divert;
+use crate::common::output;
+use crate::Result;
 use yash_env::path::Path;
-use yash_env::system::Errno;
 use yash_env::Env;
-use yash_syntax::source::pretty::AnnotationType;
-use yash_syntax::source::pretty::Message;
-use yash_syntax::syntax::Fd;
 
 impl Origin {
     /// Whether the built-in should print the target directory path.
@@ -37,28 +34,14 @@ impl Origin {
 }
 
 /// Prints the new working directory path if needed.
-pub async fn print_path(env: &mut Env, path: &Path, origin: &Origin) {
+///
+/// If printing fails, the error is reported with
+/// [`report_output_error`](crate::common::report_output_error).
+pub async fn print_path(env: &mut Env, path: &Path, origin: &Origin) -> Result {
     if !origin.should_print_path() {
-        return;
+        return Result::default();
     }
 
     let line = format!("{}\n", path.display());
-    match env.system.write_all(Fd::STDOUT, line.as_bytes()).await {
-        Ok(_) => (),
-        Err(errno) => handle_print_error(env, errno).await,
-    }
-}
-
-/// Prints a warning message for a failed print.
-///
-/// The message is only a warning because it does not affect the exit status.
-async fn handle_print_error(env: &mut Env, errno: Errno) {
-    let message = Message {
-        r#type: AnnotationType::Warning,
-        title: format!("cannot print new $PWD: {}", errno).into(),
-        annotations: vec![],
-        footers: vec![],
-    };
-    let (message, _divert) = arrange_message_and_divert(env, message);
-    env.system.print_error(&message).await;
+    output(env, &line).await
 }
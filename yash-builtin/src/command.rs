@@ -113,7 +113,7 @@
 //!
 //! [`type`]: crate::type
 
-use crate::common::report_error;
+use crate::common::report_usage_error;
 use enumset::EnumSet;
 use enumset::EnumSetType;
 use yash_env::semantics::Field;
@@ -255,6 +255,6 @@ pub mod syntax;
 pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
     match syntax::parse(env, args) {
         Ok(command) => command.execute(env).await,
-        Err(error) => report_error(env, &error).await,
+        Err(error) => report_usage_error(env, &error).await,
     }
 }
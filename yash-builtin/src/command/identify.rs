@@ -30,6 +30,7 @@ use yash_env::semantics::ExitStatus;
 use yash_env::semantics::Field;
 use yash_env::str::UnixStr;
 use yash_env::Env;
+use yash_env::Shadowed;
 use yash_env::System;
 use yash_quote::quoted;
 use yash_semantics::command_search::search;
@@ -270,6 +271,10 @@ impl Identify {
     /// This function returns a string that should be printed to the standard
     /// output, as well as a list of errors that should be printed to the
     /// standard error.
+    ///
+    /// In the verbose mode, the description of an alias is followed by that of
+    /// the function or built-in hidden by the alias, if any. See
+    /// [`Env::shadowed_by_alias`].
     pub fn result(&self, env: &mut Env) -> (String, Vec<NotFound>) {
         let params = &self.search;
         let env = &mut SearchEnv { env, params };
@@ -278,7 +283,20 @@ impl Identify {
         for name in &self.names {
             match categorize(name, env) {
                 Ok(categorization) => {
-                    describe(&categorization, name, self.verbose, &mut result).unwrap()
+                    describe(&categorization, name, self.verbose, &mut result).unwrap();
+                    if self.verbose && matches!(categorization, Categorization::Alias(_)) {
+                        let shadowed = match env.env.shadowed_by_alias(&name.value) {
+                            None => continue,
+                            Some(Shadowed::Function(function)) => {
+                                Target::Function(Rc::clone(function))
+                            }
+                            Some(Shadowed::Builtin(builtin)) => Target::Builtin {
+                                builtin: *builtin,
+                                path: None,
+                            },
+                        };
+                        describe_target(&shadowed, name, true, &mut result).unwrap();
+                    }
                 }
                 Err(error) => errors.push(error),
             }
@@ -650,4 +668,72 @@ mod tests {
             ]
         );
     }
+
+    fn define_alias(env: &mut Env, name: &str, replacement: &str) {
+        env.aliases.insert(HashEntry::new(
+            name.to_string(),
+            replacement.to_string(),
+            false,
+            Location::dummy("alias"),
+        ));
+    }
+
+    #[test]
+    fn identify_result_alias_shadowing_function() {
+        let env = &mut Env::new_virtual();
+        define_alias(env, "foo", "bar");
+        let body: FullCompoundCommand = "{ :; }".parse().unwrap();
+        let function = Function::new("foo", body, Location::dummy("function"));
+        env.functions.define(function).unwrap();
+        let mut identify = Identify {
+            names: Field::dummies(["foo"]),
+            ..Identify::default()
+        };
+
+        let (result, errors) = identify.result(env);
+        assert_eq!(result, "alias foo=bar\n");
+        assert_eq!(errors, []);
+
+        identify.verbose = true;
+        let (result, errors) = identify.result(env);
+        assert_eq!(result, "foo: alias for `bar`\nfoo: function\n");
+        assert_eq!(errors, []);
+    }
+
+    #[test]
+    fn identify_result_alias_shadowing_builtin() {
+        let env = &mut Env::new_virtual();
+        define_alias(env, "foo", "bar");
+        env.builtins
+            .insert("foo", Builtin::new(Type::Mandatory, |_, _| unreachable!()));
+        let identify = Identify {
+            names: Field::dummies(["foo"]),
+            verbose: true,
+            ..Identify::default()
+        };
+
+        let (result, errors) = identify.result(env);
+        assert_eq!(result, "foo: alias for `bar`\nfoo: mandatory built-in\n");
+        assert_eq!(errors, []);
+    }
+
+    #[test]
+    fn identify_result_alias_shadowing_special_builtin_and_function() {
+        let env = &mut Env::new_virtual();
+        define_alias(env, "foo", "bar");
+        env.builtins
+            .insert("foo", Builtin::new(Type::Special, |_, _| unreachable!()));
+        let body: FullCompoundCommand = "{ :; }".parse().unwrap();
+        let function = Function::new("foo", body, Location::dummy("function"));
+        env.functions.define(function).unwrap();
+        let identify = Identify {
+            names: Field::dummies(["foo"]),
+            verbose: true,
+            ..Identify::default()
+        };
+
+        let (result, errors) = identify.result(env);
+        assert_eq!(result, "foo: alias for `bar`\nfoo: special built-in\n");
+        assert_eq!(errors, []);
+    }
 }
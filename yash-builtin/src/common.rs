@@ -25,11 +25,13 @@ use yash_env::semantics::Divert;
 use yash_env::semantics::ExitStatus;
 #[cfg(doc)]
 use yash_env::stack::Stack;
+use yash_env::system::Errno;
 use yash_env::Env;
 #[cfg(doc)]
 use yash_env::SharedSystem;
 use yash_syntax::source::pretty::Annotation;
 use yash_syntax::source::pretty::AnnotationType;
+use yash_syntax::source::pretty::Footer;
 use yash_syntax::source::pretty::Message;
 use yash_syntax::source::pretty::MessageBase;
 use yash_syntax::source::Location;
@@ -200,47 +202,97 @@ pub async fn report_simple_error(env: &mut Env, title: &str) -> yash_env::builti
     report_simple(env, title, ExitStatus::ERROR).await
 }
 
+/// Prints an error message for a usage error.
+///
+/// This function works like [`report_error`], but appends a footer showing
+/// the [synopsis](yash_env::builtin::Builtin::synopsis) of the current
+/// built-in ([`Stack::current_builtin`]) to the message. The synopsis is
+/// looked up in [`Env::builtins`] by the built-in name. No footer is added if
+/// the built-in is not found or has no synopsis.
+///
+/// You should use this function to report errors in parsing command line
+/// arguments.
+pub async fn report_usage_error<'a, M>(env: &mut Env, message: M) -> yash_env::builtin::Result
+where
+    M: Into<Message<'a>> + 'a,
+{
+    let mut message = message.into();
+    let synopsis = env
+        .stack
+        .current_builtin()
+        .and_then(|builtin| env.builtins.get(builtin.name.value.as_str()))
+        .map(|builtin| builtin.synopsis)
+        .filter(|synopsis| !synopsis.is_empty());
+    if let Some(synopsis) = synopsis {
+        message.footers.push(Footer {
+            r#type: AnnotationType::Help,
+            label: crate::help::usage(synopsis).into(),
+        });
+    }
+    report_error(env, message).await
+}
+
 /// Prints a simple error message for a command syntax error.
 ///
 /// This function constructs a [`Message`] with a predefined title and an
 /// [`Annotation`] created from the given label and location, and calls
-/// [`report_error`].
+/// [`report_usage_error`].
 pub async fn syntax_error(
     env: &mut Env,
     label: &str,
     location: &Location,
 ) -> yash_env::builtin::Result {
-    let annotation = Annotation::new(AnnotationType::Error, label.into(), location);
+    let mut annotations = vec![Annotation::new(
+        AnnotationType::Error,
+        label.into(),
+        location,
+    )];
+    location
+        .code
+        .source
+        .complement_annotations(&mut annotations);
     let message = Message {
         r#type: AnnotationType::Error,
         title: "command argument syntax error".into(),
-        annotations: vec![annotation],
+        annotations,
         footers: vec![],
     };
-    report_error(env, message).await
+    report_usage_error(env, message).await
 }
 
 /// Prints a text to the standard output.
 ///
 /// This function prints the given text to the standard output, and returns
-/// the default result. In case of an error, an error message is printed to
-/// the standard error and the returned result has exit status
-/// [`ExitStatus::FAILURE`]. Any errors that occur while printing the error
-/// message are ignored.
+/// the default result. The text is written entirely even if the underlying
+/// system call writes only part of it at a time. In case of an error, the
+/// error is reported by [`report_output_error`] and its result is returned.
 pub async fn output(env: &mut Env, content: &str) -> yash_env::builtin::Result {
     match env.system.write_all(Fd::STDOUT, content.as_bytes()).await {
         Ok(_) => Default::default(),
+        Err(errno) => report_output_error(env, errno).await,
+    }
+}
 
-        Err(errno) => {
-            let message = Message {
-                r#type: AnnotationType::Error,
-                title: format!("error printing results to stdout: {errno}").into(),
-                annotations: vec![],
-                footers: vec![],
-            };
-            report_failure(env, message).await
-        }
+/// Reports an error that occurred in printing results to the standard output.
+///
+/// This function returns a result with exit status [`ExitStatus::FAILURE`]
+/// and the divert value returned by [`arrange_message_and_divert`].
+/// An error message is printed to the standard error unless `errno` is
+/// [`Errno::EPIPE`], which means that the reader of the output has gone away
+/// as in `kill -l | head -n 1`. Any errors that occur while printing the error
+/// message are ignored.
+pub async fn report_output_error(env: &mut Env, errno: Errno) -> yash_env::builtin::Result {
+    let message = Message {
+        r#type: AnnotationType::Error,
+        title: format!("error printing results to stdout: {errno}").into(),
+        annotations: vec![],
+        footers: vec![],
+    };
+    let (message, divert) = arrange_message_and_divert(env, message);
+    if errno != Errno::EPIPE {
+        env.system.print_error(&message).await;
     }
+    yash_env::builtin::Result::with_exit_status_and_divert(ExitStatus::FAILURE, divert)
 }
 
 /// Converts errors to a single message.
@@ -265,9 +317,17 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use futures_util::FutureExt as _;
+    use std::rc::Rc;
     use yash_env::semantics::Field;
     use yash_env::stack::Builtin;
     use yash_env::stack::Frame;
+    use yash_env::System as _;
+    use yash_env::VirtualSystem;
+    use yash_env_test_helper::assert_stderr;
+    use yash_env_test_helper::assert_stdout;
+    use yash_syntax::source::Code;
+    use yash_syntax::source::Source;
 
     fn dummy_message() -> Message<'static> {
         Message {
@@ -308,4 +368,133 @@ mod tests {
         let (_message, divert) = arrange_message_and_divert(&env, dummy_message());
         assert_eq!(divert, Continue(()));
     }
+
+    #[test]
+    fn output_to_stdout() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+
+        let result = output(&mut env, "foo\n").now_or_never().unwrap();
+        assert_eq!(result, yash_env::builtin::Result::default());
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "foo\n"));
+    }
+
+    #[test]
+    fn output_to_closed_stdout() {
+        let mut system = VirtualSystem::new();
+        system.current_process_mut().close_fd(Fd::STDOUT);
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+
+        let result = output(&mut env, "foo\n").now_or_never().unwrap();
+        assert_eq!(result.exit_status(), ExitStatus::FAILURE);
+        assert_eq!(result.divert(), Continue(()));
+        assert_stderr(&state, |stderr| {
+            assert!(stderr.contains("error printing results"), "{stderr:?}")
+        });
+    }
+
+    #[test]
+    fn output_to_broken_pipe() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        let (reader, writer) = env.system.pipe().unwrap();
+        env.system.close(reader).unwrap();
+        env.system.dup2(writer, Fd::STDOUT).unwrap();
+        env.system.close(writer).unwrap();
+
+        let result = output(&mut env, "foo\n").now_or_never().unwrap();
+        assert_eq!(result.exit_status(), ExitStatus::FAILURE);
+        assert_eq!(result.divert(), Continue(()));
+        assert_stderr(&state, |stderr| assert_eq!(stderr, ""));
+    }
+
+    #[test]
+    fn output_error_in_special_builtin() {
+        let mut system = VirtualSystem::new();
+        system.current_process_mut().close_fd(Fd::STDOUT);
+        let mut env = Env::with_system(Box::new(system));
+        let mut env = env.push_frame(Frame::Builtin(Builtin {
+            name: Field::dummy("builtin"),
+            is_special: true,
+        }));
+
+        let result = output(&mut env, "foo\n").now_or_never().unwrap();
+        assert_eq!(result.exit_status(), ExitStatus::FAILURE);
+        assert_eq!(result.divert(), Break(Divert::Interrupt(None)));
+    }
+
+    #[test]
+    fn usage_error_with_synopsis() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        env.builtins.insert(
+            "foo",
+            yash_env::builtin::Builtin::new(
+                yash_env::builtin::Type::Mandatory,
+                |_, _| unreachable!(),
+            )
+            .with_synopsis("foo [-x] bar"),
+        );
+        let mut env = env.push_frame(Frame::Builtin(Builtin {
+            name: Field::dummy("foo"),
+            is_special: false,
+        }));
+
+        let result = report_usage_error(&mut env, dummy_message())
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result.exit_status(), ExitStatus::ERROR);
+        assert_stderr(&state, |stderr| {
+            assert!(stderr.contains("usage: foo [-x] bar"), "{stderr:?}")
+        });
+    }
+
+    #[test]
+    fn usage_error_without_synopsis() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        let mut env = env.push_frame(Frame::Builtin(Builtin {
+            name: Field::dummy("foo"),
+            is_special: false,
+        }));
+
+        let result = report_usage_error(&mut env, dummy_message())
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result.exit_status(), ExitStatus::ERROR);
+        assert_stderr(&state, |stderr| {
+            assert!(!stderr.contains("usage:"), "{stderr:?}")
+        });
+    }
+
+    #[test]
+    fn syntax_error_in_field_resulting_from_field_splitting() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        let original = Location::dummy("$var");
+        let code = Rc::new(Code {
+            value: "foo bar".to_string().into(),
+            start_line_number: 1.try_into().unwrap(),
+            source: Rc::new(Source::Expansion { original }),
+        });
+        let location = Location { code, range: 4..7 };
+
+        let result = syntax_error(&mut env, "bad operand", &location)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result.exit_status(), ExitStatus::ERROR);
+        assert_stderr(&state, |stderr| {
+            assert!(stderr.contains("bad operand"), "{stderr:?}");
+            assert!(
+                stderr.contains("field resulted from the expansion of this word"),
+                "{stderr:?}"
+            );
+        });
+    }
 }
@@ -77,8 +77,8 @@
 //! break built-in implementation.
 //! This module re-exports [`super::break::syntax`].
 
-use crate::common::report_error;
 use crate::common::report_simple_failure;
+use crate::common::report_usage_error;
 use yash_env::builtin::Result;
 use yash_env::semantics::Field;
 use yash_env::Env;
@@ -96,7 +96,7 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> Result {
             Ok(result) => result,
             Err(e) => report_simple_failure(env, &format!("cannot continue: {e}")).await,
         },
-        Err(e) => report_error(env, &e).await,
+        Err(e) => report_usage_error(env, &e).await,
     }
 }
 
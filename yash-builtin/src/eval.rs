@@ -29,6 +29,10 @@
 //! This built-in parses and executes the argument as a shell script in
 //! the current shell environment.
 //!
+//! The command string is parsed with the aliases defined in the current
+//! environment. Aliases defined by the evaluated commands apply to the
+//! remaining lines of the string and remain defined after the built-in returns.
+//!
 //! # Options
 //!
 //! None.
@@ -46,6 +50,9 @@
 //! During parsing and execution, any syntax error or runtime error may
 //! occur.
 //!
+//! It is an error if the [call depth limit](Env::call_depth_limit) has been
+//! reached, which usually results from infinite recursion.
+//!
 //! # Exit status
 //!
 //! The exit status of the `eval` built-in is the exit status of the last
@@ -59,12 +66,14 @@
 //! Guidelines, which means portable scripts cannot use any options or the `--`
 //! separator for the built-in.
 
+use crate::common::report_simple_error;
 use crate::Result;
 use std::cell::RefCell;
 use std::rc::Rc;
 #[cfg(doc)]
 use yash_env::semantics::ExitStatus;
 use yash_env::semantics::Field;
+use yash_env::stack::Frame;
 use yash_env::Env;
 use yash_semantics::read_eval_loop;
 use yash_syntax::input::Memory;
@@ -78,6 +87,15 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> Result {
         None => return Result::default(),
     };
 
+    if env.call_depth_exceeded() {
+        let title = format!(
+            "too deep recursion: call depth limit {} exceeded",
+            env.call_depth_limit
+        );
+        return report_simple_error(env, &title).await;
+    }
+    let env = &mut *env.push_frame(Frame::Eval);
+
     // Parse and execute the command string
     let mut config = Lexer::config();
     config.source = Some(Rc::new(Source::Eval {
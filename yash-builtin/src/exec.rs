@@ -148,7 +148,7 @@ mod tests {
     use yash_env::option::State::On;
     use yash_env::system::r#virtual::{FileBody, Inode};
     use yash_env::system::Mode;
-    use yash_env::variable::{Scope, PATH};
+    use yash_env::variable::{Context, Scope, Value, PATH};
     use yash_env::VirtualSystem;
 
     fn executable_file() -> Inode {
@@ -225,6 +225,36 @@ mod tests {
         assert_eq!(arguments.2, [c"PATH=/usr/bin".to_owned()]);
     }
 
+    #[test]
+    fn passing_innermost_exported_variables_to_external_utility() {
+        let system = VirtualSystem::new();
+        let mut env = Env::with_system(Box::new(system.clone()));
+        system
+            .state
+            .borrow_mut()
+            .file_system
+            .save("/bin/env", Rc::new(RefCell::new(executable_file())))
+            .unwrap();
+        let mut var = env.variables.get_or_new("var", Scope::Global);
+        var.assign("global", None).unwrap();
+        var.export(true);
+        let mut var = env.variables.get_or_new("array", Scope::Global);
+        var.assign(Value::array(["1", "2"]), None).unwrap();
+        var.export(true);
+
+        let mut env = env.push_context(Context::Volatile);
+        let mut var = env.variables.get_or_new("var", Scope::Volatile);
+        var.assign("temporary", None).unwrap();
+        var.export(true);
+
+        let args = Field::dummies(["/bin/env"]);
+        _ = main(&mut env, args).now_or_never().unwrap();
+
+        let process = &system.current_process();
+        let arguments = process.last_exec().as_ref().unwrap();
+        assert_eq!(arguments.2, [c"var=temporary".to_owned()]);
+    }
+
     #[test]
     fn utility_name_with_slash() {
         let system = VirtualSystem::new();
@@ -245,7 +275,7 @@ mod tests {
         let arguments = process.last_exec().as_ref().unwrap();
         assert_eq!(arguments.0, c"/bin/echo".to_owned());
         assert_eq!(arguments.1, [c"/bin/echo".to_owned()]);
-        assert_eq!(arguments.2, []);
+        assert_eq!(arguments.2, [] as [std::ffi::CString; 0]);
     }
 
     #[test]
@@ -82,8 +82,9 @@
 
 use crate::bg::OperandErrorKind;
 use crate::bg::ResumeError;
-use crate::common::report_error;
+use crate::common::report_output_error;
 use crate::common::report_simple_failure;
+use crate::common::report_usage_error;
 use crate::common::syntax::parse_arguments;
 use crate::common::syntax::Mode;
 use yash_env::io::Fd;
@@ -129,7 +130,8 @@ async fn resume_job_by_index(env: &mut Env, index: usize) -> Result<ProcessState
     }
 
     let line = format!("{}\n", job.name);
-    env.system.write_all(Fd::STDOUT, line.as_bytes()).await?;
+    let result = env.system.write_all(Fd::STDOUT, line.as_bytes()).await;
+    result.map_err(ResumeError::Output)?;
     drop(line);
 
     let mut state = job.state;
@@ -171,7 +173,7 @@ async fn resume_job_by_id(env: &mut Env, job_id: &str) -> Result<ProcessState, O
 pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
     let (options, operands) = match parse_arguments(&[], Mode::with_env(env), args) {
         Ok(result) => result,
-        Err(error) => return report_error(env, &error).await,
+        Err(error) => return report_usage_error(env, &error).await,
     };
     debug_assert_eq!(options, []);
 
@@ -190,6 +192,9 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
 
     match result {
         Ok(state) => ExitStatus::try_from(state).unwrap().into(),
+        Err(OperandErrorKind::CannotResume(ResumeError::Output(errno))) => {
+            report_output_error(env, errno).await
+        }
         Err(error) => report_simple_failure(env, &error.to_string()).await,
     }
 }
@@ -462,6 +467,26 @@ mod tests {
         });
     }
 
+    #[test]
+    fn main_fails_on_output_error() {
+        let mut system = VirtualSystem::new();
+        system.current_process_mut().close_fd(Fd::STDOUT);
+        stub_tty(&system.state);
+        let mut env = Env::with_system(Box::new(system.clone()));
+        let mut job = Job::new(Pid(123));
+        job.job_controlled = true;
+        job.state = ProcessState::exited(0);
+        let index = env.jobs.add(job);
+        env.jobs.set_current_job(index).unwrap();
+
+        let result = main(&mut env, vec![]).now_or_never().unwrap();
+
+        assert_eq!(result, crate::Result::from(ExitStatus::FAILURE));
+        assert_stderr(&system.state, |stderr| {
+            assert!(stderr.contains("error printing results"), "{stderr:?}");
+        });
+    }
+
     #[test]
     fn main_with_operand_resumes_specified_job() {
         in_virtual_system(|mut env, state| async move {
@@ -185,9 +185,16 @@
 //! This implementation uses the `any` field in the [`Env`] to check if the
 //! built-in is invoked with the same arguments and `$OPTIND` as the previous
 //! invocation.
+//!
+//! The parsing position is kept only in the `$OPTIND` variable, so there is no
+//! hook in variable assignment to detect a reset. When the built-in finds
+//! `$OPTIND` is `1`, it discards the state saved in the `any` field and starts
+//! parsing the new arguments from the beginning. This allows reusing the
+//! built-in for another set of arguments after assigning `1` to `$OPTIND`.
 
 use crate::common::report_error;
 use crate::common::report_simple_error;
+use crate::common::report_usage_error;
 use crate::common::syntax::parse_arguments;
 use crate::common::syntax::Mode;
 use either::Either::{Left, Right};
@@ -225,7 +232,7 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
     // Parse arguments
     let operands = match parse_arguments(&[], Mode::with_env(env), args) {
         Ok((_, operands)) => operands,
-        Err(error) => return report_error(env, &error).await,
+        Err(error) => return report_usage_error(env, &error).await,
     };
     if operands.len() < 2 {
         let message = format!(
@@ -290,6 +297,8 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use futures_util::FutureExt as _;
+    use yash_env::variable::Scope;
 
     fn non_zero(i: usize) -> NonZeroUsize {
         NonZeroUsize::new(i).unwrap()
@@ -329,4 +338,65 @@ mod tests {
         assert_eq!(indexes_to_optind(non_zero(2), non_zero(4)), "2:4");
         assert_eq!(indexes_to_optind(non_zero(10), non_zero(13)), "10:13");
     }
+
+    fn run_getopts(env: &mut Env, args: &[&str]) -> (ExitStatus, String, String) {
+        let args = Field::dummies(["ab", "o"].iter().chain(args).copied());
+        let result = main(env, args).now_or_never().unwrap();
+        let option = env.variables.get_scalar("o").unwrap_or_default().to_owned();
+        let optind = env
+            .variables
+            .get_scalar(OPTIND)
+            .unwrap_or_default()
+            .to_owned();
+        (result.exit_status(), option, optind)
+    }
+
+    #[test]
+    fn reusing_getopts_after_resetting_optind() {
+        let mut env = Env::new_virtual();
+        env.init_variables();
+
+        let first = ["-a", "-b", "foo"];
+        assert_eq!(
+            run_getopts(&mut env, &first),
+            (ExitStatus::SUCCESS, "a".into(), "2".into())
+        );
+        assert_eq!(
+            run_getopts(&mut env, &first),
+            (ExitStatus::SUCCESS, "b".into(), "3".into())
+        );
+        assert_eq!(
+            run_getopts(&mut env, &first),
+            (ExitStatus::FAILURE, "?".into(), "3".into())
+        );
+
+        env.get_or_create_variable(OPTIND, Scope::Global)
+            .assign("1", None)
+            .unwrap();
+
+        let second = ["-ba", "bar"];
+        assert_eq!(
+            run_getopts(&mut env, &second),
+            (ExitStatus::SUCCESS, "b".into(), "1:2".into())
+        );
+        assert_eq!(
+            run_getopts(&mut env, &second),
+            (ExitStatus::SUCCESS, "a".into(), "2".into())
+        );
+        assert_eq!(
+            run_getopts(&mut env, &second),
+            (ExitStatus::FAILURE, "?".into(), "2".into())
+        );
+    }
+
+    #[test]
+    fn different_arguments_without_resetting_optind() {
+        let mut env = Env::new_virtual();
+        env.init_variables();
+
+        let (exit_status, _, _) = run_getopts(&mut env, &["-a", "-b"]);
+        assert_eq!(exit_status, ExitStatus::SUCCESS);
+        let (exit_status, _, _) = run_getopts(&mut env, &["-b", "-a"]);
+        assert_eq!(exit_status, ExitStatus::ERROR);
+    }
 }
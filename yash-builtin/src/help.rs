@@ -0,0 +1,277 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Help built-in
+//!
+//! The **`help`** built-in prints usage of built-ins.
+//!
+//! # Synopsis
+//!
+//! ```sh
+//! help [name…]
+//! ```
+//!
+//! # Description
+//!
+//! Without operands, the built-in prints a list of all built-ins available in
+//! the current environment, one per line, with a short
+//! [description](yash_env::builtin::Builtin::description) of each built-in.
+//!
+//! With operands, the built-in prints the description and
+//! [synopsis](yash_env::builtin::Builtin::synopsis) of each built-in
+//! specified by the operands.
+//!
+//! # Options
+//!
+//! None.
+//!
+//! # Operands
+//!
+//! Operands are names of built-ins.
+//!
+//! # Standard output
+//!
+//! For each operand, the built-in prints the name and description of the
+//! built-in on the first line and the synopsis on the following lines:
+//!
+//! ```text
+//! kill: send a signal to processes
+//! usage: kill [-s SIGNAL|-n SIGNAL|-SIGNAL] target…
+//!        kill -l|-v [SIGNAL|exit_status]…
+//! ```
+//!
+//! # Errors
+//!
+//! It is an error if an operand does not name an existing built-in.
+//!
+//! # Exit status
+//!
+//! Zero unless an error occurs. One if any operand does not name an existing
+//! built-in.
+//!
+//! # Portability
+//!
+//! This built-in is an extension that is not defined in POSIX.
+//! The output format differs among shells that have a built-in of this name.
+
+use crate::common::output;
+use crate::common::report_failure;
+use crate::common::report_usage_error;
+use crate::common::syntax::parse_arguments;
+use crate::common::syntax::Mode;
+use crate::common::to_single_message;
+use std::borrow::Cow;
+use std::fmt::Write as _;
+use thiserror::Error;
+use yash_env::builtin::Builtin;
+use yash_env::semantics::Field;
+use yash_env::Env;
+use yash_syntax::source::pretty::Annotation;
+use yash_syntax::source::pretty::AnnotationType;
+use yash_syntax::source::pretty::MessageBase;
+
+/// Formats a synopsis into a usage text.
+///
+/// The first line of the result starts with `usage: `. The following lines
+/// are indented to align with the first line. The result does not end with a
+/// newline.
+///
+/// ```
+/// # use yash_builtin::help::usage;
+/// assert_eq!(usage("foo [-x]\nfoo -y"), "usage: foo [-x]\n       foo -y");
+/// ```
+#[must_use]
+pub fn usage(synopsis: &str) -> String {
+    const PREFIX: &str = "usage: ";
+    let mut result = String::new();
+    for (i, line) in synopsis.lines().enumerate() {
+        if i == 0 {
+            result.push_str(PREFIX);
+        } else {
+            result.push('\n');
+            result.extend(std::iter::repeat_n(' ', PREFIX.len()));
+        }
+        result.push_str(line);
+    }
+    result
+}
+
+/// Formats the help text for a built-in.
+///
+/// The result ends with a newline. See the [module documentation](self) for
+/// the format.
+#[must_use]
+pub fn help_text(name: &str, builtin: &Builtin) -> String {
+    let mut result = String::from(name);
+    if !builtin.description.is_empty() {
+        result.push_str(": ");
+        result.push_str(builtin.description);
+    }
+    result.push('\n');
+    if !builtin.synopsis.is_empty() {
+        result.push_str(&usage(builtin.synopsis));
+        result.push('\n');
+    }
+    result
+}
+
+/// Formats the list of all built-ins in the environment.
+fn list_builtins(env: &Env) -> String {
+    let mut builtins = env.builtins.iter().collect::<Vec<_>>();
+    builtins.sort_unstable_by_key(|&(name, _)| name);
+    let width = builtins
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut result = String::new();
+    for (name, builtin) in builtins {
+        let line = format!("{name:width$}  {}", builtin.description);
+        writeln!(result, "{}", line.trim_end()).unwrap();
+    }
+    result
+}
+
+/// Error indicating that an operand does not name a built-in
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+#[error("no such built-in: {}", .0.value)]
+pub struct NotFound(pub Field);
+
+impl MessageBase for NotFound {
+    fn message_title(&self) -> Cow<'_, str> {
+        "cannot print help".into()
+    }
+
+    fn main_annotation(&self) -> Annotation<'_> {
+        Annotation::new(
+            AnnotationType::Error,
+            self.to_string().into(),
+            &self.0.origin,
+        )
+    }
+}
+
+/// Entry point of the `help` built-in
+pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
+    let (options, operands) = match parse_arguments(&[], Mode::with_env(env), args) {
+        Ok(result) => result,
+        Err(error) => return report_usage_error(env, &error).await,
+    };
+    debug_assert_eq!(options, []);
+
+    if operands.is_empty() {
+        let list = list_builtins(env);
+        return output(env, &list).await;
+    }
+
+    let mut text = String::new();
+    let mut errors = Vec::new();
+    for operand in operands {
+        match env.builtins.get(operand.value.as_str()) {
+            Some(builtin) => text.push_str(&help_text(&operand.value, builtin)),
+            None => errors.push(NotFound(operand)),
+        }
+    }
+
+    let result = output(env, &text).await;
+    match to_single_message(&{ errors }) {
+        None => result,
+        Some(message) => result.max(report_failure(env, message).await),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt as _;
+    use std::rc::Rc;
+    use yash_env::builtin::Type;
+    use yash_env::semantics::ExitStatus;
+    use yash_env::VirtualSystem;
+    use yash_env_test_helper::assert_stderr;
+    use yash_env_test_helper::assert_stdout;
+
+    fn env_with_builtins(system: VirtualSystem) -> Env {
+        let mut env = Env::with_system(Box::new(system));
+        env.builtins.extend(crate::BUILTINS.iter().cloned());
+        env
+    }
+
+    #[test]
+    fn usage_of_single_line_synopsis() {
+        assert_eq!(usage("foo [-x]"), "usage: foo [-x]");
+    }
+
+    #[test]
+    fn listing_all_builtins() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = env_with_builtins(system);
+        env.builtins
+            .insert("x", Builtin::new(Type::Extension, |_, _| unreachable!()));
+
+        let result = main(&mut env, vec![]).now_or_never().unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert_stdout(&state, |stdout| {
+            assert!(
+                stdout.contains("\nkill      send a signal to processes\n"),
+                "{stdout:?}"
+            );
+            assert!(stdout.contains("\nx\n"), "{stdout:?}");
+        });
+    }
+
+    #[test]
+    fn printing_help_for_kill() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = env_with_builtins(system);
+        let synopsis = env.builtins["kill"].synopsis;
+        assert_ne!(synopsis, "");
+
+        let args = Field::dummies(["kill"]);
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert_stdout(&state, |stdout| {
+            assert!(
+                stdout.starts_with("kill: send a signal to processes\n"),
+                "{stdout:?}"
+            );
+            assert!(stdout.contains(&usage(synopsis)), "{stdout:?}");
+        });
+    }
+
+    #[test]
+    fn unknown_builtin() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = env_with_builtins(system);
+
+        let args = Field::dummies(["no_such_builtin", "true"]);
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result.exit_status(), ExitStatus::FAILURE);
+        assert_stdout(&state, |stdout| {
+            assert_eq!(stdout, "true: do nothing, successfully\nusage: true\n")
+        });
+        assert_stderr(&state, |stderr| {
+            assert!(
+                stderr.contains("no such built-in: no_such_builtin"),
+                "{stderr:?}"
+            )
+        });
+    }
+}
@@ -84,8 +84,8 @@
 //! leading `%`, the built-in assumes one silently, which is not portable.
 
 use crate::common::output;
-use crate::common::report_error;
 use crate::common::report_failure;
+use crate::common::report_usage_error;
 use crate::common::syntax::parse_arguments;
 use crate::common::syntax::Mode;
 use crate::common::syntax::OptionSpec;
@@ -124,7 +124,7 @@ fn find_error_message(error: FindError, operand: &Field) -> Message {
 pub async fn main(env: &mut Env, args: Vec<Field>) -> Result {
     let (options, operands) = match parse_arguments(OPTIONS, Mode::with_env(env), args) {
         Ok(result) => result,
-        Err(error) => return report_error(env, &error).await,
+        Err(error) => return report_usage_error(env, &error).await,
     };
 
     let mut accumulator = Accumulator {
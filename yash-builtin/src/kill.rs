@@ -96,10 +96,20 @@
 //!
 //! # Exit status
 //!
-//! The exit status is zero unless an error occurs. The exit status is zero if
+//! The exit status is zero unless an error occurs. When some operands fail,
+//! the signal is still sent to the other operands in the order they are given,
+//! and the exit status is non-zero. The exit status is zero if
 //! the signal is sent to at least one process for each operand, even if the
 //! signal cannot be sent to some of the processes.
 //!
+//! # Job states
+//!
+//! When the built-in sends `SIGKILL`, `SIGSTOP`, or `SIGCONT` to a job
+//! specified by a job ID, the built-in updates the state of the job to
+//! killed, stopped, or running, respectively, without waiting for the job to
+//! actually change its state. These signals cannot be caught or ignored, so
+//! the [`jobs`](crate::jobs) built-in can report the new state right away.
+//!
 //! # Usage notes
 //!
 //! When a target is specified as a job ID, the built-in cannot tell whether
@@ -149,7 +159,7 @@
 //! signal. This implementation currently prints all names, but this behavior
 //! may change in the future.
 
-use crate::common::report_error;
+use crate::common::report_usage_error;
 use yash_env::semantics::Field;
 use yash_env::Env;
 
@@ -204,6 +214,6 @@ impl Command {
 pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
     match syntax::parse(env, args) {
         Ok(command) => command.execute(env).await,
-        Err(error) => report_error(env, error.to_message()).await,
+        Err(error) => report_usage_error(env, error.to_message()).await,
     }
 }
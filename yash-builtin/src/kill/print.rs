@@ -146,6 +146,8 @@ pub async fn execute(env: &mut Env, signals: &[(Signal, Field)], verbose: bool)
 #[cfg(test)]
 mod tests {
     use super::*;
+    use futures_util::FutureExt as _;
+    use yash_env::io::Fd;
     use yash_env::semantics::ExitStatus;
     use yash_env::system::r#virtual::VirtualSystem;
     use yash_env::system::r#virtual::SIGKILL;
@@ -221,4 +223,15 @@ mod tests {
             RTMIN+4\nRTMAX-3\nRTMAX-2\nRTMAX-1\nRTMAX\n"
         );
     }
+
+    #[test]
+    fn execute_with_closed_stdout() {
+        let mut system = VirtualSystem::new();
+        system.current_process_mut().close_fd(Fd::STDOUT);
+        let mut env = Env::with_system(Box::new(system));
+        let signals = &[(Signal::Name(Name::Int), Field::dummy("INT"))];
+
+        let result = execute(&mut env, signals, false).now_or_never().unwrap();
+        assert_eq!(result.exit_status(), ExitStatus::FAILURE);
+    }
 }
@@ -27,11 +27,14 @@ use std::num::ParseIntError;
 use thiserror::Error;
 use yash_env::job::id::parse_tail;
 use yash_env::job::Pid;
+use yash_env::job::ProcessResult;
+use yash_env::job::ProcessState;
 use yash_env::job::{id::FindError, JobList};
 use yash_env::semantics::Field;
 use yash_env::signal;
 use yash_env::system::Errno;
 use yash_env::system::System as _;
+use yash_env::system::SystemEx as _;
 use yash_env::Env;
 use yash_syntax::source::pretty::{Annotation, AnnotationType, MessageBase};
 
@@ -81,7 +84,31 @@ pub fn resolve_target(jobs: &JobList, target: &str) -> Result<Pid, Error> {
     }
 }
 
+/// Returns the state a job is certain to enter after receiving the signal.
+///
+/// `SIGKILL` and `SIGSTOP` cannot be caught or ignored, so they always
+/// terminate or stop the job, respectively. `SIGCONT` always resumes a stopped
+/// job. For other signals, the effect depends on the job, so this function
+/// returns `None`.
+fn expected_state(env: &Env, signal: signal::Number) -> Option<ProcessState> {
+    match env.system.signal_name_from_number(signal) {
+        signal::Name::Kill => Some(ProcessState::Halted(ProcessResult::Signaled {
+            signal,
+            core_dump: false,
+        })),
+        signal::Name::Stop => Some(ProcessState::stopped(signal)),
+        signal::Name::Cont => Some(ProcessState::Running),
+        _ => None,
+    }
+}
+
 /// Sends the specified signal to the specified target.
+///
+/// If the target is a job ID and the signal is `SIGKILL`, `SIGSTOP`, or
+/// `SIGCONT`, the state of the job in `env.jobs` is updated in advance, so
+/// that the next job status report reflects the new state. The state is also
+/// set as the [expected state](yash_env::job::JobRefMut::expect) of the job so
+/// that the change is not reported again when the shell actually observes it.
 pub async fn send(
     env: &mut Env,
     signal: Option<signal::Number>,
@@ -89,6 +116,19 @@ pub async fn send(
 ) -> Result<(), Error> {
     let pid = resolve_target(&env.jobs, &target.value)?;
     env.system.kill(pid, signal).await?;
+
+    if target.value.starts_with('%') {
+        if let Some(state) = signal.and_then(|signal| expected_state(env, signal)) {
+            let job_pid = -pid;
+            if let Some(index) = env.jobs.find_by_pid(job_pid) {
+                if env.jobs[index].state != state {
+                    env.jobs.update_status(job_pid, state);
+                    env.jobs.get_mut(index).unwrap().expect(state);
+                }
+            }
+        }
+    }
+
     Ok(())
 }
 
@@ -175,11 +215,14 @@ mod tests {
     use super::*;
     use assert_matches::assert_matches;
     use futures_util::FutureExt;
+    use std::cell::RefCell;
     use std::rc::Rc;
     use yash_env::job::Job;
-    use yash_env::job::ProcessState;
     use yash_env::semantics::ExitStatus;
+    use yash_env::system::r#virtual::Process;
+    use yash_env::system::r#virtual::SystemState;
     use yash_env::system::r#virtual::VirtualSystem;
+    use yash_env::system::r#virtual::{SIGKILL, SIGSTOP};
     use yash_env_test_helper::assert_stderr;
 
     #[test]
@@ -274,4 +317,121 @@ mod tests {
         assert_eq!(result, crate::Result::from(ExitStatus::FAILURE));
         assert_stderr(&state, |stderr| assert_ne!(stderr, ""));
     }
+
+    fn env_with_processes() -> (Env, Rc<RefCell<SystemState>>) {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        {
+            let mut state = state.borrow_mut();
+            for (pid, pgid) in [(10, 10), (20, 20), (21, 20)] {
+                let process = Process::with_parent_and_group(system.process_id, Pid(pgid));
+                state.processes.insert(Pid(pid), process);
+            }
+        }
+        let mut env = Env::with_system(Box::new(system));
+        let mut job = Job::new(Pid(20));
+        job.job_controlled = true;
+        job.is_owned = true;
+        job.state = ProcessState::Running;
+        job.name = "my job".into();
+        let index = env.jobs.add(job);
+        env.jobs.get_mut(index).unwrap().state_reported();
+        (env, state)
+    }
+
+    fn process_state(state: &RefCell<SystemState>, pid: i32) -> ProcessState {
+        state.borrow().processes[&Pid(pid)].state()
+    }
+
+    #[test]
+    fn execute_with_partial_failure() {
+        let (mut env, state) = env_with_processes();
+        let targets = Field::dummies(["10", "%nosuch", "%my"]);
+
+        let result = execute(&mut env, Signal::Name(signal::Name::Kill), None, &targets)
+            .now_or_never()
+            .unwrap();
+
+        assert_eq!(result, crate::Result::from(ExitStatus::FAILURE));
+        let killed = ProcessState::Halted(ProcessResult::Signaled {
+            signal: SIGKILL,
+            core_dump: false,
+        });
+        assert_eq!(process_state(&state, 10), killed);
+        assert_eq!(process_state(&state, 20), killed);
+        assert_eq!(process_state(&state, 21), killed);
+        assert_stderr(&state, |stderr| {
+            assert!(stderr.contains("%nosuch"), "{stderr:?}")
+        });
+    }
+
+    #[test]
+    fn execute_updates_job_state_for_sigkill() {
+        let (mut env, _state) = env_with_processes();
+        let targets = Field::dummies(["%my"]);
+
+        let result = execute(&mut env, Signal::Name(signal::Name::Kill), None, &targets)
+            .now_or_never()
+            .unwrap();
+
+        assert_eq!(result, crate::Result::default());
+        let killed = ProcessState::Halted(ProcessResult::Signaled {
+            signal: SIGKILL,
+            core_dump: false,
+        });
+        let job = &env.jobs[0];
+        assert_eq!(job.state, killed);
+        assert!(job.state_changed);
+        assert_eq!(job.expected_state, Some(killed));
+    }
+
+    #[test]
+    fn execute_updates_job_state_for_sigstop() {
+        let (mut env, _state) = env_with_processes();
+        let targets = Field::dummies(["%my"]);
+
+        let result = execute(&mut env, Signal::Name(signal::Name::Stop), None, &targets)
+            .now_or_never()
+            .unwrap();
+
+        assert_eq!(result, crate::Result::default());
+        let job = &env.jobs[0];
+        assert_eq!(job.state, ProcessState::stopped(SIGSTOP));
+        assert!(job.state_changed);
+        assert_eq!(job.expected_state, Some(ProcessState::stopped(SIGSTOP)));
+    }
+
+    #[test]
+    fn execute_keeps_job_state_for_catchable_signal() {
+        let (mut env, state) = env_with_processes();
+        let targets = Field::dummies(["%my"]);
+
+        let result = execute(&mut env, Signal::Name(signal::Name::Term), None, &targets)
+            .now_or_never()
+            .unwrap();
+
+        assert_eq!(result, crate::Result::default());
+        assert_ne!(process_state(&state, 20), ProcessState::Running);
+        let job = &env.jobs[0];
+        assert_eq!(job.state, ProcessState::Running);
+        assert!(!job.state_changed);
+        assert_eq!(job.expected_state, None);
+    }
+
+    #[test]
+    fn execute_reports_errors_in_operand_order() {
+        let (mut env, state) = env_with_processes();
+        let targets = Field::dummies(["%foo", "10", "%bar"]);
+
+        let result = execute(&mut env, Signal::Name(signal::Name::Kill), None, &targets)
+            .now_or_never()
+            .unwrap();
+
+        assert_eq!(result, crate::Result::from(ExitStatus::FAILURE));
+        assert_stderr(&state, |stderr| {
+            let foo = stderr.find("%foo").unwrap();
+            let bar = stderr.find("%bar").unwrap();
+            assert!(foo < bar, "{stderr:?}");
+        });
+    }
 }
@@ -71,6 +71,7 @@ pub mod export;
 pub mod r#false;
 pub mod fg;
 pub mod getopts;
+pub mod help;
 pub mod jobs;
 pub mod kill;
 pub mod pwd;
@@ -82,6 +83,7 @@ pub mod set;
 pub mod shift;
 #[cfg(feature = "yash-semantics")]
 pub mod source;
+pub mod suspend;
 pub mod times;
 pub mod trap;
 pub mod r#true;
@@ -103,7 +105,7 @@ use yash_env::stack::{Frame, Stack};
 use yash_env::Env;
 
 use std::future::ready;
-use Type::{Elective, Mandatory, Special};
+use Type::{Elective, Extension, Mandatory, Special};
 
 /// Array of all the implemented built-in utilities.
 ///
@@ -112,150 +114,235 @@ pub const BUILTINS: &[(&str, Builtin)] = &[
     #[cfg(feature = "yash-semantics")]
     (
         ".",
-        Builtin::new(Special, |env, args| Box::pin(source::main(env, args))),
+        Builtin::new(Special, |env, args| Box::pin(source::main(env, args)))
+            .with_description("read and execute commands from a file")
+            .with_synopsis(". file [arguments…]"),
     ),
     (
         ":",
-        Builtin::new(Special, |env, args| Box::pin(ready(colon::main(env, args)))),
+        Builtin::new(Special, |env, args| Box::pin(ready(colon::main(env, args))))
+            .with_description("do nothing")
+            .with_synopsis(": […]"),
     ),
     (
         "alias",
-        Builtin::new(Mandatory, |env, args| Box::pin(alias::main(env, args))),
+        Builtin::new(Mandatory, |env, args| Box::pin(alias::main(env, args)))
+            .with_description("define or print aliases")
+            .with_synopsis("alias [name[=value]…]"),
     ),
     (
         "bg",
-        Builtin::new(Mandatory, |env, args| Box::pin(bg::main(env, args))),
+        Builtin::new(Mandatory, |env, args| Box::pin(bg::main(env, args)))
+            .with_description("resume jobs in the background")
+            .with_synopsis("bg [job_id…]"),
     ),
     (
         "break",
-        Builtin::new(Special, |env, args| Box::pin(r#break::main(env, args))),
+        Builtin::new(Special, |env, args| Box::pin(r#break::main(env, args)))
+            .with_description("exit from loops")
+            .with_synopsis("break [n]"),
     ),
     (
         "cd",
-        Builtin::new(Mandatory, |env, args| Box::pin(cd::main(env, args))),
+        Builtin::new(Mandatory, |env, args| Box::pin(cd::main(env, args)))
+            .with_description("change the working directory")
+            .with_synopsis("cd [-L|-P [-e]] [directory]"),
     ),
     #[cfg(feature = "yash-semantics")]
     ("command", {
-        let mut builtin = Builtin::new(Mandatory, |env, args| Box::pin(command::main(env, args)));
+        let mut builtin = Builtin::new(Mandatory, |env, args| Box::pin(command::main(env, args)))
+            .with_description("execute or identify a command")
+            .with_synopsis("command [-p] name [arguments…]\ncommand -v|-V [-p] name");
         builtin.is_declaration_utility = None;
         builtin
     }),
     (
         "continue",
-        Builtin::new(Special, |env, args| Box::pin(r#continue::main(env, args))),
+        Builtin::new(Special, |env, args| Box::pin(r#continue::main(env, args)))
+            .with_description("continue to the next loop iteration")
+            .with_synopsis("continue [n]"),
     ),
     #[cfg(feature = "yash-semantics")]
     (
         "eval",
-        Builtin::new(Special, |env, args| Box::pin(eval::main(env, args))),
+        Builtin::new(Special, |env, args| Box::pin(eval::main(env, args)))
+            .with_description("evaluate arguments as a command")
+            .with_synopsis("eval [command…]"),
     ),
     #[cfg(feature = "yash-semantics")]
     (
         "exec",
-        Builtin::new(Special, |env, args| Box::pin(exec::main(env, args))),
+        Builtin::new(Special, |env, args| Box::pin(exec::main(env, args)))
+            .with_description("replace the shell process with a command")
+            .with_synopsis("exec [name [arguments…]]"),
     ),
     (
         "exit",
-        Builtin::new(Special, |env, args| Box::pin(exit::main(env, args))),
+        Builtin::new(Special, |env, args| Box::pin(exit::main(env, args)))
+            .with_description("exit the shell")
+            .with_synopsis("exit [exit_status]"),
     ),
     ("export", {
-        let mut builtin = Builtin::new(Special, |env, args| Box::pin(export::main(env, args)));
+        let mut builtin = Builtin::new(Special, |env, args| Box::pin(export::main(env, args)))
+            .with_description("export variables to the environment")
+            .with_synopsis("export [-p] [name[=value]…]");
         builtin.is_declaration_utility = Some(true);
         builtin
     }),
     (
         "false",
-        Builtin::new(Mandatory, |env, args| Box::pin(r#false::main(env, args))),
+        Builtin::new(Mandatory, |env, args| Box::pin(r#false::main(env, args)))
+            .with_description("do nothing, unsuccessfully")
+            .with_synopsis("false"),
     ),
     (
         "fg",
-        Builtin::new(Mandatory, |env, args| Box::pin(fg::main(env, args))),
+        Builtin::new(Mandatory, |env, args| Box::pin(fg::main(env, args)))
+            .with_description("resume a job in the foreground")
+            .with_synopsis("fg [job_id]"),
     ),
     (
         "getopts",
-        Builtin::new(Mandatory, |env, args| Box::pin(getopts::main(env, args))),
+        Builtin::new(Mandatory, |env, args| Box::pin(getopts::main(env, args)))
+            .with_description("parse command options")
+            .with_synopsis("getopts option_spec variable_name [argument…]"),
+    ),
+    (
+        "help",
+        Builtin::new(Extension, |env, args| Box::pin(help::main(env, args)))
+            .with_description("print usage of built-ins")
+            .with_synopsis("help [name…]"),
     ),
     (
         "jobs",
-        Builtin::new(Mandatory, |env, args| Box::pin(jobs::main(env, args))),
+        Builtin::new(Mandatory, |env, args| Box::pin(jobs::main(env, args)))
+            .with_description("print information about jobs")
+            .with_synopsis("jobs [-lnprst] [job_id…]"),
     ),
     (
         "kill",
-        Builtin::new(Mandatory, |env, args| Box::pin(kill::main(env, args))),
+        Builtin::new(Mandatory, |env, args| Box::pin(kill::main(env, args)))
+            .with_description("send a signal to processes")
+            .with_synopsis(
+                "kill [-s SIGNAL|-n SIGNAL|-SIGNAL] target…\nkill -l|-v [SIGNAL|exit_status]…",
+            ),
     ),
     (
         "pwd",
-        Builtin::new(Mandatory, |env, args| Box::pin(pwd::main(env, args))),
+        Builtin::new(Mandatory, |env, args| Box::pin(pwd::main(env, args)))
+            .with_description("print the working directory")
+            .with_synopsis("pwd [-L|-P]"),
     ),
     #[cfg(feature = "yash-semantics")]
     (
         "read",
-        Builtin::new(Mandatory, |env, args| Box::pin(read::main(env, args))),
+        Builtin::new(Mandatory, |env, args| Box::pin(read::main(env, args)))
+            .with_description("read a line from the standard input")
+            .with_synopsis("read [-r] [-n count] [-t seconds] variable…"),
     ),
     ("readonly", {
-        let mut builtin = Builtin::new(Special, |env, args| Box::pin(readonly::main(env, args)));
+        let mut builtin = Builtin::new(Special, |env, args| Box::pin(readonly::main(env, args)))
+            .with_description("make variables or functions read-only")
+            .with_synopsis("readonly [-p] [name[=value]…]\nreadonly -f [-p] [name…]");
         builtin.is_declaration_utility = Some(true);
         builtin
     }),
     (
         "return",
-        Builtin::new(Special, |env, args| Box::pin(r#return::main(env, args))),
+        Builtin::new(Special, |env, args| Box::pin(r#return::main(env, args)))
+            .with_description("return from a function or script")
+            .with_synopsis("return [-n] [exit_status]"),
     ),
     (
         "set",
-        Builtin::new(Special, |env, args| Box::pin(set::main(env, args))),
+        Builtin::new(Special, |env, args| Box::pin(set::main(env, args)))
+            .with_description("set shell options and positional parameters")
+            .with_synopsis("set [option…] [--] [argument…]\nset -o|+o"),
     ),
     (
         "shift",
-        Builtin::new(Special, |env, args| Box::pin(shift::main(env, args))),
+        Builtin::new(Special, |env, args| Box::pin(shift::main(env, args)))
+            .with_description("remove positional parameters")
+            .with_synopsis("shift [n]"),
     ),
     #[cfg(feature = "yash-semantics")]
     (
         "source",
-        Builtin::new(Special, |env, args| Box::pin(source::main(env, args))),
+        Builtin::new(Special, |env, args| Box::pin(source::main(env, args)))
+            .with_description("read and execute commands from a file")
+            .with_synopsis("source file [arguments…]"),
+    ),
+    (
+        "suspend",
+        Builtin::new(Extension, |env, args| Box::pin(suspend::main(env, args)))
+            .with_description("suspend the shell")
+            .with_synopsis("suspend [-f]"),
     ),
     (
         "times",
-        Builtin::new(Special, |env, args| Box::pin(times::main(env, args))),
+        Builtin::new(Special, |env, args| Box::pin(times::main(env, args)))
+            .with_description("print the accumulated process times")
+            .with_synopsis("times"),
     ),
     (
         "trap",
-        Builtin::new(Special, |env, args| Box::pin(trap::main(env, args))),
+        Builtin::new(Special, |env, args| Box::pin(trap::main(env, args)))
+            .with_description("set or print signal handlers")
+            .with_synopsis("trap [action] condition…\ntrap [-p [condition…]]"),
     ),
     (
         "true",
-        Builtin::new(Mandatory, |env, args| Box::pin(r#true::main(env, args))),
+        Builtin::new(Mandatory, |env, args| Box::pin(r#true::main(env, args)))
+            .with_description("do nothing, successfully")
+            .with_synopsis("true"),
     ),
     #[cfg(feature = "yash-semantics")]
     (
         "type",
-        Builtin::new(Mandatory, |env, args| Box::pin(r#type::main(env, args))),
+        Builtin::new(Mandatory, |env, args| Box::pin(r#type::main(env, args)))
+            .with_description("identify commands")
+            .with_synopsis("type [name…]"),
     ),
     ("typeset", {
-        let mut builtin = Builtin::new(Elective, |env, args| Box::pin(typeset::main(env, args)));
+        let mut builtin = Builtin::new(Elective, |env, args| Box::pin(typeset::main(env, args)))
+            .with_description("define or print variables or functions")
+            .with_synopsis("typeset [-gprx] [+rx] [name[=value]…]\ntypeset -f [-pr] [+r] [name…]");
         builtin.is_declaration_utility = Some(true);
         builtin
     }),
     (
         "ulimit",
-        Builtin::new(Mandatory, |env, args| Box::pin(ulimit::main(env, args))),
+        Builtin::new(Mandatory, |env, args| Box::pin(ulimit::main(env, args)))
+            .with_description("get or set resource limits")
+            .with_synopsis(
+                "ulimit [-SH] [-a|-b|-c|-d|-e|-f|-i|-k|-l|-m|-n|-q|-R|-r|-s|-t|-u|-v|-w|-x] \
+                [limit]",
+            ),
     ),
     (
         "umask",
-        Builtin::new(Mandatory, |env, args| Box::pin(umask::main(env, args))),
+        Builtin::new(Mandatory, |env, args| Box::pin(umask::main(env, args)))
+            .with_description("get or set the file mode creation mask")
+            .with_synopsis("umask [-S] [mode]"),
     ),
     (
         "unalias",
-        Builtin::new(Mandatory, |env, args| Box::pin(unalias::main(env, args))),
+        Builtin::new(Mandatory, |env, args| Box::pin(unalias::main(env, args)))
+            .with_description("remove aliases")
+            .with_synopsis("unalias name…\nunalias -a"),
     ),
     (
         "unset",
-        Builtin::new(Special, |env, args| Box::pin(unset::main(env, args))),
+        Builtin::new(Special, |env, args| Box::pin(unset::main(env, args)))
+            .with_description("remove variables or functions")
+            .with_synopsis("unset [-fv] name…"),
     ),
     #[cfg(feature = "yash-semantics")]
     (
         "wait",
-        Builtin::new(Mandatory, |env, args| Box::pin(wait::main(env, args))),
+        Builtin::new(Mandatory, |env, args| Box::pin(wait::main(env, args)))
+            .with_description("wait for processes to finish")
+            .with_synopsis("wait [job_id_or_process_id…]"),
     ),
 ];
 
@@ -269,4 +356,14 @@ mod tests {
             .windows(2)
             .for_each(|pair| assert!(pair[0].0 < pair[1].0, "disordered pair: {pair:?}"))
     }
+
+    #[test]
+    fn builtins_have_description_and_synopsis() {
+        for (name, builtin) in BUILTINS {
+            assert_ne!(builtin.description, "", "{name}");
+            for line in builtin.synopsis.lines() {
+                assert!(line.starts_with(name), "{name}: {line:?}");
+            }
+        }
+    }
 }
@@ -21,7 +21,7 @@
 //! # Synopsis
 //!
 //! ```sh
-//! read [-r] variable…
+//! read [-r] [-n count] [-t seconds] variable…
 //! ```
 //!
 //! # Description
@@ -42,6 +42,16 @@
 //!
 //! The `-r` option disables this behavior.
 //!
+//! ## Limiting input
+//!
+//! With the `-n` option, the built-in stops reading after the given number of
+//! characters, even if it has not found a newline yet.
+//!
+//! With the `-t` option, the built-in stops reading when the given number of
+//! seconds has elapsed before it finds a newline.
+//!
+//! In either case, the characters read so far are assigned to the variables.
+//!
 //! ## Prompting
 //!
 //! By default, the read built-in does not display a prompt before reading a
@@ -55,7 +65,15 @@
 //!
 //! # Options
 //!
-//! The **`-r`** option disables the interpretation of backslashes.
+//! The **`-n`** (**`--count`**) option takes a non-negative integer argument
+//! that specifies the maximum number of characters to read.
+//!
+//! The **`-r`** (**`--raw-mode`**) option disables the interpretation of
+//! backslashes.
+//!
+//! The **`-t`** (**`--timeout`**) option takes a non-negative number argument
+//! that specifies the maximum number of seconds to wait for input. The number
+//! may have a fractional part.
 //!
 //! # Operands
 //!
@@ -68,17 +86,28 @@
 //!
 //! It is an error if any variable to be assigned is read-only.
 //!
+//! It is an error if the argument to the `-n` or `-t` option is not a valid
+//! number.
+//!
 //! # Exit status
 //!
 //! The exit status is zero if a line was read successfully and non-zero
 //! otherwise. If the built-in reaches the end of the input before finding a
 //! newline, it returns non-zero, but the variables are still assigned with the
-//! line read so far.
+//! line read so far. Reading as many characters as specified by the `-n`
+//! option counts as success.
+//!
+//! If the timeout specified by the `-t` option expires, the exit status is the
+//! one for `SIGALRM`, which is greater than 128.
+//!
+//! If a trapped signal is caught while waiting for input, the built-in runs
+//! the trap action and returns the exit status for the signal.
 //!
 //! # Portability
 //!
 //! The read built-in is defined in the POSIX standard. The `-r` option is the
-//! only option defined in the POSIX standard.
+//! only option defined in the POSIX standard. The `-n` and `-t` options are
+//! extensions also found in other shells, but their exact behavior differs.
 //!
 //! In this implementation, the value of the `PS2` variable is subject to
 //! parameter expansion, command substitution, and arithmetic expansion. Other
@@ -89,12 +118,16 @@
 //! Reading from an unseekable input may be slow because the built-in reads the
 //! input byte by byte to make sure it does not read past the end of the line.
 
-use crate::common::report_error;
 use crate::common::report_failure;
+use crate::common::report_usage_error;
 use crate::common::to_single_message;
+use input::Ending;
+use std::time::Duration;
 use yash_env::semantics::ExitStatus;
 use yash_env::semantics::Field;
+use yash_env::signal;
 use yash_env::Env;
+use yash_env::System as _;
 
 pub mod assigning;
 pub mod input;
@@ -113,6 +146,12 @@ pub struct Command {
     /// If this field is `true`, backslashes are not interpreted.
     pub is_raw: bool,
 
+    /// Maximum number of characters to read, specified by the `-n` option
+    pub max_chars: Option<usize>,
+
+    /// Time limit for reading, specified by the `-t` option
+    pub timeout: Option<Duration>,
+
     /// Names of variables to be assigned, except the last one
     pub variables: Vec<Field>,
 
@@ -127,19 +166,32 @@ pub struct Command {
 pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
     let command = match syntax::parse(env, args) {
         Ok(command) => command,
-        Err(error) => return report_error(env, &error).await,
+        Err(error) => return report_usage_error(env, &error).await,
     };
 
-    let (input, newline_found) = match input::read(env, command.is_raw).await {
+    let result = input::read(env, command.is_raw, command.max_chars, command.timeout).await;
+    let (input, ending) = match result {
         Ok(input) => input,
         Err(error) => return report_failure(env, &error).await,
     };
 
     let errors = assigning::assign(env, &input, command.variables, command.last_variable);
-    let message = to_single_message(&errors);
-    match message {
-        None if newline_found => ExitStatus::SUCCESS.into(),
-        None => ExitStatus::FAILURE.into(),
-        Some(message) => report_failure(env, message).await,
+    if let Some(message) = to_single_message(&errors) {
+        return report_failure(env, message).await;
     }
+    match ending {
+        Ending::Newline | Ending::CountReached => ExitStatus::SUCCESS.into(),
+        Ending::EndOfInput => ExitStatus::FAILURE.into(),
+        Ending::Timeout => timeout_exit_status(env).into(),
+        Ending::Trapped(signal, result) => {
+            crate::Result::with_exit_status_and_divert(ExitStatus::from(signal), result)
+        }
+    }
+}
+
+/// Returns the exit status for a timeout.
+fn timeout_exit_status(env: &Env) -> ExitStatus {
+    env.system
+        .signal_number_from_name(signal::Name::Alrm)
+        .map_or(ExitStatus::FAILURE, ExitStatus::from)
 }
@@ -20,13 +20,14 @@ use yash_env::semantics::Field;
 use yash_env::variable::Scope;
 use yash_env::variable::IFS;
 use yash_env::Env;
+use yash_semantics::assign::assign_variable;
 use yash_semantics::expansion::attr::AttrChar;
 use yash_semantics::expansion::attr_strip::Strip as _;
 use yash_semantics::expansion::quote_removal::skip_quotes;
 use yash_semantics::expansion::split::Class;
 use yash_semantics::expansion::split::Ifs;
 
-pub use crate::typeset::AssignReadOnlyError as Error;
+pub use yash_semantics::expansion::Error;
 
 /// Assigns the text to variables.
 ///
@@ -87,16 +88,7 @@ pub fn assign(
 fn assign_one(env: &mut Env, name: Field, value: &[AttrChar]) -> Result<(), Error> {
     let value = value.iter().copied();
     let value = skip_quotes(value).strip().collect::<String>();
-    let mut var = env.get_or_create_variable(name.value.clone(), Scope::Global);
-    match var.assign(value, name.origin) {
-        Ok(_old_value) => Ok(()),
-        Err(e) => Err(Error {
-            name: name.value,
-            new_value: e.new_value,
-            assigned_location: e.assigned_location.unwrap(),
-            read_only_location: e.read_only_location,
-        }),
-    }
+    assign_variable(env, name.value, Scope::Global, value.into(), name.origin).map(drop)
 }
 
 #[cfg(test)]
@@ -107,6 +99,8 @@ mod tests {
     use yash_env::variable::Variable;
     use yash_env::variable::VariableSet;
     use yash_semantics::expansion::attr::Origin;
+    use yash_semantics::expansion::AssignReadOnlyError;
+    use yash_semantics::expansion::ErrorCause;
     use yash_syntax::source::Location;
 
     fn attr_chars(s: &str) -> Vec<AttrChar> {
@@ -389,16 +383,22 @@ mod tests {
 
         assert_matches!(&errors[..], [first, last] => {
             assert_eq!(first, &Error {
-                name: "first".into(),
-                new_value: "1".into(),
-                assigned_location: Location::dummy("first"),
-                read_only_location: Location::dummy("first read-only"),
+                cause: ErrorCause::AssignReadOnly(AssignReadOnlyError {
+                    name: "first".into(),
+                    new_value: "1".into(),
+                    read_only_location: Location::dummy("first read-only"),
+                    vacancy: None,
+                }),
+                location: Location::dummy("first"),
             });
             assert_eq!(last, &Error {
-                name: "last".into(),
-                new_value: "33".into(),
-                assigned_location: Location::dummy("last"),
-                read_only_location: Location::dummy("last read-only"),
+                cause: ErrorCause::AssignReadOnly(AssignReadOnlyError {
+                    name: "last".into(),
+                    new_value: "33".into(),
+                    read_only_location: Location::dummy("last read-only"),
+                    vacancy: None,
+                }),
+                location: Location::dummy("last"),
             });
         });
         assert_variable(&env.variables, "second", "222");
@@ -16,11 +16,22 @@
 
 //! Reading input
 
+use std::future::poll_fn;
+use std::future::Future as _;
+use std::ops::ControlFlow::{self, Break, Continue};
+use std::pin::pin;
+use std::rc::Rc;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
 use thiserror::Error;
+use yash_env::signal;
 use yash_env::system::Errno;
 use yash_env::Env;
+use yash_env::System as _;
 use yash_semantics::expansion::attr::AttrChar;
 use yash_semantics::expansion::attr::Origin;
+use yash_semantics::trap::run_trap_if_caught;
 use yash_syntax::source::pretty::AnnotationType;
 use yash_syntax::source::pretty::Message;
 use yash_syntax::syntax::Fd;
@@ -83,11 +94,28 @@ fn plain(value: char) -> AttrChar {
     }
 }
 
+/// Reason why [`read`] stopped reading
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Ending {
+    /// A newline was found.
+    Newline,
+    /// The maximum number of characters were read.
+    CountReached,
+    /// The end of input was reached before a newline.
+    EndOfInput,
+    /// The timeout expired before a newline was found.
+    Timeout,
+    /// A signal was caught and the trap action was executed.
+    Trapped(signal::Number, yash_env::semantics::Result),
+}
+
 /// Reads a line from the standard input.
 ///
 /// This function reads a line from the standard input and returns a vector of
 /// [`AttrChar`]s representing the line. The line is terminated by a newline
-/// character, which is not included in the returned vector.
+/// character, which is not included in the returned vector. Only the
+/// terminating newline is removed; a carriage return preceding it is returned
+/// as part of the line.
 ///
 /// If `is_raw` is `true`, the read line is not subject to backslash processing.
 /// Otherwise, backslash-newline pairs are treated as line continuations, and
@@ -98,47 +126,82 @@ fn plain(value: char) -> AttrChar {
 /// is interactive and the input is from a terminal. This requires the optional
 /// `yash-prompt` feature.
 ///
+/// If `max_chars` is `Some(count)`, this function stops reading after `count`
+/// characters have been read, without waiting for a newline. Quoting
+/// backslashes and line continuations are not counted.
+///
+/// If `timeout` is `Some(duration)`, this function stops reading when the
+/// duration has elapsed before finding a newline. A duration too long to
+/// compute the deadline is treated as no timeout.
+///
+/// While waiting for input, this function executes the trap action for any
+/// trapped signal that is caught, and then stops reading.
+///
 /// If successful, this function returns a vector of [`AttrChar`]s representing
-/// the line read and a boolean value indicating whether the line was terminated
-/// by a newline character.
-pub async fn read(env: &mut Env, is_raw: bool) -> Result<(Vec<AttrChar>, bool), Error> {
+/// the characters read and the reason why reading stopped. The vector contains
+/// the characters read so far even if reading stopped before a newline.
+pub async fn read(
+    env: &mut Env,
+    is_raw: bool,
+    max_chars: Option<usize>,
+    timeout: Option<Duration>,
+) -> Result<(Vec<AttrChar>, Ending), Error> {
+    let deadline = timeout.and_then(|timeout| env.system.now().checked_add(timeout));
     let mut result = Vec::new();
+    let mut count = 0;
+
+    let ending = loop {
+        if max_chars.is_some_and(|max_chars| count >= max_chars) {
+            break Ending::CountReached;
+        }
 
-    let newline_found = loop {
         // TODO Read in bulk if the standard input is seekable
-        match read_char(env).await? {
-            None => break false,
-            Some('\n') => break true,
+        let c = match read_char(env, deadline).await? {
+            Continue(c) => c,
+            Break(ending) => break ending,
+        };
+        match c {
+            '\n' => break Ending::Newline,
 
             // Backslash escape
-            Some('\\') if !is_raw => {
-                let c = read_char(env).await?;
-                if c == Some('\n') {
+            '\\' if !is_raw => {
+                let c = match read_char(env, deadline).await? {
+                    Continue(c) => c,
+                    Break(ending) => {
+                        result.push(quoting('\\'));
+                        break ending;
+                    }
+                };
+                if c == '\n' {
                     // Line continuation
                     print_prompt(env).await;
                     continue;
                 }
                 result.push(quoting('\\'));
-                match c {
-                    None => break false,
-                    Some(c) => result.push(quoted(c)),
-                }
+                result.push(quoted(c));
             }
 
             // Plain character
-            Some(c) => result.push(plain(c)),
+            c => result.push(plain(c)),
         }
+        count += 1;
     };
 
-    Ok((result, newline_found))
+    Ok((result, ending))
 }
 
 /// Reads one character from the standard input.
 ///
 /// This function reads a single UTF-8-encoded character from the standard
-/// input. If the standard input is empty, this function returns `Ok(None)`.
-/// If the input is not a valid UTF-8 sequence, this function returns an error.
-async fn read_char(env: &mut Env) -> Result<Option<char>, Error> {
+/// input. If the standard input is empty, this function returns
+/// `Break(Ending::EndOfInput)`. If the input is not a valid UTF-8 sequence,
+/// this function returns an error.
+///
+/// See [`read_byte`] for the other cases where this function returns `Break`.
+async fn read_char(
+    env: &mut Env,
+    deadline: Option<Instant>,
+) -> Result<ControlFlow<Ending, char>, Error> {
     // Any character is at most 4 bytes in UTF-8.
     let mut buffer = [0; 4];
     let mut len = 0;
@@ -146,11 +209,14 @@ async fn read_char(env: &mut Env) -> Result<Option<char>, Error> {
         // Read from the standard input byte by byte so that we don't consume
         // more than one character.
         let byte = std::slice::from_mut(&mut buffer[len]);
-        let count = env.system.read_async(Fd::STDIN, byte).await?;
+        let count = match read_byte(env, byte, deadline).await? {
+            Continue(count) => count,
+            Break(ending) => return Ok(Break(ending)),
+        };
         if count == 0 {
             // End of input
             return if len == 0 {
-                Ok(None)
+                Ok(Break(Ending::EndOfInput))
             } else {
                 // The input ended in the middle of a UTF-8 sequence.
                 Err(Errno::EILSEQ.into())
@@ -166,7 +232,7 @@ async fn read_char(env: &mut Env) -> Result<Option<char>, Error> {
                 let c = chars.next().unwrap();
                 // And it must be the only character.
                 debug_assert_eq!(chars.next(), None);
-                return Ok(Some(c));
+                return Ok(Continue(c));
             }
             Err(e) => match e.error_len() {
                 None => {
@@ -180,6 +246,56 @@ async fn read_char(env: &mut Env) -> Result<Option<char>, Error> {
     }
 }
 
+/// Reads one byte from the standard input.
+///
+/// This function waits for the standard input to be readable and reads into
+/// the one-byte buffer. It returns the number of bytes read, which is zero at
+/// the end of input.
+///
+/// If the `deadline` passes before the input becomes readable, this function
+/// returns `Break(Ending::Timeout)`. If a signal is caught while waiting and
+/// the signal has a trap action, this function executes the action and
+/// returns `Break(Ending::Trapped(..))`.
+async fn read_byte(
+    env: &mut Env,
+    byte: &mut [u8],
+    deadline: Option<Instant>,
+) -> Result<ControlFlow<Ending, usize>, Error> {
+    loop {
+        let system = env.system.clone();
+        let event = {
+            let mut read = pin!(system.read_async(Fd::STDIN, byte));
+            let mut signals = pin!(async {
+                match deadline {
+                    None => Some(system.wait_for_signals().await),
+                    Some(deadline) => system.wait_for_signals_until(deadline).await,
+                }
+            });
+            poll_fn(|context| {
+                if let Poll::Ready(result) = read.as_mut().poll(context) {
+                    return Poll::Ready(Ok(result));
+                }
+                signals.as_mut().poll(context).map(Err)
+            })
+            .await
+        };
+
+        let signals: Rc<[signal::Number]> = match event {
+            Ok(result) => return Ok(Continue(result?)),
+            Err(None) => return Ok(Break(Ending::Timeout)),
+            Err(Some(signals)) => signals,
+        };
+        for signal in signals.iter().copied() {
+            env.traps.catch_signal(signal);
+        }
+        for signal in signals.iter().copied() {
+            if let Some(result) = run_trap_if_caught(env, signal).await {
+                return Ok(Break(Ending::Trapped(signal, result)));
+            }
+        }
+    }
+}
+
 /// Prints the prompt string for the continuation line.
 ///
 /// This function prints the value of the `PS2` variable as a prompt for the
@@ -213,7 +329,10 @@ mod tests {
     use std::cell::RefCell;
     use yash_env::system::r#virtual::FileBody;
     use yash_env::system::r#virtual::SystemState;
+    use yash_env::system::r#virtual::SIGUSR1;
+    use yash_env::trap::Action;
     use yash_env_test_helper::in_virtual_system;
+    use yash_syntax::source::Location;
 
     fn set_stdin<B: Into<Vec<u8>>>(system: &RefCell<SystemState>, bytes: B) {
         let state = system.borrow_mut();
@@ -221,6 +340,17 @@ mod tests {
         stdin.borrow_mut().body = FileBody::new(bytes);
     }
 
+    /// Replaces the standard input with a pipe containing the given bytes.
+    ///
+    /// The writer end of the pipe is left open so that reading the pipe
+    /// blocks after consuming the bytes.
+    fn set_stdin_pipe(env: &mut Env, bytes: &[u8]) {
+        let (reader, writer) = env.system.pipe().unwrap();
+        env.system.dup2(reader, Fd::STDIN).unwrap();
+        env.system.close(reader).unwrap();
+        env.system.write(writer, bytes).unwrap();
+    }
+
     fn attr_chars(s: &str) -> Vec<AttrChar> {
         s.chars().map(plain).collect()
     }
@@ -228,8 +358,8 @@ mod tests {
     #[test]
     fn empty_input() {
         in_virtual_system(|mut env, _| async move {
-            let result = read(&mut env, false).await;
-            assert_eq!(result, Ok((vec![], false)));
+            let result = read(&mut env, false, None, None).await;
+            assert_eq!(result, Ok((vec![], Ending::EndOfInput)));
         })
     }
 
@@ -238,14 +368,14 @@ mod tests {
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, "foo\nbar\n");
 
-            let result = read(&mut env, false).await;
-            assert_eq!(result, Ok((attr_chars("foo"), true)));
+            let result = read(&mut env, false, None, None).await;
+            assert_eq!(result, Ok((attr_chars("foo"), Ending::Newline)));
 
-            let result = read(&mut env, false).await;
-            assert_eq!(result, Ok((attr_chars("bar"), true)));
+            let result = read(&mut env, false, None, None).await;
+            assert_eq!(result, Ok((attr_chars("bar"), Ending::Newline)));
 
-            let result = read(&mut env, false).await;
-            assert_eq!(result, Ok((vec![], false)));
+            let result = read(&mut env, false, None, None).await;
+            assert_eq!(result, Ok((vec![], Ending::EndOfInput)));
         })
     }
 
@@ -254,11 +384,24 @@ mod tests {
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, "newline");
 
-            let result = read(&mut env, false).await;
-            assert_eq!(result, Ok((attr_chars("newline"), false)));
+            let result = read(&mut env, false, None, None).await;
+            assert_eq!(result, Ok((attr_chars("newline"), Ending::EndOfInput)));
+
+            let result = read(&mut env, false, None, None).await;
+            assert_eq!(result, Ok((vec![], Ending::EndOfInput)));
+        })
+    }
+
+    #[test]
+    fn carriage_return_before_newline() {
+        in_virtual_system(|mut env, system| async move {
+            set_stdin(&system, "a\r\n\n");
+
+            let result = read(&mut env, false, None, None).await;
+            assert_eq!(result, Ok((attr_chars("a\r"), Ending::Newline)));
 
-            let result = read(&mut env, false).await;
-            assert_eq!(result, Ok((vec![], false)));
+            let result = read(&mut env, false, None, None).await;
+            assert_eq!(result, Ok((vec![], Ending::Newline)));
         })
     }
 
@@ -267,8 +410,8 @@ mod tests {
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, "©⁉😀\n");
 
-            let result = read(&mut env, false).await;
-            assert_eq!(result, Ok((attr_chars("©⁉😀"), true)));
+            let result = read(&mut env, false, None, None).await;
+            assert_eq!(result, Ok((attr_chars("©⁉😀"), Ending::Newline)));
         })
     }
 
@@ -277,8 +420,8 @@ mod tests {
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, "\\foo\\\nbar\\\nbaz\n");
 
-            let result = read(&mut env, true).await;
-            assert_eq!(result, Ok((attr_chars("\\foo\\"), true)));
+            let result = read(&mut env, true, None, None).await;
+            assert_eq!(result, Ok((attr_chars("\\foo\\"), Ending::Newline)));
         })
     }
 
@@ -287,7 +430,7 @@ mod tests {
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, "\\foo\\\nbar\\\nbaz\n");
 
-            let result = read(&mut env, false).await;
+            let result = read(&mut env, false, None, None).await;
             assert_eq!(
                 result,
                 Ok((
@@ -303,7 +446,7 @@ mod tests {
                         plain('a'),
                         plain('z'),
                     ],
-                    true,
+                    Ending::Newline,
                 )),
             );
         })
@@ -314,12 +457,12 @@ mod tests {
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, "foo\\");
 
-            let result = read(&mut env, false).await;
+            let result = read(&mut env, false, None, None).await;
             assert_eq!(
                 result,
                 Ok((
                     vec![plain('f'), plain('o'), plain('o'), quoting('\\')],
-                    false,
+                    Ending::EndOfInput,
                 )),
             );
         })
@@ -330,24 +473,136 @@ mod tests {
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, *b"\xFF");
 
-            let result = read(&mut env, false).await;
+            let result = read(&mut env, false, None, None).await;
             assert_eq!(result, Err(Errno::EILSEQ.into()));
         });
 
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, *b"\xCF\xD0");
 
-            let result = read(&mut env, false).await;
+            let result = read(&mut env, false, None, None).await;
             assert_eq!(result, Err(Errno::EILSEQ.into()));
         });
 
         in_virtual_system(|mut env, system| async move {
             set_stdin(&system, *b"\xCF");
 
-            let result = read(&mut env, false).await;
+            let result = read(&mut env, false, None, None).await;
             assert_eq!(result, Err(Errno::EILSEQ.into()));
         });
     }
 
+    #[test]
+    fn count_reached() {
+        in_virtual_system(|mut env, system| async move {
+            set_stdin(&system, "abcdef\n");
+
+            let result = read(&mut env, false, Some(3), None).await;
+            assert_eq!(result, Ok((attr_chars("abc"), Ending::CountReached)));
+
+            let result = read(&mut env, false, Some(5), None).await;
+            assert_eq!(result, Ok((attr_chars("def"), Ending::Newline)));
+        })
+    }
+
+    #[test]
+    fn count_zero() {
+        in_virtual_system(|mut env, system| async move {
+            set_stdin(&system, "foo\n");
+
+            let result = read(&mut env, false, Some(0), None).await;
+            assert_eq!(result, Ok((vec![], Ending::CountReached)));
+
+            let result = read(&mut env, false, None, None).await;
+            assert_eq!(result, Ok((attr_chars("foo"), Ending::Newline)));
+        })
+    }
+
+    #[test]
+    fn count_excludes_quoting_backslashes_and_line_continuations() {
+        in_virtual_system(|mut env, system| async move {
+            set_stdin(&system, "\\a\\\nbc");
+
+            let result = read(&mut env, false, Some(2), None).await;
+            assert_eq!(
+                result,
+                Ok((
+                    vec![quoting('\\'), quoted('a'), plain('b')],
+                    Ending::CountReached,
+                )),
+            );
+        })
+    }
+
+    #[test]
+    fn line_read_within_timeout() {
+        in_virtual_system(|mut env, system| async move {
+            let start = Instant::now();
+            system.borrow_mut().now = Some(start);
+            set_stdin_pipe(&mut env, b"foo\n");
+
+            let timeout = Some(Duration::from_secs(5));
+            let result = read(&mut env, false, None, timeout).await;
+            assert_eq!(result, Ok((attr_chars("foo"), Ending::Newline)));
+            assert_eq!(system.borrow().now, Some(start));
+        })
+    }
+
+    #[test]
+    fn timeout_expiring_mid_line() {
+        in_virtual_system(|mut env, system| async move {
+            let start = Instant::now();
+            system.borrow_mut().now = Some(start);
+            set_stdin_pipe(&mut env, b"foo");
+
+            let timeout = Some(Duration::from_secs(5));
+            let result = read(&mut env, false, None, timeout).await;
+            assert_eq!(result, Ok((attr_chars("foo"), Ending::Timeout)));
+            assert_eq!(system.borrow().now, Some(start + Duration::from_secs(5)));
+        })
+    }
+
+    #[test]
+    fn huge_timeout_is_no_deadline() {
+        in_virtual_system(|mut env, system| async move {
+            let start = Instant::now();
+            system.borrow_mut().now = Some(start);
+            set_stdin_pipe(&mut env, b"foo\n");
+
+            let timeout = Some(Duration::from_secs_f64(1e19));
+            let result = read(&mut env, false, None, timeout).await;
+            assert_eq!(result, Ok((attr_chars("foo"), Ending::Newline)));
+            assert_eq!(system.borrow().now, Some(start));
+        })
+    }
+
+    #[test]
+    fn trap_executed_while_waiting_for_input() {
+        in_virtual_system(|mut env, system| async move {
+            env.traps
+                .set_action(
+                    &mut env.system,
+                    SIGUSR1,
+                    Action::Command("x=trapped".into()),
+                    Location::dummy(""),
+                    false,
+                )
+                .unwrap();
+            set_stdin_pipe(&mut env, b"foo");
+            {
+                let mut state = system.borrow_mut();
+                let process = state.processes.get_mut(&env.main_pid).unwrap();
+                _ = process.raise_signal(SIGUSR1);
+            }
+
+            let result = read(&mut env, false, None, None).await;
+            assert_eq!(
+                result,
+                Ok((attr_chars("foo"), Ending::Trapped(SIGUSR1, Continue(())))),
+            );
+            assert_eq!(env.variables.get_scalar("x"), Some("trapped"));
+        })
+    }
+
     // TODO Test PS2 prompt
 }
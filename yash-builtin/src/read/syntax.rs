@@ -19,10 +19,13 @@
 use super::Command;
 use crate::common::syntax::parse_arguments;
 use crate::common::syntax::Mode;
+use crate::common::syntax::OptionArgumentSpec;
 use crate::common::syntax::OptionSpec;
+use std::time::Duration;
 use thiserror::Error;
 use yash_env::semantics::Field;
 use yash_env::Env;
+use yash_syntax::source::pretty::Annotation;
 use yash_syntax::source::pretty::AnnotationType;
 use yash_syntax::source::pretty::Message;
 
@@ -34,6 +37,14 @@ pub enum Error {
     #[error(transparent)]
     CommonError(#[from] crate::common::syntax::ParseError<'static>),
 
+    /// The argument to the `-n` option is not a non-negative integer.
+    #[error("invalid character count")]
+    InvalidCount(Field),
+
+    /// The argument to the `-t` option is not a non-negative number.
+    #[error("invalid timeout")]
+    InvalidTimeout(Field),
+
     /// No operand is given.
     #[error("missing operand")]
     MissingOperand,
@@ -45,6 +56,28 @@ impl Error {
         match self {
             Error::CommonError(e) => e.into(),
 
+            Error::InvalidCount(field) => Message {
+                r#type: AnnotationType::Error,
+                title: self.to_string().into(),
+                annotations: vec![Annotation::new(
+                    AnnotationType::Error,
+                    format!("{:?} is not a non-negative integer", field.value).into(),
+                    &field.origin,
+                )],
+                footers: vec![],
+            },
+
+            Error::InvalidTimeout(field) => Message {
+                r#type: AnnotationType::Error,
+                title: self.to_string().into(),
+                annotations: vec![Annotation::new(
+                    AnnotationType::Error,
+                    format!("{:?} is not a non-negative number of seconds", field.value).into(),
+                    &field.origin,
+                )],
+                footers: vec![],
+            },
+
             Error::MissingOperand => Message {
                 r#type: AnnotationType::Error,
                 title: self.to_string().into(),
@@ -62,7 +95,30 @@ impl<'a> From<&'a Error> for Message<'a> {
     }
 }
 
-const OPTION_SPECS: &[OptionSpec] = &[OptionSpec::new().short('r').long("raw-mode")];
+const OPTION_SPECS: &[OptionSpec] = &[
+    OptionSpec::new()
+        .short('n')
+        .long("count")
+        .argument(OptionArgumentSpec::Required),
+    OptionSpec::new().short('r').long("raw-mode"),
+    OptionSpec::new()
+        .short('t')
+        .long("timeout")
+        .argument(OptionArgumentSpec::Required),
+];
+
+fn parse_count(field: Field) -> Result<usize, Error> {
+    field.value.parse().map_err(|_| Error::InvalidCount(field))
+}
+
+fn parse_timeout(field: Field) -> Result<Duration, Error> {
+    field
+        .value
+        .parse()
+        .ok()
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .ok_or(Error::InvalidTimeout(field))
+}
 
 /// Parses command line arguments.
 pub fn parse(env: &Env, args: Vec<Field>) -> Result<Command, Error> {
@@ -71,9 +127,13 @@ pub fn parse(env: &Env, args: Vec<Field>) -> Result<Command, Error> {
 
     // Parse options
     let mut is_raw = false;
+    let mut max_chars = None;
+    let mut timeout = None;
     for option in options {
         match option.spec.get_short() {
+            Some('n') => max_chars = Some(parse_count(option.argument.unwrap())?),
             Some('r') => is_raw = true,
+            Some('t') => timeout = Some(parse_timeout(option.argument.unwrap())?),
             _ => unreachable!(),
         }
     }
@@ -84,6 +144,8 @@ pub fn parse(env: &Env, args: Vec<Field>) -> Result<Command, Error> {
 
     Ok(Command {
         is_raw,
+        max_chars,
+        timeout,
         variables,
         last_variable,
     })
@@ -92,6 +154,7 @@ pub fn parse(env: &Env, args: Vec<Field>) -> Result<Command, Error> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use assert_matches::assert_matches;
 
     #[test]
     fn no_raw_mode() {
@@ -100,6 +163,8 @@ mod tests {
             parse(&env, Field::dummies(["var"])),
             Ok(Command {
                 is_raw: false,
+                max_chars: None,
+                timeout: None,
                 variables: vec![],
                 last_variable: Field::dummy("var"),
             })
@@ -113,12 +178,79 @@ mod tests {
             parse(&env, Field::dummies(["-r", "var"])),
             Ok(Command {
                 is_raw: true,
+                max_chars: None,
+                timeout: None,
+                variables: vec![],
+                last_variable: Field::dummy("var"),
+            })
+        );
+    }
+
+    #[test]
+    fn count() {
+        let env = Env::new_virtual();
+        assert_eq!(
+            parse(&env, Field::dummies(["-n", "3", "var"])),
+            Ok(Command {
+                is_raw: false,
+                max_chars: Some(3),
+                timeout: None,
                 variables: vec![],
                 last_variable: Field::dummy("var"),
             })
         );
     }
 
+    #[test]
+    fn invalid_count() {
+        let env = Env::new_virtual();
+        assert_eq!(
+            parse(&env, Field::dummies(["-n", "-1", "var"])),
+            Err(Error::InvalidCount(Field::dummy("-1")))
+        );
+        assert_matches!(
+            parse(&env, Field::dummies(["-nx", "var"])),
+            Err(Error::InvalidCount(field)) => assert_eq!(field.value, "x")
+        );
+    }
+
+    #[test]
+    fn timeout() {
+        let env = Env::new_virtual();
+        assert_eq!(
+            parse(&env, Field::dummies(["-t", "1.5", "var"])),
+            Ok(Command {
+                is_raw: false,
+                max_chars: None,
+                timeout: Some(Duration::from_millis(1500)),
+                variables: vec![],
+                last_variable: Field::dummy("var"),
+            })
+        );
+        assert_eq!(
+            parse(&env, Field::dummies(["-rt0", "var"])),
+            Ok(Command {
+                is_raw: true,
+                max_chars: None,
+                timeout: Some(Duration::ZERO),
+                variables: vec![],
+                last_variable: Field::dummy("var"),
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_timeout() {
+        let env = Env::new_virtual();
+        for value in ["-1", "x", "inf", "nan", ""] {
+            assert_eq!(
+                parse(&env, Field::dummies(["-t", value, "var"])),
+                Err(Error::InvalidTimeout(Field::dummy(value))),
+                "{value:?}"
+            );
+        }
+    }
+
     #[test]
     fn many_operands() {
         let env = Env::new_virtual();
@@ -126,6 +258,8 @@ mod tests {
             parse(&env, Field::dummies(["foo", "bar"])),
             Ok(Command {
                 is_raw: false,
+                max_chars: None,
+                timeout: None,
                 variables: Field::dummies(["foo"]),
                 last_variable: Field::dummy("bar"),
             })
@@ -135,6 +269,8 @@ mod tests {
             parse(&env, Field::dummies(["first", "second", "third"])),
             Ok(Command {
                 is_raw: false,
+                max_chars: None,
+                timeout: None,
                 variables: Field::dummies(["first", "second"]),
                 last_variable: Field::dummy("third"),
             })
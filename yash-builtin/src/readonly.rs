@@ -188,8 +188,8 @@
 //! - Printed commands do not include options that modify variable attributes.
 
 use crate::common::output;
-use crate::common::report_error;
 use crate::common::report_failure;
+use crate::common::report_usage_error;
 use crate::common::to_single_message;
 use crate::typeset::syntax::interpret;
 use crate::typeset::syntax::parse;
@@ -239,9 +239,9 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> Result {
                     Err(errors) => report_failure(env, to_single_message(&errors).unwrap()).await,
                 }
             }
-            Err(error) => report_error(env, &error).await,
+            Err(error) => report_usage_error(env, &error).await,
         },
-        Err(error) => report_error(env, &error).await,
+        Err(error) => report_usage_error(env, &error).await,
     }
 }
 
@@ -123,7 +123,7 @@
 //! place of an option-operand separator. This behavior is not portable either.
 
 use crate::common::output;
-use crate::common::report_error;
+use crate::common::report_usage_error;
 use std::fmt::Write;
 use yash_env::builtin::Result;
 #[cfg(doc)]
@@ -206,12 +206,8 @@ fn modify(
 pub async fn main(env: &mut Env, args: Vec<Field>) -> Result {
     match syntax::parse(args) {
         Ok(Command::PrintVariables) => {
-            let mut vars: Vec<_> = env.variables.iter(Global).collect();
-            // TODO apply current locale's collation
-            vars.sort_unstable_by_key(|&(name, _)| name);
-
             let mut print = String::new();
-            for (name, var) in vars {
+            for (name, var) in env.variables.iter(Global) {
                 if let Some(value) = &var.value {
                     // TODO skip if the name contains a character inappropriate for a name
                     writeln!(print, "{}={}", name, value.quote()).unwrap();
@@ -221,24 +217,12 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> Result {
         }
 
         Ok(Command::PrintOptionsHumanReadable) => {
-            let mut print = String::new();
-            for option in yash_env::option::Option::iter() {
-                let state = env.options.get(option);
-                writeln!(print, "{option:16} {state}").unwrap();
-            }
+            let print = env.options.to_human_readable();
             output(env, &print).await
         }
 
         Ok(Command::PrintOptionsMachineReadable) => {
-            let mut print = String::new();
-            for option in yash_env::option::Option::iter() {
-                let skip = if option.is_modifiable() { "" } else { "#" };
-                let flag = match env.options.get(option) {
-                    State::On => '-',
-                    State::Off => '+',
-                };
-                writeln!(print, "{skip}set {flag}o {option}").unwrap();
-            }
+            let print = env.options.to_commands();
             output(env, &print).await
         }
 
@@ -250,7 +234,7 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> Result {
             Result::new(ExitStatus::SUCCESS)
         }
 
-        Err(error) => report_error(env, &error).await,
+        Err(error) => report_usage_error(env, &error).await,
     }
 }
 
@@ -311,18 +295,23 @@ mod tests {
             assert_eq!(
                 stdout,
                 "allexport        on
+braceexpand      off
+caseglob         on
 clobber          on
 cmdline          off
 errexit          off
 exec             on
+failglob         off
 glob             on
 hashondefinition off
 ignoreeof        off
 interactive      off
+lastpipe         off
 log              on
 login            off
 monitor          off
 notify           off
+nullglob         off
 posixlycorrect   off
 stdin            off
 unset            off
@@ -366,6 +355,37 @@ xtrace           off
         assert_stderr(&state, |stderr| assert_eq!(stderr, ""));
     }
 
+    #[test]
+    fn machine_readable_output_restores_all_modifiable_options() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        env.builtins.insert(
+            "set",
+            Builtin::new(Special, |env, args| Box::pin(main(env, args))),
+        );
+        // Turning off Exec would prevent the rest of the output from being
+        // executed, and turning on Monitor would require job control.
+        for option in yash_env::option::Option::iter() {
+            if option.is_modifiable() && option != Exec && option != Monitor {
+                env.options.set(option, !env.options.get(option));
+            }
+        }
+        let options = env.options;
+
+        let args = Field::dummies(["+o"]);
+        let result = main(&mut env, args).now_or_never().unwrap();
+        assert_eq!(result, Result::new(ExitStatus::SUCCESS));
+        let commands: List = assert_stdout(&state, |stdout| stdout.parse().unwrap());
+
+        env.options = Default::default();
+        let result = commands.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+        assert_eq!(env.options, options);
+        assert_stderr(&state, |stderr| assert_eq!(stderr, ""));
+    }
+
     #[test]
     fn setting_some_options() {
         let mut env = Env::new_virtual();
@@ -19,9 +19,10 @@
 use super::Command;
 use std::iter::Peekable;
 use thiserror::Error;
-use yash_env::option::canonicalize;
-use yash_env::option::parse_long;
-use yash_env::option::parse_short;
+use yash_env::option::parse_long_with_sign;
+use yash_env::option::parse_short_with_sign;
+use yash_env::option::split_long_option;
+use yash_env::option::split_short_options;
 use yash_env::option::FromStrError::*;
 use yash_env::option::State;
 use yash_env::semantics::Field;
@@ -94,40 +95,26 @@ fn try_parse_short<I: Iterator<Item = Field>>(
     args: &mut Peekable<I>,
     option_occurrences: &mut Vec<(yash_env::option::Option, State)>,
 ) -> Result<bool, Error> {
-    let field = match args.peek() {
-        Some(field) => field,
-        None => return Ok(false),
+    let Some(field) = args.next_if(|field| split_short_options(&field.value).is_some()) else {
+        return Ok(false);
     };
 
-    let mut chars = field.value.chars();
-    let negate = match chars.next() {
-        Some('-') => false,
-        Some('+') => true,
-        _ => return Ok(false),
-    };
-    match chars.next() {
-        Some('-') if !negate => return Ok(false),
-        Some('+') if negate => return Ok(false),
-        None => return Ok(false),
-        _ => (),
-    }
-
-    let mut field = args.next().unwrap();
-    let mut chars = field.value.chars();
-    chars.next().unwrap();
+    let mut field = field;
+    let (negate, options) = split_short_options(&field.value).unwrap();
+    let mut chars = options.chars();
     while let Some(c) = chars.next() {
         if c == 'o' {
             let name = chars.as_str();
-            let name = if !name.is_empty() {
-                canonicalize(name)
+            let result = if !name.is_empty() {
+                parse_long_with_sign(name, negate)
             } else {
                 let prev = field;
                 field = args.next().ok_or(Error::MissingOptionArgument(prev))?;
-                canonicalize(&field.value)
+                parse_long_with_sign(&field.value, negate)
             };
-            match parse_long(&name) {
+            match result {
                 Ok((option, state)) if option.is_modifiable() => {
-                    option_occurrences.push((option, if negate { !state } else { state }));
+                    option_occurrences.push((option, state));
                     break;
                 }
                 Ok(_) => return Err(Error::UnmodifiableLongOption(field)),
@@ -136,9 +123,9 @@ fn try_parse_short<I: Iterator<Item = Field>>(
             }
         }
 
-        match parse_short(c) {
+        match parse_short_with_sign(c, negate) {
             Some((option, state)) if option.is_modifiable() => {
-                option_occurrences.push((option, if negate { !state } else { state }))
+                option_occurrences.push((option, state))
             }
             Some(_) => return Err(Error::UnmodifiableShortOption(c, field)),
             None => return Err(Error::UnknownShortOption(c, field)),
@@ -151,29 +138,13 @@ fn try_parse_short<I: Iterator<Item = Field>>(
 fn try_parse_long<I: Iterator<Item = Field>>(
     args: &mut Peekable<I>,
 ) -> Result<std::option::Option<(yash_env::option::Option, State)>, Error> {
-    let field = match args.peek() {
-        Some(field) => field,
-        None => return Ok(None),
-    };
-
-    let (name, negate) = if let Some(name) = field.value.strip_prefix("--") {
-        if name.is_empty() {
-            return Ok(None);
-        }
-        (name, false)
-    } else if let Some(name) = field.value.strip_prefix("++") {
-        (name, true)
-    } else {
+    let Some(field) = args.next_if(|field| split_long_option(&field.value).is_some()) else {
         return Ok(None);
     };
 
-    let name = canonicalize(name);
-    let result = parse_long(&name);
-    let field = args.next().unwrap();
-    match result {
-        Ok((option, state)) if option.is_modifiable() => {
-            Ok(Some((option, if negate { !state } else { state })))
-        }
+    let (negate, name) = split_long_option(&field.value).unwrap();
+    match parse_long_with_sign(name, negate) {
+        Ok((option, state)) if option.is_modifiable() => Ok(Some((option, state))),
         Ok(_) => Err(Error::UnmodifiableLongOption(field)),
         Err(NoSuchOption) => Err(Error::UnknownLongOption(field)),
         Err(Ambiguous) => Err(Error::AmbiguousLongOption(field)),
@@ -32,12 +32,16 @@
 //!
 //! The `.` built-in reads and executes commands from the specified file in the
 //! current shell environment.
+//! Like the `eval` built-in, the file is parsed with the aliases defined in the
+//! current environment, and aliases defined in the file remain defined after
+//! the built-in returns.
 //!
 //! If the filename does not contain a slash, the shell searches the directories
 //! in the `$PATH` variable for the file. The file does not need to be
-//! executable; any readable file found first is used. (TODO: If no file is
-//! found, the built-in falls back to the file in the current working
-//! directory.)
+//! executable; any readable file found first is used. An empty component in
+//! `$PATH` names the current working directory. If `$PATH` is unset, no
+//! directory is searched. (TODO: If no file is found, the built-in falls back
+//! to the file in the current working directory.)
 //!
 //! If there are any operands after the filename, they are assigned to the
 //! positional parameters (`$1`, `$2`, etc.) during the execution of the file.
@@ -67,6 +71,10 @@
 //! It is an error if the file cannot be found or read.
 //! During parsing and execution, any syntax error or runtime error may occur.
 //!
+//! It is also an error if the [call depth
+//! limit](yash_env::Env::call_depth_limit) has been reached, which usually
+//! results from infinite recursion.
+//!
 //! # Exit status
 //!
 //! The exit status of the source built-in is the exit status of the last
@@ -77,6 +85,8 @@
 //! ([`ExitStatus::FAILURE`]).
 //! In case of a syntax error in the file, the exit status is 2
 //! ([`ExitStatus::ERROR`]).
+//! If an I/O error occurs while reading commands from the file, the exit
+//! status is 126 ([`ExitStatus::NOEXEC`]).
 //!
 //! # Portability
 //!
@@ -18,6 +18,7 @@
 
 use super::Command;
 use crate::common::report_failure;
+use crate::common::report_simple_error;
 use std::cell::RefCell;
 use std::ffi::CStr;
 use std::ffi::CString;
@@ -37,6 +38,8 @@ use yash_env::system::OfdAccess;
 use yash_env::system::OpenFlag;
 use yash_env::system::System;
 use yash_env::system::SystemEx as _;
+use yash_env::variable::Context;
+use yash_env::variable::PositionalParams;
 use yash_env::variable::PATH;
 use yash_env::Env;
 use yash_semantics::read_eval_loop;
@@ -51,7 +54,17 @@ impl Command {
     ///
     /// If the file is not found or cannot be read, this method reports an error
     /// to the standard error and returns `ExitStatus::FAILURE.into()`.
+    /// If the [call depth limit](Env::call_depth_limit) has been reached, this
+    /// method reports an error and returns `ExitStatus::ERROR` without
+    /// executing the file.
     pub async fn execute(self, env: &mut Env) -> crate::Result {
+        if env.call_depth_exceeded() {
+            let title = format!(
+                "too deep recursion: call depth limit {} exceeded",
+                env.call_depth_limit
+            );
+            return report_simple_error(env, &title).await;
+        }
         let env = &mut *env.push_frame(Frame::DotScript);
 
         let fd = match find_and_open_file(env, &self.file.value) {
@@ -59,7 +72,18 @@ impl Command {
             Err(errno) => return report_find_and_open_file_failure(env, &self.file, errno).await,
         };
 
-        // TODO set positional parameters
+        // Set positional parameters
+        let mut context_guard;
+        let env = if self.params.is_empty() {
+            env
+        } else {
+            let positional_params = PositionalParams {
+                values: self.params.into_iter().map(|field| field.value).collect(),
+                last_modified_location: Some(self.file.origin.clone()),
+            };
+            context_guard = env.push_context(Context::Regular { positional_params });
+            &mut *context_guard
+        };
 
         // Parse and execute the command script
         let system = env.system.clone();
@@ -244,6 +268,38 @@ mod tests {
         });
     }
 
+    #[test]
+    fn empty_path_component_names_current_directory() {
+        let mut system = system_with_file("/dir/file", "");
+        system.current_process_mut().chdir(PathBuf::from("/dir"));
+        let mut env = Env::with_system(Box::new(system));
+        env.variables
+            .get_or_new(PATH, Scope::Global)
+            .assign("/foo:/bar", None)
+            .unwrap();
+
+        let result = find_and_open_file(&mut env, "file");
+        assert_eq!(result, Err(Errno::ENOENT));
+
+        env.variables
+            .get_or_new(PATH, Scope::Global)
+            .assign("/foo::/bar", None)
+            .unwrap();
+
+        let result = find_and_open_file(&mut env, "file");
+        assert_matches!(result, Ok(fd) if fd >= MIN_INTERNAL_FD);
+    }
+
+    #[test]
+    fn file_not_searched_without_path() {
+        let mut system = system_with_file("/dir/file", "");
+        system.current_process_mut().chdir(PathBuf::from("/dir"));
+        let mut env = Env::with_system(Box::new(system));
+
+        let result = find_and_open_file(&mut env, "file");
+        assert_eq!(result, Err(Errno::ENOENT));
+    }
+
     #[test]
     fn open_file_result_lower_bound() {
         let mut system = system_with_file("/foo/file", "");
@@ -261,6 +317,42 @@ mod tests {
         assert_eq!(fd_body.flags, EnumSet::only(FdFlag::CloseOnExec));
     }
 
+    #[test]
+    fn operands_are_positional_parameters_during_execution() {
+        let system = system_with_file("/foo/file", "count=$# params=$*");
+        let mut env = Env::with_system(Box::new(system));
+        env.variables.positional_params_mut().values = vec!["a".to_string()];
+        let command = Command {
+            file: Field::dummy("/foo/file"),
+            params: Field::dummies(["1", "2"]),
+        };
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+
+        assert_eq!(result, crate::Result::new(ExitStatus::SUCCESS));
+        let count = env.variables.get("count").unwrap();
+        assert_eq!(count.value, Some("2".into()));
+        let params = env.variables.get("params").unwrap();
+        assert_eq!(params.value, Some("1 2".into()));
+        assert_eq!(env.variables.positional_params().values, ["a"]);
+    }
+
+    #[test]
+    fn positional_parameters_are_shared_without_operands() {
+        let system = system_with_file("/foo/file", "count=$#");
+        let mut env = Env::with_system(Box::new(system));
+        env.variables.positional_params_mut().values = vec!["a".to_string()];
+        let command = Command {
+            file: Field::dummy("/foo/file"),
+            params: vec![],
+        };
+
+        _ = command.execute(&mut env).now_or_never().unwrap();
+
+        let count = env.variables.get("count").unwrap();
+        assert_eq!(count.value, Some("1".into()));
+    }
+
     #[test]
     fn fd_is_closed_after_execute() {
         let system = system_with_file("/foo/file", "");
@@ -277,4 +369,22 @@ mod tests {
             assert_matches!(process.get_fd(Fd(fd)), None, "fd={fd}");
         }
     }
+
+    #[test]
+    fn file_not_executed_at_call_depth_limit() {
+        let system = system_with_file("/foo/file", "executed=yes");
+        let mut env = Env::with_system(Box::new(system));
+        env.call_depth_limit = 1;
+        let mut env = env.push_frame(Frame::Eval);
+        let command = Command {
+            file: Field::dummy("/foo/file"),
+            params: vec![],
+        };
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+
+        assert_eq!(result.exit_status(), ExitStatus::ERROR);
+        assert_eq!(env.variables.get("executed"), None);
+        assert_eq!(env.stack[..], [Frame::Eval]);
+    }
 }
@@ -0,0 +1,235 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Suspend built-in
+//!
+//! The **`suspend`** built-in suspends the shell.
+//!
+//! # Synopsis
+//!
+//! ```sh
+//! suspend [-f]
+//! ```
+//!
+//! # Description
+//!
+//! The built-in sends the `SIGTSTP` signal to the process group of the shell
+//! to suspend the shell. The shell resumes when it receives the `SIGCONT`
+//! signal, typically from the `fg` or `bg` built-in of the parent shell.
+//!
+//! While the shell is [controlling jobs](Env::controls_jobs) interactively,
+//! it usually ignores `SIGTSTP` so that the user cannot suspend the shell by
+//! accidentally typing the suspend character on the terminal. This built-in
+//! temporarily resets the disposition for `SIGTSTP` to the default so that
+//! the signal can actually stop the shell.
+//!
+//! # Options
+//!
+//! The **`-f`** (**`--force`**) option makes the built-in suspend the shell
+//! even if it is a login shell.
+//!
+//! # Operands
+//!
+//! None.
+//!
+//! # Errors
+//!
+//! It is an error if:
+//!
+//! - the shell is not controlling jobs,
+//! - the shell is a [login shell](yash_env::option::Option::Login) and the
+//!   `-f` option is not specified, or
+//! - the signal cannot be sent.
+//!
+//! # Exit status
+//!
+//! Zero if the shell was suspended and resumed; non-zero on error.
+//!
+//! # Portability
+//!
+//! This built-in is an extension that is not defined in POSIX.
+//!
+//! A login shell is refused to be suspended because there is usually no
+//! parent shell that can resume it.
+
+use crate::common::report_simple_failure;
+use crate::common::report_usage_error;
+use crate::common::syntax::parse_arguments;
+use crate::common::syntax::Mode;
+use crate::common::syntax::OptionSpec;
+use crate::common::syntax_error;
+use yash_env::option::Option::Login;
+use yash_env::option::State::On;
+use yash_env::semantics::Field;
+use yash_env::signal;
+use yash_env::system::Errno;
+use yash_env::Env;
+use yash_env::System as _;
+
+const OPTIONS: &[OptionSpec] = &[OptionSpec::new().short('f').long("force")];
+
+/// Suspends the shell by sending `SIGTSTP` to its process group.
+///
+/// This function resets the internal disposition for `SIGTSTP` before sending
+/// the signal and restores it after the shell is resumed. The returned future
+/// completes when the shell has been resumed.
+pub async fn suspend(env: &mut Env) -> Result<(), Errno> {
+    let sigtstp = env.system.signal_number_from_name(signal::Name::Tstp);
+    let sigtstp = sigtstp.ok_or(Errno::EINVAL)?;
+
+    env.traps
+        .disable_internal_dispositions_for_stoppers(&mut env.system)?;
+    let result = env.system.kill(-env.main_pgid, Some(sigtstp)).await;
+    if env.is_interactive() {
+        env.traps
+            .enable_internal_dispositions_for_stoppers(&mut env.system)?;
+    }
+    result
+}
+
+/// Entry point of the `suspend` built-in
+pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
+    let (options, operands) = match parse_arguments(OPTIONS, Mode::with_env(env), args) {
+        Ok(result) => result,
+        Err(error) => return report_usage_error(env, &error).await,
+    };
+    if let Some(operand) = operands.first() {
+        return syntax_error(env, "unexpected operand", &operand.origin).await;
+    }
+    let force = !options.is_empty();
+
+    if !env.controls_jobs() {
+        return report_simple_failure(env, "cannot suspend the shell without job control").await;
+    }
+    if env.options.get(Login) == On && !force {
+        return report_simple_failure(env, "refusing to suspend the login shell").await;
+    }
+
+    match suspend(env).await {
+        Ok(()) => crate::Result::default(),
+        Err(errno) => {
+            report_simple_failure(env, &format!("cannot suspend the shell: {errno}")).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt as _;
+    use std::future::Future as _;
+    use std::pin::pin;
+    use std::rc::Rc;
+    use std::task::{Context, Poll};
+    use yash_env::job::ProcessState;
+    use yash_env::option::Option::{Interactive, Monitor};
+    use yash_env::semantics::ExitStatus;
+    use yash_env::system::r#virtual::{SIGCONT, SIGTSTP};
+    use yash_env::system::Disposition;
+    use yash_env::VirtualSystem;
+    use yash_env_test_helper::assert_stderr;
+
+    fn interactive_env(system: VirtualSystem) -> Env {
+        let mut env = Env::with_system(Box::new(system));
+        env.options.set(Interactive, On);
+        env.options.set(Monitor, On);
+        env.traps
+            .enable_internal_dispositions_for_stoppers(&mut env.system)
+            .unwrap();
+        env
+    }
+
+    #[test]
+    fn suspending_and_resuming_shell() {
+        let system = VirtualSystem::new();
+        let pid = system.process_id;
+        let state = Rc::clone(&system.state);
+        let mut env = interactive_env(system);
+
+        let mut future = pin!(main(&mut env, vec![]));
+        let mut context = Context::from_waker(futures_util::task::noop_waker_ref());
+        assert_eq!(future.as_mut().poll(&mut context), Poll::Pending);
+        {
+            let state = state.borrow();
+            let process = &state.processes[&pid];
+            assert_eq!(process.state(), ProcessState::stopped(SIGTSTP));
+            assert_eq!(process.disposition(SIGTSTP), Disposition::Default);
+        }
+
+        _ = state
+            .borrow_mut()
+            .processes
+            .get_mut(&pid)
+            .unwrap()
+            .raise_signal(SIGCONT);
+        let result = future.as_mut().poll(&mut context);
+        assert_eq!(result, Poll::Ready(crate::Result::default()));
+
+        let state = state.borrow();
+        let process = &state.processes[&pid];
+        assert_eq!(process.state(), ProcessState::Running);
+        assert_eq!(process.disposition(SIGTSTP), Disposition::Ignore);
+    }
+
+    #[test]
+    fn refusing_without_job_control() {
+        let system = VirtualSystem::new();
+        let pid = system.process_id;
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+
+        let result = main(&mut env, vec![]).now_or_never().unwrap();
+        assert_eq!(result.exit_status(), ExitStatus::FAILURE);
+        assert_eq!(
+            state.borrow().processes[&pid].state(),
+            ProcessState::Running
+        );
+        assert_stderr(&state, |stderr| assert_ne!(stderr, ""));
+    }
+
+    #[test]
+    fn refusing_login_shell() {
+        let system = VirtualSystem::new();
+        let pid = system.process_id;
+        let state = Rc::clone(&system.state);
+        let mut env = interactive_env(system);
+        env.options.set(Login, On);
+
+        let result = main(&mut env, vec![]).now_or_never().unwrap();
+        assert_eq!(result.exit_status(), ExitStatus::FAILURE);
+        assert_eq!(
+            state.borrow().processes[&pid].state(),
+            ProcessState::Running
+        );
+        assert_stderr(&state, |stderr| assert!(stderr.contains("login shell")));
+    }
+
+    #[test]
+    fn forcing_login_shell() {
+        let system = VirtualSystem::new();
+        let pid = system.process_id;
+        let state = Rc::clone(&system.state);
+        let mut env = interactive_env(system);
+        env.options.set(Login, On);
+
+        let result = main(&mut env, Field::dummies(["-f"])).now_or_never();
+        assert_eq!(result, None);
+        assert_eq!(
+            state.borrow().processes[&pid].state(),
+            ProcessState::stopped(SIGTSTP)
+        );
+    }
+}
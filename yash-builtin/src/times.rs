@@ -69,8 +69,8 @@
 //! does not necessarily indicate the precision of the times.
 
 use crate::common::output;
-use crate::common::report_error;
 use crate::common::report_simple_failure;
+use crate::common::report_usage_error;
 use yash_env::semantics::Field;
 use yash_env::Env;
 use yash_env::System;
@@ -90,6 +90,6 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
                 report_simple_failure(env, &format!("cannot obtain times: {error}")).await
             }
         },
-        Err(error) => report_error(env, &error).await,
+        Err(error) => report_usage_error(env, &error).await,
     }
 }
@@ -140,6 +140,7 @@ mod cond;
 pub use self::cond::CondSpec;
 use crate::common::report_error;
 use crate::common::report_failure;
+use crate::common::report_usage_error;
 use crate::common::syntax::parse_arguments;
 use crate::common::syntax::Mode;
 use crate::common::to_single_message;
@@ -403,7 +404,7 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
     let (options, operands) = match parse_arguments(syntax::OPTION_SPECS, Mode::with_env(env), args)
     {
         Ok(result) => result,
-        Err(error) => return report_error(env, &error).await,
+        Err(error) => return report_usage_error(env, &error).await,
     };
 
     let command = match syntax::interpret(options, operands) {
@@ -709,6 +710,22 @@ mod tests {
         });
     }
 
+    #[test]
+    fn printing_exit_trap_in_subshell() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        let args = Field::dummies(["echo exiting", "EXIT"]);
+        let _ = main(&mut env, args).now_or_never().unwrap();
+        env.traps.enter_subshell(&mut env.system, false, false);
+
+        let result = main(&mut env, vec![]).now_or_never().unwrap();
+        assert_eq!(result, Result::new(ExitStatus::SUCCESS));
+        assert_stdout(&state, |stdout| {
+            assert_eq!(stdout, "trap -- 'echo exiting' EXIT\n")
+        });
+    }
+
     #[test]
     fn printing_traps_after_setting_in_subshell() {
         let system = Box::new(VirtualSystem::new());
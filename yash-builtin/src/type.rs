@@ -63,7 +63,7 @@
 
 use crate::command::syntax::interpret;
 use crate::command::Command;
-use crate::common::report_error;
+use crate::common::report_usage_error;
 use crate::common::syntax::parse_arguments;
 use crate::common::syntax::Mode;
 use crate::common::syntax::OptionOccurrence;
@@ -99,6 +99,6 @@ fn parse(env: &mut Env, args: Vec<Field>) -> Result<Command, crate::command::syn
 pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
     match parse(env, args) {
         Ok(command) => command.execute(env).await,
-        Err(error) => report_error(env, &error).await,
+        Err(error) => report_usage_error(env, &error).await,
     }
 }
@@ -276,7 +276,7 @@
 //! differently from all of them.
 
 use self::syntax::OptionSpec;
-use crate::common::{output, report_error, report_failure, to_single_message};
+use crate::common::{output, report_failure, report_usage_error, to_single_message};
 use thiserror::Error;
 use yash_env::function::Function;
 use yash_env::option::State;
@@ -660,8 +660,8 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> yash_env::builtin::Result
                 Ok(result) => output(env, &result).await,
                 Err(errors) => report_failure(env, to_single_message(&errors).unwrap()).await,
             },
-            Err(error) => report_error(env, &error).await,
+            Err(error) => report_usage_error(env, &error).await,
         },
-        Err(error) => report_error(env, &error).await,
+        Err(error) => report_usage_error(env, &error).await,
     }
 }
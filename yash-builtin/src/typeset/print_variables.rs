@@ -29,10 +29,7 @@ impl PrintVariables {
         let mut errors = Vec::new();
 
         if self.variables.is_empty() {
-            let mut variables = variables.iter(self.scope.into()).collect::<Vec<_>>();
-            // TODO Honor the collation order in the locale.
-            variables.sort_unstable_by_key(|&(name, _)| name);
-            for (name, var) in variables {
+            for (name, var) in variables.iter(self.scope.into()) {
                 print_one(name, var, &self.attrs, context, &mut output);
             }
         } else {
@@ -143,7 +143,7 @@
 //! The `hard` and `soft` values for the *limit* operand are not defined in
 //! POSIX.
 
-use crate::common::{output, report_error, report_simple_failure};
+use crate::common::{output, report_simple_failure, report_usage_error};
 use yash_env::semantics::Field;
 use yash_env::system::resource::{Limit, Resource};
 use yash_env::system::Errno;
@@ -252,6 +252,123 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
             Ok(result) => output(env, &result).await,
             Err(e) => report_simple_failure(env, &e.to_string()).await,
         },
-        Err(e) => report_error(env, &e).await,
+        Err(e) => report_usage_error(env, &e).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt as _;
+    use std::rc::Rc;
+    use yash_env::semantics::ExitStatus;
+    use yash_env::system::resource::{LimitPair, INFINITY};
+    use yash_env::VirtualSystem;
+    use yash_env_test_helper::assert_stderr;
+    use yash_env_test_helper::assert_stdout;
+
+    #[test]
+    fn printing_current_limit() {
+        let mut system = VirtualSystem::new();
+        let limits = LimitPair {
+            soft: 64,
+            hard: 256,
+        };
+        system.setrlimit(Resource::NOFILE, limits).unwrap();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+
+        let result = main(&mut env, Field::dummies(["-n"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+        let result = main(&mut env, Field::dummies(["-Hn"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "64\n256\n"));
+    }
+
+    #[test]
+    fn setting_both_limits_and_then_soft_limit() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+
+        let result = main(&mut env, Field::dummies(["-t", "100"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+        let result = main(&mut env, Field::dummies(["-S", "-t", "10"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+
+        let limits = env.system.getrlimit(Resource::CPU).unwrap();
+        assert_eq!(
+            limits,
+            LimitPair {
+                soft: 10,
+                hard: 100
+            }
+        );
+        assert_stdout(&state, |stdout| assert_eq!(stdout, ""));
+    }
+
+    #[test]
+    fn setting_unlimited() {
+        let mut system = VirtualSystem::new();
+        let limits = LimitPair {
+            soft: 0,
+            hard: INFINITY,
+        };
+        system.setrlimit(Resource::CORE, limits).unwrap();
+        let mut env = Env::with_system(Box::new(system));
+
+        let result = main(&mut env, Field::dummies(["-c", "unlimited"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, crate::Result::default());
+
+        let limits = env.system.getrlimit(Resource::CORE).unwrap();
+        assert_eq!(
+            limits,
+            LimitPair {
+                soft: INFINITY,
+                hard: INFINITY
+            }
+        );
+    }
+
+    #[test]
+    fn raising_hard_limit_without_privilege() {
+        let mut system = VirtualSystem::new();
+        let limits = LimitPair {
+            soft: 512,
+            hard: 1024,
+        };
+        system.setrlimit(Resource::FSIZE, limits).unwrap();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+
+        let result = main(&mut env, Field::dummies(["-Hf", "unlimited"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result.exit_status(), ExitStatus::FAILURE);
+        assert_stderr(&state, |stderr| {
+            assert!(
+                stderr.contains("no permission to raise hard limit"),
+                "{stderr:?}"
+            )
+        });
+
+        let limits = env.system.getrlimit(Resource::FSIZE).unwrap();
+        assert_eq!(
+            limits,
+            LimitPair {
+                soft: 512,
+                hard: 1024
+            }
+        );
     }
 }
@@ -110,7 +110,7 @@
 //! The permission symbols other than `r`, `w`, and `x` are not widely supported.
 //! This implementation currently ignores the `s` symbol.
 
-use crate::common::{output, report_error};
+use crate::common::{output, report_usage_error};
 use yash_env::semantics::Field;
 use yash_env::system::Mode;
 use yash_env::{Env, System};
@@ -185,6 +185,6 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
             let result = command.execute(env);
             output(env, &result).await
         }
-        Err(e) => report_error(env, &e).await,
+        Err(e) => report_usage_error(env, &e).await,
     }
 }
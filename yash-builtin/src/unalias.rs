@@ -55,8 +55,8 @@
 //! Some shells implement some built-in utilities as predefined aliases. Using
 //! `unalias -a` may make such built-ins unavailable.
 
-use crate::common::report_error;
 use crate::common::report_failure;
+use crate::common::report_usage_error;
 use yash_env::semantics::Field;
 use yash_env::Env;
 use yash_syntax::source::pretty::Message;
@@ -99,6 +99,6 @@ pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
                 Some(message) => report_failure(env, message).await,
             }
         }
-        Err(e) => report_error(env, e.to_message()).await,
+        Err(e) => report_usage_error(env, e.to_message()).await,
     }
 }
@@ -92,6 +92,7 @@
 
 use crate::common::report_error;
 use crate::common::report_simple_failure;
+use crate::common::report_usage_error;
 use crate::common::to_single_message;
 use itertools::Itertools as _;
 use yash_env::job::Pid;
@@ -183,7 +184,7 @@ impl Command {
 pub async fn main(env: &mut Env, args: Vec<Field>) -> crate::Result {
     match syntax::parse(env, args) {
         Ok(command) => command.execute(env).await,
-        Err(error) => report_error(env, &error).await,
+        Err(error) => report_usage_error(env, &error).await,
     }
 }
 
@@ -66,7 +66,7 @@ pub async fn wait_for_any_job_or_trap(env: &mut Env) -> Result<(), Error> {
     loop {
         // Poll for a job state change. Note that this `wait` call returns
         // immediately regardless of whether there is a new job state.
-        match env.system.wait(Pid::ALL) {
+        match env.system.wait_with_usage(Pid::ALL) {
             Ok(None) => {
                 // The current process has child processes, but none of them has
                 // changed its state. Wait for a signal.
@@ -78,9 +78,9 @@ pub async fn wait_for_any_job_or_trap(env: &mut Env) -> Result<(), Error> {
                 }
             }
 
-            Ok(Some((pid, state))) => {
+            Ok(Some((pid, state, usage))) => {
                 // Some job has changed its state.
-                env.jobs.update_status(pid, state);
+                env.jobs.update_status_with_usage(pid, state, usage);
                 return Ok(());
             }
 
@@ -21,13 +21,15 @@
 //!
 //! The entry point for the shell is the [`main`] function, which is to be used
 //! as the `main` function in the binary crate. The function sets up the shell
-//! environment and runs the main read-eval loop.
+//! environment and runs the main read-eval loop. The [`run`] function performs
+//! the part of the work that follows the command-line argument parsing.
 
 pub mod startup;
 // mod runner;
 
 use self::startup::args::Parse;
-use self::startup::init_file::run_rcfile;
+use self::startup::args::Run;
+use self::startup::init_file::{run_profile, run_rcfile};
 use self::startup::input::prepare_input;
 use std::cell::RefCell;
 use std::ops::ControlFlow::{Break, Continue};
@@ -48,39 +50,63 @@ async fn print_version(env: &mut Env) -> ExitStatus {
     result.exit_status()
 }
 
-// The RefCell is local to this function, so it is safe to keep borrows across await points.
-#[allow(clippy::await_holding_refcell_ref)]
 async fn parse_and_print(mut env: Env) -> ExitStatus {
     // Parse the command-line arguments
+    let arg0 = std::env::args().next().unwrap_or_else(|| "yash".to_owned());
     let run = match self::startup::args::parse(std::env::args()) {
         Ok(Parse::Help) => todo!("print help"),
         Ok(Parse::Version) => return print_version(&mut env).await,
         Ok(Parse::Run(run)) => run,
         Err(e) => {
-            let arg0 = std::env::args().next().unwrap_or_else(|| "yash".to_owned());
             env.system.print_error(&format!("{}: {}\n", arg0, e)).await;
             return ExitStatus::ERROR;
         }
     };
 
     // Import environment variables
-    env.variables.extend_env(std::env::vars());
+    env.import_environ();
+
+    self::run(&mut env, &arg0, run).await
+}
 
-    let work = self::startup::configure_environment(&mut env, run);
+/// Runs the shell as specified by the parsed command-line arguments.
+///
+/// This function [configures the environment](startup::configure_environment)
+/// with `run`, executes the [initialization files](startup::init_file), and
+/// reads and executes commands from the [input source](startup::args::Source)
+/// until the end of the input or until the shell exits. The EXIT trap is
+/// executed before the function returns the final exit status of the shell.
+///
+/// `shell_name` is the name of the shell executable, which is used as the
+/// prefix of error messages. [`env.arg0`](Env::arg0) is set to `run.arg0`, so
+/// `$0` expands to the script path if the shell runs a script file, and to the
+/// command name operand if given with the `-c` option. The remaining operands
+/// become the positional parameters.
+///
+/// If the script file cannot be opened, this function prints an error message
+/// and returns [`ExitStatus::NOT_FOUND`] (127) if the file does not exist and
+/// [`ExitStatus::NOEXEC`] (126) for other errors, without running the EXIT
+/// trap.
+///
+/// All system calls are performed through `env.system`, so this function can
+/// be tested with a virtual system.
+// The RefCell is local to this function, so it is safe to keep borrows across await points.
+#[allow(clippy::await_holding_refcell_ref)]
+pub async fn run(env: &mut Env, shell_name: &str, run: Run) -> ExitStatus {
+    let work = self::startup::configure_environment(env, run);
 
     let is_interactive = env.options.get(Interactive) == On;
 
     // Run initialization files
-    // TODO run profile if login
-    run_rcfile(&mut env, work.rcfile).await;
+    run_profile(env, work.profile).await;
+    run_rcfile(env, work.rcfile).await;
 
     // Prepare the input for the main read-eval loop
-    let ref_env = &RefCell::new(&mut env);
+    let ref_env = &RefCell::new(&mut *env);
     let lexer = match prepare_input(ref_env, &work.source) {
         Ok(lexer) => lexer,
         Err(e) => {
-            let arg0 = std::env::args().next().unwrap_or_else(|| "yash".to_owned());
-            let message = format!("{}: {}\n", arg0, e);
+            let message = format!("{}: {}\n", shell_name, e);
             // The borrow checker of Rust 1.79.0 is not smart enough to reason
             // about the lifetime of `e` here, so we re-borrow from `ref_env`
             // instead of reusing `env`.
@@ -108,14 +134,60 @@ async fn parse_and_print(mut env: Env) -> ExitStatus {
         | Break(Divert::Break { .. })
         | Break(Divert::Return(_))
         | Break(Divert::Interrupt(_))
-        | Break(Divert::Exit(_)) => run_exit_trap(&mut env).await,
+        | Break(Divert::Exit(_)) => run_exit_trap(env).await,
         Break(Divert::Abort(_)) => (),
     }
 
     env.exit_status
 }
 
+/// Stack size of the thread that runs the shell
+///
+/// The stack of the main thread is usually too small for deeply nested
+/// function calls. With this size, the shell reaches
+/// [`Env::call_depth_limit`] before overflowing the stack even in a debug
+/// build.
+const STACK_SIZE: usize = 1 << 30;
+
 pub fn main() -> ! {
+    // The shell runs in a separate thread with a large stack. The main thread
+    // blocks all signals so that they are delivered to the shell thread, which
+    // restores the original signal mask before starting the shell.
+    let mut old_mask = std::mem::MaybeUninit::<libc::sigset_t>::uninit();
+    // SAFETY: The pointers are valid, and sigfillset initializes all_signals
+    // before it is used.
+    unsafe {
+        let mut all_signals = std::mem::MaybeUninit::<libc::sigset_t>::uninit();
+        libc::sigfillset(all_signals.as_mut_ptr());
+        libc::pthread_sigmask(libc::SIG_BLOCK, all_signals.as_ptr(), old_mask.as_mut_ptr());
+    }
+    // SAFETY: pthread_sigmask has initialized old_mask.
+    let old_mask = unsafe { old_mask.assume_init() };
+    let restore_mask = move || {
+        // SAFETY: The pointer is valid.
+        unsafe { libc::pthread_sigmask(libc::SIG_SETMASK, &old_mask, std::ptr::null_mut()) };
+    };
+
+    let thread = std::thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            restore_mask();
+            run_shell()
+        });
+    match thread {
+        Ok(thread) => match thread.join() {
+            Ok(()) => unreachable!("the shell thread should exit the process"),
+            Err(panic) => std::panic::resume_unwind(panic),
+        },
+        Err(_) => {
+            restore_mask();
+            run_shell()
+        }
+    }
+}
+
+/// Runs the shell in the current thread.
+fn run_shell() -> ! {
     // SAFETY: This is the only instance of RealSystem we create in the whole
     // process.
     let system = unsafe { RealSystem::new() };
@@ -136,11 +208,77 @@ pub fn main() -> ! {
         let exit_status = parse_and_print(env).await;
         std::process::exit(exit_status.0);
     });
-    // SAFETY: We never create new threads in the whole process, so wakers are
-    // never shared between threads.
+    // SAFETY: The executor and its tasks only live in this thread, so wakers
+    // are never shared between threads.
     unsafe { executor.spawn_pinned(task) }
     loop {
         executor.run_until_stalled();
         system.select(false).ok();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+    use yash_env::system::r#virtual::{Inode, SystemState};
+    use yash_env::variable::Value;
+    use yash_env_test_helper::{assert_stderr, in_virtual_system};
+
+    fn parse_run(args: &[&str]) -> Run {
+        match self::startup::args::parse(args.iter().copied()) {
+            Ok(Parse::Run(run)) => run,
+            other => panic!("unexpected parse result: {other:?}"),
+        }
+    }
+
+    fn save_file(state: &RefCell<SystemState>, path: &str, content: &str) {
+        let inode = Rc::new(RefCell::new(Inode::new(content)));
+        state.borrow_mut().file_system.save(path, inode).unwrap();
+    }
+
+    fn value(env: &Env, name: &str) -> Option<Value> {
+        env.variables.get(name)?.value.clone()
+    }
+
+    #[test]
+    fn running_script_file_with_operands() {
+        in_virtual_system(|mut env, state| async move {
+            save_file(&state, "/script.sh", "a=$0 b=$# c=$2\n(exit 5)\n");
+            let run = parse_run(&["yash", "/script.sh", "foo", "bar baz"]);
+
+            let exit_status = self::run(&mut env, "yash", run).await;
+            assert_eq!(exit_status, ExitStatus(5));
+            assert_eq!(env.arg0, "/script.sh");
+            assert_eq!(value(&env, "a"), Some(Value::scalar("/script.sh")));
+            assert_eq!(value(&env, "b"), Some(Value::scalar("2")));
+            assert_eq!(value(&env, "c"), Some(Value::scalar("bar baz")));
+        })
+    }
+
+    #[test]
+    fn running_command_string_with_name_and_operands() {
+        in_virtual_system(|mut env, _state| async move {
+            let run = parse_run(&["yash", "-c", "a=$0 b=$*; exit 3", "name", "1", "2"]);
+
+            let exit_status = self::run(&mut env, "yash", run).await;
+            assert_eq!(exit_status, ExitStatus(3));
+            assert_eq!(value(&env, "a"), Some(Value::scalar("name")));
+            assert_eq!(value(&env, "b"), Some(Value::scalar("1 2")));
+        })
+    }
+
+    #[test]
+    fn running_missing_script_file() {
+        in_virtual_system(|mut env, state| async move {
+            let run = parse_run(&["yash", "/no/such/file"]);
+
+            let exit_status = self::run(&mut env, "yash", run).await;
+            assert_eq!(exit_status, ExitStatus::NOT_FOUND);
+            assert_stderr(&state, |stderr| {
+                assert!(stderr.starts_with("yash: "), "{stderr:?}");
+                assert!(stderr.contains("/no/such/file"), "{stderr:?}");
+            });
+        })
+    }
+}
@@ -19,8 +19,10 @@
 use std::iter::Peekable;
 use thiserror::Error;
 use yash_env::option::canonicalize;
-use yash_env::option::parse_long;
-use yash_env::option::parse_short;
+use yash_env::option::parse_long_with_sign;
+use yash_env::option::parse_short_with_sign;
+use yash_env::option::split_long_option;
+use yash_env::option::split_short_options;
 use yash_env::option::FromStrError::{Ambiguous, NoSuchOption};
 use yash_env::option::Option as ShellOption;
 use yash_env::option::State;
@@ -275,17 +277,13 @@ fn try_parse_short<I: Iterator<Item = String>>(
     args: &mut Peekable<I>,
     option_occurrences: &mut Vec<(ShellOption, State)>,
 ) -> Result<Option<ShortOption>, Error> {
-    let Some(mut arg) = args.next_if(|arg| is_short_option(arg)) else {
+    let Some(mut arg) = args.next_if(|arg| split_short_options(arg).is_some()) else {
         return Ok(None);
     };
 
-    let mut chars = arg.chars();
-    let negate = match chars.next() {
-        Some('-') => false,
-        Some('+') => true,
-        _ => unreachable!(),
-    };
-
+    let (negate, options) = split_short_options(&arg).unwrap();
+    let options = options.to_owned();
+    let mut chars = options.chars();
     while let Some(c) = chars.next() {
         if c == 'V' {
             return if negate {
@@ -297,64 +295,42 @@ fn try_parse_short<I: Iterator<Item = String>>(
         if c == 'o' {
             let name = chars.as_str();
             let name = if !name.is_empty() {
-                canonicalize(name)
+                name
             } else {
                 let prev = arg;
                 arg = args.next().ok_or(Error::MissingOptionArgument(prev))?;
-                canonicalize(&arg)
+                &arg
             };
-            match parse_long(&name) {
-                Ok((option, state)) => {
-                    option_occurrences.push((option, if negate { !state } else { state }));
+            match parse_long_with_sign(name, negate) {
+                Ok(option) => {
+                    option_occurrences.push(option);
                     break;
                 }
-                Err(NoSuchOption) => return Err(Error::UnknownLongOption(name.into_owned())),
-                Err(Ambiguous) => return Err(Error::AmbiguousLongOption(name.into_owned())),
+                Err(NoSuchOption) => {
+                    return Err(Error::UnknownLongOption(canonicalize(name).into_owned()))
+                }
+                Err(Ambiguous) => {
+                    return Err(Error::AmbiguousLongOption(canonicalize(name).into_owned()))
+                }
             }
         }
 
-        let (option, state) = parse_short(c).ok_or(Error::UnknownShortOption(c))?;
-        option_occurrences.push((option, if negate { !state } else { state }));
+        let option = parse_short_with_sign(c, negate).ok_or(Error::UnknownShortOption(c))?;
+        option_occurrences.push(option);
     }
 
     Ok(Some(ShortOption::Shell))
 }
 
-/// Tests if the given string is a short option.
-fn is_short_option(arg: &str) -> bool {
-    let mut chars = arg.chars();
-    let negate = match chars.next() {
-        Some('-') => false,
-        Some('+') => true,
-        _ => return false,
-    };
-    match chars.next() {
-        Some('-') if !negate => false,
-        Some('+') if negate => false,
-        Some(_) => true,
-        None => false,
-    }
-}
-
 /// Tries to parse and consume the next argument in `args` as a long option.
 fn try_parse_long<I: Iterator<Item = String>>(
     args: &mut Peekable<I>,
 ) -> Result<Option<LongOption>, Error> {
-    let Some(arg) = args.next_if(|arg| is_long_option(arg)) else {
+    let Some(arg) = args.next_if(|arg| split_long_option(arg).is_some()) else {
         return Ok(None);
     };
 
-    let mut chars = arg.chars();
-    let negate = match chars.next() {
-        Some('-') => false,
-        Some('+') => true,
-        _ => unreachable!(),
-    };
-
-    // Skip the second `-` or `+`
-    chars.next();
-
-    let chars = chars.as_str();
+    let (negate, chars) = split_long_option(&arg).unwrap();
 
     // Parse non-shell options
     let (name, value) = match chars.split_once('=') {
@@ -364,7 +340,7 @@ fn try_parse_long<I: Iterator<Item = String>>(
     let non_shell_option = NonShellOptionConstructor::from_name(name);
 
     // Parse shell options
-    let shell_option = parse_long(&canonicalize(chars));
+    let shell_option = parse_long_with_sign(chars, negate);
 
     // Check if the result is unique and return the final result
     match (non_shell_option, shell_option) {
@@ -393,20 +369,10 @@ fn try_parse_long<I: Iterator<Item = String>>(
             Ok(Some(ctor(value)))
         }
 
-        (None, Ok((option, state))) if negate => Ok(Some(LongOption::Shell(option, !state))),
         (None, Ok((option, state))) => Ok(Some(LongOption::Shell(option, state))),
     }
 }
 
-/// Tests if the given string is a long option.
-fn is_long_option(arg: &str) -> bool {
-    if let Some(name) = arg.strip_prefix("--") {
-        !name.is_empty()
-    } else {
-        arg.starts_with("++")
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -19,13 +19,14 @@
 //! This module provides functions for running initialization files in the shell.
 //! The initialization file is a script that is executed when the shell starts up.
 //!
-//! Currently, this module only supports running the POSIX-defined rcfile, whose
-//! path is determined by the value of the `ENV` environment variable.
+//! An interactive shell runs the POSIX-defined rcfile, whose path is
+//! determined by the value of the `ENV` environment variable. A login shell
+//! additionally runs a profile before the rcfile.
 //! (TODO: Support for yash-specific initialization files will be added later.)
 //!
-//! The [`run_rcfile`] function is the main entry point for running the rcfile.
-//! Helper functions that are used by `run_rcfile` are also provided in this
-//! module.
+//! The [`run_rcfile`] and [`run_profile`] functions are the main entry points
+//! for running the rcfile and profile, respectively. Helper functions that are
+//! used by them are also provided in this module.
 
 use super::args::InitFile;
 use std::cell::RefCell;
@@ -34,11 +35,11 @@ use std::rc::Rc;
 use thiserror::Error;
 use yash_env::input::{Echo, FdReader};
 use yash_env::io::Fd;
-use yash_env::option::Option::Interactive;
-use yash_env::option::State::Off;
+use yash_env::option::Option::{Interactive, Login, PosixlyCorrect};
+use yash_env::option::State::{Off, On};
 use yash_env::stack::Frame;
 use yash_env::system::{Errno, Mode, OfdAccess, OpenFlag, SystemEx};
-use yash_env::variable::ENV;
+use yash_env::variable::{ENV, HOME};
 use yash_env::Env;
 use yash_env::System;
 use yash_semantics::expansion::expand_text;
@@ -131,6 +132,46 @@ pub async fn resolve_rcfile_path(
     }
 }
 
+/// Finds the path to the default profile.
+///
+/// The default profile is `~/.yash_profile`, or `~/.profile` if the
+/// [`PosixlyCorrect`] option is on, where `~` is the value of the [`HOME`]
+/// variable. If the variable is not set or empty, the result will be an empty
+/// string.
+pub fn default_profile_path(env: &Env) -> String {
+    let home = env.variables.get_scalar(HOME).unwrap_or_default();
+    if home.is_empty() {
+        return String::default();
+    }
+    let name = if env.options.get(PosixlyCorrect) == On {
+        ".profile"
+    } else {
+        ".yash_profile"
+    };
+    format!("{}/{name}", home.trim_end_matches('/'))
+}
+
+/// Resolves the path to the profile.
+///
+/// This function resolves the path to the profile specified by the `file`
+/// argument. If the file is `InitFile::Default`, the default profile path is
+/// determined by calling [`default_profile_path`].
+///
+/// This function returns an empty string if `file` is `InitFile::None` or the
+/// [`Login`] shell option is off, in which case the profile should not be
+/// executed.
+pub fn resolve_profile_path(env: &Env, file: InitFile) -> String {
+    if env.options.get(Login) == Off {
+        return String::default();
+    }
+
+    match file {
+        InitFile::None => String::default(),
+        InitFile::Default => default_profile_path(env),
+        InitFile::File { path } => path,
+    }
+}
+
 /// Runs an initialization file, reading from the specified path.
 ///
 /// This function reads the contents of the initialization file and executes
@@ -138,6 +179,11 @@ pub async fn resolve_rcfile_path(
 /// argument.
 ///
 /// If `path` is an empty string, the function returns immediately.
+///
+/// If the file cannot be opened, an error message is printed to the standard
+/// error. However, a file that does not exist is silently ignored unless the
+/// [`PosixlyCorrect`] option is on. Errors that occur while executing the file
+/// are reported as usual but do not make the shell exit.
 pub async fn run_init_file(env: &mut Env, path: &str) {
     if path.is_empty() {
         return;
@@ -156,6 +202,7 @@ pub async fn run_init_file(env: &mut Env, path: &str) {
 
     let fd = match open_fd(&mut env.system, path.to_owned()) {
         Ok(fd) => fd,
+        Err(Errno::ENOENT) if env.options.get(PosixlyCorrect) == Off => return,
         Err(errno) => {
             env.system
                 .print_error(&format!(
@@ -200,15 +247,45 @@ pub async fn run_rcfile(env: &mut Env, file: InitFile) {
     }
 }
 
+/// Runs the profile specified by the `file` argument.
+///
+/// This function resolves the path to the profile using
+/// [`resolve_profile_path`] and then runs the profile using
+/// [`run_init_file`].
+pub async fn run_profile(env: &mut Env, file: InitFile) {
+    let path = resolve_profile_path(env, file);
+    run_init_file(env, &path).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use assert_matches::assert_matches;
     use futures_util::FutureExt as _;
-    use yash_env::option::State::On;
+    use yash_env::system::r#virtual::{Inode, SystemState};
     use yash_env::system::{Gid, Uid};
     use yash_env::variable::Scope::Global;
     use yash_env::VirtualSystem;
+    use yash_env_test_helper::{assert_stderr, in_virtual_system};
+
+    fn save_file(state: &RefCell<SystemState>, path: &str, content: &str) {
+        let inode = Rc::new(RefCell::new(Inode::new(content)));
+        state.borrow_mut().file_system.save(path, inode).unwrap();
+    }
+
+    fn assign(env: &mut Env, name: &str, value: &str) {
+        env.variables
+            .get_or_new(name, Global)
+            .assign(value, None)
+            .unwrap();
+    }
+
+    fn interactive_env_with_rcfile(mut env: Env) -> Env {
+        env.options.set(Interactive, On);
+        assign(&mut env, HOME, "/home/user");
+        assign(&mut env, ENV, "$HOME/.yashrc");
+        env
+    }
 
     #[test]
     fn default_rcfile_path_with_unset_env() {
@@ -330,4 +407,112 @@ mod tests {
         let result = resolve_rcfile_path(&mut env, file).now_or_never().unwrap();
         assert_eq!(result.unwrap(), "");
     }
+
+    #[test]
+    fn resolve_profile_path_non_login() {
+        let mut env = Env::new_virtual();
+        assign(&mut env, HOME, "/home/user");
+        let result = resolve_profile_path(&env, InitFile::Default);
+        assert_eq!(result, "");
+    }
+
+    #[test]
+    fn resolve_profile_path_none() {
+        let mut env = Env::new_virtual();
+        env.options.set(Login, On);
+        assign(&mut env, HOME, "/home/user");
+        let result = resolve_profile_path(&env, InitFile::None);
+        assert_eq!(result, "");
+    }
+
+    #[test]
+    fn resolve_profile_path_default() {
+        let mut env = Env::new_virtual();
+        env.options.set(Login, On);
+        assign(&mut env, HOME, "/home/user/");
+        let result = resolve_profile_path(&env, InitFile::Default);
+        assert_eq!(result, "/home/user/.yash_profile");
+
+        env.options.set(PosixlyCorrect, On);
+        let result = resolve_profile_path(&env, InitFile::Default);
+        assert_eq!(result, "/home/user/.profile");
+    }
+
+    #[test]
+    fn resolve_profile_path_default_without_home() {
+        let mut env = Env::new_virtual();
+        env.options.set(Login, On);
+        let result = resolve_profile_path(&env, InitFile::Default);
+        assert_eq!(result, "");
+    }
+
+    #[test]
+    fn resolve_profile_path_exact() {
+        let mut env = Env::new_virtual();
+        env.options.set(Login, On);
+        let path = "/path/to/profile".to_string();
+        let result = resolve_profile_path(&env, InitFile::File { path });
+        assert_eq!(result, "/path/to/profile");
+    }
+
+    #[test]
+    fn run_rcfile_defines_variables() {
+        in_virtual_system(|env, state| async move {
+            save_file(&state, "/home/user/.yashrc", "x=1\ny=${x}2\n");
+            let mut env = interactive_env_with_rcfile(env);
+
+            run_rcfile(&mut env, InitFile::Default).await;
+            assert_eq!(env.variables.get_scalar("x"), Some("1"));
+            assert_eq!(env.variables.get_scalar("y"), Some("12"));
+            assert_stderr(&state, |stderr| assert_eq!(stderr, ""));
+        })
+    }
+
+    #[test]
+    fn run_rcfile_ignores_missing_file_silently() {
+        in_virtual_system(|env, state| async move {
+            let mut env = interactive_env_with_rcfile(env);
+
+            run_rcfile(&mut env, InitFile::Default).await;
+            assert_stderr(&state, |stderr| assert_eq!(stderr, ""));
+        })
+    }
+
+    #[test]
+    fn run_rcfile_reports_missing_file_in_posix_mode() {
+        in_virtual_system(|env, state| async move {
+            let mut env = interactive_env_with_rcfile(env);
+            env.options.set(PosixlyCorrect, On);
+
+            run_rcfile(&mut env, InitFile::Default).await;
+            assert_stderr(&state, |stderr| {
+                assert!(stderr.contains("/home/user/.yashrc"), "{stderr:?}")
+            });
+        })
+    }
+
+    #[test]
+    fn run_rcfile_reports_error_without_exiting() {
+        in_virtual_system(|env, state| async move {
+            save_file(&state, "/home/user/.yashrc", "x=1\n${unset?}\ny=2\n");
+            let mut env = interactive_env_with_rcfile(env);
+
+            run_rcfile(&mut env, InitFile::Default).await;
+            assert_eq!(env.variables.get_scalar("x"), Some("1"));
+            assert_eq!(env.variables.get_scalar("y"), None);
+            assert_stderr(&state, |stderr| assert_ne!(stderr, ""));
+        })
+    }
+
+    #[test]
+    fn run_profile_in_login_shell() {
+        in_virtual_system(|mut env, state| async move {
+            save_file(&state, "/home/user/.yash_profile", "p=profile\n");
+            env.options.set(Login, On);
+            assign(&mut env, HOME, "/home/user");
+
+            run_profile(&mut env, InitFile::Default).await;
+            assert_eq!(env.variables.get_scalar("p"), Some("profile"));
+        })
+    }
 }
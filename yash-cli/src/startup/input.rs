@@ -63,8 +63,10 @@ pub struct PrepareInputError<'a> {
 /// This function constructs a lexer from the given source with the
 /// following decorators applied to the input object:
 ///
-/// - If the source is read with a file descriptor, the [`Echo`] decorator is
-///   applied to the input to implement the [`Verbose`] shell option.
+/// - The [`Echo`] decorator is applied to the input to implement the
+///   [`Verbose`] shell option. The decorator checks the option each time it
+///   reads a line, so changes made by the `set` built-in take effect for the
+///   following lines.
 /// - If the [`Interactive`] option is enabled and the source is read with a
 ///   file descriptor, the [`Prompter`] decorator is applied to the input to
 ///   show the prompt.
@@ -133,7 +135,7 @@ pub fn prepare_input<'s: 'i + 'e, 'i, 'e>(
         }
 
         Source::String(command) => {
-            let basic_input = Memory::new(command);
+            let basic_input = Echo::new(Memory::new(command), env);
 
             let is_interactive = env.borrow().options.get(Interactive) == On;
             let input: Box<dyn InputObject> = if is_interactive {
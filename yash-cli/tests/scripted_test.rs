@@ -170,6 +170,11 @@ fn command_builtin() {
     run("command-p.sh")
 }
 
+#[test]
+fn command_builtin_ex() {
+    run("command-y.sh")
+}
+
 #[test]
 fn command_substitution() {
     run("cmdsub-p.sh")
@@ -260,6 +265,11 @@ fn function() {
     run("function-p.sh")
 }
 
+#[test]
+fn function_ex() {
+    run("function-y.sh")
+}
+
 #[test]
 fn getopts_builtin() {
     run("getopts-p.sh")
@@ -340,6 +350,11 @@ fn parameter_expansion() {
     run("param-p.sh")
 }
 
+#[test]
+fn parameter_expansion_ex() {
+    run("param-y.sh")
+}
+
 // a.k.a. globbing
 #[test]
 fn pathname_expansion() {
@@ -351,6 +366,11 @@ fn pipeline() {
     run("pipeline-p.sh")
 }
 
+#[test]
+fn pipeline_ex() {
+    run("pipeline-y.sh")
+}
+
 #[test]
 fn ppid_variable() {
     run("ppid-p.sh")
@@ -371,6 +391,11 @@ fn read_builtin() {
     run("read-p.sh")
 }
 
+#[test]
+fn read_builtin_ex() {
+    run("read-y.sh")
+}
+
 #[test]
 fn readonly_builtin() {
     run("readonly-p.sh")
@@ -406,6 +431,11 @@ fn source_builtin() {
     run("source-p.sh")
 }
 
+#[test]
+fn source_builtin_ex() {
+    run("source-y.sh")
+}
+
 #[test]
 fn startup() {
     run("startup-p.sh")
@@ -421,6 +451,11 @@ fn tilde_expansion() {
     run("tilde-p.sh")
 }
 
+#[test]
+fn tilde_expansion_ex() {
+    run("tilde-y.sh")
+}
+
 // This test case also covers the behavior of the trap execution.
 #[test]
 fn trap_builtin() {
@@ -590,6 +590,17 @@ __IN__
 % echo % !
 __OUT__
 
+test_oE 'mutually recursive aliases'
+a() { echo function a; }
+b() { echo function b; }
+alias a=b b=a
+a
+b
+__IN__
+function a
+function b
+__OUT__
+
 test_oE 'alias in command substitution'
 alias e=:
 func() {
@@ -600,3 +611,57 @@ func
 __IN__
 ok
 __OUT__
+
+test_oE 'alias defined before eval is used in evaluated string'
+alias e='echo alias'
+eval 'e ok'
+__IN__
+alias ok
+__OUT__
+
+test_oE 'alias defined in eval is used after eval'
+eval "alias e='echo alias'"
+e ok
+__IN__
+alias ok
+__OUT__
+
+test_oE 'alias defined in eval is used in next line of evaluated string'
+eval "alias e='echo alias'
+e ok"
+__IN__
+alias ok
+__OUT__
+
+test_oE 'alias defined before trap is used in trap action'
+alias e='echo alias'
+trap 'e trapped' USR1
+kill -s USR1 $$
+:
+__IN__
+alias trapped
+__OUT__
+
+test_oE 'alias defined in trap action is used after trap'
+trap "alias e='echo alias'" USR1
+kill -s USR1 $$
+e ok
+__IN__
+alias ok
+__OUT__
+
+test_oE 'alias defined before dot script is used in script'
+echo 'e sourced' >alias_dot_1
+alias e='echo alias'
+. ./alias_dot_1
+__IN__
+alias sourced
+__OUT__
+
+test_oE 'alias defined in dot script is used after script'
+echo "alias e='echo alias'" >alias_dot_2
+. ./alias_dot_2
+e ok
+__IN__
+alias ok
+__OUT__
@@ -72,6 +72,18 @@ test_OE -e 0 'exit status of case command with subject containing command substi
 case $(echo 1; exit 42) in esac
 __IN__
 
+test_oE 'tilde expansion results in pattern are matched literally'
+HOME='/a*'
+case /ab in
+    (~) echo not reached ;;
+esac
+case '/a*' in
+    (~) echo matched ;;
+esac
+__IN__
+matched
+__OUT__
+
 test_O -d -e 2 'in without case'
 in
 __IN__
@@ -170,8 +170,15 @@ test_O -d -e 5 'invalid option'
 cd --no-such-option
 __IN__
 
-test_O -e 0 'printing to closed stream'
+test_O -d -e 1 'printing to closed stream'
 OLDPWD=/ cd - >&-
 __IN__
 
+test_oE 'directory is changed even if printing fails'
+OLDPWD=/ cd - >&- 2>/dev/null
+pwd
+__IN__
+/
+__OUT__
+
 # vim: set ft=sh ts=8 sts=4 sw=4 et:
@@ -30,6 +30,13 @@ y][x
 y]
 __OUT__
 
+test_oE 'carriage returns are not removed'
+a=$(printf 'a\r\n') b=$(printf 'a\r\r\n\n') c=$(printf '\n\n\n')
+echo "${#a}" "${#b}" "${#c}"
+__IN__
+2 3 0
+__OUT__
+
 test_oE 'stdin is not redirected'
 echo a | echo $(cat)
 __IN__
@@ -0,0 +1,35 @@
+# command-y.sh: yash-specific test of the command built-in
+
+test_oE 'describing alias shadowing function (-V)'
+foo() { :; }
+alias foo=bar
+command -V foo
+__IN__
+foo: alias for `bar`
+foo: function
+__OUT__
+
+test_oE 'describing alias shadowing built-in (-V)'
+alias cd=bar
+command -V cd
+__IN__
+cd: alias for `bar`
+cd: mandatory built-in
+__OUT__
+
+test_oE 'describing alias shadowing special built-in and function (-V)'
+set() { :; }
+alias set=bar
+command -V set
+__IN__
+set: alias for `bar`
+set: special built-in
+__OUT__
+
+test_oE 'describing alias shadowing function (-v)'
+foo() { :; }
+alias foo=bar
+command -v foo
+__IN__
+alias foo=bar
+__OUT__
@@ -40,6 +40,22 @@ __IN__
 {#
 __OUT__
 
+test_oE 'hash sign in parameter expansions'
+set a b c
+x=##abc
+echo $# ${#x} ${x#\#} ${x##*#}
+__IN__
+3 5 #abc abc
+__OUT__
+
+test_oE 'comment containing here-document operator'
+echo foo # <<END
+echo bar
+__IN__
+foo
+bar
+__OUT__
+
 test_oE 'comment in pipeline'
 echo foo |###
 cat #|:
@@ -13,6 +13,16 @@ __IN__
 1  *  2
 __OUT__
 
+test_oE 'field splitting in assignment-like operand of non-declaration utility'
+a="1  2"
+printf '[%s]\n' A=$a
+echo "${A-unset}"
+__IN__
+[A=1]
+[2]
+unset
+__OUT__
+
 test_oE 'tilde expansions in export A=~:~' 
 HOME=/foo
 export A=~:~
@@ -156,3 +156,21 @@ echo $foo
 __IN__
 bar
 __OUT__
+
+test_oE 'positional parameters in nested function calls'
+outer() {
+    inner i1 i2 i3
+    echo outer: $# "$*"
+}
+inner() {
+    shift
+    echo inner: $# "$*"
+}
+set t1 t2 t3 t4
+outer o1 o2
+echo top: $# "$*"
+__IN__
+inner: 2 i2 i3
+outer: 2 o1 o2
+top: 4 t1 t2 t3 t4
+__OUT__
@@ -0,0 +1,22 @@
+# function-y.sh: yash-specific test of functions
+
+test_O -d -e 2 'infinite recursion is stopped by call depth limit'
+f() { f; }
+f
+echo not reached
+__IN__
+
+test_O -d -e 2 'infinite recursion through eval is stopped by call depth limit'
+f() { eval f; }
+f
+echo not reached
+__IN__
+
+test_oE 'recursion within call depth limit'
+f() { case $1 in (0) echo done;; (*) f $(($1 - 1));; esac; }
+f 500
+__IN__
+done
+__OUT__
+
+# vim: set ft=sh ts=8 sts=4 sw=4 et:
@@ -400,6 +400,32 @@ echo 3
 fi
 __ERR__
 
+test_oe 'verbose enabled and disabled by set'
+echo 1
+set -v
+echo 2
+set +v
+echo 3
+__IN__
+1
+2
+3
+__OUT__
+echo 2
+set +v
+__ERR__
+
+test_oe 'verbose on: effect in command string' -vc 'echo 1
+echo 2
+'
+__IN__
+1
+2
+__OUT__
+echo 1
+echo 2
+__ERR__
+
 test_x -e 0 'xtrace (short) on: $-' -x
 printf '%s\n' "$-" | grep -q x
 __IN__
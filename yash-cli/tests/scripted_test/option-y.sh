@@ -115,3 +115,56 @@ __IN__
 test_x -e 0 'concatenation of option and -o and argument' -aoerrexit
 echo $- | grep a | grep -q e
 __IN__
+
+test_oE 'braceexpand on' -o braceexpand
+echo a{b,c{d,e}}f "{x,y}" {z}
+__IN__
+abf acdf acef {x,y} {z}
+__OUT__
+
+test_oE 'braceexpand on: sequences' -o braceexpand
+echo {1..3} {05..1..2} {a..c}x {1..a}
+__IN__
+1 2 3 05 03 01 ax bx cx {1..a}
+__OUT__
+
+test_oE 'braceexpand off' +o braceexpand
+echo a{b,c}
+__IN__
+a{b,c}
+__OUT__
+
+mkdir caseglob
+>caseglob/Foo.TXT
+
+test_oE 'case and pathname expansion respect caseglob' -o caseglob
+case foo.txt in (FOO.txt) echo case matched; esac
+echo caseglob/*.txt
+__IN__
+caseglob/*.txt
+__OUT__
+
+test_oE 'case and pathname expansion respect nocaseglob' +o caseglob
+case foo.txt in (FOO.txt) echo case matched; esac
+echo caseglob/*.txt caseglob/foo.txt
+__IN__
+case matched
+caseglob/Foo.TXT caseglob/foo.txt
+__OUT__
+
+test_oE 'last command of pipeline runs in subshell without lastpipe' +o lastpipe
+echo hi | read x
+echo "${x-unset}"
+__IN__
+unset
+__OUT__
+
+test_oE 'last command of pipeline runs in current shell with lastpipe' -o lastpipe
+echo hi | read x
+echo "${x-unset}"
+echo foo | { read y; z=$y; }
+echo "$z"
+__IN__
+hi
+foo
+__OUT__
@@ -148,6 +148,23 @@ __IN__
 [-a-][--][-a-][-a-]
 __OUT__
 
+test_oE 'command substitution in unused embedded word is not executed'
+a=a
+bracket "${a-$(echo x >sidefile)}" "${a:-$(echo y >>sidefile)}"
+cat sidefile 2>/dev/null || echo no file
+__IN__
+[a][a]
+no file
+__OUT__
+
+test_oE 'nested default values'
+a= c='1  2'
+unset b
+bracket ${a:-${b:-x}} "${a:-${b:-x  y}}" ${a:-${b-$c}} "${a:-${b-$c}}"
+__IN__
+[x][x  y][1][2][1  2]
+__OUT__
+
 test_oE 'end of embedded word'
 a=a
 bracket ${a-x}b} ${a-x${a-x}x}b}
@@ -401,6 +418,18 @@ posix
 posix
 __OUT__
 
+test_oE 'positional parameters beyond nine'
+set a b c d e f g h i j k
+echo ${10} ${11} $11 ${12:-unset}
+__IN__
+j k a1 unset
+__OUT__
+
+test_O -d -e n 'out-of-range positional parameter in ${n:?}'
+set a
+echo ${2:?}
+__IN__
+
 test_oE 'removing prefix with expanded word'
 HOME=/home/foo
 x=$HOME/src/cmd
@@ -0,0 +1,242 @@
+# param-y.sh: yash-specific test of parameter expansion
+
+setup 'set -e'
+
+test_oE 'substring with offset'
+x=abcde
+echo ${x:0} ${x:1} ${x:4} ${x:5}- ${x:9}-
+__IN__
+abcde bcde e - -
+__OUT__
+
+test_oE 'substring with offset and length'
+x=abcde
+echo ${x:0:2} ${x:1:3} ${x:3:9} ${x:1:0}- ${x:9:1}-
+__IN__
+ab bcd de - -
+__OUT__
+
+test_oE 'substring with negative offset'
+x=abcde
+echo ${x: -1} ${x: -3:2} ${x:(-2)} ${x: -9}
+__IN__
+e cd de abcde
+__OUT__
+
+test_oE 'substring with negative length'
+x=abcde
+echo ${x:1:-1} ${x: -4:-2} ${x:2:-3}-
+__IN__
+bcd bc -
+__OUT__
+
+test_oE 'substring with arithmetic expressions'
+x=abcdef i=1
+echo ${x:i+1:i*2} ${x:$i} "${x:i:$((i+1))}"
+__IN__
+cd bcdef bc
+__OUT__
+
+test_oE 'substring with empty offset and length'
+x=abc e=
+echo ${x::2} ${x:$e:1} ${x:1:}-
+__IN__
+ab a -
+__OUT__
+
+test_O -d -e 2 'colon without offset'
+x=abc
+echo ${x:}
+__IN__
+
+test_oE 'substring of unset variable'
+unset x
+echo ${x:1:2}-
+__IN__
+-
+__OUT__
+
+test_oE 'substring of positional parameters'
+set a b c d
+echo ${@:1} / ${*:1:2} / ${@: -1} / ${@:4} / ${@:5}-
+__IN__
+a b c d / a b / d / d / -
+__OUT__
+
+test_oE 'substring of positional parameters from $0'
+set a b c d
+echo "${@:0:2}" | sed 's/^[^ ]* /[0] /'
+__IN__
+[0] a
+__OUT__
+
+test_oE 'substring counts characters'
+x=αβγδ
+echo ${x:1:2}
+__IN__
+βγ
+__OUT__
+
+test_oE 'substitution of first match'
+x=abcabc
+echo ${x/b/X} ${x/bc/} ${x/z/X}
+__IN__
+aXcabc aabc abcabc
+__OUT__
+
+test_oE 'substitution of all matches'
+x=abcabc
+echo ${x//b/X} ${x//bc/} ${x//z/X}
+__IN__
+aXcaXc aa abcabc
+__OUT__
+
+test_oE 'substitution with pattern'
+x=abcabc
+echo ${x/b*/X} ${x//[ac]/-} ${x//?/.}
+__IN__
+aX -b--b- ......
+__OUT__
+
+test_oE 'substitution with empty pattern'
+x=abc
+echo ${x//} ${x//X} ${x///X}
+__IN__
+abc abc abc
+__OUT__
+
+test_oE 'substitution without replacement'
+x=abcabc
+echo ${x/b} ${x//b}
+__IN__
+acabc acac
+__OUT__
+
+test_oE 'substitution with quoted pattern'
+x='a*b*c'
+echo "${x//\*/-}" "${x//'*'/+}"
+__IN__
+a-b-c a+b+c
+__OUT__
+
+test_oE 'substitution with expanded pattern and replacement'
+x=abcabc p='b?' r='1 2'
+echo "${x//$p/$r}" "${x//"$p"/$r}"
+__IN__
+a1 2a1 2 abcabc
+__OUT__
+
+test_oE 'replacement containing slash'
+x=a-b
+echo ${x/-//}
+__IN__
+a/b
+__OUT__
+
+test_oE 'substitution on positional parameters'
+set aa ba ca
+echo ${@/a/x} / ${*//a/y}
+__IN__
+xa bx cx / yy by cy
+__OUT__
+
+test_oE 'substitution of unset variable'
+unset x
+echo ${x/a/b}-
+__IN__
+-
+__OUT__
+
+test_O -d -e 2 'substring is rejected in POSIX mode'
+set -o posixlycorrect
+x=abc
+echo ${x:1}
+__IN__
+
+test_O -d -e 2 'substitution is rejected in POSIX mode'
+set -o posixlycorrect
+x=abc
+echo ${x/a/b}
+__IN__
+
+test_oE 'assigning to read-only variable in ${var:=value}'
+readonly r
+result="$( (echo ${r:=x}) 2>&1)" || :
+printf '%s\n' "$result" | grep -e '^error:' -e 'cannot assign' -e 'info: assignment' |
+sed 's/^[ |]*//'
+__IN__
+error: error assigning to variable
+cannot assign to read-only variable "r"
+info: assignment was attempted because the parameter was not set
+__OUT__
+
+test_oE 'assigning to positional parameter in ${1:=value}'
+set ''
+result="$( (echo ${1:=x}) 2>&1)" || :
+printf '%s\n' "$result" | grep -e '^error:' -e 'not an assignable' -e 'info: assignment' |
+sed 's/^[ |]*//'
+__IN__
+error: cannot assign to parameter
+parameter `1` is not an assignable variable
+info: assignment was attempted because the parameter was an empty string
+__OUT__
+
+test_oE 'error message of ${var?message}'
+result="$( (echo ${foo?custom message}) 2>&1)" || :
+printf '%s\n' "$result" | grep -e '^error:' -e 'is not set' | sed 's/^[ |^]*//'
+__IN__
+error: custom message
+parameter `foo` is not set
+__OUT__
+
+test_oE 'error messages of nounset'
+result="$( (set -u; echo $foo) 2>&1)" || :
+printf '%s\n' "$result" | grep -e '^error:' -e 'is not set' | sed 's/^[ |^]*//'
+result="$( (set -u; echo $3) 2>&1)" || :
+printf '%s\n' "$result" | grep -e 'is not set' | sed 's/^[ |^]*//'
+__IN__
+error: cannot expand unset parameter
+variable `foo` is not set
+positional parameter 3 is not set
+__OUT__
+
+test_oE 'default error message of ${var:?}'
+foo=
+result="$( (echo ${foo:?}) 2>&1)" || :
+printf '%s\n' "$result" | grep -e '^error:' -e 'empty string' | sed 's/^[ |^]*//'
+__IN__
+error: parameter expansion with empty value
+parameter `foo` is an empty string
+__OUT__
+
+test_oE 'alternative value of set and empty variables'
+x=
+printf '[%s]' "${x+set}" "${x:+non-empty}" "${u+set}" "${u:+non-empty}"; echo
+__IN__
+[set][][][]
+__OUT__
+
+test_oE 'quoting in alternative value'
+x=x
+printf '[%s]' "${x:+a  b}" ${x:+"a  b"} ${x:+a  b} ${x:+"q"}-; echo
+__IN__
+[a  b][a  b][a][b][q-]
+__OUT__
+
+test_oE 'length of all positional parameters'
+set a bb ccc
+echo ${#*} ${#@} "${#*}" "${#@}"
+set --
+echo ${#*} ${#@} "${#*}" "${#@}"
+__IN__
+3 3 3 3
+0 0 0 0
+__OUT__
+
+test_oE 'length counts characters'
+x='aあいう'
+set 1 2 3 4 'éè'
+echo ${#x} ${#5} ${#unset}
+__IN__
+4 2 0
+__OUT__
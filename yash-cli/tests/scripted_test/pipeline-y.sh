@@ -0,0 +1,69 @@
+# pipeline-y.sh: yash-specific test of pipeline
+
+test_oE 'time reports real, user, and system times'
+{ time echo foo; } 2>&1 | tr '\t' ' ' | sed 's/[0-9]/N/g'
+__IN__
+foo
+real NmN.NNNs
+user NmN.NNNs
+sys NmN.NNNs
+__OUT__
+
+test_oE 'time -p reports times in POSIX format'
+{ time -p echo foo | cat; } 2>&1 | sed 's/[0-9]/N/g'
+__IN__
+foo
+real N.NN
+user N.NN
+sys N.NN
+__OUT__
+
+test_oE 'time reports in POSIX format in POSIX mode'
+set -o posixlycorrect
+{ time true; } 2>&1 | sed 's/[0-9]/N/g'
+__IN__
+real N.NN
+user N.NN
+sys N.NN
+__OUT__
+
+test_oE 'exit status of timed pipelines'
+{ time false; } 2>/dev/null
+echo $?
+{ time ! false; } 2>/dev/null
+echo $?
+__IN__
+1
+0
+__OUT__
+
+test_oE 'time is not a reserved word in non-command position'
+echo time -p
+__IN__
+time -p
+__OUT__
+
+test_oE 'quoted -p is not an option of time'
+{ time '-p'; } 2>/dev/null
+echo $?
+__IN__
+127
+__OUT__
+
+test_oE 'time without command is a command name'
+\time() { echo "time:$*"; }
+time
+time -p
+{ time; }
+__IN__
+time:
+time:-p
+time:
+__OUT__
+
+test_oE 'time is not a reserved word after |'
+\time() { echo "time:$*"; }
+echo foo | time cat
+__IN__
+time:cat
+__OUT__
@@ -0,0 +1,85 @@
+# read-y.sh: yash-specific test of the read built-in
+
+setup -d
+
+test_oE 'reading limited number of characters'
+read -n 3 a <<\END
+abcdef
+END
+echoraw $? "[${a-unset}]"
+__IN__
+0 [abc]
+__OUT__
+
+test_oE 'characters after count are left unread'
+{
+    read -n 2 a
+    read b
+} <<\END
+abcdef
+END
+echoraw $? "[${a-unset}]" "[${b-unset}]"
+__IN__
+0 [ab] [cdef]
+__OUT__
+
+test_oE 'newline before count is reached'
+read -n 5 a <<\END
+ab
+cd
+END
+echoraw $? "[${a-unset}]"
+__IN__
+0 [ab]
+__OUT__
+
+test_oE 'line read within timeout'
+read -t 10 a <<\END
+foo
+END
+echoraw $? "[${a-unset}]"
+__IN__
+0 [foo]
+__OUT__
+
+test_oE 'timeout expiring mid-line'
+(printf foo; sleep 1) | {
+    read -t 0.1 a
+    status=$?
+    echoraw $((status > 128)) "[${a-unset}]"
+}
+__IN__
+1 [foo]
+__OUT__
+
+test_O -d -e 2 'invalid count'
+read -n x a
+__IN__
+
+test_O -d -e 2 'invalid timeout'
+read -t -1 a
+__IN__
+
+test_oE 'carriage return before newline is kept'
+printf 'a\r\n\n' >crlf
+{
+    read a
+    read b
+} <crlf
+echoraw $? "${#a}" "[${b-unset}]"
+__IN__
+0 2 []
+__OUT__
+
+test_oE 'read-only error from read matches that from for loop'
+readonly x=1
+from_read="$(echo foo | read x 2>&1)"
+from_for="$( (for x in foo; do :; done) 2>&1)"
+printf '%s\n' "$from_read" | grep -e '^error:' -e 'cannot assign' | sed 's/^.*\^ //'
+printf '%s\n' "$from_for" | grep -e '^error:' -e 'cannot assign' | sed 's/^.*\^ //'
+__IN__
+error: error assigning to variable
+cannot assign to read-only variable "x"
+error: error assigning to variable
+cannot assign to read-only variable "x"
+__OUT__
@@ -178,6 +178,16 @@ foo
 ------ 0
 __OUT__
 
+test_O -d -e n 'output redirection, empty pathname'
+unset f
+>"$f"
+__IN__
+
+test_O -d -e n 'output redirection, directory'
+mkdir dir1
+>dir1
+__IN__
+
 test_O -d -e n 'clobbering redirection, creation failure'
 >|_no_such_dir_/foo
 __IN__
@@ -228,6 +238,21 @@ __IN__
 foo
 __OUT__
 
+test_oE 'in-out redirection, reading and writing same file descriptor'
+printf 'line1\nline2\n' >inout2
+{
+    read line
+    echo "$line" is read
+    echo new >&0
+} <>inout2
+cat inout2
+__IN__
+line1 is read
+line1
+new
+2
+__OUT__
+
 test_O -d -e n 'in-out redirection, failure'
 <>_no_such_dir_/foo
 __IN__
@@ -510,6 +535,29 @@ END2
 foo
 __OUT__
 
+test_oE -e 0 'here-document content after newline in for loop values'
+cat <<END; for i in 1 2
+here
+END
+do echo $i; done
+__IN__
+here
+1
+2
+__OUT__
+
+test_oE -e 0 'here-document in while loop condition'
+i=0
+while cat <<END; [ $i -lt 2 ]
+$i
+END
+do i=$((i+1)); done
+__IN__
+0
+1
+2
+__OUT__
+
 test_o 'redirection is temporary' -e
 {
     cat </dev/null
@@ -0,0 +1,37 @@
+# source-y.sh: yash-specific test of the dot built-in
+
+cat <<\__END__ >params
+echo "$# $*"
+shift
+echo "$# $*"
+__END__
+
+test_oE 'operands are set as positional parameters'
+set a b c
+. ./params 1 2
+echo "$# $*"
+__IN__
+2 1 2
+1 2
+3 a b c
+__OUT__
+
+test_oE 'positional parameters are shared without operands'
+set a b c
+. ./params
+echo "$# $*"
+__IN__
+3 a b c
+2 b c
+2 b c
+__OUT__
+
+test_oE 'positional parameters are restored after return in dot script'
+echo 'echo "$*"; return; echo not reached' >return
+set a b c
+. ./return 1 2
+echo "$*"
+__IN__
+1 2
+a b c
+__OUT__
@@ -0,0 +1,12 @@
+# tilde-y.sh: yash-specific test of tilde expansion
+
+setup -d
+
+test_oE 'tilde with unknown user name is not expanded'
+bracket ~_no_such_user_ ~_no_such_user_/x
+a=~_no_such_user_:~_no_such_user_/y
+bracket "$a"
+__IN__
+[~_no_such_user_][~_no_such_user_/x]
+[~_no_such_user_:~_no_such_user_/y]
+__OUT__
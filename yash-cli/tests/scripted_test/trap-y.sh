@@ -69,6 +69,23 @@ trap -- '' USR1
 trap -- 'echo USR2' USR2
 __OUT__
 
+test_oE 'traps captured by command substitution can be restored with eval'
+trap 'echo exiting' EXIT
+trap '' USR1
+trap 'echo "USR2 $x"' USR2
+saved_traps=$(trap)
+trap - EXIT USR1 USR2
+echo "reset: [$(trap)]"
+eval "$saved_traps"
+trap
+__IN__
+reset: []
+trap -- 'echo exiting' EXIT
+trap -- '' USR1
+trap -- 'echo "USR2 $x"' USR2
+exiting
+__OUT__
+
 test_oE 'ignored signal is still ignored in subshell'
 trap '' USR1
 (sh -c 'kill -s USR1 $PPID'; echo reached)
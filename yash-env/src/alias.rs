@@ -16,6 +16,9 @@
 
 //! Implementation of the environment about aliases
 
+use crate::builtin::Builtin;
+use crate::builtin::Type::Special;
+use crate::function::Function;
 use crate::Env;
 use std::rc::Rc;
 use yash_syntax::alias::{Alias, Glossary};
@@ -33,3 +36,121 @@ impl Glossary for Env {
         self.aliases.is_empty()
     }
 }
+
+/// Command hidden by an alias of the same name
+///
+/// This is the result type of [`Env::shadowed_by_alias`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Shadowed<'a> {
+    /// Function
+    Function(&'a Rc<Function>),
+    /// Built-in utility
+    Builtin(&'a Builtin),
+}
+
+impl Env {
+    /// Returns the command hidden by the alias of the given name.
+    ///
+    /// Because aliases are substituted when commands are parsed, an alias
+    /// prevents the function or built-in of the same name from being invoked
+    /// by the name. This function returns the command that would be found by
+    /// the name if the alias were not defined: a special built-in, a function,
+    /// or another built-in, in this order of precedence. External utilities
+    /// are not considered.
+    ///
+    /// The result is `None` if `name` is not an alias or there is no function
+    /// or built-in with the name.
+    #[must_use]
+    pub fn shadowed_by_alias(&self, name: &str) -> Option<Shadowed<'_>> {
+        self.aliases.get(name)?;
+        let builtin = self.builtins.get(name);
+        if let Some(builtin) = builtin.filter(|builtin| builtin.r#type == Special) {
+            return Some(Shadowed::Builtin(builtin));
+        }
+        if let Some(function) = self.functions.get(name) {
+            return Some(Shadowed::Function(function));
+        }
+        builtin.map(Shadowed::Builtin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builtin::Type::{Mandatory, Special};
+    use crate::function::Function;
+    use futures_util::future::ready;
+    use yash_syntax::alias::HashEntry;
+    use yash_syntax::source::Location;
+    use yash_syntax::syntax::FullCompoundCommand;
+
+    fn define_alias(env: &mut Env, name: &str) {
+        env.aliases.insert(HashEntry::new(
+            name.to_string(),
+            "replacement".to_string(),
+            false,
+            Location::dummy("alias"),
+        ));
+    }
+
+    fn define_function(env: &mut Env, name: &str) -> Rc<Function> {
+        let body: FullCompoundCommand = "{ :; }".parse().unwrap();
+        let function = Rc::new(Function::new(name, body, Location::dummy("function")));
+        env.functions.define(function.clone()).unwrap();
+        function
+    }
+
+    fn define_builtin(env: &mut Env, name: &'static str, r#type: crate::builtin::Type) {
+        let builtin = Builtin::new(r#type, |_, _| Box::pin(ready(Default::default())));
+        env.builtins.insert(name, builtin);
+    }
+
+    #[test]
+    fn shadowed_by_alias_without_alias() {
+        let mut env = Env::new_virtual();
+        define_function(&mut env, "foo");
+        assert_eq!(env.shadowed_by_alias("foo"), None);
+    }
+
+    #[test]
+    fn shadowed_by_alias_without_command() {
+        let mut env = Env::new_virtual();
+        define_alias(&mut env, "foo");
+        assert_eq!(env.shadowed_by_alias("foo"), None);
+    }
+
+    #[test]
+    fn shadowed_by_alias_function() {
+        let mut env = Env::new_virtual();
+        define_alias(&mut env, "foo");
+        define_builtin(&mut env, "foo", Mandatory);
+        let function = define_function(&mut env, "foo");
+        assert_eq!(
+            env.shadowed_by_alias("foo"),
+            Some(Shadowed::Function(&function))
+        );
+    }
+
+    #[test]
+    fn shadowed_by_alias_builtin() {
+        let mut env = Env::new_virtual();
+        define_alias(&mut env, "foo");
+        define_builtin(&mut env, "foo", Mandatory);
+        assert_matches::assert_matches!(
+            env.shadowed_by_alias("foo"),
+            Some(Shadowed::Builtin(builtin)) => assert_eq!(builtin.r#type, Mandatory)
+        );
+    }
+
+    #[test]
+    fn shadowed_by_alias_special_builtin_precedes_function() {
+        let mut env = Env::new_virtual();
+        define_alias(&mut env, "foo");
+        define_function(&mut env, "foo");
+        define_builtin(&mut env, "foo", Special);
+        assert_matches::assert_matches!(
+            env.shadowed_by_alias("foo"),
+            Some(Shadowed::Builtin(builtin)) => assert_eq!(builtin.r#type, Special)
+        );
+    }
+}
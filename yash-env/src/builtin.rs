@@ -258,6 +258,22 @@ pub struct Builtin {
     ///
     /// [method description]: yash_syntax::decl_util::Glossary::is_declaration_utility
     pub is_declaration_utility: Option<bool>,
+
+    /// Short description of the built-in
+    ///
+    /// This is a one-line summary of what the built-in does, such as "print
+    /// working directory". It is used by the `help` built-in to list
+    /// available built-ins. The string may be empty if no description is
+    /// provided.
+    pub description: &'static str,
+
+    /// Synopsis of the built-in
+    ///
+    /// This string shows the command line syntax of the built-in, such as
+    /// `pwd [-L|-P]`. If the built-in has more than one form of syntax, each
+    /// form is given on a separate line. The string may be empty if no
+    /// synopsis is provided.
+    pub synopsis: &'static str,
 }
 
 impl Debug for Builtin {
@@ -275,11 +291,30 @@ impl Builtin {
     /// The `type` and `execute` fields are set to the given arguments.
     /// The `is_declaration_utility` field is set to `Some(false)`, indicating
     /// that the built-in is not a declaration utility.
+    /// The `description` and `synopsis` fields are set to empty strings.
     pub const fn new(r#type: Type, execute: Main) -> Self {
         Self {
             r#type,
             execute,
             is_declaration_utility: Some(false),
+            description: "",
+            synopsis: "",
         }
     }
+
+    /// Sets the [`description`](Self::description) field.
+    #[inline]
+    #[must_use]
+    pub const fn with_description(mut self, description: &'static str) -> Self {
+        self.description = description;
+        self
+    }
+
+    /// Sets the [`synopsis`](Self::synopsis) field.
+    #[inline]
+    #[must_use]
+    pub const fn with_synopsis(mut self, synopsis: &'static str) -> Self {
+        self.synopsis = synopsis;
+        self
+    }
 }
@@ -43,6 +43,7 @@
 
 use crate::semantics::ExitStatus;
 use crate::signal;
+use crate::system::ResourceUsage;
 use slab::Slab;
 use std::collections::HashMap;
 use std::iter::FusedIterator;
@@ -281,10 +282,43 @@ pub struct Job {
     pub is_owned: bool,
 
     /// String representation of this process
+    ///
+    /// The name is usually the command text of the job. [`JobList::add`]
+    /// truncates the name to at most [`Job::MAX_NAME_LENGTH`] characters.
     pub name: String,
+
+    /// Resource usage of the finished process
+    ///
+    /// This value is set by [`JobList::update_status_with_usage`] when the job
+    /// has finished. It is `None` while the job is running or if the resource
+    /// usage has not been collected.
+    pub resource_usage: Option<ResourceUsage>,
 }
 
 impl Job {
+    /// Maximum number of characters in a job name
+    ///
+    /// A job for a long command, such as a large compound command run
+    /// asynchronously, would otherwise make the output of the `jobs` built-in
+    /// unreadable. See [`Job::truncate_name`].
+    pub const MAX_NAME_LENGTH: usize = 256;
+
+    /// Truncates the name of the job to at most [`Job::MAX_NAME_LENGTH`]
+    /// characters.
+    ///
+    /// If the name is longer than the limit, its end is replaced with `...`
+    /// so that the result has exactly `MAX_NAME_LENGTH` characters.
+    pub fn truncate_name(&mut self) {
+        const ELLIPSIS: &str = "...";
+        let mut indices = self.name.char_indices().map(|(index, _)| index);
+        if let Some(end) = indices.nth(Self::MAX_NAME_LENGTH - ELLIPSIS.len()) {
+            if indices.nth(ELLIPSIS.len() - 1).is_some() {
+                self.name.truncate(end);
+                self.name.push_str(ELLIPSIS);
+            }
+        }
+    }
+
     /// Creates a new job instance.
     ///
     /// This function requires a process ID to initialize the new job. The other
@@ -298,6 +332,7 @@ impl Job {
             state_changed: true,
             is_owned: true,
             name: String::new(),
+            resource_usage: None,
         }
     }
 
@@ -607,11 +642,15 @@ impl JobList {
     /// If there already is a job that has the same process ID as that of the
     /// new job, the existing job is silently removed.
     ///
+    /// The name of the new job is [truncated](Job::truncate_name) if it is too
+    /// long.
+    ///
     /// If the new job is suspended and the [current job](Self::current_job) is
     /// not, the new job becomes the current job. If the new job and the current
     /// job are suspended but the [previous job](Self::previous_job) is not, the
     /// new job becomes the previous job.
-    pub fn add(&mut self, job: Job) -> usize {
+    pub fn add(&mut self, mut job: Job) -> usize {
+        job.truncate_name();
         let new_job_is_suspended = job.is_suspended();
         let ex_current_job_is_suspended =
             self.current_job().map(|index| self[index].is_suspended());
@@ -792,6 +831,27 @@ impl JobList {
         Some(index)
     }
 
+    /// Updates the state of a job and records its resource usage.
+    ///
+    /// This function is the same as [`update_status`](Self::update_status)
+    /// except that it also saves the given resource usage in the
+    /// [`resource_usage`](Job::resource_usage) of the job if the job has
+    /// finished. Pass the result of
+    /// [`System::wait_with_usage`](crate::System::wait_with_usage) to this
+    /// function.
+    pub fn update_status_with_usage(
+        &mut self,
+        pid: Pid,
+        state: ProcessState,
+        usage: ResourceUsage,
+    ) -> Option<usize> {
+        let index = self.update_status(pid, state)?;
+        if !state.is_alive() {
+            self.jobs[index].resource_usage = Some(usage);
+        }
+        Some(index)
+    }
+
     /// Disowns all jobs.
     ///
     /// This function sets the `is_owned` flag of all jobs to `false`.
@@ -928,6 +988,7 @@ pub mod id;
 mod tests {
     use super::*;
     use crate::system::r#virtual::{SIGSTOP, SIGTSTP, SIGTTIN, SIGTTOU};
+    use std::time::Duration;
 
     #[test]
     fn job_list_find_by_pid() {
@@ -993,6 +1054,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn truncating_job_name() {
+        let mut job = Job::new(Pid(10));
+        job.name = "a".repeat(Job::MAX_NAME_LENGTH);
+        job.truncate_name();
+        assert_eq!(job.name, "a".repeat(Job::MAX_NAME_LENGTH));
+
+        job.name = "\u{3042}".repeat(Job::MAX_NAME_LENGTH + 1);
+        job.truncate_name();
+        assert_eq!(job.name.chars().count(), Job::MAX_NAME_LENGTH);
+        assert!(job.name.ends_with("\u{3042}..."), "{:?}", job.name);
+    }
+
+    #[test]
+    fn job_list_add_truncates_long_name() {
+        let mut list = JobList::default();
+        let mut job = Job::new(Pid(10));
+        job.name = "x".repeat(1000);
+        let index = list.add(job);
+
+        let name = &list[index].name;
+        assert_eq!(name.len(), Job::MAX_NAME_LENGTH);
+        assert_eq!(
+            name[..Job::MAX_NAME_LENGTH - 3],
+            "x".repeat(Job::MAX_NAME_LENGTH - 3)
+        );
+        assert!(name.ends_with("..."), "{name:?}");
+    }
+
     #[test]
     fn job_list_extract_if() {
         let mut list = JobList::default();
@@ -1086,6 +1176,45 @@ mod tests {
         assert_eq!(job.state_changed, true);
     }
 
+    #[test]
+    fn updating_job_status_with_usage_of_finished_process() {
+        let mut list = JobList::default();
+        let pid = Pid(20);
+        let i20 = list.add(Job::new(pid));
+        let usage = ResourceUsage {
+            user_time: Duration::from_secs(1),
+            system_time: Duration::from_secs(2),
+            max_rss: 100,
+        };
+
+        let result = list.update_status_with_usage(pid, ProcessState::exited(0), usage);
+        assert_eq!(result, Some(i20));
+
+        let job = &list[i20];
+        assert_eq!(job.state, ProcessState::exited(0));
+        assert_eq!(job.resource_usage, Some(usage));
+    }
+
+    #[test]
+    fn updating_job_status_with_usage_of_stopped_process() {
+        let mut list = JobList::default();
+        let pid = Pid(20);
+        let i20 = list.add(Job::new(pid));
+        let usage = ResourceUsage {
+            user_time: Duration::from_secs(1),
+            system_time: Duration::from_secs(2),
+            max_rss: 100,
+        };
+
+        let state = ProcessState::stopped(SIGSTOP);
+        let result = list.update_status_with_usage(pid, state, usage);
+        assert_eq!(result, Some(i20));
+
+        let job = &list[i20];
+        assert_eq!(job.state, state);
+        assert_eq!(job.resource_usage, None);
+    }
+
     #[test]
     #[allow(clippy::bool_assert_comparison)]
     fn disowning_jobs() {
@@ -31,12 +31,14 @@
 //! the underlying system. [`VirtualSystem`] is a dummy for simulating the
 //! system's behavior without affecting the actual system.
 
+pub use self::alias::Shadowed;
 use self::any::DataSet;
 use self::builtin::Builtin;
 use self::function::FunctionSet;
 use self::io::Fd;
 use self::job::JobList;
 use self::job::Pid;
+use self::job::ProcessResult;
 use self::job::ProcessState;
 use self::option::On;
 use self::option::OptionSet;
@@ -59,15 +61,18 @@ use self::variable::VariableSet;
 use self::variable::PPID;
 use futures_util::task::noop_waker_ref;
 use std::collections::HashMap;
+use std::ffi::CString;
 use std::fmt::Debug;
 use std::future::Future;
 use std::ops::ControlFlow::{self, Break, Continue};
 use std::rc::Rc;
 use std::task::Context;
 use std::task::Poll;
+use std::time::Instant;
 pub use unix_path as path;
 pub use unix_str as str;
 use yash_syntax::alias::AliasSet;
+use yash_syntax::source::Location;
 
 /// Whole shell execution environment.
 ///
@@ -96,6 +101,22 @@ pub struct Env {
     /// Built-in utilities available in the environment
     pub builtins: HashMap<&'static str, Builtin>,
 
+    /// Maximum depth of nested function calls, `eval` commands, and dot scripts
+    ///
+    /// When the [call depth](Stack::call_depth) reaches this limit, further
+    /// calls fail with an error instead of exhausting the native stack. The
+    /// default is [`DEFAULT_CALL_DEPTH_LIMIT`](stack::DEFAULT_CALL_DEPTH_LIMIT).
+    /// Note that the native stack may still overflow before the limit is
+    /// reached if the stack is too small.
+    pub call_depth_limit: usize,
+
+    /// Maximum number of bytes a command substitution may produce
+    ///
+    /// If the output of a command substitution exceeds this limit, the
+    /// expansion fails with an error instead of buffering the rest of the
+    /// output. `None` means no limit, which is the default.
+    pub command_subst_output_limit: Option<usize>,
+
     /// Exit status of the last executed command
     pub exit_status: ExitStatus,
 
@@ -142,6 +163,8 @@ impl Env {
     /// Creates a new environment with the given system.
     ///
     /// Members of the new environments are default-constructed except that:
+    /// - `call_depth_limit` is initialized as
+    ///   [`DEFAULT_CALL_DEPTH_LIMIT`](stack::DEFAULT_CALL_DEPTH_LIMIT)
     /// - `main_pid` is initialized as `system.getpid()`
     /// - `system` is initialized as `SharedSystem::new(system)`
     #[must_use]
@@ -150,6 +173,8 @@ impl Env {
             aliases: Default::default(),
             arg0: Default::default(),
             builtins: Default::default(),
+            call_depth_limit: stack::DEFAULT_CALL_DEPTH_LIMIT,
+            command_subst_output_limit: None,
             exit_status: Default::default(),
             functions: Default::default(),
             jobs: Default::default(),
@@ -173,15 +198,23 @@ impl Env {
 
     /// Clones this environment.
     ///
-    /// The application-managed parts of the environment are cloned normally.
-    /// The system-managed parts are replaced with the provided `System`
-    /// instance.
+    /// The application-managed parts of the environment, such as variables,
+    /// functions, aliases, and options, are cloned normally so that changes in
+    /// the returned environment are not visible in `self` and vice versa. The
+    /// system-managed parts are replaced with the provided `System` instance.
+    ///
+    /// The virtual system uses this function to start a child process, where
+    /// the clone is the equivalent of a forked process. The subshell-specific
+    /// changes such as resetting traps are made later by
+    /// [`Subshell`](subshell::Subshell), in the same way as in the real system.
     #[must_use]
     pub fn clone_with_system(&self, system: Box<dyn System>) -> Env {
         Env {
             aliases: self.aliases.clone(),
             arg0: self.arg0.clone(),
             builtins: self.builtins.clone(),
+            call_depth_limit: self.call_depth_limit,
+            command_subst_output_limit: self.command_subst_output_limit,
             exit_status: self.exit_status,
             functions: self.functions.clone(),
             jobs: self.jobs.clone(),
@@ -197,6 +230,21 @@ impl Env {
         }
     }
 
+    /// Imports environment variables inherited from the parent process.
+    ///
+    /// This function obtains the environment variables from
+    /// [`System::environ`] and adds them to `self.variables` as exported
+    /// variables by [`VariableSet::extend_env`]. Variables with an invalid
+    /// name are skipped. None of the imported variables are read-only.
+    ///
+    /// This function should be called before
+    /// [`init_variables`](Self::init_variables) so that the default values
+    /// do not override the imported ones.
+    pub fn import_environ(&mut self) {
+        let vars = self.system.environ();
+        self.variables.extend_env(vars);
+    }
+
     /// Initializes default variables.
     ///
     /// This function assigns the following variables to `self`:
@@ -209,7 +257,8 @@ impl Env {
     /// - `PPID=(parent process ID)`
     /// - `PWD=(current working directory)` (See [`Env::prepare_pwd`])
     ///
-    /// This function ignores any errors that may occur.
+    /// See [`VariableSet::init`] for the variables that keep their values if
+    /// already set. This function ignores any errors that may occur.
     ///
     /// TODO: PS1 should be set to `"# "` for root users.
     pub fn init_variables(&mut self) {
@@ -239,6 +288,26 @@ impl Env {
         result
     }
 
+    /// Waits for some signals to be caught in the current process until the
+    /// specified time point.
+    ///
+    /// Returns an array of signals caught, or `None` if no signals are caught
+    /// before `target`.
+    ///
+    /// This function is a wrapper for [`SharedSystem::wait_for_signals_until`].
+    /// Like [`wait_for_signals`](Self::wait_for_signals), it passes the results
+    /// to [`TrapSet::catch_signal`].
+    pub async fn wait_for_signals_until(
+        &mut self,
+        target: Instant,
+    ) -> Option<Rc<[signal::Number]>> {
+        let result = self.system.wait_for_signals_until(target).await;
+        for signal in result.iter().flat_map(|signals| signals.iter().copied()) {
+            self.traps.catch_signal(signal);
+        }
+        result
+    }
+
     /// Waits for a specific signal to be caught in the current process.
     ///
     /// This function calls [`wait_for_signals`](Self::wait_for_signals)
@@ -338,9 +407,10 @@ impl Env {
     /// - `pid`: the child whose process ID is `pid`
     /// - `-pgid`: any child in the process group whose process group ID is `pgid`
     ///
-    /// When [`self.system.wait`](System::wait) returned a new state of the
-    /// target, it is sent to `self.jobs` ([`JobList::update_status`]) before
-    /// being returned from this function.
+    /// When [`self.system.wait_with_usage`](System::wait_with_usage) returned
+    /// a new state of the target, it is sent to `self.jobs`
+    /// ([`JobList::update_status_with_usage`]) before being returned from this
+    /// function.
     ///
     /// If there is no matching target, this function returns
     /// `Err(Errno::ECHILD)`.
@@ -359,8 +429,8 @@ impl Env {
             .signal_number_from_name(signal::Name::Chld)
             .unwrap();
         loop {
-            if let Some((pid, state)) = self.system.wait(target)? {
-                self.jobs.update_status(pid, state);
+            if let Some((pid, state, usage)) = self.system.wait_with_usage(target)? {
+                self.jobs.update_status_with_usage(pid, state, usage);
                 return Ok((pid, state));
             }
             self.wait_for_signal(sigchld).await;
@@ -373,16 +443,18 @@ impl Env {
     /// [`wait_for_subshell`](Self::wait_for_subshell), but returns only when
     /// the target is finished (either exited or killed by a signal).
     ///
-    /// Returns the process ID of the awaited process and its exit status.
+    /// Returns the process ID of the awaited process and its result, which
+    /// tells whether the process exited or was terminated by a signal. Use
+    /// [`ExitStatus::from`] to convert the result to an exit status.
     pub async fn wait_for_subshell_to_finish(
         &mut self,
         target: Pid,
-    ) -> Result<(Pid, ExitStatus), Errno> {
+    ) -> Result<(Pid, ProcessResult), Errno> {
         loop {
             let (pid, state) = self.wait_for_subshell(target).await?;
-            if let Ok(exit_status) = state.try_into() {
-                if !state.is_alive() {
-                    return Ok((pid, exit_status));
+            if let ProcessState::Halted(result) = state {
+                if !result.is_stopped() {
+                    return Ok((pid, result));
                 }
             }
         }
@@ -390,15 +462,15 @@ impl Env {
 
     /// Applies all job status updates to jobs in `self.jobs`.
     ///
-    /// This function calls [`self.system.wait`](System::wait) repeatedly until
-    /// all status updates available are applied to `self.jobs`
-    /// ([`JobList::update_status`]).
+    /// This function calls [`self.system.wait_with_usage`](System::wait_with_usage)
+    /// repeatedly until all status updates available are applied to
+    /// `self.jobs` ([`JobList::update_status_with_usage`]).
     ///
     /// Note that updates of subshells that are not managed in `self.jobs` are
     /// lost when you call this function.
     pub fn update_all_subshell_statuses(&mut self) {
-        while let Ok(Some((pid, state))) = self.system.wait(Pid::ALL) {
-            self.jobs.update_status(pid, state);
+        while let Ok(Some((pid, state, usage))) = self.system.wait_with_usage(Pid::ALL) {
+            self.jobs.update_status_with_usage(pid, state, usage);
         }
     }
 
@@ -422,6 +494,49 @@ impl Env {
         variable
     }
 
+    /// Returns the location where a variable was last assigned.
+    ///
+    /// This method returns the [`last_assigned_location`] of the variable
+    /// visible in the current context. It returns `None` if the variable is not
+    /// defined or its value was not assigned by a command in the shell
+    /// session, e.g., if it was imported from the environment.
+    ///
+    /// [`last_assigned_location`]: variable::Variable::last_assigned_location
+    #[must_use]
+    pub fn last_assigned_location(&self, name: &str) -> Option<&Location> {
+        self.variables.get(name)?.last_assigned_location.as_ref()
+    }
+
+    /// Returns the environment variables to pass to an external utility.
+    ///
+    /// The result contains a `name=value` string for each exported variable
+    /// visible in the current context. If a variable is defined in more than
+    /// one context, only the innermost definition is included, so a temporary
+    /// assignment of a simple command hides the variable of the same name in
+    /// the enclosing contexts. The value of a variable with a
+    /// [quirk](variable::Quirk) is computed by [`Variable::expand`] with the
+    /// given location, which should be the location of the command being
+    /// executed.
+    ///
+    /// Exported arrays are not included in the result. Variables whose name or
+    /// value contains a null character are not included either because they
+    /// cannot be represented in C strings.
+    ///
+    /// The order of the variables in the result is unspecified.
+    ///
+    /// [`Variable::expand`]: variable::Variable::expand
+    #[must_use]
+    pub fn environment_for_exec(&self, location: &Location) -> Vec<CString> {
+        self.variables
+            .iter(Scope::Global)
+            .filter(|(_, var)| var.is_exported)
+            .filter_map(|(name, var)| match var.expand(location) {
+                variable::Expansion::Scalar(value) => CString::new(format!("{name}={value}")).ok(),
+                variable::Expansion::Array(_) | variable::Expansion::Unset => None,
+            })
+            .collect()
+    }
+
     /// Tests whether the [`ErrExit`] option is applicable in the current context.
     ///
     /// This function returns true if and only if:
@@ -439,6 +554,13 @@ impl Env {
     /// The function returns `Break(Divert::Exit(None))` if the [`errexit`
     /// option is applicable](Self::errexit_is_applicable) and the current
     /// `self.exit_status` is non-zero. Otherwise, it returns `Continue(())`.
+    ///
+    /// Commands whose exit status should not trigger the errexit option, such
+    /// as the condition of an `if` command, the left-hand side of `&&` and
+    /// `||`, and negated pipelines, are executed with a [`Frame::Condition`]
+    /// pushed to the stack. Command implementations do not need to check
+    /// the context themselves; they only call this function after updating
+    /// the exit status.
     pub fn apply_errexit(&self) -> ControlFlow<Divert> {
         if !self.exit_status.is_successful() && self.errexit_is_applicable() {
             Break(Divert::Exit(None))
@@ -490,15 +612,18 @@ mod tests {
     use crate::system::r#virtual::FileBody;
     use crate::system::r#virtual::Inode;
     use crate::system::r#virtual::SystemState;
+    use crate::system::r#virtual::SIGABRT;
     use crate::system::r#virtual::SIGCHLD;
+    use crate::system::r#virtual::SIGKILL;
+    use crate::system::ResourceUsage;
     use crate::trap::Action;
+    use crate::variable::{IFS, OPTIND, PS1, PS2};
     use assert_matches::assert_matches;
     use futures_executor::LocalPool;
     use futures_util::task::LocalSpawnExt as _;
     use futures_util::FutureExt as _;
     use std::cell::RefCell;
     use std::str::from_utf8;
-    use yash_syntax::source::Location;
 
     /// Helper function to perform a test in a virtual system with an executor.
     pub fn in_virtual_system<F, Fut, T>(f: F) -> T
@@ -563,6 +688,58 @@ mod tests {
         })
     }
 
+    #[test]
+    fn wait_for_signals_until_remembers_signal_in_trap_set() {
+        in_virtual_system(|mut env, state| async move {
+            env.traps
+                .set_action(
+                    &mut env.system,
+                    SIGCHLD,
+                    Action::Command("".into()),
+                    Location::dummy(""),
+                    false,
+                )
+                .unwrap();
+            let start = Instant::now();
+            state.borrow_mut().now = Some(start);
+            {
+                let mut state = state.borrow_mut();
+                let process = state.processes.get_mut(&env.main_pid).unwrap();
+                let _ = process.raise_signal(SIGCHLD);
+            }
+            let target = start + std::time::Duration::from_secs(1);
+            let result = env.wait_for_signals_until(target).await;
+
+            assert_eq!(result.as_deref(), Some(&[SIGCHLD][..]));
+            let trap_state = env.traps.get_state(SIGCHLD).0.unwrap();
+            assert!(trap_state.pending);
+        })
+    }
+
+    #[test]
+    fn wait_for_signals_until_times_out() {
+        in_virtual_system(|mut env, state| async move {
+            env.traps
+                .set_action(
+                    &mut env.system,
+                    SIGCHLD,
+                    Action::Command("".into()),
+                    Location::dummy(""),
+                    false,
+                )
+                .unwrap();
+            let start = Instant::now();
+            state.borrow_mut().now = Some(start);
+            let target = start + std::time::Duration::from_secs(1);
+            let result = env.wait_for_signals_until(target).await;
+
+            assert_eq!(result, None);
+            assert_eq!(state.borrow().now, Some(target));
+            let trap_state = env.traps.get_state(SIGCHLD).0.unwrap();
+            assert!(!trap_state.pending);
+        })
+    }
+
     fn poll_signals_env() -> (Env, VirtualSystem) {
         let system = VirtualSystem::new();
         let shared_system = SharedSystem::new(Box::new(system.clone()));
@@ -661,10 +838,87 @@ mod tests {
             let result = env.wait_for_subshell(pid).await;
             assert_eq!(result, Ok((pid, ProcessState::exited(42))));
             job.state = ProcessState::exited(42);
+            job.resource_usage = Some(Default::default());
             assert_eq!(env.jobs[job_index], job);
         });
     }
 
+    #[test]
+    fn wait_for_subshell_records_resource_usage_in_job_list() {
+        in_virtual_system(|mut env, state| async move {
+            let usage = ResourceUsage {
+                user_time: std::time::Duration::from_millis(1500),
+                system_time: std::time::Duration::from_millis(250),
+                max_rss: 4096,
+            };
+            let subshell = Subshell::new(move |env, _job_control| {
+                Box::pin(async move {
+                    let pid = env.system.getpid();
+                    let mut state = state.borrow_mut();
+                    let process = state.processes.get_mut(&pid).unwrap();
+                    process.set_resource_usage(usage);
+                })
+            });
+            let (pid, _) = subshell.start(&mut env).await.unwrap();
+            let job_index = env.jobs.add(Job::new(pid));
+            assert_eq!(env.jobs[job_index].resource_usage, None);
+
+            env.wait_for_subshell_to_finish(pid).await.unwrap();
+            assert_eq!(env.jobs[job_index].resource_usage, Some(usage));
+        });
+    }
+
+    #[test]
+    fn wait_for_subshell_to_finish_exited() {
+        in_virtual_system(|mut env, _state| async move {
+            let subshell = Subshell::new(|env, _job_control| {
+                Box::pin(async { env.exit_status = ExitStatus(17) })
+            });
+            let (pid, _) = subshell.start(&mut env).await.unwrap();
+            let result = env.wait_for_subshell_to_finish(pid).await;
+            assert_eq!(result, Ok((pid, ProcessResult::exited(17))));
+        });
+    }
+
+    #[test]
+    fn wait_for_subshell_to_finish_signaled() {
+        in_virtual_system(|mut env, _state| async move {
+            let subshell = Subshell::new(|env, _job_control| {
+                Box::pin(async {
+                    let pid = env.system.getpid();
+                    _ = env.system.kill(pid, Some(SIGKILL)).await;
+                })
+            });
+            let (pid, _) = subshell.start(&mut env).await.unwrap();
+            let result = env.wait_for_subshell_to_finish(pid).await;
+            let signaled = ProcessResult::Signaled {
+                signal: SIGKILL,
+                core_dump: false,
+            };
+            assert_eq!(result, Ok((pid, signaled)));
+            assert_eq!(ExitStatus::from(signaled), ExitStatus::from(SIGKILL));
+        });
+    }
+
+    #[test]
+    fn wait_for_subshell_to_finish_signaled_with_core_dump() {
+        in_virtual_system(|mut env, _state| async move {
+            let subshell = Subshell::new(|env, _job_control| {
+                Box::pin(async {
+                    let pid = env.system.getpid();
+                    _ = env.system.kill(pid, Some(SIGABRT)).await;
+                })
+            });
+            let (pid, _) = subshell.start(&mut env).await.unwrap();
+            let result = env.wait_for_subshell_to_finish(pid).await;
+            let signaled = ProcessResult::Signaled {
+                signal: SIGABRT,
+                core_dump: true,
+            };
+            assert_eq!(result, Ok((pid, signaled)));
+        });
+    }
+
     #[test]
     fn wait_for_subshell_no_subshell() {
         let system = VirtualSystem::new();
@@ -738,6 +992,151 @@ mod tests {
         assert_eq!(env.jobs[job_3].state, ProcessState::Running);
     }
 
+    #[test]
+    fn import_environ_adds_exported_variables() {
+        let system = VirtualSystem::new();
+        system.state.borrow_mut().environ = HashMap::from([
+            ("HOME".to_string(), "/home/user".to_string()),
+            ("PATH".to_string(), "/bin:/usr/bin".to_string()),
+            ("1abc".to_string(), "invalid".to_string()),
+            ("a-b".to_string(), "invalid".to_string()),
+            ("".to_string(), "invalid".to_string()),
+        ]);
+        let mut env = Env::with_system(Box::new(system));
+
+        env.import_environ();
+
+        let home = env.variables.get("HOME").unwrap();
+        assert_eq!(home.value, Some("/home/user".into()));
+        assert!(home.is_exported);
+        assert_eq!(home.read_only_location, None);
+        let path = env.variables.get("PATH").unwrap();
+        assert_eq!(path.value, Some("/bin:/usr/bin".into()));
+        assert!(path.is_exported);
+        assert_eq!(path.read_only_location, None);
+        assert_eq!(env.variables.get("1abc"), None);
+        assert_eq!(env.variables.get("a-b"), None);
+        assert_eq!(env.variables.get(""), None);
+    }
+
+    #[test]
+    fn init_variables_keeps_imported_values() {
+        let system = VirtualSystem::new();
+        system.state.borrow_mut().environ = HashMap::from([
+            ("IFS".to_string(), ":".to_string()),
+            ("PS1".to_string(), "% ".to_string()),
+            ("OPTIND".to_string(), "3".to_string()),
+        ]);
+        let mut env = Env::with_system(Box::new(system));
+
+        env.import_environ();
+        env.init_variables();
+
+        let ifs = env.variables.get(IFS).unwrap();
+        assert_eq!(ifs.value, Some(" \t\n".into()));
+        let ps1 = env.variables.get(PS1).unwrap();
+        assert_eq!(ps1.value, Some("% ".into()));
+        assert!(ps1.is_exported);
+        let optind = env.variables.get(OPTIND).unwrap();
+        assert_eq!(optind.value, Some("1".into()));
+        let ps2 = env.variables.get(PS2).unwrap();
+        assert_eq!(ps2.value, Some("> ".into()));
+        assert!(!ps2.is_exported);
+    }
+
+    #[test]
+    fn last_assigned_location_of_variables() {
+        let mut env = Env::new_virtual();
+        let location = Location::dummy("assigned");
+        let mut a = env.get_or_create_variable("a", Scope::Global);
+        a.assign("1", location.clone()).unwrap();
+        let mut b = env.get_or_create_variable("b", Scope::Global);
+        b.assign("2", None).unwrap();
+
+        assert_eq!(env.last_assigned_location("a"), Some(&location));
+        assert_eq!(env.last_assigned_location("b"), None);
+        assert_eq!(env.last_assigned_location("c"), None);
+    }
+
+    fn sorted_environment_for_exec(env: &Env, location: &Location) -> Vec<CString> {
+        let mut envs = env.environment_for_exec(location);
+        envs.sort_unstable();
+        envs
+    }
+
+    #[test]
+    fn environment_for_exec_with_nested_contexts() {
+        let mut env = Env::new_virtual();
+        let mut foo = env.variables.get_or_new("foo", Scope::Global);
+        foo.assign("global", None).unwrap();
+        foo.export(true);
+        let mut bar = env.variables.get_or_new("bar", Scope::Global);
+        bar.assign("global", None).unwrap();
+        bar.export(true);
+        let mut baz = env.variables.get_or_new("baz", Scope::Global);
+        baz.assign("global", None).unwrap();
+
+        let mut env = env.push_context(variable::Context::default());
+        let mut bar = env.variables.get_or_new("bar", Scope::Local);
+        bar.assign("local", None).unwrap();
+        let mut baz = env.variables.get_or_new("baz", Scope::Local);
+        baz.assign("local", None).unwrap();
+        baz.export(true);
+
+        let location = Location::dummy("");
+        {
+            let mut env = env.push_context(variable::Context::Volatile);
+            let mut foo = env.variables.get_or_new("foo", Scope::Volatile);
+            foo.assign("temporary", None).unwrap();
+            foo.export(true);
+
+            assert_eq!(
+                sorted_environment_for_exec(&env, &location),
+                [c"baz=local".to_owned(), c"foo=temporary".to_owned()]
+            );
+        }
+
+        assert_eq!(
+            sorted_environment_for_exec(&env, &location),
+            [c"baz=local".to_owned(), c"foo=global".to_owned()]
+        );
+    }
+
+    #[test]
+    fn environment_for_exec_skips_arrays() {
+        let mut env = Env::new_virtual();
+        let mut a = env.variables.get_or_new("a", Scope::Global);
+        a.assign(variable::Value::array(["1", "2"]), None).unwrap();
+        a.export(true);
+        let mut s = env.variables.get_or_new("s", Scope::Global);
+        s.assign("1", None).unwrap();
+        s.export(true);
+        let mut u = env.variables.get_or_new("u", Scope::Global);
+        u.export(true);
+
+        let location = Location::dummy("");
+        assert_eq!(
+            sorted_environment_for_exec(&env, &location),
+            [c"s=1".to_owned()]
+        );
+    }
+
+    #[test]
+    fn environment_for_exec_applies_quirks() {
+        let mut env = Env::new_virtual();
+        env.variables.init();
+        env.variables
+            .get_or_new("LINENO", Scope::Global)
+            .export(true);
+
+        let location = Location::dummy("\n\necho\n");
+        let envs = env.environment_for_exec(&Location {
+            range: 2..6,
+            ..location
+        });
+        assert!(envs.contains(&c"LINENO=3".to_owned()), "{envs:?}");
+    }
+
     #[test]
     fn get_or_create_variable_with_all_export_off() {
         let mut env = Env::new_virtual();
@@ -783,6 +1182,36 @@ mod tests {
         assert_eq!(env.apply_errexit(), Continue(()));
     }
 
+    #[test]
+    fn errexit_in_nested_conditions() {
+        let mut env = Env::new_virtual();
+        env.exit_status = ExitStatus::FAILURE;
+        env.options.set(ErrExit, On);
+        let mut env = env.push_frame(Frame::Condition);
+        let mut env = env.push_frame(Frame::Loop);
+        let env = env.push_frame(Frame::Condition);
+        assert_eq!(env.apply_errexit(), Continue(()));
+    }
+
+    #[test]
+    fn errexit_after_leaving_condition() {
+        let mut env = Env::new_virtual();
+        env.exit_status = ExitStatus::FAILURE;
+        env.options.set(ErrExit, On);
+        let env2 = env.push_frame(Frame::Condition);
+        Env::pop_frame(env2);
+        assert_eq!(env.apply_errexit(), Break(Divert::Exit(None)));
+    }
+
+    #[test]
+    fn errexit_in_loop_outside_condition() {
+        let mut env = Env::new_virtual();
+        env.exit_status = ExitStatus(2);
+        env.options.set(ErrExit, On);
+        let env = env.push_frame(Frame::Loop);
+        assert_eq!(env.apply_errexit(), Break(Divert::Exit(None)));
+    }
+
     #[test]
     fn errexit_off() {
         let mut env = Env::new_virtual();
@@ -29,6 +29,7 @@ use enumset::EnumSetType;
 use std::borrow::Cow;
 use std::fmt::Display;
 use std::fmt::Formatter;
+use std::fmt::Write as _;
 use std::ops::Not;
 use std::str::FromStr;
 use thiserror::Error;
@@ -101,6 +102,10 @@ impl From<State> for bool {
 pub enum Option {
     /// Makes all variables exported when they are assigned.
     AllExport,
+    /// Enables brace expansion.
+    BraceExpand,
+    /// Makes pattern matching case-sensitive.
+    CaseGlob,
     /// Allows overwriting and truncating an existing file with the `>`
     /// redirection.
     Clobber,
@@ -110,6 +115,8 @@ pub enum Option {
     ErrExit,
     /// Makes the shell to actually run commands.
     Exec,
+    /// Makes pathname expansion fail when a pattern matches no files.
+    FailGlob,
     /// Enables pathname expansion.
     Glob,
     /// Performs command search for each command in a function on its
@@ -120,6 +127,9 @@ pub enum Option {
     IgnoreEof,
     /// Enables features for interactive use.
     Interactive,
+    /// Executes the last command of a pipeline in the current shell
+    /// environment.
+    LastPipe,
     /// Allows function definition commands to be recorded in the command
     /// history.
     Log,
@@ -129,6 +139,8 @@ pub enum Option {
     Monitor,
     /// Automatically reports the results of asynchronous jobs.
     Notify,
+    /// Makes a pattern that matches no files expand to nothing.
+    NullGlob,
     /// Disables most non-POSIX extensions.
     PosixlyCorrect,
     /// Reads commands from the standard input.
@@ -165,18 +177,23 @@ impl Option {
     pub const fn short_name(self) -> std::option::Option<(char, State)> {
         match self {
             AllExport => Some(('a', On)),
+            BraceExpand => None,
+            CaseGlob => None,
             Clobber => Some(('C', Off)),
             CmdLine => Some(('c', On)),
             ErrExit => Some(('e', On)),
             Exec => Some(('n', Off)),
+            FailGlob => None,
             Glob => Some(('f', Off)),
             HashOnDefinition => Some(('h', On)),
             IgnoreEof => None,
             Interactive => Some(('i', On)),
+            LastPipe => None,
             Log => None,
             Login => Some(('l', On)),
             Monitor => Some(('m', On)),
             Notify => Some(('b', On)),
+            NullGlob => None,
             PosixlyCorrect => None,
             Stdin => Some(('s', On)),
             Unset => Some(('u', Off)),
@@ -194,18 +211,23 @@ impl Option {
     pub const fn long_name(self) -> &'static str {
         match self {
             AllExport => "allexport",
+            BraceExpand => "braceexpand",
+            CaseGlob => "caseglob",
             Clobber => "clobber",
             CmdLine => "cmdline",
             ErrExit => "errexit",
             Exec => "exec",
+            FailGlob => "failglob",
             Glob => "glob",
             HashOnDefinition => "hashondefinition",
             IgnoreEof => "ignoreeof",
             Interactive => "interactive",
+            LastPipe => "lastpipe",
             Log => "log",
             Login => "login",
             Monitor => "monitor",
             Notify => "notify",
+            NullGlob => "nullglob",
             PosixlyCorrect => "posixlycorrect",
             Stdin => "stdin",
             Unset => "unset",
@@ -258,18 +280,23 @@ impl FromStr for Option {
     fn from_str(name: &str) -> Result<Self, FromStrError> {
         const OPTIONS: &[(&str, Option)] = &[
             ("allexport", AllExport),
+            ("braceexpand", BraceExpand),
+            ("caseglob", CaseGlob),
             ("clobber", Clobber),
             ("cmdline", CmdLine),
             ("errexit", ErrExit),
             ("exec", Exec),
+            ("failglob", FailGlob),
             ("glob", Glob),
             ("hashondefinition", HashOnDefinition),
             ("ignoreeof", IgnoreEof),
             ("interactive", Interactive),
+            ("lastpipe", LastPipe),
             ("log", Log),
             ("login", Login),
             ("monitor", Monitor),
             ("notify", Notify),
+            ("nullglob", NullGlob),
             ("posixlycorrect", PosixlyCorrect),
             ("stdin", Stdin),
             ("unset", Unset),
@@ -443,6 +470,116 @@ pub fn canonicalize(name: &str) -> Cow<'_, str> {
     }
 }
 
+/// Splits the sign from a command line argument that specifies short options.
+///
+/// The shell invocation and the set built-in accept short options in an
+/// argument that starts with `-` or `+`. A `-` enables the options and a `+`
+/// disables them. This function returns the sign as `negate` (`true` for `+`)
+/// and the rest of the argument, which contains the option characters.
+///
+/// ```
+/// # use yash_env::option::split_short_options;
+/// assert_eq!(split_short_options("-ex"), Some((false, "ex")));
+/// assert_eq!(split_short_options("+o"), Some((true, "o")));
+/// assert_eq!(split_short_options("-+"), Some((false, "+")));
+/// assert_eq!(split_short_options("-"), None);
+/// assert_eq!(split_short_options("--"), None);
+/// assert_eq!(split_short_options("++x"), None);
+/// assert_eq!(split_short_options("x"), None);
+/// ```
+///
+/// This function returns `None` if the argument does not specify short
+/// options, that is, if the argument does not start with a sign, consists of
+/// the sign only, or starts with a repeated sign, which is a long option (see
+/// [`split_long_option`]).
+#[must_use]
+pub fn split_short_options(arg: &str) -> std::option::Option<(bool, &str)> {
+    let (negate, rest) = if let Some(rest) = arg.strip_prefix('-') {
+        (false, rest)
+    } else {
+        (true, arg.strip_prefix('+')?)
+    };
+    let sign = if negate { '+' } else { '-' };
+    if rest.is_empty() || rest.starts_with(sign) {
+        None
+    } else {
+        Some((negate, rest))
+    }
+}
+
+/// Splits the sign from a command line argument that specifies a long option.
+///
+/// A long option starts with `--` to enable the option or `++` to disable it.
+/// This function returns the sign as `negate` (`true` for `++`) and the rest
+/// of the argument, which is the option name possibly followed by `=` and a
+/// value.
+///
+/// ```
+/// # use yash_env::option::split_long_option;
+/// assert_eq!(split_long_option("--errexit"), Some((false, "errexit")));
+/// assert_eq!(split_long_option("++err-exit"), Some((true, "err-exit")));
+/// assert_eq!(split_long_option("++"), Some((true, "")));
+/// assert_eq!(split_long_option("--"), None);
+/// assert_eq!(split_long_option("-e"), None);
+/// ```
+///
+/// The argument `--` alone is not a long option but the separator between
+/// options and operands, so this function returns `None` for it.
+#[must_use]
+pub fn split_long_option(arg: &str) -> std::option::Option<(bool, &str)> {
+    if let Some(name) = arg.strip_prefix("--") {
+        (!name.is_empty()).then_some((false, name))
+    } else {
+        arg.strip_prefix("++").map(|name| (true, name))
+    }
+}
+
+/// Parses a short option name with a sign.
+///
+/// This function is similar to [`parse_short`], but inverts the state if
+/// `negate` is `true`, that is, if the option was specified with `+`.
+///
+/// ```
+/// # use yash_env::option::{parse_short_with_sign, Option::*, State::*};
+/// assert_eq!(parse_short_with_sign('e', false), Some((ErrExit, On)));
+/// assert_eq!(parse_short_with_sign('e', true), Some((ErrExit, Off)));
+/// assert_eq!(parse_short_with_sign('C', true), Some((Clobber, On)));
+/// ```
+#[must_use]
+pub const fn parse_short_with_sign(
+    name: char,
+    negate: bool,
+) -> std::option::Option<(self::Option, State)> {
+    match parse_short(name) {
+        Some((option, state)) => Some((option, apply_sign(state, negate))),
+        None => None,
+    }
+}
+
+/// Parses a long option name with a sign.
+///
+/// This function [canonicalizes](canonicalize) the name, [parses](parse_long)
+/// it, and inverts the state if `negate` is `true`, that is, if the option was
+/// specified with `+o` or `++`.
+///
+/// ```
+/// # use yash_env::option::{parse_long_with_sign, Option::*, State::*};
+/// assert_eq!(parse_long_with_sign("err-exit", false), Ok((ErrExit, On)));
+/// assert_eq!(parse_long_with_sign("errexit", true), Ok((ErrExit, Off)));
+/// assert_eq!(parse_long_with_sign("noclobber", true), Ok((Clobber, On)));
+/// ```
+pub fn parse_long_with_sign(name: &str, negate: bool) -> Result<(Option, State), FromStrError> {
+    let (option, state) = parse_long(&canonicalize(name))?;
+    Ok((option, apply_sign(state, negate)))
+}
+
+const fn apply_sign(state: State, negate: bool) -> State {
+    match (state, negate) {
+        (On, false) | (Off, true) => On,
+        (Off, false) | (On, true) => Off,
+    }
+}
+
 /// Set of the shell options and their states.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct OptionSet {
@@ -452,10 +589,10 @@ pub struct OptionSet {
 /// Defines the default option set.
 ///
 /// Note that the default set is not empty. The following options are enabled by
-/// default: `Clobber`, `Exec`, `Glob`, `Log`, `Unset`
+/// default: `CaseGlob`, `Clobber`, `Exec`, `Glob`, `Log`, `Unset`
 impl Default for OptionSet {
     fn default() -> Self {
-        let enabled_options = Clobber | Exec | Glob | Log | Unset;
+        let enabled_options = CaseGlob | Clobber | Exec | Glob | Log | Unset;
         OptionSet { enabled_options }
     }
 }
@@ -494,6 +631,58 @@ impl OptionSet {
     }
 }
 
+impl OptionSet {
+    /// Lists the option states in a human-readable format.
+    ///
+    /// This is the format of the output of `set -o`. Each line contains the
+    /// name of an option and its state (`on` or `off`). The options are
+    /// listed in alphabetical order.
+    ///
+    /// ```
+    /// # use yash_env::option::OptionSet;
+    /// let listing = OptionSet::default().to_human_readable();
+    /// assert!(listing.contains("\nerrexit          off\n"), "{listing}");
+    /// ```
+    #[must_use]
+    pub fn to_human_readable(&self) -> String {
+        let mut listing = String::new();
+        for option in Option::iter() {
+            let state = self.get(option);
+            writeln!(listing, "{option:16} {state}").unwrap();
+        }
+        listing
+    }
+
+    /// Lists the option states as commands that restore them.
+    ///
+    /// This is the format of the output of `set +o`. Each line is a `set`
+    /// command that sets an option to its current state, such as
+    /// `set -o errexit` or `set +o noclobber`. The options are listed in
+    /// alphabetical order. The lines for options that cannot be
+    /// [modified](Option::is_modifiable) by the set built-in are commented out
+    /// with `#` so that the output can be passed to `eval`.
+    ///
+    /// ```
+    /// # use yash_env::option::OptionSet;
+    /// let listing = OptionSet::default().to_commands();
+    /// assert!(listing.contains("\nset +o errexit\n"), "{listing}");
+    /// assert!(listing.contains("\n#set +o interactive\n"), "{listing}");
+    /// ```
+    #[must_use]
+    pub fn to_commands(&self) -> String {
+        let mut listing = String::new();
+        for option in Option::iter() {
+            let skip = if option.is_modifiable() { "" } else { "#" };
+            let sign = match self.get(option) {
+                On => '-',
+                Off => '+',
+            };
+            writeln!(listing, "{skip}set {sign}o {option}").unwrap();
+        }
+        listing
+    }
+}
+
 impl Extend<Option> for OptionSet {
     fn extend<T: IntoIterator<Item = Option>>(&mut self, iter: T) {
         self.enabled_options.extend(iter);
@@ -530,7 +719,8 @@ mod tests {
     fn from_str_unambiguous_abbreviation() {
         assert_eq!(Option::from_str("allexpor"), Ok(AllExport));
         assert_eq!(Option::from_str("a"), Ok(AllExport));
-        assert_eq!(Option::from_str("n"), Ok(Notify));
+        assert_eq!(Option::from_str("no"), Ok(Notify));
+        assert_eq!(Option::from_str("nu"), Ok(NullGlob));
     }
 
     #[test]
@@ -567,7 +757,8 @@ mod tests {
     fn parse_unambiguous_abbreviation() {
         assert_eq!(parse_long("allexpor"), Ok((AllExport, On)));
         assert_eq!(parse_long("not"), Ok((Notify, On)));
-        assert_eq!(parse_long("non"), Ok((Notify, Off)));
+        assert_eq!(parse_long("nono"), Ok((Notify, Off)));
+        assert_eq!(parse_long("nonu"), Ok((NullGlob, Off)));
         assert_eq!(parse_long("un"), Ok((Unset, On)));
         assert_eq!(parse_long("noun"), Ok((Unset, Off)));
     }
@@ -580,6 +771,7 @@ mod tests {
         assert_eq!(parse_long("noe"), Err(Ambiguous));
         assert_eq!(parse_long("e"), Err(Ambiguous));
         assert_eq!(parse_long("nolo"), Err(Ambiguous));
+        assert_eq!(parse_long("non"), Err(Ambiguous));
     }
 
     #[test]
@@ -601,4 +793,120 @@ mod tests {
         assert_eq!(canonicalize(" no  such_Option "), "nosuchoption");
         assert_eq!(canonicalize("Ａｂｃ"), "Ａｂｃ");
     }
+
+    #[test]
+    fn splitting_short_options() {
+        assert_eq!(split_short_options("-a"), Some((false, "a")));
+        assert_eq!(split_short_options("+abc"), Some((true, "abc")));
+        assert_eq!(split_short_options("+-"), Some((true, "-")));
+        assert_eq!(split_short_options("-"), None);
+        assert_eq!(split_short_options("+"), None);
+        assert_eq!(split_short_options("--a"), None);
+        assert_eq!(split_short_options("++a"), None);
+        assert_eq!(split_short_options(""), None);
+        assert_eq!(split_short_options("a-"), None);
+    }
+
+    #[test]
+    fn splitting_long_option() {
+        assert_eq!(split_long_option("--a"), Some((false, "a")));
+        assert_eq!(split_long_option("++a=b"), Some((true, "a=b")));
+        assert_eq!(split_long_option("---"), Some((false, "-")));
+        assert_eq!(split_long_option("--"), None);
+        assert_eq!(split_long_option("-a"), None);
+        assert_eq!(split_long_option("+a"), None);
+        assert_eq!(split_long_option(""), None);
+    }
+
+    #[test]
+    fn parsing_short_with_sign() {
+        for c in "abCcefhilmnsuvx".chars() {
+            let (option, state) = parse_short(c).unwrap();
+            assert_eq!(parse_short_with_sign(c, false), Some((option, state)));
+            assert_eq!(parse_short_with_sign(c, true), Some((option, !state)));
+        }
+        assert_eq!(parse_short_with_sign('z', false), None);
+        assert_eq!(parse_short_with_sign('z', true), None);
+    }
+
+    #[test]
+    fn parsing_long_with_sign() {
+        assert_eq!(
+            parse_long_with_sign("AllExport", false),
+            Ok((AllExport, On))
+        );
+        assert_eq!(
+            parse_long_with_sign("all-export", true),
+            Ok((AllExport, Off))
+        );
+        assert_eq!(parse_long_with_sign("no_exec", false), Ok((Exec, Off)));
+        assert_eq!(parse_long_with_sign("noexec", true), Ok((Exec, On)));
+        assert_eq!(parse_long_with_sign("xyz", false), Err(NoSuchOption));
+        assert_eq!(parse_long_with_sign("no", true), Err(Ambiguous));
+    }
+
+    #[test]
+    fn short_name_agrees_with_parse_short_with_sign() {
+        for option in Option::iter() {
+            if let Some((name, state)) = option.short_name() {
+                assert_eq!(parse_short_with_sign(name, false), Some((option, state)));
+                assert_eq!(parse_short_with_sign(name, true), Some((option, !state)));
+            }
+        }
+    }
+
+    #[test]
+    fn human_readable_listing() {
+        let mut options = OptionSet::empty();
+        options.set(ErrExit, On);
+        let listing = options.to_human_readable();
+        let lines: Vec<&str> = listing.lines().collect();
+        assert_eq!(lines.len(), Option::iter().len());
+        assert!(lines.contains(&"errexit          on"), "{listing}");
+        assert!(lines.contains(&"clobber          off"), "{listing}");
+        for (line, option) in lines.iter().zip(Option::iter()) {
+            assert!(line.starts_with(option.long_name()), "{line:?}");
+        }
+    }
+
+    /// Parses the output of [`OptionSet::to_commands`] in the way the set
+    /// built-in does, applying the commented-out lines as well.
+    fn parse_commands(listing: &str, options: &mut OptionSet) {
+        for line in listing.lines() {
+            let line = line.strip_prefix('#').unwrap_or(line);
+            let mut words = line.split(' ');
+            assert_eq!(words.next(), Some("set"), "{line:?}");
+            let (negate, flags) = split_short_options(words.next().unwrap()).unwrap();
+            assert_eq!(flags, "o", "{line:?}");
+            let (option, state) = parse_long_with_sign(words.next().unwrap(), negate).unwrap();
+            assert_eq!(words.next(), None, "{line:?}");
+            options.set(option, state);
+        }
+    }
+
+    #[test]
+    fn commands_listing_round_trip() {
+        let mut options = OptionSet::default();
+        options.set(AllExport, On);
+        options.set(Clobber, Off);
+        options.set(Interactive, On);
+        options.set(PosixlyCorrect, On);
+        let listing = options.to_commands();
+
+        let mut restored = OptionSet::empty();
+        parse_commands(&listing, &mut restored);
+        assert_eq!(restored, options);
+
+        let mut restored = OptionSet::default();
+        parse_commands(&listing, &mut restored);
+        assert_eq!(restored, options);
+    }
+
+    #[test]
+    fn commands_listing_comments_out_unmodifiable_options() {
+        let listing = OptionSet::default().to_commands();
+        for (line, option) in listing.lines().zip(Option::iter()) {
+            assert_eq!(line.starts_with('#'), !option.is_modifiable(), "{line:?}");
+        }
+    }
 }
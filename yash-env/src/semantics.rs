@@ -32,6 +32,10 @@ pub struct Field {
     /// String value of the field.
     pub value: String,
     /// Location of the word this field resulted from.
+    ///
+    /// If the word was divided into more than one field by field splitting,
+    /// the location points to the range of this field in the expanded word,
+    /// whose source is [`Source::Expansion`](yash_syntax::source::Source::Expansion).
     pub origin: Location,
 }
 
@@ -30,10 +30,15 @@
 //! `Stack`. [`Env::push_frame`] returns a [`EnvFrameGuard`] that implements
 //! `DerefMut<Target = Env>`.
 
+use crate::function::Function;
 use crate::semantics::Field;
 use crate::Env;
 use std::ops::Deref;
 use std::ops::DerefMut;
+use std::rc::Rc;
+
+/// Default value of [`Env::call_depth_limit`]
+pub const DEFAULT_CALL_DEPTH_LIMIT: usize = 1000;
 
 /// Information about the currently executing built-in
 ///
@@ -74,10 +79,15 @@ pub enum Frame {
     /// Shell script file executed by the `.` built-in
     DotScript,
 
+    /// Command string executed by the `eval` built-in
+    Eval,
+
+    /// Function being executed
+    Function(Rc<Function>),
+
     /// Trap
     Trap(crate::trap::Condition),
 
-    // TODO function
     /// File executed during shell startup
     InitFile,
 }
@@ -162,7 +172,11 @@ impl Stack {
     pub fn loop_count(&self, max_count: usize) -> usize {
         fn retains_context(frame: &Frame) -> bool {
             match frame {
-                Frame::Loop | Frame::Condition | Frame::Builtin(_) => true,
+                Frame::Loop
+                | Frame::Condition
+                | Frame::Builtin(_)
+                | Frame::Eval
+                | Frame::Function(_) => true,
                 Frame::Subshell | Frame::DotScript | Frame::Trap(_) | Frame::InitFile => false,
             }
         }
@@ -176,6 +190,19 @@ impl Stack {
             .count()
     }
 
+    /// Returns the number of nested calls.
+    ///
+    /// The call depth is the number of `Frame::Function`, `Frame::Eval`, and
+    /// `Frame::DotScript` frames in the stack. It is compared with
+    /// [`Env::call_depth_limit`] to prevent infinite recursion.
+    #[must_use]
+    pub fn call_depth(&self) -> usize {
+        self.inner
+            .iter()
+            .filter(|frame| matches!(frame, Frame::Function(_) | Frame::Eval | Frame::DotScript))
+            .count()
+    }
+
     /// Returns the innermost built-in in the stack, if any.
     #[must_use]
     pub fn current_builtin(&self) -> Option<&Builtin> {
@@ -227,6 +254,16 @@ impl Env {
         EnvFrameGuard { env: self }
     }
 
+    /// Returns whether the call depth has reached the limit.
+    ///
+    /// This function returns true if [`Stack::call_depth`] is not less than
+    /// [`Env::call_depth_limit`], in which case no more functions, `eval`
+    /// commands, or dot scripts should be executed.
+    #[must_use]
+    pub fn call_depth_exceeded(&self) -> bool {
+        self.stack.call_depth() >= self.call_depth_limit
+    }
+
     /// Pops the topmost frame from the runtime execution context stack.
     #[inline]
     pub fn pop_frame(guard: EnvFrameGuard<'_>) -> Frame {
@@ -412,4 +449,44 @@ mod tests {
         let stack = stack.push(Frame::Builtin(builtin.clone()));
         assert_eq!(stack.current_builtin(), Some(&builtin));
     }
+
+    fn dummy_function() -> Rc<Function> {
+        let body: yash_syntax::syntax::FullCompoundCommand = "{ :; }".parse().unwrap();
+        let origin = yash_syntax::source::Location::dummy("f");
+        Rc::new(Function::new("f", body, origin))
+    }
+
+    #[test]
+    fn call_depth_counts_functions_evals_and_dot_scripts() {
+        let mut stack = Stack::default();
+        assert_eq!(stack.call_depth(), 0);
+        let mut stack = stack.push(Frame::Loop);
+        let mut stack = stack.push(Frame::Eval);
+        assert_eq!(stack.call_depth(), 1);
+        let mut stack = stack.push(Frame::Subshell);
+        let mut stack = stack.push(Frame::DotScript);
+        assert_eq!(stack.call_depth(), 2);
+        let stack = stack.push(Frame::Function(dummy_function()));
+        assert_eq!(stack.call_depth(), 3);
+    }
+
+    #[test]
+    fn loop_count_through_functions_and_evals() {
+        let mut stack = Stack::default();
+        let mut stack = stack.push(Frame::Loop);
+        let mut stack = stack.push(Frame::Eval);
+        let stack = stack.push(Frame::Function(dummy_function()));
+        assert_eq!(stack.loop_count(usize::MAX), 1);
+    }
+
+    #[test]
+    fn call_depth_exceeded() {
+        let mut env = Env::new_virtual();
+        env.call_depth_limit = 2;
+        assert!(!env.call_depth_exceeded());
+        let mut env = env.push_frame(Frame::Eval);
+        assert!(!env.call_depth_exceeded());
+        let env = env.push_frame(Frame::DotScript);
+        assert!(env.call_depth_exceeded());
+    }
 }
@@ -25,6 +25,22 @@
 //! [`System::new_child_process`]. You should prefer `Subshell` for the purpose
 //! of creating a subshell because it helps to arrange the child process
 //! properly.
+//!
+//! # Subshell environment
+//!
+//! The subshell starts with a copy of the parent's [`Env`]. In the real
+//! system, the whole process is forked. In the virtual system, the
+//! environment is copied with [`Env::clone_with_system`]. Either way, the
+//! variables, functions, aliases, options, and other application-managed
+//! parts of the environment are independent of the parent after the subshell
+//! has started: changes in one are not visible to the other.
+//!
+//! On the other hand, the system-managed parts such as the file system are
+//! shared between the parent and the subshell as in a real process.
+//!
+//! Before running the task, the subshell [disowns](crate::job::JobList::disown_all)
+//! all jobs, resets traps as required by POSIX, and pushes a
+//! [`Frame::Subshell`] onto the stack.
 
 use crate::job::Pid;
 use crate::job::ProcessResult;
@@ -39,6 +55,7 @@ use crate::system::SystemEx;
 use crate::Env;
 use std::future::Future;
 use std::pin::Pin;
+use thiserror::Error;
 
 /// Job state of a newly created subshell
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -49,6 +66,67 @@ pub enum JobControl {
     Background,
 }
 
+/// Error in starting a subshell
+///
+/// The variants tell at which stage the subshell failed so that the caller can
+/// decide how to react.
+#[derive(Clone, Copy, Debug, Eq, Error, Hash, PartialEq)]
+pub enum StartError {
+    /// Error in preparing the subshell before creating the child process
+    ///
+    /// For example, this error occurs if the terminal cannot be opened to
+    /// start a foreground job.
+    #[error("cannot prepare the subshell: {0}")]
+    Setup(Errno),
+
+    /// Error in creating the child process
+    ///
+    /// `EAGAIN` and `ENOMEM` mean that the system is temporarily short of
+    /// resources.
+    #[error("cannot create a child process: {0}")]
+    Fork(Errno),
+
+    /// The system does not support creating a child process.
+    ///
+    /// The virtual system returns this error if it has no executor to run the
+    /// child process. The [`errno`](Self::errno) of this error is `ENOSYS`.
+    #[error("cannot create a child process: no executor to run it")]
+    Unsupported,
+
+    /// Error in waiting for the started subshell
+    ///
+    /// Only [`Subshell::start_and_wait`] returns this error.
+    #[error("cannot wait for the subshell: {0}")]
+    Wait(Errno),
+}
+
+impl StartError {
+    /// Returns the underlying error number.
+    #[must_use]
+    pub const fn errno(&self) -> Errno {
+        match *self {
+            StartError::Setup(errno) | StartError::Fork(errno) | StartError::Wait(errno) => errno,
+            StartError::Unsupported => Errno::ENOSYS,
+        }
+    }
+
+    /// Tests whether the error is due to a temporary shortage of system
+    /// resources.
+    ///
+    /// This function returns true for a [`Fork`](Self::Fork) error with
+    /// `EAGAIN` or `ENOMEM`. Retrying the operation later may succeed.
+    #[must_use]
+    pub fn is_resource_shortage(&self) -> bool {
+        matches!(self, StartError::Fork(Errno::EAGAIN | Errno::ENOMEM))
+    }
+}
+
+impl From<StartError> for Errno {
+    fn from(error: StartError) -> Self {
+        error.errno()
+    }
+}
+
 /// Subshell builder
 ///
 /// See the [module documentation](self) for details.
@@ -147,14 +225,14 @@ where
     ///
     /// If the subshell started successfully, the return value is a pair of the
     /// child process ID and the actual job control. Otherwise, it indicates the
-    /// error.
-    pub async fn start(self, env: &mut Env) -> Result<(Pid, Option<JobControl>), Errno> {
+    /// error and the stage where it occurred.
+    pub async fn start(self, env: &mut Env) -> Result<(Pid, Option<JobControl>), StartError> {
         // Do some preparation before starting a child process
         let job_control = env.controls_jobs().then_some(self.job_control).flatten();
         let tty = match job_control {
             None | Some(JobControl::Background) => None,
             // Open the tty in the parent process so we can reuse the FD for other jobs
-            Some(JobControl::Foreground) => Some(env.get_tty()?),
+            Some(JobControl::Foreground) => Some(env.get_tty().map_err(StartError::Setup)?),
         };
         // Block SIGINT and SIGQUIT before forking the child process to prevent
         // the child from being killed by those signals until the child starts
@@ -198,7 +276,14 @@ where
         });
 
         // Start the child
-        let child = mask_guard.env.system.new_child_process()?;
+        let child = mask_guard
+            .env
+            .system
+            .new_child_process()
+            .map_err(|errno| match errno {
+                Errno::ENOSYS => StartError::Unsupported,
+                errno => StartError::Fork(errno),
+            })?;
         let child_pid = child(mask_guard.env, task);
 
         // The finishing
@@ -225,7 +310,7 @@ where
     ///
     /// If the subshell started successfully, the return value is the process ID
     /// and the process result of the subshell. If there was an error starting
-    /// the subshell, this function returns the error.
+    /// or waiting for the subshell, this function returns the error.
     ///
     /// If you set [`job_control`](Self::job_control) to
     /// `JobControl::Foreground` and job control is effective as per
@@ -234,10 +319,14 @@ where
     ///
     /// When a job-controlled subshell suspends, this function does not add it
     /// to `env.jobs`. You have to do it for yourself if necessary.
-    pub async fn start_and_wait(self, env: &mut Env) -> Result<(Pid, ProcessResult), Errno> {
+    pub async fn start_and_wait(self, env: &mut Env) -> Result<(Pid, ProcessResult), StartError> {
         let (pid, job_control) = self.start(env).await?;
         let result = loop {
-            let state = env.wait_for_subshell(pid).await?.1;
+            let state = env
+                .wait_for_subshell(pid)
+                .await
+                .map_err(StartError::Wait)?
+                .1;
             if let ProcessState::Halted(result) = state {
                 if !result.is_stopped() || job_control.is_some() {
                     break result;
@@ -312,23 +401,31 @@ impl Drop for MaskGuard<'_> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::function::Function;
     use crate::job::Job;
     use crate::option::Option::{Interactive, Monitor};
-    use crate::option::State::On;
+    use crate::option::State::{Off, On};
     use crate::semantics::ExitStatus;
     use crate::system::r#virtual::Inode;
     use crate::system::r#virtual::SystemState;
     use crate::system::r#virtual::{SIGCHLD, SIGINT, SIGQUIT, SIGTSTP, SIGTTIN, SIGTTOU};
     use crate::system::Disposition;
     use crate::system::Errno;
+    use crate::system::Mode;
+    use crate::system::OfdAccess;
+    use crate::system::OpenFlag;
     use crate::tests::in_virtual_system;
     use crate::trap::Action;
+    use crate::variable::Scope;
     use assert_matches::assert_matches;
+    use enumset::EnumSet;
     use futures_executor::LocalPool;
     use std::cell::Cell;
     use std::cell::RefCell;
+    use std::ffi::CStr;
     use std::rc::Rc;
     use yash_syntax::source::Location;
+    use yash_syntax::syntax::FullCompoundCommand;
 
     fn stub_tty(state: &RefCell<SystemState>) {
         state
@@ -363,7 +460,28 @@ mod tests {
         let subshell =
             Subshell::new(|_env, _job_control| unreachable!("subshell not expected to run"));
         let result = executor.run_until(subshell.start(env));
-        assert_eq!(result, Err(Errno::ENOSYS));
+        assert_eq!(result, Err(StartError::Unsupported));
+    }
+
+    #[test]
+    fn subshell_start_failing_with_eagain() {
+        in_virtual_system(|mut env, state| async move {
+            state.borrow_mut().fork_error = Some(Errno::EAGAIN);
+            let subshell =
+                Subshell::new(|_env, _job_control| unreachable!("subshell not expected to run"));
+            let error = subshell.start(&mut env).await.unwrap_err();
+            assert_eq!(error, StartError::Fork(Errno::EAGAIN));
+            assert!(error.is_resource_shortage());
+            assert_eq!(error.errno(), Errno::EAGAIN);
+        });
+    }
+
+    #[test]
+    fn start_error_resource_shortage() {
+        assert!(StartError::Fork(Errno::ENOMEM).is_resource_shortage());
+        assert!(!StartError::Unsupported.is_resource_shortage());
+        assert!(!StartError::Setup(Errno::EAGAIN).is_resource_shortage());
+        assert!(!StartError::Wait(Errno::ECHILD).is_resource_shortage());
     }
 
     #[test]
@@ -420,6 +538,98 @@ mod tests {
         });
     }
 
+    #[test]
+    fn parent_changes_after_start_not_visible_in_subshell() {
+        in_virtual_system(|mut env, _state| async move {
+            env.variables
+                .get_or_new("x", Scope::Global)
+                .assign("1", None)
+                .unwrap();
+            let subshell = Subshell::new(|env, _job_control| {
+                Box::pin(async {
+                    assert_eq!(env.variables.get_scalar("x"), Some("1"));
+                    assert_eq!(env.functions.get("f"), None);
+                    assert_eq!(env.options.get(Interactive), Off);
+                })
+            });
+            let pid = subshell.start(&mut env).await.unwrap().0;
+
+            env.variables
+                .get_or_new("x", Scope::Global)
+                .assign("2", None)
+                .unwrap();
+            let body = "{ :; }".parse::<FullCompoundCommand>().unwrap();
+            let function = Function::new("f", body, Location::dummy("f"));
+            env.functions.define(function).unwrap();
+            env.options.set(Interactive, On);
+
+            let result = env.wait_for_subshell_to_finish(pid).await.unwrap().1;
+            assert_eq!(result, ProcessResult::exited(0));
+        });
+    }
+
+    #[test]
+    fn subshell_changes_not_visible_in_parent() {
+        in_virtual_system(|mut env, _state| async move {
+            let subshell = Subshell::new(|env, _job_control| {
+                Box::pin(async {
+                    env.variables
+                        .get_or_new("x", Scope::Global)
+                        .assign("1", None)
+                        .unwrap();
+                    let body = "{ :; }".parse::<FullCompoundCommand>().unwrap();
+                    let function = Function::new("f", body, Location::dummy("f"));
+                    env.functions.define(function).unwrap();
+                    env.options.set(Interactive, On);
+                })
+            });
+            let pid = subshell.start(&mut env).await.unwrap().0;
+            env.wait_for_subshell_to_finish(pid).await.unwrap();
+
+            assert_eq!(env.variables.get("x"), None);
+            assert_eq!(env.functions.get("f"), None);
+            assert_eq!(env.options.get(Interactive), Off);
+        });
+    }
+
+    #[test]
+    fn file_system_shared_with_subshell() {
+        in_virtual_system(|mut env, _state| async move {
+            let subshell = Subshell::new(|env, _job_control| {
+                Box::pin(async {
+                    assert_eq!(read_file(env, c"/foo"), "from parent");
+                    write_file(env, c"/bar", "from child").await;
+                })
+            });
+            let pid = subshell.start(&mut env).await.unwrap().0;
+            write_file(&mut env, c"/foo", "from parent").await;
+            env.wait_for_subshell_to_finish(pid).await.unwrap();
+
+            assert_eq!(read_file(&mut env, c"/bar"), "from child");
+        });
+    }
+
+    async fn write_file(env: &mut Env, path: &CStr, content: &str) {
+        let access = OfdAccess::WriteOnly;
+        let flags = OpenFlag::Create | OpenFlag::Truncate;
+        let mode = Mode::from_bits_retain(0o644);
+        let fd = env.system.open(path, access, flags, mode).unwrap();
+        env.system.write_all(fd, content.as_bytes()).await.unwrap();
+        env.system.close(fd).unwrap();
+    }
+
+    fn read_file(env: &mut Env, path: &CStr) -> String {
+        let access = OfdAccess::ReadOnly;
+        let fd = env
+            .system
+            .open(path, access, EnumSet::empty(), Mode::empty());
+        let fd = fd.unwrap();
+        let mut buffer = [0; 64];
+        let count = env.system.read(fd, &mut buffer).unwrap();
+        env.system.close(fd).unwrap();
+        String::from_utf8(buffer[..count].to_vec()).unwrap()
+    }
+
     #[test]
     fn subshell_with_no_job_control() {
         in_virtual_system(|mut parent_env, state| async move {
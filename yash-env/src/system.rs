@@ -424,6 +424,17 @@ pub trait System: Debug {
     /// `Err(_)`.
     fn wait(&mut self, target: Pid) -> Result<Option<(Pid, ProcessState)>>;
 
+    /// Reports updated status of a child process with its resource usage.
+    ///
+    /// This function is the same as [`wait`](Self::wait) except that it also
+    /// returns the resource usage of the child process, like the `wait4`
+    /// system call. The resource usage is meaningful only if the process has
+    /// terminated.
+    fn wait_with_usage(
+        &mut self,
+        target: Pid,
+    ) -> Result<Option<(Pid, ProcessState, ResourceUsage)>>;
+
     // TODO Consider passing raw pointers for optimization
     /// Replaces the current process with an external utility.
     ///
@@ -453,6 +464,16 @@ pub trait System: Debug {
     /// Returns `Ok(None)` if the user is not found.
     fn getpwnam_dir(&self, name: &CStr) -> Result<Option<PathBuf>>;
 
+    /// Returns the name of the user having the given user ID.
+    ///
+    /// Returns `Ok(None)` if the user is not found.
+    fn getpwuid_name(&self, uid: Uid) -> Result<Option<UnixString>>;
+
+    /// Returns the host name of the system.
+    ///
+    /// This is a thin wrapper around the `gethostname` system call.
+    fn gethostname(&self) -> Result<UnixString>;
+
     /// Returns the standard `$PATH` value where all standard utilities are
     /// expected to be found.
     ///
@@ -466,6 +487,12 @@ pub trait System: Debug {
     /// shell.
     fn shell_path(&self) -> CString;
 
+    /// Returns the environment variables inherited by the current process.
+    ///
+    /// The result is a list of name-value pairs. Variables whose name or value
+    /// is not valid UTF-8 are omitted.
+    fn environ(&self) -> Vec<(String, String)>;
+
     /// Returns the limits for the specified resource.
     ///
     /// This function returns a pair of the soft and hard limits for the given
@@ -506,6 +533,19 @@ pub struct Times {
     pub children_system: f64,
 }
 
+/// Resource usage of a child process
+///
+/// This structure is returned by [`System::wait_with_usage`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ResourceUsage {
+    /// User CPU time consumed by the process
+    pub user_time: Duration,
+    /// System CPU time consumed by the process
+    pub system_time: Duration,
+    /// Maximum resident set size of the process in kilobytes
+    pub max_rss: u64,
+}
+
 /// Operation applied to the signal blocking mask
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 #[non_exhaustive]
@@ -42,6 +42,7 @@ use super::Gid;
 use super::Mode;
 use super::OfdAccess;
 use super::OpenFlag;
+use super::ResourceUsage;
 use super::Result;
 use super::SigmaskOp;
 use super::Stat;
@@ -130,34 +131,76 @@ fn to_timespec(duration: Duration) -> MaybeUninit<libc::timespec> {
     timespec
 }
 
-/// Array of slots to store caught signals.
+/// Fixed-size set of slots to store caught signals
 ///
-/// This array is used to store caught signals. All slots are initialized with
-/// 0, which indicates that the slot is available. When a signal is caught, the
-/// signal number is written into one of unoccupied slots.
-static CAUGHT_SIGNALS: [AtomicIsize; 8] = [const { AtomicIsize::new(0) }; 8];
+/// All slots are initialized with 0, which indicates that the slot is
+/// available. When a signal is caught, the signal number is written into the
+/// first unoccupied slot. Slots are always filled from the front, so the first
+/// empty slot marks the end of the recorded signals.
+///
+/// Recording a signal only performs atomic operations on the slots, which is
+/// async-signal-safe. Taking the recorded signals, on the other hand, should be
+/// done while the signals are blocked so that the handler does not interleave
+/// with it. [`SharedSystem`](super::SharedSystem) satisfies this requirement
+/// by blocking the caught signals except while waiting in `pselect`.
+#[derive(Debug)]
+struct CaughtSignals([AtomicIsize; 8]);
+
+impl CaughtSignals {
+    /// Creates an empty set of slots.
+    const fn new() -> Self {
+        CaughtSignals([const { AtomicIsize::new(0) }; 8])
+    }
+
+    /// Records a signal in the first unoccupied slot.
+    ///
+    /// If there is a slot having the same signal already, this function does
+    /// nothing. If there is no available slot, the signal will be lost!
+    ///
+    /// This function is async-signal-safe.
+    fn record(&self, signal: isize) {
+        for slot in &self.0 {
+            match slot.compare_exchange(0, signal, Ordering::Relaxed, Ordering::Relaxed) {
+                Ok(_) => break,
+                Err(slot_value) if slot_value == signal => break,
+                _ => continue,
+            }
+        }
+    }
+
+    /// Clears the slots and returns the signals recorded in them.
+    ///
+    /// The signals are returned in the order they were recorded.
+    fn take(&self) -> Vec<isize> {
+        let mut signals = Vec::new();
+        for slot in &self.0 {
+            // Need a fence to ensure we examine the slots in order.
+            compiler_fence(Ordering::Acquire);
+
+            let signal = slot.swap(0, Ordering::Relaxed);
+            if signal == 0 {
+                // The `record` function always fills the first unused slot,
+                // so there is no more slot filled with a signal.
+                break;
+            }
+            signals.push(signal);
+        }
+        signals
+    }
+}
+
+/// Signals caught by [`catch_signal`]
+static CAUGHT_SIGNALS: CaughtSignals = CaughtSignals::new();
 
 /// Signal catching function.
 ///
 /// This function is set as a signal handler for all signals that the shell
-/// wants to catch. When a signal is caught, the signal number is written into
-/// one of the slots in [`CAUGHT_SIGNALS`].
+/// wants to catch. When a signal is caught, the signal number is recorded in
+/// [`CAUGHT_SIGNALS`].
 extern "C" fn catch_signal(signal: c_int) {
     // This function can only perform async-signal-safe operations.
     // Performing unsafe operations is undefined behavior!
-
-    // Find an unused slot (having a value of 0) in CAUGHT_SIGNALS and write the
-    // signal number into it.
-    // If there is a slot having a value of the signal already, do nothing.
-    // If there is no available slot, the signal will be lost!
-    let signal = signal as isize;
-    for slot in &CAUGHT_SIGNALS {
-        match slot.compare_exchange(0, signal, Ordering::Relaxed, Ordering::Relaxed) {
-            Ok(_) => break,
-            Err(slot_value) if slot_value == signal => break,
-            _ => continue,
-        }
-    }
+    CAUGHT_SIGNALS.record(signal as isize);
 }
 
 fn sigaction_impl(signal: signal::Number, disposition: Option<Disposition>) -> Result<Disposition> {
@@ -221,6 +264,52 @@ impl RealSystem {
     }
 }
 
+/// Converts the status returned by `waitpid` to a `ProcessState`.
+fn process_state_from_wait_status(status: c_int) -> ProcessState {
+    if libc::WIFCONTINUED(status) {
+        ProcessState::Running
+    } else if libc::WIFEXITED(status) {
+        let exit_status = libc::WEXITSTATUS(status);
+        ProcessState::exited(exit_status)
+    } else if libc::WIFSIGNALED(status) {
+        let signal = libc::WTERMSIG(status);
+        let core_dump = libc::WCOREDUMP(status);
+        // SAFETY: The signal number is always a valid signal number, which is non-zero.
+        let raw_number = unsafe { NonZero::new_unchecked(signal) };
+        let signal = signal::Number::from_raw_unchecked(raw_number);
+        let process_result = ProcessResult::Signaled { signal, core_dump };
+        process_result.into()
+    } else if libc::WIFSTOPPED(status) {
+        let signal = libc::WSTOPSIG(status);
+        // SAFETY: The signal number is always a valid signal number, which is non-zero.
+        let raw_number = unsafe { NonZero::new_unchecked(signal) };
+        let signal = signal::Number::from_raw_unchecked(raw_number);
+        ProcessState::stopped(signal)
+    } else {
+        unreachable!()
+    }
+}
+
+/// Converts the `rusage` structure to a `ResourceUsage`.
+fn resource_usage_from_rusage(rusage: &libc::rusage) -> ResourceUsage {
+    fn duration(time: &libc::timeval) -> Duration {
+        let secs = time.tv_sec.try_into().unwrap_or_default();
+        let micros = time.tv_usec.try_into().unwrap_or_default();
+        Duration::from_secs(secs) + Duration::from_micros(micros)
+    }
+
+    let max_rss: u64 = rusage.ru_maxrss.try_into().unwrap_or_default();
+    // macOS reports the maximum resident set size in bytes.
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    let max_rss = max_rss / 1024;
+
+    ResourceUsage {
+        user_time: duration(&rusage.ru_utime),
+        system_time: duration(&rusage.ru_stime),
+        max_rss,
+    }
+}
+
 impl System for RealSystem {
     fn fstat(&self, fd: Fd) -> Result<Stat> {
         let mut stat = MaybeUninit::<libc::stat>::uninit();
@@ -521,25 +610,12 @@ impl System for RealSystem {
     }
 
     fn caught_signals(&mut self) -> Vec<signal::Number> {
-        let mut signals = Vec::new();
-        for slot in &CAUGHT_SIGNALS {
-            // Need a fence to ensure we examine the slots in order.
-            compiler_fence(Ordering::Acquire);
-
-            let signal = slot.swap(0, Ordering::Relaxed);
-            if signal == 0 {
-                // The `catch_signal` function always fills the first unused
-                // slot, so there is no more slot filled with a signal.
-                break;
-            }
-
-            if let Some((_name, number)) = self.validate_signal(signal as signal::RawNumber) {
-                signals.push(number)
-            } else {
-                // ignore unknown signal
-            }
-        }
-        signals
+        CAUGHT_SIGNALS
+            .take()
+            .into_iter()
+            .filter_map(|signal| self.validate_signal(signal as signal::RawNumber))
+            .map(|(_name, number)| number)
+            .collect()
     }
 
     fn kill(
@@ -688,32 +764,27 @@ impl System for RealSystem {
         let mut status = 0;
         let options = libc::WUNTRACED | libc::WCONTINUED | libc::WNOHANG;
         match unsafe { libc::waitpid(target.0, &mut status, options) } {
+            -1 => Err(Errno::last()),
+            0 => Ok(None),
+            pid => Ok(Some((Pid(pid), process_state_from_wait_status(status)))),
+        }
+    }
+
+    fn wait_with_usage(
+        &mut self,
+        target: Pid,
+    ) -> Result<Option<(Pid, ProcessState, ResourceUsage)>> {
+        let mut status = 0;
+        let mut rusage = MaybeUninit::<libc::rusage>::zeroed();
+        let options = libc::WUNTRACED | libc::WCONTINUED | libc::WNOHANG;
+        match unsafe { libc::wait4(target.0, &mut status, options, rusage.as_mut_ptr()) } {
             -1 => Err(Errno::last()),
             0 => Ok(None),
             pid => {
-                let state = if libc::WIFCONTINUED(status) {
-                    ProcessState::Running
-                } else if libc::WIFEXITED(status) {
-                    let exit_status = libc::WEXITSTATUS(status);
-                    ProcessState::exited(exit_status)
-                } else if libc::WIFSIGNALED(status) {
-                    let signal = libc::WTERMSIG(status);
-                    let core_dump = libc::WCOREDUMP(status);
-                    // SAFETY: The signal number is always a valid signal number, which is non-zero.
-                    let raw_number = unsafe { NonZero::new_unchecked(signal) };
-                    let signal = signal::Number::from_raw_unchecked(raw_number);
-                    let process_result = ProcessResult::Signaled { signal, core_dump };
-                    process_result.into()
-                } else if libc::WIFSTOPPED(status) {
-                    let signal = libc::WSTOPSIG(status);
-                    // SAFETY: The signal number is always a valid signal number, which is non-zero.
-                    let raw_number = unsafe { NonZero::new_unchecked(signal) };
-                    let signal = signal::Number::from_raw_unchecked(raw_number);
-                    ProcessState::stopped(signal)
-                } else {
-                    unreachable!()
-                };
-                Ok(Some((Pid(pid), state)))
+                // SAFETY: `rusage` has been zero-initialized and then filled by `wait4`.
+                let rusage = unsafe { rusage.assume_init() };
+                let state = process_state_from_wait_status(status);
+                Ok(Some((Pid(pid), state, resource_usage_from_rusage(&rusage))))
             }
         }
     }
@@ -810,6 +881,34 @@ impl System for RealSystem {
         Ok(Some(UnixString::from_vec(dir.to_bytes().to_vec()).into()))
     }
 
+    fn getpwuid_name(&self, uid: Uid) -> Result<Option<UnixString>> {
+        Errno::clear();
+        let passwd = unsafe { libc::getpwuid(uid.0) };
+        if passwd.is_null() {
+            let errno = Errno::last();
+            return if errno == Errno::NO_ERROR {
+                Ok(None)
+            } else {
+                Err(errno)
+            };
+        }
+
+        let name = unsafe { CStr::from_ptr((*passwd).pw_name) };
+        Ok(Some(UnixString::from_vec(name.to_bytes().to_vec())))
+    }
+
+    fn gethostname(&self) -> Result<UnixString> {
+        // POSIX guarantees that host names are at most 255 bytes long
+        // (HOST_NAME_MAX), excluding the terminating null byte.
+        let mut buffer = vec![0u8; 256];
+        unsafe { libc::gethostname(buffer.as_mut_ptr().cast(), buffer.len()) }.errno_if_m1()?;
+        // The name may not be null-terminated if it has been truncated.
+        if let Some(len) = buffer.iter().position(|&b| b == 0) {
+            buffer.truncate(len);
+        }
+        Ok(UnixString::from_vec(buffer))
+    }
+
     fn confstr_path(&self) -> Result<UnixString> {
         // TODO Support other platforms
         #[cfg(any(
@@ -870,6 +969,12 @@ impl System for RealSystem {
         c"/bin/sh".to_owned()
     }
 
+    fn environ(&self) -> Vec<(String, String)> {
+        std::env::vars_os()
+            .filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)))
+            .collect()
+    }
+
     fn getrlimit(&self, resource: Resource) -> Result<LimitPair> {
         let raw_resource = resource.as_raw_type().ok_or(Errno::EINVAL)?;
 
@@ -946,6 +1051,39 @@ mod tests {
         assert!(count > 0);
     }
 
+    #[test]
+    fn caught_signals_are_taken_in_order() {
+        let slots = CaughtSignals::new();
+        assert_eq!(slots.take(), []);
+
+        slots.record(2);
+        slots.record(15);
+        slots.record(17);
+        assert_eq!(slots.take(), [2, 15, 17]);
+        assert_eq!(slots.take(), []);
+    }
+
+    #[test]
+    fn caught_signal_is_recorded_once() {
+        let slots = CaughtSignals::new();
+        slots.record(15);
+        slots.record(2);
+        slots.record(15);
+        assert_eq!(slots.take(), [15, 2]);
+    }
+
+    #[test]
+    fn caught_signals_exceeding_slots_are_lost() {
+        let slots = CaughtSignals::new();
+        for signal in 1..=10 {
+            slots.record(signal);
+        }
+        assert_eq!(slots.take(), [1, 2, 3, 4, 5, 6, 7, 8]);
+
+        slots.record(9);
+        assert_eq!(slots.take(), [9]);
+    }
+
     // This test depends on static variables.
     #[test]
     fn real_system_caught_signals() {
@@ -969,4 +1107,35 @@ mod tests {
             assert_eq!(result, []);
         }
     }
+
+    // This test changes the signal disposition and blocking mask of the test
+    // process and depends on static variables, so it is not run by default.
+    #[test]
+    #[ignore = "modifies the signal handling state of the test process"]
+    fn real_system_catches_signal_during_select() {
+        use crate::system::SharedSystem;
+        use crate::system::SignalSystem as _;
+        use futures_util::FutureExt as _;
+        use std::pin::pin;
+
+        let mut system = SharedSystem::new(Box::new(unsafe { RealSystem::new() }));
+        let sigusr1 = System::signal_number_from_name(&system, signal::Name::Usr1).unwrap();
+        system.set_disposition(sigusr1, Disposition::Catch).unwrap();
+
+        {
+            // The signal stays pending while blocked outside `select`.
+            unsafe { libc::raise(libc::SIGUSR1) }.errno_if_m1().unwrap();
+            let mut signals = pin!(system.wait_for_signals());
+            assert_eq!(signals.as_mut().now_or_never(), None);
+
+            // `pselect` unblocks the signal so that it is caught.
+            system.select(false).unwrap();
+            let signals = signals.now_or_never().unwrap();
+            assert_eq!(*signals, [sigusr1]);
+        }
+
+        system
+            .set_disposition(sigusr1, Disposition::Default)
+            .unwrap();
+    }
 }
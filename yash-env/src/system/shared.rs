@@ -30,6 +30,7 @@ use super::OpenFlag;
 use super::Path;
 use super::PathBuf;
 use super::Resource;
+use super::ResourceUsage;
 use super::Result;
 use super::SelectSystem;
 use super::SigmaskOp;
@@ -233,6 +234,19 @@ impl SharedSystem {
         .await
     }
 
+    /// Waits for the specified duration.
+    ///
+    /// This function is equivalent to calling [`wait_until`](Self::wait_until)
+    /// with the current time plus `duration`. If the target time is too far in
+    /// the future to be represented as an [`Instant`], this function never
+    /// returns.
+    pub async fn sleep(&self, duration: Duration) {
+        match self.now().checked_add(duration) {
+            Some(target) => self.wait_until(target).await,
+            None => std::future::pending().await,
+        }
+    }
+
     /// Waits for some signals to be delivered to this process.
     ///
     /// Before calling this function, you need to [set the signal
@@ -261,6 +275,40 @@ impl SharedSystem {
         .await
     }
 
+    /// Waits for some signals to be delivered to this process until the
+    /// specified time point.
+    ///
+    /// This function is similar to [`wait_for_signals`](Self::wait_for_signals)
+    /// but returns `None` if no signals are caught before `target`.
+    ///
+    /// If this `SharedSystem` is part of an [`Env`], you should call
+    /// [`Env::wait_for_signals_until`] rather than calling this function
+    /// directly so that the trap set can remember the caught signal.
+    pub async fn wait_for_signals_until(&self, target: Instant) -> Option<Rc<[signal::Number]>> {
+        let status = self.0.borrow_mut().add_signal_waker();
+        // See wait_until for why we retain the timeout waker here.
+        let waker = Rc::new(RefCell::new(None));
+        poll_fn(|context| {
+            let mut status = status.borrow_mut();
+            let dummy_status = SignalStatus::Expected(None);
+            let old_status = std::mem::replace(&mut *status, dummy_status);
+            match old_status {
+                SignalStatus::Caught(signals) => Poll::Ready(Some(signals)),
+                SignalStatus::Expected(_) => {
+                    let mut system = self.0.borrow_mut();
+                    if system.now() >= target {
+                        return Poll::Ready(None);
+                    }
+                    *status = SignalStatus::Expected(Some(context.waker().clone()));
+                    *waker.borrow_mut() = Some(context.waker().clone());
+                    system.add_timeout(target, Rc::downgrade(&waker));
+                    Poll::Pending
+                }
+            }
+        })
+        .await
+    }
+
     /// Waits for a signal to be delivered to this process.
     ///
     /// Before calling this function, you need to [set the signal
@@ -437,6 +485,12 @@ impl System for &SharedSystem {
     fn wait(&mut self, target: Pid) -> Result<Option<(Pid, ProcessState)>> {
         self.0.borrow_mut().wait(target)
     }
+    fn wait_with_usage(
+        &mut self,
+        target: Pid,
+    ) -> Result<Option<(Pid, ProcessState, ResourceUsage)>> {
+        self.0.borrow_mut().wait_with_usage(target)
+    }
     fn execve(&mut self, path: &CStr, args: &[CString], envs: &[CString]) -> Result<Infallible> {
         self.0.borrow_mut().execve(path, args, envs)
     }
@@ -461,12 +515,21 @@ impl System for &SharedSystem {
     fn getpwnam_dir(&self, name: &CStr) -> Result<Option<PathBuf>> {
         self.0.borrow().getpwnam_dir(name)
     }
+    fn getpwuid_name(&self, uid: Uid) -> Result<Option<UnixString>> {
+        self.0.borrow().getpwuid_name(uid)
+    }
+    fn gethostname(&self) -> Result<UnixString> {
+        self.0.borrow().gethostname()
+    }
     fn confstr_path(&self) -> Result<UnixString> {
         self.0.borrow().confstr_path()
     }
     fn shell_path(&self) -> CString {
         self.0.borrow().shell_path()
     }
+    fn environ(&self) -> Vec<(String, String)> {
+        self.0.borrow().environ()
+    }
     fn getrlimit(&self, resource: Resource) -> Result<LimitPair> {
         self.0.borrow().getrlimit(resource)
     }
@@ -656,6 +719,13 @@ impl System for SharedSystem {
         (&mut &*self).wait(target)
     }
     #[inline]
+    fn wait_with_usage(
+        &mut self,
+        target: Pid,
+    ) -> Result<Option<(Pid, ProcessState, ResourceUsage)>> {
+        (&mut &*self).wait_with_usage(target)
+    }
+    #[inline]
     fn execve(&mut self, path: &CStr, args: &[CString], envs: &[CString]) -> Result<Infallible> {
         (&mut &*self).execve(path, args, envs)
     }
@@ -688,6 +758,14 @@ impl System for SharedSystem {
         (&self).getpwnam_dir(name)
     }
     #[inline]
+    fn getpwuid_name(&self, uid: Uid) -> Result<Option<UnixString>> {
+        (&self).getpwuid_name(uid)
+    }
+    #[inline]
+    fn gethostname(&self) -> Result<UnixString> {
+        (&self).gethostname()
+    }
+    #[inline]
     fn confstr_path(&self) -> Result<UnixString> {
         (&self).confstr_path()
     }
@@ -696,6 +774,10 @@ impl System for SharedSystem {
         (&self).shell_path()
     }
     #[inline]
+    fn environ(&self) -> Vec<(String, String)> {
+        (&self).environ()
+    }
+    #[inline]
     fn getrlimit(&self, resource: Resource) -> Result<LimitPair> {
         (&self).getrlimit(resource)
     }
@@ -928,6 +1010,100 @@ mod tests {
         assert_eq!(state.borrow().now, Some(target));
     }
 
+    #[test]
+    fn shared_system_sleep() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let system = SharedSystem::new(Box::new(system));
+        let start = Instant::now();
+        state.borrow_mut().now = Some(start);
+
+        let mut future = Box::pin(system.sleep(Duration::from_secs(5)));
+        let mut context = Context::from_waker(noop_waker_ref());
+        let poll = future.as_mut().poll(&mut context);
+        assert_eq!(poll, Poll::Pending);
+
+        state.borrow_mut().advance_time(Duration::from_secs(4));
+        system.select(true).unwrap();
+        let poll = future.as_mut().poll(&mut context);
+        assert_eq!(poll, Poll::Pending);
+
+        state.borrow_mut().advance_time(Duration::from_secs(1));
+        system.select(true).unwrap();
+        let poll = future.as_mut().poll(&mut context);
+        assert_eq!(poll, Poll::Ready(()));
+        assert_eq!(state.borrow().now, Some(start + Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn shared_system_sleep_overflowing_instant() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let system = SharedSystem::new(Box::new(system));
+        state.borrow_mut().now = Some(Instant::now());
+
+        let mut future = Box::pin(system.sleep(Duration::MAX));
+        let mut context = Context::from_waker(noop_waker_ref());
+        let poll = future.as_mut().poll(&mut context);
+        assert_eq!(poll, Poll::Pending);
+
+        state
+            .borrow_mut()
+            .advance_time(Duration::from_secs(1 << 40));
+        let poll = future.as_mut().poll(&mut context);
+        assert_eq!(poll, Poll::Pending);
+    }
+
+    #[test]
+    fn shared_system_wait_for_signals_until_caught() {
+        let system = VirtualSystem::new();
+        let process_id = system.process_id;
+        let state = Rc::clone(&system.state);
+        let mut system = SharedSystem::new(Box::new(system));
+        system.set_disposition(SIGUSR1, Disposition::Catch).unwrap();
+        let start = Instant::now();
+        state.borrow_mut().now = Some(start);
+        let target = start + Duration::from_secs(10);
+
+        let mut context = Context::from_waker(noop_waker_ref());
+        let mut future = Box::pin(system.wait_for_signals_until(target));
+        let result = future.as_mut().poll(&mut context);
+        assert_eq!(result, Poll::Pending);
+
+        {
+            let mut state = state.borrow_mut();
+            let process = state.processes.get_mut(&process_id).unwrap();
+            let _ = process.raise_signal(SIGUSR1);
+        }
+        system.select(false).unwrap();
+        let result = future.as_mut().poll(&mut context);
+        assert_matches!(result, Poll::Ready(Some(signals)) => {
+            assert_eq!(*signals, [SIGUSR1]);
+        });
+        assert_eq!(state.borrow().now, Some(start));
+    }
+
+    #[test]
+    fn shared_system_wait_for_signals_until_timeout() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut system = SharedSystem::new(Box::new(system));
+        system.set_disposition(SIGUSR1, Disposition::Catch).unwrap();
+        let start = Instant::now();
+        state.borrow_mut().now = Some(start);
+        let target = start + Duration::from_secs(10);
+
+        let mut context = Context::from_waker(noop_waker_ref());
+        let mut future = Box::pin(system.wait_for_signals_until(target));
+        let result = future.as_mut().poll(&mut context);
+        assert_eq!(result, Poll::Pending);
+
+        system.select(false).unwrap();
+        let result = future.as_mut().poll(&mut context);
+        assert_eq!(result, Poll::Ready(None));
+        assert_eq!(state.borrow().now, Some(target));
+    }
+
     #[test]
     fn shared_system_wait_for_signals() {
         let system = VirtualSystem::new();
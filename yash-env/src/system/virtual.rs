@@ -63,6 +63,7 @@ use super::FdFlag;
 use super::Gid;
 use super::OfdAccess;
 use super::OpenFlag;
+use super::ResourceUsage;
 use super::Result;
 use super::SigmaskOp;
 use super::Stat;
@@ -444,6 +445,19 @@ impl System for VirtualSystem {
             OfdAccess::Exec | OfdAccess::Search => (false, false),
         };
 
+        if is_writable && matches!(file.borrow().body, FileBody::Directory { .. }) {
+            return Err(Errno::EISDIR);
+        }
+
+        if is_readable {
+            let file = file.borrow();
+            if matches!(file.body, FileBody::Directory { .. })
+                && !file.permissions.contains(Mode::USER_READ)
+            {
+                return Err(Errno::EACCES);
+            }
+        }
+
         if let FileBody::Fifo {
             readers, writers, ..
         } = &mut file.borrow_mut().body
@@ -847,10 +861,16 @@ impl System for VirtualSystem {
     /// been set in the [`SystemState`]. If the system state does not have an
     /// executor, this function fails with `Errno::ENOSYS`.
     ///
+    /// If [`SystemState::fork_error`] is `Some(errno)`, this function takes it
+    /// and fails with `errno` without creating a process.
+    ///
     /// The process ID of the child will be the maximum of existing process IDs
     /// plus 1. If there are no other processes, it will be 2.
     fn new_child_process(&mut self) -> Result<ChildProcessStarter> {
         let mut state = self.state.borrow_mut();
+        if let Some(errno) = state.fork_error.take() {
+            return Err(errno);
+        }
         let executor = state.executor.clone().ok_or(Errno::ENOSYS)?;
         let process_id = state
             .processes
@@ -904,13 +924,23 @@ impl System for VirtualSystem {
 
     /// Waits for a child.
     ///
+    /// When a terminated child is reaped, its [resource
+    /// usage](Process::resource_usage) is added to the `children_user` and
+    /// `children_system` of [`SystemState::times`].
+    ///
     /// TODO: Currently, this function only supports `target == -1 || target > 0`.
     fn wait(&mut self, target: Pid) -> Result<Option<(Pid, ProcessState)>> {
         let parent_pid = self.process_id;
         let mut state = self.state.borrow_mut();
         if let Some((pid, process)) = state.child_to_wait_for(parent_pid, target) {
             if process.state_has_changed() {
-                Ok(Some((pid, process.take_state())))
+                let process_state = process.take_state();
+                if !process_state.is_alive() {
+                    let usage = process.resource_usage();
+                    state.times.children_user += usage.user_time.as_secs_f64();
+                    state.times.children_system += usage.system_time.as_secs_f64();
+                }
+                Ok(Some((pid, process_state)))
             } else if process.state().is_alive() {
                 Ok(None)
             } else {
@@ -921,11 +951,34 @@ impl System for VirtualSystem {
         }
     }
 
+    /// Waits for a child and returns its resource usage.
+    ///
+    /// This function is the same as [`wait`](Self::wait) except that it
+    /// also returns the [resource usage](Process::resource_usage) of the
+    /// child process.
+    fn wait_with_usage(
+        &mut self,
+        target: Pid,
+    ) -> Result<Option<(Pid, ProcessState, ResourceUsage)>> {
+        let result = self.wait(target)?;
+        Ok(result.map(|(pid, state)| {
+            let state_ref = self.state.borrow();
+            let usage = state_ref.processes[&pid].resource_usage();
+            (pid, state, usage)
+        }))
+    }
+
     /// Stub for the `execve` system call.
     ///
     /// The `execve` system call cannot be simulated in the userland. This
     /// function returns `ENOSYS` if the file at `path` is a native executable,
     /// `ENOEXEC` if a non-executable file, and `ENOENT` otherwise.
+    ///
+    /// For a native executable, the function saves the arguments in the
+    /// [`last_exec`](Process::last_exec) of the current process before
+    /// returning `ENOSYS`. Since the call fails, file descriptors that have the
+    /// [`CloseOnExec`](FdFlag::CloseOnExec) flag remain open, as they would
+    /// after a failed `execve`.
     fn execve(&mut self, path: &CStr, args: &[CString], envs: &[CString]) -> Result<Infallible> {
         let os_path = UnixStr::from_bytes(path.to_bytes());
         let mut state = self.state.borrow_mut();
@@ -946,7 +999,6 @@ impl System for VirtualSystem {
             let args = args.to_owned();
             let envs = envs.to_owned();
             process.last_exec = Some((path, args, envs));
-
             Err(Errno::ENOSYS)
         } else {
             Err(Errno::ENOEXEC)
@@ -996,6 +1048,21 @@ impl System for VirtualSystem {
         Ok(state.home_dirs.get(name).cloned())
     }
 
+    /// Returns the name of the user having the given user ID.
+    ///
+    /// This function looks up the user ID in [`SystemState::user_names`].
+    fn getpwuid_name(&self, uid: Uid) -> Result<Option<UnixString>> {
+        let state = self.state.borrow();
+        Ok(state.user_names.get(&uid).cloned().map(UnixString::from))
+    }
+
+    /// Returns the host name of the system.
+    ///
+    /// This function returns the value of [`SystemState::hostname`].
+    fn gethostname(&self) -> Result<UnixString> {
+        Ok(self.state.borrow().hostname.clone())
+    }
+
     /// Returns the standard path for the system.
     ///
     /// This function returns the value of [`SystemState::path`]. If it is empty,
@@ -1016,6 +1083,14 @@ impl System for VirtualSystem {
         c"/bin/sh".to_owned()
     }
 
+    /// Returns the environment variables in [`SystemState::environ`].
+    fn environ(&self) -> Vec<(String, String)> {
+        let state = self.state.borrow();
+        let vars = state.environ.iter();
+        vars.map(|(name, value)| (name.clone(), value.clone()))
+            .collect()
+    }
+
     fn getrlimit(&self, resource: Resource) -> Result<LimitPair> {
         Ok(self
             .current_process()
@@ -1093,6 +1168,9 @@ pub struct SystemState {
     pub now: Option<Instant>,
 
     /// Consumed CPU time
+    ///
+    /// The children's CPU time is increased by [`VirtualSystem::wait`] when a
+    /// terminated child process is reaped.
     pub times: Times,
 
     /// Task manager that can execute asynchronous tasks
@@ -1120,11 +1198,47 @@ pub struct SystemState {
     /// dictionary.
     pub home_dirs: HashMap<String, PathBuf>,
 
+    /// Map from user IDs to user names
+    ///
+    /// [`VirtualSystem::getpwuid_name`] looks up its argument in this
+    /// dictionary.
+    pub user_names: HashMap<Uid, String>,
+
+    /// Host name returned by [`VirtualSystem::gethostname`]
+    pub hostname: UnixString,
+
+    /// Error to be returned by the next call to
+    /// [`VirtualSystem::new_child_process`]
+    ///
+    /// This field is reset to `None` when the error is returned. It can be
+    /// used to simulate a failure in creating a child process.
+    pub fork_error: Option<Errno>,
+
+    /// Environment variables returned by [`VirtualSystem::environ`]
+    ///
+    /// This map represents the environment the shell process inherits when
+    /// it starts.
+    pub environ: HashMap<String, String>,
+
     /// Standard path returned by [`VirtualSystem::confstr_path`]
     pub path: UnixString,
 }
 
 impl SystemState {
+    /// Advances the current time by the specified duration.
+    ///
+    /// This function adds `duration` to [`now`](Self::now), which must not be
+    /// `None`. Tasks waiting for a time point that has been reached are woken
+    /// in the next [`select`](crate::system::SharedSystem::select).
+    ///
+    /// Note that [`VirtualSystem::select`] also advances the time when it
+    /// would otherwise block until a timeout.
+    pub fn advance_time(&mut self, duration: Duration) {
+        let now = self.now.as_mut();
+        let now = now.expect("now time unspecified; cannot advance time");
+        *now += duration;
+    }
+
     /// Performs [`select`](crate::system::SharedSystem::select) on all
     /// processes in the system.
     ///
@@ -1595,6 +1709,45 @@ mod tests {
         assert_eq!(count, 0);
     }
 
+    #[test]
+    fn open_directory_for_writing() {
+        let mut system = VirtualSystem::new();
+        let dir = Rc::new(RefCell::new(Inode {
+            body: FileBody::Directory {
+                files: Default::default(),
+            },
+            permissions: Mode::ALL_9,
+        }));
+        system
+            .state
+            .borrow_mut()
+            .file_system
+            .save("/dir", dir)
+            .unwrap();
+
+        let result = system.open(
+            c"/dir",
+            OfdAccess::WriteOnly,
+            EnumSet::empty(),
+            Mode::empty(),
+        );
+        assert_eq!(result, Err(Errno::EISDIR));
+        let result = system.open(
+            c"/dir",
+            OfdAccess::ReadWrite,
+            EnumSet::empty(),
+            Mode::empty(),
+        );
+        assert_eq!(result, Err(Errno::EISDIR));
+        let result = system.open(
+            c"/dir",
+            OfdAccess::ReadOnly,
+            EnumSet::empty(),
+            Mode::empty(),
+        );
+        assert_eq!(result, Ok(Fd(3)));
+    }
+
     #[test]
     fn open_appending() {
         let mut system = VirtualSystem::new();
@@ -1652,6 +1805,38 @@ mod tests {
         assert_eq!(result, Ok(Fd(4)));
     }
 
+    #[test]
+    fn open_directory_without_read_permission() {
+        let mut system = VirtualSystem::new();
+        let dir = Rc::new(RefCell::new(Inode {
+            body: FileBody::Directory {
+                files: Default::default(),
+            },
+            permissions: Mode::USER_WRITE | Mode::USER_EXEC,
+        }));
+        system
+            .state
+            .borrow_mut()
+            .file_system
+            .save("/dir", dir)
+            .unwrap();
+
+        let result = system.open(
+            c"/dir",
+            OfdAccess::ReadOnly,
+            OpenFlag::Directory.into(),
+            Mode::empty(),
+        );
+        assert_eq!(result, Err(Errno::EACCES));
+        let result = system.open(
+            c"/dir",
+            OfdAccess::Search,
+            OpenFlag::Directory.into(),
+            Mode::empty(),
+        );
+        assert_eq!(result, Ok(Fd(3)));
+    }
+
     #[test]
     fn open_non_directory_path_prefix() {
         let mut system = VirtualSystem::new();
@@ -2311,6 +2496,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn new_child_process_with_fork_error() {
+        let (mut system, _executor) = virtual_system_with_executor();
+        system.state.borrow_mut().fork_error = Some(Errno::EAGAIN);
+
+        match system.new_child_process() {
+            Ok(_) => panic!("unexpected Ok value"),
+            Err(e) => assert_eq!(e, Errno::EAGAIN),
+        }
+        let state = system.state.borrow();
+        assert_eq!(state.processes.len(), 1);
+        assert_eq!(state.fork_error, None);
+        drop(state);
+
+        // Only the next call fails.
+        assert!(system.new_child_process().is_ok());
+    }
+
     #[test]
     fn new_child_process_with_executor() {
         let (mut system, _executor) = virtual_system_with_executor();
@@ -2327,6 +2530,43 @@ mod tests {
         assert_eq!(pid, Pid(3));
     }
 
+    #[test]
+    fn child_process_shares_open_file_descriptions() {
+        let (mut system, mut executor) = virtual_system_with_executor();
+        let fd = system
+            .open(
+                c"file",
+                OfdAccess::WriteOnly,
+                OpenFlag::Create.into(),
+                Mode::empty(),
+            )
+            .unwrap();
+        system.write(fd, b"1").unwrap();
+        let state = Rc::clone(&system.state);
+
+        let child_process = system.new_child_process();
+
+        let mut env = Env::with_system(Box::new(system));
+        let child_process = child_process.unwrap();
+        child_process(
+            &mut env,
+            Box::new(move |env| {
+                Box::pin(async move {
+                    env.system.write(fd, b"2").unwrap();
+                })
+            }),
+        );
+        executor.run_until_stalled();
+        env.system.write(fd, b"3").unwrap();
+
+        // The child wrote at the offset advanced by the parent, and the parent
+        // continued from the offset advanced by the child.
+        let file = state.borrow().file_system.get("file").unwrap();
+        assert_matches!(&file.borrow().body, FileBody::Regular { content, .. } => {
+            assert_eq!(content[..], *b"123");
+        });
+    }
+
     #[test]
     fn wait_for_running_child() {
         let (mut system, _executor) = virtual_system_with_executor();
@@ -2359,6 +2599,66 @@ mod tests {
         assert_eq!(result, Ok(Some((pid, ProcessState::exited(5)))));
     }
 
+    #[test]
+    fn wait_with_usage_for_exited_child() {
+        let (mut system, mut executor) = virtual_system_with_executor();
+        let state = Rc::clone(&system.state);
+
+        let child_process = system.new_child_process();
+
+        let mut env = Env::with_system(Box::new(system));
+        let child_process = child_process.unwrap();
+        let pid = child_process(
+            &mut env,
+            Box::new(|env| Box::pin(async move { env.exit_status = ExitStatus(3) })),
+        );
+        let usage = ResourceUsage {
+            user_time: Duration::from_secs(2),
+            system_time: Duration::from_millis(10),
+            max_rss: 1024,
+        };
+        state
+            .borrow_mut()
+            .processes
+            .get_mut(&pid)
+            .unwrap()
+            .set_resource_usage(usage);
+        executor.run_until_stalled();
+
+        let result = env.system.wait_with_usage(pid);
+        assert_eq!(result, Ok(Some((pid, ProcessState::exited(3), usage))));
+    }
+
+    #[test]
+    fn wait_adds_resource_usage_of_terminated_child_to_times() {
+        let (mut system, mut executor) = virtual_system_with_executor();
+        let state = Rc::clone(&system.state);
+        state.borrow_mut().times.children_user = 1.0;
+
+        let child_process = system.new_child_process();
+
+        let mut env = Env::with_system(Box::new(system));
+        let child_process = child_process.unwrap();
+        let pid = child_process(&mut env, Box::new(|_env| Box::pin(async {})));
+        let usage = ResourceUsage {
+            user_time: Duration::from_millis(1500),
+            system_time: Duration::from_millis(250),
+            max_rss: 0,
+        };
+        state
+            .borrow_mut()
+            .processes
+            .get_mut(&pid)
+            .unwrap()
+            .set_resource_usage(usage);
+        executor.run_until_stalled();
+
+        env.system.wait(pid).unwrap();
+        let times = state.borrow().times;
+        assert_eq!(times.children_user, 2.5);
+        assert_eq!(times.children_system, 0.25);
+    }
+
     #[test]
     fn wait_for_signaled_child() {
         let (mut system, mut executor) = virtual_system_with_executor();
@@ -2528,6 +2828,41 @@ mod tests {
         assert_eq!(arguments.2, envs);
     }
 
+    #[test]
+    fn execve_keeps_cloexec_fds_on_failure() {
+        let mut system = VirtualSystem::new();
+        let path = "/some/file";
+        let mut content = Inode::default();
+        content.body = FileBody::Regular {
+            content: vec![],
+            is_native_executable: true,
+        };
+        content.permissions.set(Mode::USER_EXEC, true);
+        let content = Rc::new(RefCell::new(content));
+        let mut state = system.state.borrow_mut();
+        state.file_system.save(path, content).unwrap();
+        drop(state);
+        let (reader, writer) = system.pipe().unwrap();
+        system
+            .fcntl_setfd(writer, FdFlag::CloseOnExec.into())
+            .unwrap();
+
+        // The simulated exec of a native executable fails with ENOSYS.
+        let path = CString::new(path).unwrap();
+        let result = system.execve(&path, &[], &[]);
+        assert_eq!(result, Err(Errno::ENOSYS));
+        {
+            let process = system.current_process();
+            assert!(process.get_fd(reader).is_some());
+            assert!(process.get_fd(writer).is_some());
+        }
+
+        // So does the exec of a missing file.
+        let result = system.execve(c"/no/such/file", &[], &[]);
+        assert_eq!(result, Err(Errno::ENOENT));
+        assert!(system.current_process().get_fd(writer).is_some());
+    }
+
     #[test]
     fn execve_returns_enoexec_for_non_executable_file() {
         let mut system = VirtualSystem::new();
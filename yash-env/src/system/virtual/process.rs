@@ -32,6 +32,7 @@ use crate::path::PathBuf;
 use crate::system::resource::LimitPair;
 use crate::system::resource::Resource;
 use crate::system::resource::INFINITY;
+use crate::system::ResourceUsage;
 use crate::system::SelectSystem;
 use std::cell::Cell;
 use std::cell::RefCell;
@@ -106,6 +107,10 @@ pub struct Process {
     /// Limits for system resources
     pub(crate) resource_limits: HashMap<Resource, LimitPair>,
 
+    /// Resource usage reported to the parent process by
+    /// [`wait_with_usage`](crate::System::wait_with_usage)
+    resource_usage: ResourceUsage,
+
     /// Weak reference to the `SelectSystem` for this process
     ///
     /// This weak reference is empty for the initial process of a
@@ -157,6 +162,7 @@ impl Process {
             pending_signals: BTreeSet::new(),
             caught_signals: Vec::new(),
             resource_limits: HashMap::new(),
+            resource_usage: ResourceUsage::default(),
             selector: Weak::new(),
             last_exec: None,
         }
@@ -554,6 +560,26 @@ impl Process {
     pub fn last_exec(&self) -> &Option<(CString, Vec<CString>, Vec<CString>)> {
         &self.last_exec
     }
+
+    /// Returns the resource usage of this process.
+    ///
+    /// This is the value reported to the parent process by
+    /// [`wait_with_usage`](crate::System::wait_with_usage).
+    #[inline(always)]
+    #[must_use]
+    pub fn resource_usage(&self) -> ResourceUsage {
+        self.resource_usage
+    }
+
+    /// Sets the resource usage of this process.
+    ///
+    /// The virtual system does not measure resource usage, so the value
+    /// defaults to zero. Use this method to simulate a process that consumed
+    /// resources.
+    #[inline(always)]
+    pub fn set_resource_usage(&mut self, usage: ResourceUsage) {
+        self.resource_usage = usage;
+    }
 }
 
 /// Result of operations that may deliver a signal to a process.
@@ -253,8 +253,7 @@ pub struct UnsetError<'a> {
 /// [`VariableSet::iter`] returns this iterator.
 #[derive(Clone, Debug)]
 pub struct Iter<'a> {
-    inner: std::collections::hash_map::Iter<'a, String, Vec<VariableInContext>>,
-    min_context_index: usize,
+    inner: std::vec::IntoIter<(&'a str, &'a Variable)>,
 }
 
 impl VariableSet {
@@ -563,18 +562,47 @@ impl VariableSet {
     /// - `Local`: variables in the topmost [regular] context or above.
     /// - `Volatile`: variables above the topmost [regular] context
     ///
-    /// In all cases, the iterator ignores variables hidden by another.
+    /// In all cases, the iterator ignores variables hidden by another, so it
+    /// yields at most one variable for each name.
     ///
-    /// The order of iterated variables is unspecified.
+    /// The iterator yields variables in the order of their names.
     ///
     /// [regular]: Context::Regular
     pub fn iter(&self, scope: Scope) -> Iter {
+        let min_context_index = Self::index_of_context(scope, &self.contexts);
+        let mut variables = self
+            .all_variables
+            .iter()
+            .filter_map(|(name, vars)| {
+                let vic = vars.last()?;
+                (vic.context_index >= min_context_index).then_some((name.as_str(), &vic.variable))
+            })
+            .collect::<Vec<_>>();
+        // TODO apply current locale's collation
+        variables.sort_unstable_by_key(|&(name, _)| name);
         Iter {
-            inner: self.all_variables.iter(),
-            min_context_index: Self::index_of_context(scope, &self.contexts),
+            inner: variables.into_iter(),
         }
     }
 
+    /// Returns an iterator of exported variables.
+    ///
+    /// This function is equivalent to [`iter`](Self::iter) except that the
+    /// iterator only yields variables that are
+    /// [exported](Variable::is_exported).
+    pub fn iter_exported(&self, scope: Scope) -> impl Iterator<Item = (&str, &Variable)> {
+        self.iter(scope).filter(|(_, var)| var.is_exported)
+    }
+
+    /// Returns an iterator of read-only variables.
+    ///
+    /// This function is equivalent to [`iter`](Self::iter) except that the
+    /// iterator only yields variables that are
+    /// [read-only](Variable::is_read_only).
+    pub fn iter_read_only(&self, scope: Scope) -> impl Iterator<Item = (&str, &Variable)> {
+        self.iter(scope).filter(|(_, var)| var.is_read_only())
+    }
+
     /// Returns environment variables in a new vector of C string.
     #[must_use]
     pub fn env_c_strings(&self) -> Vec<CString> {
@@ -599,7 +627,12 @@ impl VariableSet {
     ///
     /// The argument iterator must yield name-value pairs. This function assigns
     /// the values to the variable set, overwriting existing variables. The
-    /// variables are exported.
+    /// variables are exported. Variables are never made read-only by this
+    /// function.
+    ///
+    /// Pairs whose name is not a valid variable name are skipped. A valid name
+    /// is non-empty, does not start with a digit, and consists of
+    /// [name characters](yash_syntax::parser::lex::is_name_char) only.
     ///
     /// If an assignment fails because of an existing read-only variable, this
     /// function ignores the error and continues to the next assignment.
@@ -610,6 +643,10 @@ impl VariableSet {
         V: Into<String>,
     {
         for (name, value) in vars {
+            let name = name.into();
+            if !is_valid_name(&name) {
+                continue;
+            }
             let mut var = self.get_or_new(name, Scope::Global);
             if var.assign(value.into(), None).is_ok() {
                 var.export(true)
@@ -628,6 +665,11 @@ impl VariableSet {
     /// - `PS4='+ '`
     /// - `LINENO` (with no value, but has its `quirk` set to [`Quirk::LineNumber`])
     ///
+    /// `PS1`, `PS2`, and `PS4` are assigned only if they do not have a value
+    /// yet, so that values [imported from the environment](Self::extend_env)
+    /// take precedence. `IFS` and `OPTIND` are always reset as required by
+    /// POSIX.
+    ///
     /// The following variables are not assigned by this function as their
     /// values cannot be determined independently:
     ///
@@ -637,13 +679,18 @@ impl VariableSet {
     /// This function ignores any assignment errors.
     pub fn init(&mut self) {
         const VARIABLES: &[(&str, &str)] = &[
-            (IFS, IFS_INITIAL_VALUE),
-            (OPTIND, OPTIND_INITIAL_VALUE),
             (PS1, PS1_INITIAL_VALUE_NON_ROOT),
             (PS2, PS2_INITIAL_VALUE),
             (PS4, PS4_INITIAL_VALUE),
         ];
         for &(name, value) in VARIABLES {
+            let mut var = self.get_or_new(name, Scope::Global);
+            if var.value.is_none() {
+                var.assign(value, None).ok();
+            }
+        }
+
+        for (name, value) in [(IFS, IFS_INITIAL_VALUE), (OPTIND, OPTIND_INITIAL_VALUE)] {
             self.get_or_new(name, Scope::Global)
                 .assign(value, None)
                 .ok();
@@ -714,24 +761,26 @@ impl<'a> Iterator for Iter<'a> {
     type Item = (&'a str, &'a Variable);
 
     fn next(&mut self) -> Option<(&'a str, &'a Variable)> {
-        loop {
-            let next = self.inner.next()?;
-            if let Some(variable) = next.1.last() {
-                if variable.context_index >= self.min_context_index {
-                    return Some((next.0, &variable.variable));
-                }
-            }
-        }
+        self.inner.next()
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let (_min, max) = self.inner.size_hint();
-        (0, max)
+        self.inner.size_hint()
     }
 }
 
+impl ExactSizeIterator for Iter<'_> {}
+
 impl FusedIterator for Iter<'_> {}
 
+/// Tests whether the string is a valid variable name to import from the
+/// environment.
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(yash_syntax::parser::lex::is_name_char)
+}
+
 mod guard;
 
 pub use self::guard::{ContextGuard, EnvContextGuard};
@@ -1274,8 +1323,7 @@ mod tests {
     #[test]
     fn iter_global() {
         test_iter(|set| {
-            let mut v: Vec<_> = set.iter(Scope::Global).collect();
-            v.sort_unstable_by_key(|&(name, _)| name);
+            let v: Vec<_> = set.iter(Scope::Global).collect();
             assert_eq!(
                 v,
                 [
@@ -1290,8 +1338,7 @@ mod tests {
     #[test]
     fn iter_local() {
         test_iter(|set| {
-            let mut v: Vec<_> = set.iter(Scope::Local).collect();
-            v.sort_unstable_by_key(|&(name, _)| name);
+            let v: Vec<_> = set.iter(Scope::Local).collect();
             assert_eq!(
                 v,
                 [
@@ -1305,8 +1352,7 @@ mod tests {
     #[test]
     fn iter_volatile() {
         test_iter(|set| {
-            let mut v: Vec<_> = set.iter(Scope::Volatile).collect();
-            v.sort_unstable_by_key(|&(name, _)| name);
+            let v: Vec<_> = set.iter(Scope::Volatile).collect();
             assert_eq!(v, [("volatile", &Variable::new("volatile value"))]);
         })
     }
@@ -1314,16 +1360,88 @@ mod tests {
     #[test]
     fn iter_size_hint() {
         test_iter(|set| {
-            assert_eq!(set.iter(Scope::Global).size_hint(), (0, Some(3)));
-            assert_eq!(set.iter(Scope::Local).size_hint(), (0, Some(3)));
-            assert_eq!(set.iter(Scope::Volatile).size_hint(), (0, Some(3)));
+            assert_eq!(set.iter(Scope::Global).size_hint(), (3, Some(3)));
+            assert_eq!(set.iter(Scope::Local).size_hint(), (2, Some(2)));
+            assert_eq!(set.iter(Scope::Volatile).size_hint(), (1, Some(1)));
+        })
+    }
+
+    fn test_iter_shadowed<F: FnOnce(&VariableSet)>(f: F) {
+        let mut set = VariableSet::new();
+        let mut var = set.get_or_new("b", Scope::Global);
+        var.assign("global b", None).unwrap();
+        var.export(true);
+        let mut var = set.get_or_new("a", Scope::Global);
+        var.assign("global a", None).unwrap();
+        var.make_read_only(Location::dummy("a"));
+        let mut var = set.get_or_new("c", Scope::Global);
+        var.assign("global c", None).unwrap();
+        var.export(true);
+
+        set.push_context_impl(Context::default());
+        let mut var = set.get_or_new("b", Scope::Local);
+        var.assign("local b", None).unwrap();
+        let mut var = set.get_or_new("d", Scope::Local);
+        var.assign("local d", None).unwrap();
+        var.make_read_only(Location::dummy("d"));
+
+        set.push_context_impl(Context::Volatile);
+        let mut var = set.get_or_new("c", Scope::Volatile);
+        var.assign("volatile c", None).unwrap();
+        var.export(true);
+
+        f(&set);
+    }
+
+    #[test]
+    fn iter_yields_innermost_variables_in_name_order() {
+        test_iter_shadowed(|set| {
+            let names: Vec<_> = set.iter(Scope::Global).map(|(name, _)| name).collect();
+            assert_eq!(names, ["a", "b", "c", "d"]);
+            let b = set.iter(Scope::Global).find(|&(name, _)| name == "b");
+            assert_eq!(b.unwrap().1.value, Some("local b".into()));
+            let c = set.iter(Scope::Global).find(|&(name, _)| name == "c");
+            assert_eq!(c.unwrap().1.value, Some("volatile c".into()));
+        })
+    }
+
+    #[test]
+    fn iter_exported_skips_variables_shadowed_by_unexported_ones() {
+        test_iter_shadowed(|set| {
+            let v: Vec<_> = set
+                .iter_exported(Scope::Global)
+                .map(|(name, var)| (name, var.value.clone()))
+                .collect();
+            assert_eq!(v, [("c", Some("volatile c".into()))]);
+
+            let v: Vec<_> = set.iter_exported(Scope::Local).collect();
+            assert_eq!(v, [("c", set.get("c").unwrap())]);
+        })
+    }
+
+    #[test]
+    fn iter_read_only() {
+        test_iter_shadowed(|set| {
+            let names: Vec<_> = set
+                .iter_read_only(Scope::Global)
+                .map(|(name, _)| name)
+                .collect();
+            assert_eq!(names, ["a", "d"]);
+
+            let names: Vec<_> = set
+                .iter_read_only(Scope::Local)
+                .map(|(name, _)| name)
+                .collect();
+            assert_eq!(names, ["d"]);
+
+            assert_eq!(set.iter_read_only(Scope::Volatile).count(), 0);
         })
     }
 
     #[test]
     fn env_c_strings() {
         let mut variables = VariableSet::new();
-        assert_eq!(&variables.env_c_strings(), &[]);
+        assert_eq!(&variables.env_c_strings(), &[] as &[CString; 0]);
 
         let mut var = variables.get_or_new("foo", Scope::Global);
         var.assign("FOO", None).unwrap();
@@ -1364,6 +1482,36 @@ mod tests {
         assert!(bar.is_exported);
     }
 
+    #[test]
+    fn extend_env_skips_invalid_names() {
+        let mut variables = VariableSet::new();
+
+        variables.extend_env([
+            ("_a1", "ok"),
+            ("1a", "digit"),
+            ("a=b", "equal"),
+            ("", "empty"),
+        ]);
+
+        assert_eq!(variables.get("_a1").unwrap().value, Some("ok".into()));
+        assert_eq!(variables.get("1a"), None);
+        assert_eq!(variables.get("a=b"), None);
+        assert_eq!(variables.get(""), None);
+    }
+
+    #[test]
+    fn init_does_not_override_existing_values() {
+        let mut variables = VariableSet::new();
+        variables.extend_env([(IFS, "-"), (PS4, "++ "), (OPTIND, "2")]);
+
+        variables.init();
+
+        assert_eq!(variables.get(IFS).unwrap().value, Some(" \t\n".into()));
+        assert_eq!(variables.get(PS4).unwrap().value, Some("++ ".into()));
+        assert_eq!(variables.get(OPTIND).unwrap().value, Some("1".into()));
+        assert_eq!(variables.get(PS1).unwrap().value, Some("$ ".into()));
+    }
+
     #[test]
     fn init_lineno() {
         let mut variables = VariableSet::new();
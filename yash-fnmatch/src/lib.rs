@@ -81,10 +81,11 @@ pub struct Config {
 
     /// Whether the pattern should match case-insensitively
     ///
-    /// For patterns that are literal (i.e., [`Pattern::as_literal`] returns
-    /// `Some(literal)`), this flag is ignored.
-    /// For non-literal patterns, the "simple" case folding rules defined by
-    /// Unicode are applied to allow case-insensitive matches.
+    /// When this flag is `true`, the "simple" case folding rules defined by
+    /// Unicode are applied to allow case-insensitive matches. A pattern
+    /// compiled with this flag is never literal, that is,
+    /// [`Pattern::as_literal`] returns `None` even if the pattern contains no
+    /// special characters.
     pub case_insensitive: bool,
 }
 
@@ -172,7 +173,12 @@ impl Pattern {
 
     /// Compiles a pattern from the given AST.
     pub fn from_ast_and_config(ast: &Ast, config: Config) -> Result<Self, Error> {
-        let body = if let Some(literal) = ast.to_literal() {
+        let literal = if config.case_insensitive {
+            None
+        } else {
+            ast.to_literal()
+        };
+        let body = if let Some(literal) = literal {
             Body::Literal(literal)
         } else {
             Body::Regex {
@@ -1319,4 +1325,21 @@ mod tests {
         assert_eq!(p.rfind("A-Z"), Some(0..3));
         assert_eq!(p.rfind("b&b"), None);
     }
+
+    #[test]
+    fn literal_with_case_insensitive() {
+        let config = Config {
+            case_insensitive: true,
+            ..Config::default()
+        };
+        let p = Pattern::parse_with_config(without_escape("a.z"), config).unwrap();
+        assert_eq!(p.as_literal(), None);
+
+        assert!(p.is_match("a.z"));
+        assert!(p.is_match("A.Z"));
+        assert!(!p.is_match("a-z"));
+
+        assert_eq!(p.find("xA.Zx"), Some(1..4));
+        assert_eq!(p.rfind("a.zA.Z"), Some(3..6));
+    }
 }
@@ -0,0 +1,274 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2026 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Prompt string expansion (yash-specific)
+
+use super::expand_posix::{expand, parse};
+use yash_env::system::System as _;
+use yash_env::system::Uid;
+use yash_env::variable::HOME;
+use yash_env::Env;
+use yash_syntax::syntax::TextUnit::{self, Backslashed};
+
+/// Expands the prompt string with yash-specific backslash escapes.
+///
+/// This function recognizes the following backslash escapes in the prompt
+/// string:
+///
+/// - `\u`: name of the current user
+/// - `\h`: host name up to the first `.`
+/// - `\w`: current working directory, where the value of `$HOME` at the
+///   beginning is replaced with `~`
+/// - `\$`: `#` if the effective user ID is zero, and `$` otherwise
+/// - `\!`: literal `!` that is not replaced with the history number
+/// - `\\`: literal backslash
+///
+/// The escapes are recognized only in the literal part of the prompt string,
+/// that is, outside quotes, parameter expansions, command substitutions, and
+/// arithmetic expansions. A backslash followed by any other character is left
+/// intact. The rest of the prompt string is expanded in the same way as
+/// [`expand_posix`](crate::expand_posix). The strings that replace the
+/// escapes are not subject to the expansion.
+///
+/// If the user name, host name, or working directory cannot be obtained, the
+/// escape is replaced with an empty string.
+pub async fn expand_ex(env: &mut Env, prompt: &str, excl: bool) -> String {
+    let mut text = parse(prompt, is_escapable);
+    replace_escapes(env, &mut text.0);
+    expand(env, text, excl).await
+}
+
+/// Characters that make a backslash escape in the prompt string
+fn is_escapable(c: char) -> bool {
+    matches!(c, 'u' | 'h' | 'w' | '$' | '!' | '\\')
+}
+
+/// Replaces backslash escapes in the top-level text units.
+///
+/// The replacing strings are inserted as [`Backslashed`] units so that they
+/// are not subject to the expansion.
+fn replace_escapes(env: &Env, units: &mut Vec<TextUnit>) {
+    let mut result = Vec::with_capacity(units.len());
+    for unit in units.drain(..) {
+        let replacement = match unit {
+            Backslashed('u') => user_name(env),
+            Backslashed('h') => host_name(env),
+            Backslashed('w') => working_directory(env),
+            Backslashed('$') if env.system.geteuid() == Uid(0) => "#".to_owned(),
+            unit => {
+                result.push(unit);
+                continue;
+            }
+        };
+        result.extend(replacement.chars().map(Backslashed));
+    }
+    *units = result;
+}
+
+fn user_name(env: &Env) -> String {
+    match env.system.getpwuid_name(env.system.getuid()) {
+        Ok(Some(name)) => name.to_string_lossy().into_owned(),
+        _ => String::new(),
+    }
+}
+
+fn host_name(env: &Env) -> String {
+    let Ok(name) = env.system.gethostname() else {
+        return String::new();
+    };
+    let name = name.to_string_lossy();
+    name.split('.').next().unwrap_or_default().to_owned()
+}
+
+fn working_directory(env: &Env) -> String {
+    let Ok(path) = env.system.getcwd() else {
+        return String::new();
+    };
+    let path = path.to_string_lossy();
+    match env.variables.get_scalar(HOME) {
+        Some(home) if !home.is_empty() && home != "/" => match path.strip_prefix(home) {
+            Some(rest) if rest.is_empty() || rest.starts_with('/') => format!("~{rest}"),
+            _ => path.into_owned(),
+        },
+        _ => path.into_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt as _;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::rc::Rc;
+    use yash_env::builtin::Builtin;
+    use yash_env::builtin::Type::Mandatory;
+    use yash_env::io::Fd;
+    use yash_env::semantics::ExitStatus;
+    use yash_env::semantics::Field;
+    use yash_env::system::r#virtual::VirtualSystem;
+    use yash_env::variable::Scope::Global;
+    use yash_env_test_helper::in_virtual_system;
+
+    fn env_with_system(system: VirtualSystem) -> Env {
+        Env::with_system(Box::new(system))
+    }
+
+    fn set_variable(env: &mut Env, name: &str, value: &str) {
+        env.variables
+            .get_or_new(name, Global)
+            .assign(value, None)
+            .unwrap();
+    }
+
+    fn set_cwd(system: &VirtualSystem, path: &str) {
+        let mut state = system.state.borrow_mut();
+        let pid = system.process_id;
+        state.processes.get_mut(&pid).unwrap().chdir(path.into());
+    }
+
+    #[test]
+    fn working_directory_escape() {
+        let system = VirtualSystem::new();
+        set_cwd(&system, "/usr/local");
+        let mut env = env_with_system(system);
+        let result = expand_ex(&mut env, "[\\w]$ ", false)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, "[/usr/local]$ ");
+    }
+
+    #[test]
+    fn working_directory_escape_abbreviates_home() {
+        let system = VirtualSystem::new();
+        set_cwd(&system, "/home/user/src");
+        let mut env = env_with_system(system);
+        set_variable(&mut env, HOME, "/home/user");
+        let result = expand_ex(&mut env, "\\w", false).now_or_never().unwrap();
+        assert_eq!(result, "~/src");
+
+        set_variable(&mut env, HOME, "/home/use");
+        let result = expand_ex(&mut env, "\\w", false).now_or_never().unwrap();
+        assert_eq!(result, "/home/user/src");
+    }
+
+    #[test]
+    fn working_directory_is_not_expanded() {
+        let system = VirtualSystem::new();
+        set_cwd(&system, "/$X/`a`/!");
+        let mut env = env_with_system(system);
+        set_variable(&mut env, "X", "oops");
+        let result = expand_ex(&mut env, "\\w", true).now_or_never().unwrap();
+        assert_eq!(result, "/$X/`a`/!");
+    }
+
+    #[test]
+    fn user_and_host_name_escapes() {
+        let system = VirtualSystem::new();
+        {
+            let mut state = system.state.borrow_mut();
+            let uid = state.processes[&system.process_id].uid();
+            state.user_names.insert(uid, "alice".to_owned());
+            state.hostname = "example.com".into();
+        }
+        let mut env = env_with_system(system);
+        let result = expand_ex(&mut env, "\\u@\\h", false)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, "alice@example");
+    }
+
+    #[test]
+    fn dollar_escape() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let pid = system.process_id;
+        let mut env = env_with_system(system);
+        let result = expand_ex(&mut env, "\\$ ", false).now_or_never().unwrap();
+        assert_eq!(result, "$ ");
+
+        state
+            .borrow_mut()
+            .processes
+            .get_mut(&pid)
+            .unwrap()
+            .set_euid(Uid(0));
+        let result = expand_ex(&mut env, "\\$ ", false).now_or_never().unwrap();
+        assert_eq!(result, "# ");
+    }
+
+    #[test]
+    fn escaped_exclamation_mark_is_literal() {
+        let mut env = Env::new_virtual();
+        let result = expand_ex(&mut env, "\\! ! \\\\", true)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, "! 0 \\");
+    }
+
+    #[test]
+    fn unknown_escape_is_kept() {
+        let mut env = Env::new_virtual();
+        set_variable(&mut env, "X", "x");
+        let result = expand_ex(&mut env, "\\q\\$X\\", false)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, "\\q$X\\");
+    }
+
+    #[test]
+    fn backslash_in_command_substitution_is_not_escape() {
+        fn echo_main(
+            env: &mut Env,
+            args: Vec<Field>,
+        ) -> Pin<Box<dyn Future<Output = yash_env::builtin::Result> + '_>> {
+            Box::pin(async move {
+                let message = format!("{}\n", args[0].value);
+                env.system
+                    .write_all(Fd::STDOUT, message.as_bytes())
+                    .await
+                    .ok();
+                ExitStatus::SUCCESS.into()
+            })
+        }
+
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins
+                .insert("echo", Builtin::new(Mandatory, echo_main));
+            let result = expand_ex(&mut env, "$(echo 'a\\tb\\u')", false).await;
+            assert_eq!(result, "a\\tb\\u");
+        })
+    }
+
+    #[test]
+    fn backslash_in_quoted_parameter_expansion_word_is_not_escape() {
+        let mut env = Env::new_virtual();
+        let result = expand_ex(&mut env, r#"${x:-"a\"b\\"}"#, false)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, r#"a"b\"#);
+    }
+
+    #[test]
+    fn parameter_expansion_after_escapes() {
+        let mut env = Env::new_virtual();
+        set_variable(&mut env, "X", "foo");
+        let result = expand_ex(&mut env, "$X \\! $ ", false)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, "foo ! $ ");
+    }
+}
@@ -21,7 +21,7 @@ use yash_env::Env;
 use yash_semantics::expansion::expand_text;
 use yash_syntax::parser::lex::Lexer;
 use yash_syntax::syntax::Text;
-use yash_syntax::syntax::TextUnit::{self, Literal};
+use yash_syntax::syntax::TextUnit::{self, Backslashed, Literal};
 
 /// Expands the prompt string according to the POSIX standard.
 ///
@@ -41,15 +41,29 @@ use yash_syntax::syntax::TextUnit::{self, Literal};
 /// implementations support backslash escapes in the prompt string. This
 /// discrepancy may be reconsidered in the future.
 pub async fn expand_posix(env: &mut Env, prompt: &str, excl: bool) -> String {
-    let mut lexer = Lexer::with_code(prompt);
-    let text_result = lexer.text(|_| false, |_| false).now_or_never().unwrap();
+    let text = parse(prompt, |_| false);
+    expand(env, text, excl).await
+}
 
-    let mut text = text_result.unwrap_or_else(|_| {
-        // If expansions in the prompt string cannot be parsed, treat all
-        // characters as literals.
-        Text::from_literal_chars(prompt.chars())
-    });
+/// Parses the prompt string.
+///
+/// The `is_escapable` function determines which characters can be quoted by a
+/// backslash in the top-level text of the prompt string. Such backslash
+/// escapes are parsed as [`Backslashed`] units.
+///
+/// If the prompt string contains an expansion that cannot be parsed, the
+/// whole string is treated as literal characters.
+pub(crate) fn parse(prompt: &str, is_escapable: fn(char) -> bool) -> Text {
+    let mut lexer = Lexer::with_code(prompt);
+    let text_result = lexer.text(|_| false, is_escapable).now_or_never().unwrap();
+    text_result.unwrap_or_else(|_| literal_text(prompt, is_escapable))
+}
 
+/// Expands the parsed prompt string.
+///
+/// This is the common part of [`expand_posix`] and
+/// [`expand_ex`](crate::expand_ex).
+pub(crate) async fn expand(env: &mut Env, mut text: Text, excl: bool) -> String {
     if excl {
         replace_exclamation_marks(&mut text.0);
     }
@@ -60,6 +74,22 @@ pub async fn expand_posix(env: &mut Env, prompt: &str, excl: bool) -> String {
     }
 }
 
+/// Converts the prompt string into a text without expansions.
+///
+/// Backslash-escaped characters are converted to [`Backslashed`] units and
+/// other characters to [`Literal`] units.
+fn literal_text(prompt: &str, is_escapable: fn(char) -> bool) -> Text {
+    let mut units = Vec::new();
+    let mut chars = prompt.chars().peekable();
+    while let Some(c) = chars.next() {
+        match chars.next_if(|&next| c == '\\' && is_escapable(next)) {
+            Some(next) => units.push(Backslashed(next)),
+            None => units.push(Literal(c)),
+        }
+    }
+    Text(units)
+}
+
 /// Replaces all occurrences of `!` in the text with the history number of the
 /// current command and `!!` with `!`.
 fn replace_exclamation_marks(text: &mut Vec<TextUnit>) {
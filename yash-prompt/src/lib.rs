@@ -30,10 +30,14 @@
 //! - [`fetch_posix`]: Fetches the value of a variable defined by POSIX for
 //!   a prompt string.
 //! - [`expand_posix`]: Expands a prompt string in a POSIX-compliant manner.
-//! - `expand_ex`: Expands a prompt string with yash-specific expansions.
-//!   (This function is not yet implemented.)
+//! - [`expand_ex`]: Expands a prompt string with yash-specific backslash
+//!   escapes in addition to the POSIX-compliant expansions.
+//!
+//! The prompter uses `expand_ex` unless the
+//! [`PosixlyCorrect`](yash_env::option::Option::PosixlyCorrect) option is on.
 //!
 //! [`expand_posix`]: expand_posix()
+//! [`expand_ex`]: expand_ex()
 //!
 //! # Examples
 //!
@@ -70,7 +74,8 @@
 mod expand_posix;
 pub use expand_posix::expand_posix;
 
-// TODO Yash-specific prompt expansion
+mod expand_ex;
+pub use expand_ex::expand_ex;
 
 mod prompter;
 pub use prompter::fetch_posix;
@@ -18,6 +18,8 @@
 
 use std::cell::RefCell;
 use yash_env::input::{Context, Input, Result};
+use yash_env::option::Option::PosixlyCorrect;
+use yash_env::option::State::On;
 use yash_env::variable::{VariableSet, PS1, PS2};
 use yash_env::Env;
 
@@ -61,7 +63,12 @@ async fn print_prompt(env: &mut Env, context: &Context) {
     let prompt = fetch_posix(&env.variables, context);
 
     // Perform parameter expansion in the prompt string
-    let expanded_prompt = super::expand_posix(env, &prompt, context.is_first_line()).await;
+    let excl = context.is_first_line();
+    let expanded_prompt = if env.options.get(PosixlyCorrect) == On {
+        super::expand_posix(env, &prompt, excl).await
+    } else {
+        super::expand_ex(env, &prompt, excl).await
+    };
 
     // Print the prompt to the standard error
     env.system.print_error(&expanded_prompt).await;
@@ -196,4 +203,45 @@ mod tests {
             .ok();
         assert_stderr(&state, |stderr| assert_eq!(stderr, "foo $ "));
     }
+
+    #[test]
+    fn backslash_escapes_in_prompt_string() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        state
+            .borrow_mut()
+            .processes
+            .get_mut(&system.process_id)
+            .unwrap()
+            .chdir("/tmp".into());
+        let mut env = Env::with_system(system);
+        define_variable(&mut env, PS1, "\\w \\! ! \\$ ");
+        let ref_env = RefCell::new(&mut env);
+        let mut prompter = Prompter::new(Memory::new(""), &ref_env);
+
+        prompter
+            .next_line(&Context::default())
+            .now_or_never()
+            .unwrap()
+            .ok();
+        assert_stderr(&state, |stderr| assert_eq!(stderr, "/tmp ! 0 $ "));
+    }
+
+    #[test]
+    fn no_backslash_escapes_in_posix_mode() {
+        let system = Box::new(VirtualSystem::new());
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(system);
+        env.options.set(PosixlyCorrect, On);
+        define_variable(&mut env, PS1, "\\w \\! $ ");
+        let ref_env = RefCell::new(&mut env);
+        let mut prompter = Prompter::new(Memory::new(""), &ref_env);
+
+        prompter
+            .next_line(&Context::default())
+            .now_or_never()
+            .unwrap()
+            .ok();
+        assert_stderr(&state, |stderr| assert_eq!(stderr, "\\w \\0 $ "));
+    }
 }
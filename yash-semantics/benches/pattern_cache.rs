@@ -0,0 +1,75 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Benchmarks for the pattern cache
+//!
+//! Run with `cargo bench --package yash-semantics`.
+//!
+//! The benchmark executes a `case` command in a loop of 10,000 iterations,
+//! with the default pattern cache and with a cache that holds nothing.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use futures_util::FutureExt as _;
+use std::fmt::Write as _;
+use std::hint::black_box;
+use yash_env::Env;
+use yash_semantics::command::Command as _;
+use yash_semantics::expansion::pattern_cache::PatternCache;
+use yash_syntax::syntax::List;
+
+/// Returns a script that runs a case command 10,000 times.
+fn case_loop() -> List {
+    let mut script = String::from("for i in");
+    for i in 0..10_000 {
+        write!(script, " word{i}").unwrap();
+    }
+    script.push_str(
+        "
+do
+    case $i in
+        (*0) ;;
+        (word[13579]*) ;;
+        (*[!0-9]?2|*4?) ;;
+        (w*d*8) ;;
+        (*) ;;
+    esac
+done
+",
+    );
+    script.parse().unwrap()
+}
+
+fn run(list: &List, cache: Option<PatternCache>) {
+    let mut env = Env::new_virtual();
+    if let Some(cache) = cache {
+        env.any.insert(Box::new(cache));
+    }
+    let result = list.execute(&mut env).now_or_never().unwrap();
+    assert_eq!(result, std::ops::ControlFlow::Continue(()));
+}
+
+fn bench_case_loop(c: &mut Criterion) {
+    let list = case_loop();
+    let mut group = c.benchmark_group("case_loop");
+    group.bench_function("cached", |b| b.iter(|| run(black_box(&list), None)));
+    group.bench_function("uncached", |b| {
+        b.iter(|| run(black_box(&list), Some(PatternCache::with_capacity(0))))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_case_loop);
+criterion_main!(benches);
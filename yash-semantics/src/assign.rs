@@ -21,7 +21,10 @@ use crate::expansion::AssignReadOnlyError;
 use crate::xtrace::XTrace;
 use std::fmt::Write;
 use yash_env::semantics::ExitStatus;
+use yash_env::variable::Value;
+use yash_env::variable::VariableRefMut;
 use yash_env::Env;
+use yash_syntax::source::Location;
 
 #[doc(no_inline)]
 pub use crate::expansion::{Error, ErrorCause, Result};
@@ -30,6 +33,44 @@ pub use yash_env::variable::Scope;
 #[doc(no_inline)]
 pub use yash_syntax::syntax::Assign;
 
+/// Assigns a value to a variable, reporting a read-only variable as an error.
+///
+/// This function [gets or creates](Env::get_or_create_variable) the variable
+/// in the specified `scope` and [assigns](VariableRefMut::assign) the `value`
+/// to it with the `location`. On success, the function returns the reference
+/// to the assigned variable so that the caller can further modify its
+/// attributes.
+///
+/// If the variable is read-only, the function returns an [`Error`] with
+/// [`ErrorCause::AssignReadOnly`] located at `location`. The
+/// [`vacancy`](AssignReadOnlyError::vacancy) of the error is `None`.
+///
+/// All constructs that assign variables, such as assignment words, the for
+/// loop, the `:=` modifier of parameter expansion, and built-ins like `read`,
+/// should use this function so that they diagnose read-only variables in the
+/// same way.
+pub fn assign_variable(
+    env: &mut Env,
+    name: String,
+    scope: Scope,
+    value: Value,
+    location: Location,
+) -> Result<VariableRefMut<'_>> {
+    let mut variable = env.get_or_create_variable(name.clone(), scope);
+    match variable.assign(value, location.clone()) {
+        Ok(_) => Ok(variable),
+        Err(e) => Err(Error {
+            cause: ErrorCause::AssignReadOnly(AssignReadOnlyError {
+                name,
+                new_value: e.new_value,
+                read_only_location: e.read_only_location,
+                vacancy: None,
+            }),
+            location,
+        }),
+    }
+}
+
 /// Performs an assignment.
 ///
 /// This function [expands the value](expand_value) and then
@@ -59,18 +100,7 @@ pub async fn perform_assignment(
         .unwrap();
     }
 
-    let mut variable = env.get_or_create_variable(name, scope);
-    variable
-        .assign(value, assign.location.clone())
-        .map_err(|e| Error {
-            cause: ErrorCause::AssignReadOnly(AssignReadOnlyError {
-                name: assign.name.clone(),
-                new_value: e.new_value,
-                read_only_location: e.read_only_location,
-                vacancy: None,
-            }),
-            location: e.assigned_location.unwrap(),
-        })?;
+    let mut variable = assign_variable(env, name, scope, value, assign.location.clone())?;
     if export {
         variable.export(true);
     }
@@ -107,10 +137,55 @@ mod tests {
     use crate::tests::return_builtin;
     use assert_matches::assert_matches;
     use futures_util::FutureExt;
-    use yash_env::variable::Value;
     use yash_env::variable::Variable;
     use yash_env_test_helper::in_virtual_system;
-    use yash_syntax::source::Location;
+
+    #[test]
+    fn assign_variable_new_value() {
+        let mut env = Env::new_virtual();
+        let location = Location::dummy("v");
+        let var = assign_variable(
+            &mut env,
+            "v".into(),
+            Scope::Global,
+            "value".into(),
+            location.clone(),
+        )
+        .unwrap();
+        assert_eq!(*var, Variable::new("value").set_assigned_location(location));
+    }
+
+    #[test]
+    fn assign_variable_read_only() {
+        let mut env = Env::new_virtual();
+        let read_only_location = Location::dummy("read-only location");
+        let mut var = env.variables.get_or_new("v", Scope::Global);
+        var.assign("read-only", None).unwrap();
+        var.make_read_only(read_only_location.clone());
+        let location = Location::dummy("v");
+        let e = assign_variable(
+            &mut env,
+            "v".into(),
+            Scope::Global,
+            "new".into(),
+            location.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            e.cause,
+            ErrorCause::AssignReadOnly(AssignReadOnlyError {
+                name: "v".into(),
+                new_value: Value::scalar("new"),
+                read_only_location,
+                vacancy: None,
+            })
+        );
+        assert_eq!(e.location, location);
+        assert_eq!(
+            env.variables.get("v").unwrap().value,
+            Some("read-only".into())
+        );
+    }
 
     #[test]
     fn perform_assignment_new_value() {
@@ -228,4 +303,34 @@ mod tests {
             );
         })
     }
+
+    #[test]
+    fn perform_assignments_sees_preceding_assignments() {
+        let mut env = Env::new_virtual();
+        let assigns = ["a=1".parse().unwrap(), "b=$a".parse().unwrap()];
+        perform_assignments(&mut env, &assigns, Scope::Global, false, None)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_eq!(env.variables.get("a").unwrap().value, Some("1".into()));
+        assert_eq!(env.variables.get("b").unwrap().value, Some("1".into()));
+    }
+
+    #[test]
+    fn perform_assignments_stops_at_first_error() {
+        let mut env = Env::new_virtual();
+        let assigns = [
+            "a=A".parse().unwrap(),
+            "b=${x?}".parse().unwrap(),
+            "c=C".parse().unwrap(),
+        ];
+        let e = perform_assignments(&mut env, &assigns, Scope::Global, false, None)
+            .now_or_never()
+            .unwrap()
+            .unwrap_err();
+        assert_matches!(e.cause, ErrorCause::VacantExpansion(_));
+        assert_eq!(env.variables.get("a").unwrap().value, Some("A".into()));
+        assert_eq!(env.variables.get("b"), None);
+        assert_eq!(env.variables.get("c"), None);
+    }
 }
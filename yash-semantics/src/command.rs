@@ -21,7 +21,9 @@ mod compound_command;
 mod function_definition;
 mod item;
 mod pipeline;
+mod signal_report;
 pub mod simple_command;
+mod time_report;
 
 use crate::trap::run_traps_for_caught_signals;
 use std::ops::ControlFlow::{Break, Continue};
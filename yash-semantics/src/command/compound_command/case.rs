@@ -21,6 +21,8 @@ use crate::expansion::attr::fnmatch::apply_escapes;
 use crate::expansion::attr::fnmatch::to_pattern_chars;
 use crate::expansion::expand_word;
 use crate::expansion::expand_word_attr;
+use crate::expansion::pattern_cache;
+use crate::expansion::pattern_options::PatternOptions;
 use crate::xtrace::print;
 use crate::xtrace::XTrace;
 use crate::Handle;
@@ -29,8 +31,6 @@ use std::ops::ControlFlow::Continue;
 use yash_env::semantics::ExitStatus;
 use yash_env::semantics::Result;
 use yash_env::Env;
-use yash_fnmatch::Config;
-use yash_fnmatch::Pattern;
 use yash_quote::quoted;
 use yash_syntax::syntax::CaseItem;
 use yash_syntax::syntax::Word;
@@ -44,13 +44,6 @@ async fn trace_subject(env: &mut Env, value: &str) {
 // We don't trace expanded patterns since they need a quoting method different
 // from yash_quote::quote.
 
-fn config() -> Config {
-    let mut config = Config::default();
-    config.anchor_begin = true;
-    config.anchor_end = true;
-    config
-}
-
 /// Executes the case command.
 pub async fn execute(env: &mut Env, subject: &Word, items: &[CaseItem]) -> Result {
     let subject = match expand_word(env, subject).await {
@@ -102,7 +95,8 @@ async fn matches(
         // Unquoted backslashes should act as quoting, as required by POSIX XCU 2.13.1
         apply_escapes(&mut pattern);
 
-        let Ok(pattern) = Pattern::parse_with_config(to_pattern_chars(&pattern), config()) else {
+        let config = PatternOptions::from_options(&env.options).case_config();
+        let Some(pattern) = pattern_cache::parse(env, to_pattern_chars(&pattern), config) else {
             // Treat the broken pattern as a valid pattern that does not match anything
             continue;
         };
@@ -125,7 +119,7 @@ mod tests {
     use std::ops::ControlFlow::Break;
     use std::rc::Rc;
     use yash_env::option::Option::ErrExit;
-    use yash_env::option::State::On;
+    use yash_env::option::State::{Off, On};
     use yash_env::semantics::Divert;
     use yash_env::system::r#virtual::SystemState;
     use yash_env::variable::Scope;
@@ -328,6 +322,22 @@ mod tests {
         assert_stderr(&state, |stderr| assert_eq!(stderr, ""));
     }
 
+    #[test]
+    fn case_insensitive_without_caseglob() {
+        let (mut env, state) = fixture();
+        env.options.set(yash_env::option::Option::CaseGlob, Off);
+        let command: CompoundCommand = "case Foo.TXT in
+        (foo) echo foo;;
+        (foo.txt) echo foo.txt;;
+        esac"
+            .parse()
+            .unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "foo.txt\n"));
+    }
+
     #[test]
     fn broken_pattern_is_ignored() {
         let (mut env, state) = fixture();
@@ -411,6 +421,25 @@ mod tests {
         assert_stdout(&state, |stdout| assert_eq!(stdout, "1\n2\n"));
     }
 
+    #[test]
+    fn continuing_terminator_matching_two_items() {
+        let (mut env, state) = fixture();
+        let command: CompoundCommand = "case foo in
+        (f*)  echo 1;;&
+        (x)   echo not reached 1;;
+        (*o)  echo 2;&
+        (x)   echo 3;;
+        (foo) echo not reached 2;;
+        esac"
+            .parse()
+            .unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "1\n2\n3\n"));
+    }
+
     #[test]
     fn return_from_body() {
         let (mut env, state) = fixture();
@@ -485,4 +514,18 @@ mod tests {
         assert_stdout(&state, |stdout| assert_eq!(stdout, ""));
         assert_stderr(&state, |stderr| assert_ne!(stderr, ""));
     }
+
+    #[test]
+    fn compiled_pattern_is_reused_in_loop() {
+        let mut env = Env::new_virtual();
+        let command: CompoundCommand = "for i in 1 2; do case x in (a*) ;; esac; done"
+            .parse()
+            .unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        let cache = env.any.get::<pattern_cache::PatternCache>().unwrap();
+        assert_eq!(cache.misses(), 1);
+        assert_eq!(cache.hits(), 1);
+    }
 }
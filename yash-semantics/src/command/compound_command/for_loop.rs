@@ -16,12 +16,10 @@
 
 //! Execution of the for loop
 
-use crate::assign::Error;
-use crate::assign::ErrorCause;
+use crate::assign::assign_variable;
 use crate::command::Command;
 use crate::expansion::expand_word;
 use crate::expansion::expand_words;
-use crate::expansion::AssignReadOnlyError;
 use crate::xtrace::print;
 use crate::xtrace::trace_fields;
 use crate::xtrace::XTrace;
@@ -78,29 +76,21 @@ pub async fn execute(
     }
 
     for Field { value, origin } in values {
-        let mut var = env.get_or_create_variable(name.value.clone(), Scope::Global);
-        match var.assign(value, origin) {
-            Ok(_) => match body.execute(env).await {
-                Break(Divert::Break { count: 0 }) => break,
-                Break(Divert::Break { count }) => return Break(Divert::Break { count: count - 1 }),
-                Break(Divert::Continue { count: 0 }) => continue,
-                Break(Divert::Continue { count }) => {
-                    return Break(Divert::Continue { count: count - 1 })
-                }
-                other => other?,
-            },
-            Err(error) => {
-                let cause = ErrorCause::AssignReadOnly(AssignReadOnlyError {
-                    name: name.value,
-                    new_value: error.new_value,
-                    read_only_location: error.read_only_location,
-                    vacancy: None,
-                });
-                let location = name.origin;
-                let error = Error { cause, location };
-                return error.handle(env).await;
+        let var_name = name.value.clone();
+        if let Err(mut error) = assign_variable(env, var_name, Scope::Global, value.into(), origin)
+        {
+            error.location = name.origin;
+            return error.handle(env).await;
+        }
+        match body.execute(env).await {
+            Break(Divert::Break { count: 0 }) => break,
+            Break(Divert::Break { count }) => return Break(Divert::Break { count: count - 1 }),
+            Break(Divert::Continue { count: 0 }) => continue,
+            Break(Divert::Continue { count }) => {
+                return Break(Divert::Continue { count: count - 1 })
             }
-        };
+            other => other?,
+        }
     }
 
     Continue(())
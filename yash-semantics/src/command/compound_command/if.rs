@@ -58,6 +58,8 @@ mod tests {
     use std::cell::RefCell;
     use std::ops::ControlFlow::Break;
     use std::rc::Rc;
+    use yash_env::option::Option::ErrExit;
+    use yash_env::option::State::On;
     use yash_env::semantics::Divert;
     use yash_env::system::r#virtual::SystemState;
     use yash_env::VirtualSystem;
@@ -247,4 +249,30 @@ mod tests {
         assert_eq!(env.exit_status, ExitStatus(13));
         assert_stdout(&state, |stdout| assert_eq!(stdout, ""));
     }
+
+    #[test]
+    fn errexit_in_condition() {
+        let (mut env, state) = fixture();
+        env.options.set(ErrExit, On);
+        let command = "if return -n 1; then echo not reached; else echo $?; fi";
+        let command: CompoundCommand = command.parse().unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "1\n"));
+    }
+
+    #[test]
+    fn errexit_in_body() {
+        let (mut env, state) = fixture();
+        env.options.set(ErrExit, On);
+        let command = "if return -n 0; then return -n 5; echo not reached; fi";
+        let command: CompoundCommand = command.parse().unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Break(Divert::Exit(None)));
+        assert_eq!(env.exit_status, ExitStatus(5));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, ""));
+    }
 }
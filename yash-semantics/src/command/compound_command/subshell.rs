@@ -16,6 +16,7 @@
 
 //! Semantics of subshell compound commands
 
+use crate::command::signal_report::report_signaled;
 use crate::command::Command;
 use crate::trap::run_exit_trap;
 use std::ops::ControlFlow::Break;
@@ -38,6 +39,7 @@ pub async fn execute(env: &mut Env, body: Rc<List>, location: &Location) -> Resu
     let subshell = subshell.job_control(JobControl::Foreground);
     match subshell.start_and_wait(env).await {
         Ok((pid, result)) => {
+            report_signaled(env, result).await;
             if result.is_stopped() {
                 let mut job = Job::new(pid);
                 job.job_controlled = true;
@@ -49,11 +51,11 @@ pub async fn execute(env: &mut Env, body: Rc<List>, location: &Location) -> Resu
             env.exit_status = result.into();
             env.apply_errexit()
         }
-        Err(errno) => {
+        Err(error) => {
             print_error(
                 env,
                 "cannot start subshell".into(),
-                errno.to_string().into(),
+                error.to_string().into(),
                 location,
             )
             .await;
@@ -82,10 +84,11 @@ mod tests {
     use std::pin::Pin;
     use std::rc::Rc;
     use yash_env::job::ProcessState;
-    use yash_env::option::Option::{ErrExit, Monitor};
+    use yash_env::option::Option::{ErrExit, Interactive, Monitor};
     use yash_env::option::State::On;
     use yash_env::system::r#virtual::VirtualSystem;
-    use yash_env::system::r#virtual::SIGSTOP;
+    use yash_env::system::r#virtual::{SIGSEGV, SIGSTOP};
+    use yash_env::System as _;
     use yash_env_test_helper::assert_stderr;
     use yash_env_test_helper::assert_stdout;
     use yash_env_test_helper::in_virtual_system;
@@ -146,6 +149,50 @@ mod tests {
         assert_stderr(&state, |stderr| assert_ne!(stderr, ""));
     }
 
+    fn segv_builtin(
+        env: &mut Env,
+        _args: Vec<yash_env::semantics::Field>,
+    ) -> Pin<Box<dyn Future<Output = yash_env::builtin::Result> + '_>> {
+        Box::pin(async move {
+            let pid = env.system.getpid();
+            env.system.kill(pid, Some(SIGSEGV)).await.unwrap();
+            yash_env::builtin::Result::default()
+        })
+    }
+
+    #[test]
+    fn signaled_subshell_reported_in_interactive_shell() {
+        in_virtual_system(|mut env, state| async move {
+            env.builtins.insert(
+                "segv",
+                yash_env::builtin::Builtin::new(yash_env::builtin::Type::Mandatory, segv_builtin),
+            );
+            env.options.set(Interactive, On);
+            let command: CompoundCommand = "(segv)".parse().unwrap();
+            let result = command.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::from(SIGSEGV));
+            assert_stderr(&state, |stderr| {
+                assert_eq!(stderr, "Killed(SIGSEGV: core dumped)\n")
+            });
+        })
+    }
+
+    #[test]
+    fn signaled_subshell_not_reported_in_non_interactive_shell() {
+        in_virtual_system(|mut env, state| async move {
+            env.builtins.insert(
+                "segv",
+                yash_env::builtin::Builtin::new(yash_env::builtin::Type::Mandatory, segv_builtin),
+            );
+            let command: CompoundCommand = "(segv)".parse().unwrap();
+            let result = command.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::from(SIGSEGV));
+            assert_stderr(&state, |stderr| assert_eq!(stderr, ""));
+        })
+    }
+
     #[test]
     fn errexit_in_subshell() {
         in_virtual_system(|mut env, _state| async move {
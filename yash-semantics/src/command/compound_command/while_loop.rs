@@ -104,6 +104,8 @@ mod tests {
     use std::pin::Pin;
     use std::rc::Rc;
     use yash_env::builtin::Builtin;
+    use yash_env::option::Option::ErrExit;
+    use yash_env::option::State::On;
     use yash_env::semantics::ExitStatus;
     use yash_env::semantics::Field;
     use yash_env::system::r#virtual::SystemState;
@@ -614,4 +616,18 @@ mod tests {
         }
         assert_stdout(&state, |stdout| assert_eq!(stdout, ""));
     }
+
+    #[test]
+    fn errexit_in_while_condition() {
+        let (mut env, state) = fixture();
+        env.options.set(ErrExit, On);
+        let command: CompoundCommand = "while return -n 3; do echo not reached; done"
+            .parse()
+            .unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+        assert_stdout(&state, |stdout| assert_eq!(stdout, ""));
+    }
 }
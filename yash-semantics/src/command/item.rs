@@ -88,11 +88,11 @@ async fn execute_async(env: &mut Env, and_or: &Rc<AndOrList>, async_flag: &Locat
             env.exit_status = ExitStatus::SUCCESS;
             Continue(())
         }
-        Err(errno) => {
+        Err(error) => {
             print_error(
                 env,
                 "cannot start a subshell to run an asynchronous command".into(),
-                errno.to_string().into(),
+                error.to_string().into(),
                 async_flag,
             )
             .await;
@@ -223,6 +223,42 @@ mod tests {
         })
     }
 
+    #[test]
+    fn item_execute_async_pipeline_job_name() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("return", return_builtin());
+
+            let item = syntax::Item {
+                and_or: Rc::new("return -n 0  |\treturn   -n 1".parse().unwrap()),
+                async_flag: Some(Location::dummy("")),
+            };
+            let result = item.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+
+            assert_eq!(env.jobs[0].name, "return -n 0 | return -n 1");
+        })
+    }
+
+    #[test]
+    fn item_execute_async_job_with_long_name() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("return", return_builtin());
+
+            let command = format!("return -n 0 {}", "x ".repeat(1000));
+            let item = syntax::Item {
+                and_or: Rc::new(command.parse().unwrap()),
+                async_flag: Some(Location::dummy("")),
+            };
+            let result = item.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+
+            let name = &env.jobs[0].name;
+            assert_eq!(name.chars().count(), Job::MAX_NAME_LENGTH);
+            assert!(name.starts_with("return -n 0 x x "), "{name:?}");
+            assert!(name.ends_with("..."), "{name:?}");
+        })
+    }
+
     #[test]
     fn item_execute_async_pid() {
         in_virtual_system(|mut env, state| async move {
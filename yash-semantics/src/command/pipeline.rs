@@ -18,23 +18,28 @@
 
 use crate::trap::run_exit_trap;
 
+use super::signal_report::report_signaled;
+use super::time_report::Stopwatch;
 use super::Command;
 use enumset::EnumSet;
 use itertools::Itertools;
 use std::ops::ControlFlow::{Break, Continue};
 use std::rc::Rc;
 use yash_env::io::Fd;
+use yash_env::io::MIN_INTERNAL_FD;
 use yash_env::job::Job;
 use yash_env::job::Pid;
-use yash_env::option::Option::{Exec, Interactive};
-use yash_env::option::State::Off;
+use yash_env::option::Option::{Exec, Interactive, LastPipe};
+use yash_env::option::State::{Off, On};
 use yash_env::semantics::Divert;
 use yash_env::semantics::ExitStatus;
 use yash_env::semantics::Result;
 use yash_env::stack::Frame;
 use yash_env::subshell::JobControl;
+use yash_env::subshell::StartError;
 use yash_env::subshell::Subshell;
 use yash_env::system::Errno;
+use yash_env::system::FdFlag;
 use yash_env::system::SystemEx;
 use yash_env::Env;
 use yash_env::System;
@@ -53,6 +58,19 @@ use yash_syntax::syntax;
 /// via a pipe, except for the standard output of the last command and the
 /// standard input of the first command, which are not modified.
 ///
+/// If the [`LastPipe`] option is [`On`] in `env.options`, the last command of
+/// a multi-command pipeline is executed in the current shell execution
+/// environment instead of a subshell, so that a built-in or function can
+/// modify the environment as in `echo hi | read x`. The standard input is
+/// connected to the pipe while the command is executed and restored
+/// afterwards. This option has no effect when job control is active because
+/// the whole pipeline then runs in a subshell that forms a job.
+///
+/// Only the last command runs in the current environment; the other commands
+/// still run in subshells. Both ends of a pipe therefore never belong to the
+/// same process, so a command that writes more than the pipe can hold does not
+/// deadlock: it waits until the next command reads from the pipe.
+///
 /// If the pipeline has no command, it is a no-op.
 ///
 /// # Exit status
@@ -66,6 +84,14 @@ use yash_syntax::syntax;
 /// shells. This implementation does not invert the exit status when the return
 /// value is `Err(Divert::...)`.
 ///
+/// # Timing
+///
+/// If `self.time` is `Some(_)`, the elapsed real time and the CPU time
+/// consumed while executing the pipeline are printed to the standard error
+/// after the pipeline finishes. The report is printed in the format of the
+/// `time -p` utility if `self.time` is `Some(TimeFormat::Posix)` or the
+/// `PosixlyCorrect` option is on.
+///
 /// # `noexec` option
 ///
 /// If the [`Exec`] and [`Interactive`] options are [`Off`] in `env.options`,
@@ -94,21 +120,32 @@ impl Command for syntax::Pipeline {
             }
         }
 
-        if !self.negation {
-            return execute_commands_in_pipeline(env, &self.commands).await;
-        }
-
-        let mut env = env.push_frame(Frame::Condition);
-        execute_commands_in_pipeline(&mut env, &self.commands).await?;
-        env.exit_status = if env.exit_status.is_successful() {
-            ExitStatus::FAILURE
-        } else {
-            ExitStatus::SUCCESS
+        let Some(format) = self.time else {
+            return execute_negatable(env, self).await;
         };
-        Continue(())
+
+        let stopwatch = Stopwatch::start(env);
+        let result = execute_negatable(env, self).await;
+        stopwatch.report(env, format).await;
+        result
     }
 }
 
+async fn execute_negatable(env: &mut Env, pipeline: &syntax::Pipeline) -> Result {
+    if !pipeline.negation {
+        return execute_commands_in_pipeline(env, &pipeline.commands).await;
+    }
+
+    let mut env = env.push_frame(Frame::Condition);
+    execute_commands_in_pipeline(&mut env, &pipeline.commands).await?;
+    env.exit_status = if env.exit_status.is_successful() {
+        ExitStatus::FAILURE
+    } else {
+        ExitStatus::SUCCESS
+    };
+    Continue(())
+}
+
 async fn execute_commands_in_pipeline(env: &mut Env, commands: &[Rc<syntax::Command>]) -> Result {
     match commands.len() {
         0 => {
@@ -145,6 +182,7 @@ async fn execute_job_controlled_pipeline(
 
     match subshell.start_and_wait(env).await {
         Ok((pid, result)) => {
+            report_signaled(env, result).await;
             if result.is_stopped() {
                 let mut job = Job::new(pid);
                 job.job_controlled = true;
@@ -156,10 +194,8 @@ async fn execute_job_controlled_pipeline(
             env.exit_status = result.into();
             Continue(())
         }
-        Err(errno) => {
-            // TODO print error location using yash_env::io::print_error
-            let message = format!("cannot start a subshell in the pipeline: {}\n", errno);
-            env.system.print_error(&message).await;
+        Err(error) => {
+            report_start_error(env, error).await;
             Break(Divert::Interrupt(Some(ExitStatus::NOEXEC)))
         }
     }
@@ -173,12 +209,19 @@ fn to_job_name(commands: &[Rc<syntax::Command>]) -> String {
 }
 
 async fn execute_multi_command_pipeline(env: &mut Env, commands: &[Rc<syntax::Command>]) -> Result {
+    let (commands, last_command) = if env.options.get(LastPipe) == On && !env.controls_jobs() {
+        let (last, init) = commands.split_last().unwrap();
+        (init, Some(last))
+    } else {
+        (commands, None)
+    };
+
     // Start commands
     let mut commands = commands.iter().cloned().peekable();
     let mut pipes = PipeSet::new();
     let mut pids = Vec::new();
     while let Some(command) = commands.next() {
-        let has_next = commands.peek().is_some();
+        let has_next = commands.peek().is_some() || last_command.is_some();
         shift_or_fail(env, &mut pipes, has_next).await?;
 
         let pipes = pipes;
@@ -195,16 +238,90 @@ async fn execute_multi_command_pipeline(env: &mut Env, commands: &[Rc<syntax::Co
 
     shift_or_fail(env, &mut pipes, false).await?;
 
-    // Await the last command
+    // Execute the last command in the current environment if applicable
+    let result = match last_command {
+        None => Continue(()),
+        Some(command) => {
+            let reader = pipes.read_previous.take();
+            let result = execute_last_command(env, reader, command).await;
+            pipes.read_previous = reader;
+            shift_or_fail(env, &mut pipes, false).await?;
+            result
+        }
+    };
+
+    // Await the commands in subshells
     for pid in pids {
-        // TODO Report if the child was signaled and the shell is interactive
-        env.exit_status = env
-            .wait_for_subshell_to_finish(pid)
-            .await
-            .expect("cannot receive exit status of child process")
-            .1;
+        let wait_result = env.wait_for_subshell_to_finish(pid).await;
+        if last_command.is_some() {
+            // The exit status of the last command has already been set. The
+            // subshell may have been reaped while the last command was
+            // executed, so errors are ignored.
+            continue;
+        }
+        let (_pid, process_result) =
+            wait_result.expect("cannot receive exit status of child process");
+        report_signaled(env, process_result).await;
+        env.exit_status = process_result.into();
     }
-    Continue(())
+    result
+}
+
+/// Executes the last command of the pipeline in the current environment.
+///
+/// The standard input is temporarily replaced with `reader` while the command
+/// is executed. The caller is responsible for closing `reader`.
+async fn execute_last_command(
+    env: &mut Env,
+    reader: Option<Fd>,
+    command: &syntax::Command,
+) -> Result {
+    let Some(reader) = reader else {
+        return command.execute(env).await;
+    };
+
+    // Save the original standard input, which may be closed
+    let saved = if reader == Fd::STDIN {
+        None
+    } else {
+        match env
+            .system
+            .dup(Fd::STDIN, MIN_INTERNAL_FD, FdFlag::CloseOnExec.into())
+        {
+            Ok(saved) => Some(saved),
+            Err(Errno::EBADF) => None,
+            Err(errno) => {
+                // TODO print error location using yash_env::io::print_error
+                let message = format!("cannot connect pipes in the pipeline: {}\n", errno);
+                env.system.print_error(&message).await;
+                return Break(Divert::Interrupt(Some(ExitStatus::NOEXEC)));
+            }
+        }
+    };
+
+    let result = match env.system.dup2(reader, Fd::STDIN) {
+        Ok(_) => command.execute(env).await,
+        Err(errno) => {
+            // TODO print error location using yash_env::io::print_error
+            let message = format!("cannot connect pipes in the pipeline: {}\n", errno);
+            env.system.print_error(&message).await;
+            Break(Divert::Interrupt(Some(ExitStatus::NOEXEC)))
+        }
+    };
+
+    // Restore the original standard input
+    match saved {
+        Some(saved) => {
+            let _ = env.system.dup2(saved, Fd::STDIN);
+            let _ = env.system.close(saved);
+        }
+        None if reader != Fd::STDIN => {
+            let _ = env.system.close(Fd::STDIN);
+        }
+        None => (),
+    }
+
+    result
 }
 
 async fn shift_or_fail(env: &mut Env, pipes: &mut PipeSet, has_next: bool) -> Result {
@@ -239,26 +356,35 @@ async fn connect_pipe_and_execute_command(
 
 async fn pid_or_fail(
     env: &mut Env,
-    start_result: std::result::Result<(Pid, Option<JobControl>), Errno>,
+    start_result: std::result::Result<(Pid, Option<JobControl>), StartError>,
 ) -> Result<Pid> {
     match start_result {
         Ok((pid, job_control)) => {
             debug_assert_eq!(job_control, None);
             Continue(pid)
         }
-        Err(errno) => {
-            // TODO print error location using yash_env::io::print_error
-            env.system
-                .print_error(&format!(
-                    "cannot start a subshell in the pipeline: {}\n",
-                    errno
-                ))
-                .await;
+        Err(error) => {
+            report_start_error(env, error).await;
             Break(Divert::Interrupt(Some(ExitStatus::NOEXEC)))
         }
     }
 }
 
+/// Prints an error message for a subshell that could not be started.
+///
+/// If the error is due to a temporary shortage of system resources, the
+/// message says so because the same pipeline may succeed later.
+async fn report_start_error(env: &mut Env, error: StartError) {
+    // TODO print error location using yash_env::io::print_error
+    let hint = if error.is_resource_shortage() {
+        " (the system is temporarily out of resources)"
+    } else {
+        ""
+    };
+    let message = format!("cannot start a subshell in the pipeline: {error}{hint}\n");
+    env.system.print_error(&message).await;
+}
+
 /// Set of pipe file descriptors that connect commands.
 #[derive(Clone, Copy, Default)]
 struct PipeSet {
@@ -332,20 +458,29 @@ mod tests {
     use crate::tests::suspend_builtin;
     use assert_matches::assert_matches;
     use futures_util::FutureExt;
+    use std::cell::RefCell;
+    use std::collections::HashSet;
     use std::future::Future;
     use std::pin::Pin;
     use std::rc::Rc;
+    use std::time::{Duration, Instant};
     use yash_env::builtin::Builtin;
     use yash_env::builtin::Type::Special;
     use yash_env::job::ProcessResult;
     use yash_env::job::ProcessState;
     use yash_env::option::Option::ErrExit;
     use yash_env::option::Option::Monitor;
+    use yash_env::option::Option::PosixlyCorrect;
     use yash_env::option::State::On;
     use yash_env::semantics::Field;
     use yash_env::system::r#virtual::FileBody;
+    use yash_env::system::r#virtual::SystemState;
+    use yash_env::system::r#virtual::PIPE_SIZE;
     use yash_env::system::r#virtual::SIGSTOP;
+    use yash_env::system::r#virtual::SIGTSTP;
+    use yash_env::system::ResourceUsage;
     use yash_env::VirtualSystem;
+    use yash_env_test_helper::assert_stderr;
     use yash_env_test_helper::assert_stdout;
     use yash_env_test_helper::in_virtual_system;
     use yash_env_test_helper::stub_tty;
@@ -354,6 +489,7 @@ mod tests {
     fn empty_pipeline() {
         let mut env = Env::new_virtual();
         let pipeline = syntax::Pipeline {
+            time: None,
             commands: vec![],
             negation: false,
         };
@@ -477,6 +613,129 @@ mod tests {
         });
     }
 
+    #[test]
+    fn subshell_start_failure_due_to_resource_shortage() {
+        in_virtual_system(|mut env, state| async move {
+            env.builtins.insert("return", return_builtin());
+            state.borrow_mut().fork_error = Some(Errno::EAGAIN);
+            let pipeline: syntax::Pipeline = "return -n 1 | return -n 2".parse().unwrap();
+            let result = pipeline.execute(&mut env).await;
+            assert_eq!(result, Break(Divert::Interrupt(Some(ExitStatus::NOEXEC))));
+            assert_stderr(&state, |stderr| {
+                assert!(
+                    stderr.contains("temporarily out of resources"),
+                    "stderr: {stderr:?}"
+                );
+            });
+        });
+    }
+
+    #[test]
+    fn subshell_start_failure_not_due_to_resource_shortage() {
+        in_virtual_system(|mut env, state| async move {
+            env.builtins.insert("return", return_builtin());
+            state.borrow_mut().fork_error = Some(Errno::EPERM);
+            let pipeline: syntax::Pipeline = "return -n 1 | return -n 2".parse().unwrap();
+            let result = pipeline.execute(&mut env).await;
+            assert_eq!(result, Break(Divert::Interrupt(Some(ExitStatus::NOEXEC))));
+            assert_stderr(&state, |stderr| {
+                assert!(
+                    stderr.starts_with("cannot start a subshell in the pipeline"),
+                    "stderr: {stderr:?}"
+                );
+                assert!(
+                    !stderr.contains("temporarily out of resources"),
+                    "stderr: {stderr:?}"
+                );
+            });
+        });
+    }
+
+    #[test]
+    fn last_command_in_subshell_without_lastpipe() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("cat", cat_builtin());
+            let pipeline: syntax::Pipeline = "cat | x=1".parse().unwrap();
+            let result = pipeline.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.variables.get("x"), None);
+        });
+    }
+
+    #[test]
+    fn last_command_in_current_environment_with_lastpipe() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("cat", cat_builtin());
+            env.options.set(LastPipe, On);
+            let pipeline: syntax::Pipeline = "cat | x=1".parse().unwrap();
+            let result = pipeline.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+            let x = env.variables.get("x").unwrap();
+            assert_eq!(x.value, Some(yash_env::variable::Value::scalar("1")));
+        });
+    }
+
+    #[test]
+    fn last_command_reads_pipe_with_lastpipe() {
+        in_virtual_system(|mut env, state| async move {
+            {
+                let file = state.borrow().file_system.get("/dev/stdin").unwrap();
+                let mut file = file.borrow_mut();
+                file.body = FileBody::new(*b"ok\n");
+            }
+            env.builtins.insert("cat", cat_builtin());
+            env.options.set(LastPipe, On);
+            let stdin = state.borrow().processes[&env.main_pid].fds()[&Fd::STDIN].clone();
+
+            let pipeline: syntax::Pipeline = "cat | cat | cat".parse().unwrap();
+            let result = pipeline.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+            assert_stdout(&state, |stdout| assert_eq!(stdout, "ok\n"));
+
+            // The standard input is restored and no pipe FDs are left open.
+            let state = state.borrow();
+            let fds = state.processes[&env.main_pid].fds();
+            assert_eq!(fds[&Fd::STDIN], stdin);
+            for fd in 3..10 {
+                assert!(!fds.contains_key(&Fd(fd)), "fd={fd}");
+            }
+        });
+    }
+
+    #[test]
+    fn large_output_through_pipe_with_lastpipe() {
+        in_virtual_system(|mut env, state| async move {
+            let content: Vec<u8> = (0..PIPE_SIZE * 8).map(|i| b'a' + (i % 26) as u8).collect();
+            {
+                let file = state.borrow().file_system.get("/dev/stdin").unwrap();
+                let mut file = file.borrow_mut();
+                file.body = FileBody::new(content.clone());
+            }
+            env.builtins.insert("cat", cat_builtin());
+            env.options.set(LastPipe, On);
+
+            let pipeline: syntax::Pipeline = "cat | cat".parse().unwrap();
+            let result = pipeline.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+            assert_stdout(&state, |stdout| assert_eq!(stdout.as_bytes(), content));
+        });
+    }
+
+    #[test]
+    fn exit_status_of_last_command_with_lastpipe() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("return", return_builtin());
+            env.options.set(LastPipe, On);
+            let pipeline: syntax::Pipeline = "return -n 3 | return -n 5".parse().unwrap();
+            let result = pipeline.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus(5));
+        });
+    }
+
     #[test]
     fn inverting_exit_status_to_0_without_divert() {
         let mut env = Env::new_virtual();
@@ -545,6 +804,23 @@ mod tests {
         });
     }
 
+    #[test]
+    fn errexit_with_inversion() {
+        let mut env = Env::new_virtual();
+        env.builtins.insert("return", return_builtin());
+        env.options.set(ErrExit, On);
+
+        let pipeline: syntax::Pipeline = "! return -n 0".parse().unwrap();
+        let result = pipeline.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus::FAILURE);
+
+        let pipeline: syntax::Pipeline = "! return -n 7".parse().unwrap();
+        let result = pipeline.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+    }
+
     #[test]
     fn stack_without_inversion() {
         fn stub_builtin(
@@ -588,6 +864,81 @@ mod tests {
         assert_eq!(result, Continue(()));
     }
 
+    /// Built-in that consumes one second of real time and sets the resource
+    /// usage of the current process.
+    ///
+    /// The system state must be stored in `env.any`.
+    fn work_builtin(
+        env: &mut Env,
+        _args: Vec<Field>,
+    ) -> Pin<Box<dyn Future<Output = yash_env::builtin::Result> + '_>> {
+        Box::pin(async move {
+            let state = env.any.get::<Rc<RefCell<SystemState>>>().unwrap();
+            let mut state = state.borrow_mut();
+            state.advance_time(Duration::from_secs(1));
+            let process = state.processes.get_mut(&env.system.getpid()).unwrap();
+            process.set_resource_usage(ResourceUsage {
+                user_time: Duration::from_millis(500),
+                system_time: Duration::from_millis(250),
+                max_rss: 0,
+            });
+            Default::default()
+        })
+    }
+
+    #[test]
+    fn timed_single_command_pipeline() {
+        in_virtual_system(|mut env, state| async move {
+            state.borrow_mut().now = Some(Instant::now());
+            env.any.insert(Box::new(Rc::clone(&state)));
+            env.builtins
+                .insert("work", Builtin::new(Special, work_builtin));
+            let pipeline: syntax::Pipeline = "time -p work".parse().unwrap();
+
+            let result = pipeline.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_stderr(&state, |stderr| {
+                assert_eq!(stderr, "real 1.00\nuser 0.00\nsys 0.00\n")
+            });
+        })
+    }
+
+    #[test]
+    fn timed_multi_command_pipeline() {
+        in_virtual_system(|mut env, state| async move {
+            state.borrow_mut().now = Some(Instant::now());
+            env.any.insert(Box::new(Rc::clone(&state)));
+            env.builtins
+                .insert("work", Builtin::new(Special, work_builtin));
+            let pipeline: syntax::Pipeline = "time work | work".parse().unwrap();
+
+            let result = pipeline.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_stderr(&state, |stderr| {
+                assert_eq!(stderr, "real\t0m2.000s\nuser\t0m1.000s\nsys\t0m0.500s\n")
+            });
+        })
+    }
+
+    #[test]
+    fn timed_negated_pipeline_in_posix_mode() {
+        in_virtual_system(|mut env, state| async move {
+            state.borrow_mut().now = Some(Instant::now());
+            env.any.insert(Box::new(Rc::clone(&state)));
+            env.builtins
+                .insert("work", Builtin::new(Special, work_builtin));
+            env.options.set(PosixlyCorrect, On);
+            let pipeline: syntax::Pipeline = "time ! work | work".parse().unwrap();
+
+            let result = pipeline.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::FAILURE);
+            assert_stderr(&state, |stderr| {
+                assert_eq!(stderr, "real 2.00\nuser 1.00\nsys 0.50\n")
+            });
+        })
+    }
+
     #[test]
     fn process_group_id_of_job_controlled_pipeline() {
         fn stub_builtin(
@@ -611,6 +962,46 @@ mod tests {
             assert_ne!(env.exit_status, ExitStatus(env.main_pgid.0 as _));
 
             // The shell should come back to the foreground after running the pipeline
+            let state = state.borrow();
+            assert_eq!(state.foreground, Some(env.main_pgid));
+
+            // All processes of the pipeline should be in the same process group
+            let pgids = state
+                .processes
+                .iter()
+                .filter(|&(&pid, _)| pid != env.main_pid)
+                .map(|(_, process)| process.pgid())
+                .collect::<HashSet<_>>();
+            assert_eq!(pgids.len(), 1, "{pgids:?}");
+            assert!(!pgids.contains(&env.main_pgid), "{pgids:?}");
+        })
+    }
+
+    #[test]
+    fn job_controlled_pipeline_in_foreground() {
+        fn stub_builtin(
+            env: &mut Env,
+            _args: Vec<Field>,
+        ) -> Pin<Box<dyn Future<Output = yash_env::builtin::Result> + '_>> {
+            let foreground = env
+                .get_tty()
+                .and_then(|tty| env.system.tcgetpgrp(tty))
+                .unwrap();
+            let in_foreground = foreground == env.system.getpgrp();
+            let exit_status = if in_foreground { 0 } else { 1 };
+            Box::pin(async move { yash_env::builtin::Result::new(ExitStatus(exit_status)) })
+        }
+
+        in_virtual_system(|mut env, state| async move {
+            env.builtins
+                .insert("foo", Builtin::new(Special, stub_builtin));
+            env.options.set(Monitor, On);
+            stub_tty(&state);
+
+            let pipeline: syntax::Pipeline = "foo | foo".parse().unwrap();
+            let result = pipeline.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::SUCCESS);
             assert_eq!(state.borrow().foreground, Some(env.main_pgid));
         })
     }
@@ -637,6 +1028,42 @@ mod tests {
         })
     }
 
+    #[test]
+    fn job_controlled_pipeline_stopped_by_sigtstp() {
+        fn stub_builtin(
+            env: &mut Env,
+            _args: Vec<Field>,
+        ) -> Pin<Box<dyn Future<Output = yash_env::builtin::Result> + '_>> {
+            Box::pin(async move {
+                env.system.kill(Pid(0), Some(SIGTSTP)).await.unwrap();
+                yash_env::builtin::Result::default()
+            })
+        }
+
+        in_virtual_system(|mut env, state| async move {
+            env.builtins.insert("return", return_builtin());
+            env.builtins
+                .insert("stop", Builtin::new(Special, stub_builtin));
+            env.options.set(Monitor, On);
+            stub_tty(&state);
+
+            let pipeline: syntax::Pipeline = "return -n 0 | stop".parse().unwrap();
+            let result = pipeline.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::from(SIGTSTP));
+
+            // The shell should take back the terminal from the stopped job
+            assert_eq!(state.borrow().foreground, Some(env.main_pgid));
+
+            assert_eq!(env.jobs.len(), 1);
+            let job = env.jobs.iter().next().unwrap().1;
+            assert!(job.job_controlled);
+            assert_eq!(job.state, ProcessState::stopped(SIGTSTP));
+            assert_ne!(job.pid, env.main_pgid);
+            assert_eq!(job.name, "return -n 0 | stop");
+        })
+    }
+
     #[test]
     fn pipe_set_shift_to_first_command() {
         let system = VirtualSystem::new();
@@ -0,0 +1,138 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Reporting foreground commands terminated by a signal
+//!
+//! When a foreground command is killed by a signal, an interactive shell
+//! prints the [state](State) of the command to the standard error, such as
+//! `Killed(SIGSEGV: core dumped)`. The report is omitted for `SIGINT` and
+//! `SIGPIPE`, which usually terminate commands as expected by the user.
+//! Non-interactive shells never print the report.
+
+use yash_env::job::fmt::State;
+use yash_env::job::ProcessResult;
+use yash_env::job::ProcessState;
+use yash_env::signal;
+use yash_env::system::SystemEx as _;
+use yash_env::Env;
+
+/// Returns the message reporting the process result, if any.
+///
+/// This function returns `None` if the process was not killed by a signal or
+/// the signal is `SIGINT` or `SIGPIPE`.
+fn message(env: &Env, result: ProcessResult) -> Option<String> {
+    let ProcessResult::Signaled { signal, .. } = result else {
+        return None;
+    };
+    let name = env.system.signal_name_from_number(signal);
+    if matches!(name, signal::Name::Int | signal::Name::Pipe) {
+        return None;
+    }
+    let state = State::from_process_state(ProcessState::Halted(result), &env.system);
+    Some(format!("{state}\n"))
+}
+
+/// Prints a message if the foreground command was killed by a signal.
+///
+/// The message is printed only if the shell [is
+/// interactive](Env::is_interactive). See the [module documentation](self) for
+/// details.
+pub async fn report_signaled(env: &mut Env, result: ProcessResult) {
+    if !env.is_interactive() {
+        return;
+    }
+    if let Some(message) = message(env, result) {
+        env.system.print_error(&message).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt as _;
+    use yash_env::option::Option::Interactive;
+    use yash_env::option::State::On;
+    use yash_env::semantics::ExitStatus;
+    use yash_env::system::r#virtual::VirtualSystem;
+    use yash_env::system::r#virtual::{SIGINT, SIGKILL, SIGPIPE, SIGSEGV};
+    use yash_env_test_helper::assert_stderr;
+
+    fn interactive_env() -> (Env, VirtualSystem) {
+        let system = VirtualSystem::new();
+        let mut env = Env::with_system(Box::new(system.clone()));
+        env.options.set(Interactive, On);
+        (env, system)
+    }
+
+    #[test]
+    fn signaled_with_core_dump() {
+        let (mut env, system) = interactive_env();
+        let result = ProcessResult::Signaled {
+            signal: SIGSEGV,
+            core_dump: true,
+        };
+        report_signaled(&mut env, result).now_or_never().unwrap();
+        assert_stderr(&system.state, |stderr| {
+            assert_eq!(stderr, "Killed(SIGSEGV: core dumped)\n")
+        });
+    }
+
+    #[test]
+    fn signaled_without_core_dump() {
+        let (mut env, system) = interactive_env();
+        let result = ProcessResult::Signaled {
+            signal: SIGKILL,
+            core_dump: false,
+        };
+        report_signaled(&mut env, result).now_or_never().unwrap();
+        assert_stderr(&system.state, |stderr| {
+            assert_eq!(stderr, "Killed(SIGKILL)\n")
+        });
+    }
+
+    #[test]
+    fn no_report_for_sigint_and_sigpipe() {
+        let (mut env, system) = interactive_env();
+        for signal in [SIGINT, SIGPIPE] {
+            let result = ProcessResult::Signaled {
+                signal,
+                core_dump: false,
+            };
+            report_signaled(&mut env, result).now_or_never().unwrap();
+        }
+        assert_stderr(&system.state, |stderr| assert_eq!(stderr, ""));
+    }
+
+    #[test]
+    fn no_report_for_exited_process() {
+        let (mut env, system) = interactive_env();
+        let result = ProcessResult::Exited(ExitStatus(139));
+        report_signaled(&mut env, result).now_or_never().unwrap();
+        assert_stderr(&system.state, |stderr| assert_eq!(stderr, ""));
+    }
+
+    #[test]
+    fn no_report_in_non_interactive_shell() {
+        let system = VirtualSystem::new();
+        let mut env = Env::with_system(Box::new(system.clone()));
+        let result = ProcessResult::Signaled {
+            signal: SIGSEGV,
+            core_dump: true,
+        };
+        report_signaled(&mut env, result).now_or_never().unwrap();
+        assert_stderr(&system.state, |stderr| assert_eq!(stderr, ""));
+    }
+}
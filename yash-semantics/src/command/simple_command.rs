@@ -47,9 +47,10 @@ use yash_syntax::syntax::Word;
 /// # Outline
 ///
 /// The execution starts with the [expansion](crate::expansion) of the command
-/// words. Next, the [command search](crate::command_search) is performed to
-/// find an execution [target](crate::command_search::Target) named by the first
-/// [field](Field) of the expansion results. The target type defines how the
+/// words. The [hooks](hook) registered in the environment are called with the
+/// expanded fields. Next, the [command search](crate::command_search) is
+/// performed to find an execution [target](crate::command_search::Target) named
+/// by the first [field](Field) of the expansion results. The target type defines how the
 /// target is executed. After the execution, the `ErrExit` option is applied
 /// with [`Env::apply_errexit`].
 ///
@@ -111,9 +112,12 @@ use yash_syntax::syntax::Word;
 ///
 /// ## Target not found
 ///
-/// If the command search could not find a valid target, the execution proceeds
-/// in the same manner as an external utility except that it does not call
-/// `execve` and performs error handling as if it failed with `ENOENT`.
+/// If the command search could not find a valid target, the handler
+/// registered in the [`not_found`] module is called, which may finish the
+/// command with an exit status or substitute another command. Otherwise, the
+/// execution proceeds in the same manner as an external utility except that it
+/// does not call `execve` and performs error handling as if it failed with
+/// `ENOENT`.
 ///
 /// # Redirections
 ///
@@ -148,6 +152,11 @@ use yash_syntax::syntax::Word;
 /// aborts with a non-zero exit status after printing an error message to the
 /// standard error.
 ///
+/// The result is a [`Divert::Interrupt`], so a non-interactive shell exits as
+/// required by POSIX, while an
+/// [interactive read-eval loop](crate::interactive_read_eval_loop) resumes
+/// with the next command.
+///
 /// ## External utility invocation failure
 ///
 /// If the external utility could not be called, the subshell exits after
@@ -168,9 +177,26 @@ impl Command for syntax::SimpleCommand {
             Err(error) => return error.handle(env).await,
         };
 
+        hook::run_hooks(env, &fields);
+
         use crate::command_search::Target::{Builtin, External, Function};
         if let Some(name) = fields.first() {
-            match search(env, &name.value) {
+            let (target, fields) = match search(env, &name.value) {
+                Some(target) => (Some(target), fields),
+                None => match not_found::run_handler(env, &fields).await {
+                    not_found::Action::Default => (None, fields),
+                    not_found::Action::ExitStatus(exit_status) => {
+                        env.exit_status = exit_status;
+                        return env.apply_errexit();
+                    }
+                    not_found::Action::Substitute(new_fields) => match new_fields.first() {
+                        Some(new_name) => (search(env, &new_name.value), new_fields),
+                        None => (None, fields),
+                    },
+                },
+            };
+
+            match target {
                 Some(Builtin { builtin, .. }) => {
                     execute_builtin(env, builtin, &self.assigns, fields, &self.redirs).await
                 }
@@ -229,6 +255,9 @@ async fn perform_assignments(
     }
 }
 
+pub mod hook;
+pub mod not_found;
+
 mod absent;
 use absent::execute_absent_target;
 
@@ -265,4 +294,20 @@ mod tests {
         assert_eq!(result, Break(Divert::Exit(None)));
         assert_eq!(env.exit_status, ExitStatus(93));
     }
+
+    #[test]
+    fn assignment_locations_are_recorded() {
+        let mut env = Env::new_virtual();
+        let command: syntax::SimpleCommand = "a=1 b=${c:=2}".parse().unwrap();
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+
+        let a_location = env.last_assigned_location("a").unwrap();
+        assert_eq!(a_location, &command.assigns[0].location);
+        let b_location = env.last_assigned_location("b").unwrap();
+        assert_eq!(b_location, &command.assigns[1].location);
+        let c_location = env.last_assigned_location("c").unwrap();
+        assert_eq!(c_location.code, a_location.code);
+        assert_eq!(c_location.range, 6..13);
+    }
 }
@@ -17,6 +17,7 @@
 //! Simple command semantics for the absent target
 
 use super::perform_assignments;
+use crate::command::signal_report::report_signaled;
 use crate::redir::RedirGuard;
 use crate::xtrace::print;
 use crate::xtrace::XTrace;
@@ -69,6 +70,7 @@ pub async fn execute_absent_target(
 
         match subshell.start_and_wait(env).await {
             Ok((pid, result)) => {
+                report_signaled(env, result).await;
                 if result.is_stopped() {
                     let mut job = Job::new(pid);
                     job.job_controlled = true;
@@ -82,11 +84,11 @@ pub async fn execute_absent_target(
 
                 result.into()
             }
-            Err(errno) => {
+            Err(error) => {
                 print_error(
                     env,
                     "cannot start subshell to perform redirection".into(),
-                    errno.to_string().into(),
+                    error.to_string().into(),
                     &first_redir_location,
                 )
                 .await;
@@ -17,6 +17,7 @@
 //! Simple command semantics for external utilities
 
 use super::perform_assignments;
+use crate::command::signal_report::report_signaled;
 use crate::redir::RedirGuard;
 use crate::xtrace::print;
 use crate::xtrace::trace_fields;
@@ -111,6 +112,7 @@ pub async fn start_external_utility_in_subshell_and_wait(
 
     match subshell.start_and_wait(env).await {
         Ok((pid, result)) => {
+            report_signaled(env, result).await;
             if result.is_stopped() {
                 let mut job = Job::new(pid);
                 job.job_controlled = true;
@@ -121,11 +123,11 @@ pub async fn start_external_utility_in_subshell_and_wait(
 
             result.into()
         }
-        Err(errno) => {
+        Err(error) => {
             print_error(
                 env,
                 format!("cannot execute external utility {:?}", name.value).into(),
-                errno.to_string().into(),
+                error.to_string().into(),
                 &name.origin,
             )
             .await;
@@ -156,9 +158,10 @@ pub fn to_c_strings(s: Vec<Field>) -> Vec<CString> {
 ///
 /// This function performs the very last step of the simple command execution.
 /// It disables the internal signal dispositions and calls the `execve` system
-/// call. If the call fails, it prints an error message to the standard error
-/// and updates `env.exit_status`, in which case the caller should immediately
-/// exit the current process with the exit status.
+/// call with the environment variables returned by
+/// [`Env::environment_for_exec`]. If the call fails, it prints an error message
+/// to the standard error and updates `env.exit_status`, in which case the
+/// caller should immediately exit the current process with the exit status.
 ///
 /// If the `execve` call fails with `ENOEXEC`, this function falls back on
 /// invoking the shell with the given arguments, so that the shell can interpret
@@ -174,7 +177,7 @@ pub async fn replace_current_process(
         .disable_internal_dispositions(&mut env.system)
         .ok();
 
-    let envs = env.variables.env_c_strings();
+    let envs = env.environment_for_exec(&location);
     let result = env.system.execve(path.as_c_str(), &args, &envs);
     // TODO Prefer into_err to unwrap_err
     let errno = result.unwrap_err();
@@ -288,6 +291,40 @@ mod tests {
         });
     }
 
+    #[test]
+    fn temporary_assignment_overrides_exported_variable_for_external_utility() {
+        in_virtual_system(|mut env, state| async move {
+            let mut content = Inode::default();
+            content.body = FileBody::Regular {
+                content: Vec::new(),
+                is_native_executable: true,
+            };
+            content.permissions.set(Mode::USER_EXEC, true);
+            let content = Rc::new(RefCell::new(content));
+            state
+                .borrow_mut()
+                .file_system
+                .save("/some/file", content)
+                .unwrap();
+
+            let mut var = env.variables.get_or_new("var", Scope::Global);
+            var.assign("global", None).unwrap();
+            var.export(true);
+            let mut var = env.variables.get_or_new("array", Scope::Global);
+            var.assign(Value::array(["1", "2"]), None).unwrap();
+            var.export(true);
+
+            let command: syntax::SimpleCommand = "var=temporary /some/file".parse().unwrap();
+            let result = command.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+
+            let state = state.borrow();
+            let process = state.processes.values().last().unwrap();
+            let arguments = process.last_exec().as_ref().unwrap();
+            assert_eq!(arguments.2, [c"var=temporary".to_owned()]);
+        });
+    }
+
     #[test]
     fn simple_command_returns_exit_status_from_external_utility() {
         in_virtual_system(|mut env, state| async move {
@@ -375,6 +412,38 @@ mod tests {
         assert_eq!(env.exit_status, ExitStatus::NOEXEC);
     }
 
+    #[test]
+    fn simple_command_reports_fork_failure_with_eagain() {
+        in_virtual_system(|mut env, state| async move {
+            let mut content = Inode::default();
+            content.body = FileBody::Regular {
+                content: Vec::new(),
+                is_native_executable: true,
+            };
+            content.permissions.set(Mode::USER_EXEC, true);
+            let content = Rc::new(RefCell::new(content));
+            {
+                let mut state = state.borrow_mut();
+                state.file_system.save("/some/file", content).unwrap();
+                state.fork_error = Some(Errno::EAGAIN);
+            }
+
+            let command: syntax::SimpleCommand = "/some/file".parse().unwrap();
+            let result = command.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::NOEXEC);
+            assert_stderr(&state, |stderr| {
+                assert!(
+                    stderr.contains("cannot execute external utility \"/some/file\""),
+                    "{stderr}"
+                );
+                assert!(stderr.contains("cannot create a child process"), "{stderr}");
+            });
+            // Only the shell process exists since no child has been created.
+            assert_eq!(state.borrow().processes.len(), 1);
+        });
+    }
+
     #[test]
     fn exit_status_is_127_on_command_not_found() {
         let mut env = Env::new_virtual();
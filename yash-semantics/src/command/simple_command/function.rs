@@ -26,12 +26,16 @@ use crate::Handle;
 use std::ops::ControlFlow::{Break, Continue};
 use std::rc::Rc;
 use yash_env::function::Function;
+use yash_env::io::print_message;
 use yash_env::semantics::Divert;
+use yash_env::semantics::ExitStatus;
 use yash_env::semantics::Field;
 use yash_env::semantics::Result;
+use yash_env::stack::Frame;
 use yash_env::variable::Context;
 use yash_env::variable::PositionalParams;
 use yash_env::Env;
+use yash_syntax::source::pretty::{Annotation, AnnotationType, Message};
 use yash_syntax::syntax::Assign;
 use yash_syntax::syntax::Redir;
 
@@ -66,6 +70,12 @@ pub async fn execute_function(
 /// The modifier function is called with the environment after the new variable
 /// context is pushed to the environment. This is useful for assigning custom
 /// local variables before the function body is executed.
+///
+/// While the function body is executed, `Frame::Function` is pushed to the
+/// stack. If the [call depth limit](Env::call_depth_limit) has been reached,
+/// the function is not executed and this function returns
+/// `Break(Divert::Interrupt(Some(ExitStatus::ERROR)))` after printing an error
+/// message.
 pub async fn execute_function_body<F>(
     env: &mut Env,
     function: Rc<Function>,
@@ -75,11 +85,38 @@ pub async fn execute_function_body<F>(
 where
     F: FnOnce(&mut Env),
 {
+    if env.call_depth_exceeded() {
+        let location = fields.first().map_or(&function.origin, |name| &name.origin);
+        let message = Message {
+            r#type: AnnotationType::Error,
+            title: format!(
+                "cannot call function `{}`: too deep recursion",
+                function.name
+            )
+            .into(),
+            annotations: vec![
+                Annotation::new(
+                    AnnotationType::Error,
+                    format!("call depth limit {} exceeded", env.call_depth_limit).into(),
+                    location,
+                ),
+                Annotation::new(
+                    AnnotationType::Info,
+                    "the function was defined here".into(),
+                    &function.origin,
+                ),
+            ],
+            footers: vec![],
+        };
+        print_message(env, message).await;
+        return Break(Divert::Interrupt(Some(ExitStatus::ERROR)));
+    }
+
+    let env = &mut *env.push_frame(Frame::Function(Rc::clone(&function)));
     let positional_params = PositionalParams::from_fields(fields);
     let mut env = env.push_context(Context::Regular { positional_params });
     modifier(&mut env);
 
-    // TODO Update control flow stack
     let result = function.body.execute(&mut env).await;
     if let Break(Divert::Return(exit_status)) = result {
         if let Some(exit_status) = exit_status {
@@ -101,6 +138,8 @@ mod tests {
     use futures_util::FutureExt;
     use std::rc::Rc;
     use std::str::from_utf8;
+    use yash_env::builtin::Builtin;
+    use yash_env::builtin::Type::{Mandatory, Special};
     use yash_env::option::State::On;
     use yash_env::semantics::ExitStatus;
     use yash_env::system::r#virtual::FileBody;
@@ -110,6 +149,7 @@ mod tests {
     use yash_env_test_helper::assert_stdout;
     use yash_syntax::source::Location;
     use yash_syntax::syntax::FullCompoundCommand;
+    use yash_syntax::syntax::List;
     use yash_syntax::syntax::SimpleCommand;
 
     #[test]
@@ -129,6 +169,52 @@ mod tests {
         assert_eq!(env.exit_status, ExitStatus(13));
     }
 
+    #[test]
+    fn function_does_not_shadow_special_builtin() {
+        let mut env = Env::new_virtual();
+        env.builtins.insert("return", return_builtin());
+        env.builtins.insert(
+            "set",
+            Builtin::new(Special, |_, _| {
+                Box::pin(async { yash_env::builtin::Result::new(ExitStatus(11)) })
+            }),
+        );
+        let function = Function::new(
+            "set",
+            "{ return -n 22; }".parse::<FullCompoundCommand>().unwrap(),
+            Location::dummy("dummy"),
+        );
+        env.functions.define(function).unwrap();
+        let command: SimpleCommand = "set".parse().unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus(11));
+    }
+
+    #[test]
+    fn function_shadows_mandatory_builtin() {
+        let mut env = Env::new_virtual();
+        env.builtins.insert("return", return_builtin());
+        env.builtins.insert(
+            "true",
+            Builtin::new(Mandatory, |_, _| {
+                Box::pin(async { yash_env::builtin::Result::new(ExitStatus(11)) })
+            }),
+        );
+        let function = Function::new(
+            "true",
+            "{ return -n 22; }".parse::<FullCompoundCommand>().unwrap(),
+            Location::dummy("dummy"),
+        );
+        env.functions.define(function).unwrap();
+        let command: SimpleCommand = "true".parse().unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus(22));
+    }
+
     #[test]
     fn simple_command_applies_redirections_to_function() {
         let system = VirtualSystem::new();
@@ -151,6 +237,29 @@ mod tests {
         });
     }
 
+    #[test]
+    fn function_definition_redirections_are_performed_on_each_call() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        env.builtins.insert("echo", echo_builtin());
+        let definition: List = "f() { echo $1; } >>/tmp/log".parse().unwrap();
+        let result = definition.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.functions.get("f").unwrap().body.redirs.len(), 1);
+        assert!(state.borrow().file_system.get("/tmp/log").is_err());
+
+        let calls: List = "f one; f two; echo three".parse().unwrap();
+        let result = calls.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        let file = state.borrow().file_system.get("/tmp/log").unwrap();
+        let file = file.borrow();
+        assert_matches!(&file.body, FileBody::Regular { content, .. } => {
+            assert_eq!(from_utf8(content), Ok("one\ntwo\n"));
+        });
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "three\n"));
+    }
+
     #[test]
     fn simple_command_skips_running_function_on_redirection_error() {
         let system = VirtualSystem::new();
@@ -207,6 +316,69 @@ mod tests {
         assert_eq!(env.exit_status, ExitStatus(26));
     }
 
+    #[test]
+    fn recursion_stops_at_call_depth_limit() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        env.builtins.insert("echo", echo_builtin());
+        env.call_depth_limit = 3;
+        let function = Function::new(
+            "f",
+            "{ echo $#; f x \"$@\"; }"
+                .parse::<FullCompoundCommand>()
+                .unwrap(),
+            Location::dummy("dummy"),
+        );
+        env.functions.define(function).unwrap();
+        let command: SimpleCommand = "f".parse().unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Break(Divert::Interrupt(Some(ExitStatus::ERROR))));
+        assert_eq!(env.stack[..], []);
+        assert_eq!(env.variables.positional_params().values, [] as [String; 0]);
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "0\n1\n2\n"));
+        assert_stderr(&state, |stderr| {
+            assert!(
+                stderr.contains("cannot call function `f`: too deep recursion"),
+                "{stderr:?}"
+            );
+            assert!(stderr.contains("call depth limit 3 exceeded"), "{stderr:?}");
+        });
+    }
+
+    #[test]
+    fn function_frame_is_pushed_while_executing_function() {
+        fn stack_builtin(
+            env: &mut Env,
+            _args: Vec<Field>,
+        ) -> std::pin::Pin<Box<dyn std::future::Future<Output = yash_env::builtin::Result> + '_>>
+        {
+            Box::pin(async move {
+                assert_matches!(
+                    &env.stack[..],
+                    [Frame::Function(function), Frame::Builtin(_)] => assert_eq!(function.name, "foo")
+                );
+                Default::default()
+            })
+        }
+
+        let mut env = Env::new_virtual();
+        env.builtins
+            .insert("check", Builtin::new(Mandatory, stack_builtin));
+        let function = Function::new(
+            "foo",
+            "{ check; }".parse::<FullCompoundCommand>().unwrap(),
+            Location::dummy("dummy"),
+        );
+        env.functions.define(function).unwrap();
+        let command: SimpleCommand = "foo".parse().unwrap();
+
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.stack[..], []);
+    }
+
     #[test]
     fn simple_command_passes_arguments_to_function() {
         let system = VirtualSystem::new();
@@ -0,0 +1,181 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Hooks called before executing simple commands
+//!
+//! A hook is a function that is called with the expanded fields of a simple
+//! command before the command is executed. Hooks allow library users to
+//! observe command execution without modifying the executor, for example to
+//! collect statistics or implement a debugger.
+//!
+//! Hooks are stored in a [`Hooks`] instance in [`env.any`](Env::any). Use
+//! [`add_hook`] to register a hook. The [execution of a simple
+//! command](crate::command::Command) calls the hooks in the order of
+//! registration after expanding the command words and before performing
+//! redirections and assignments. The hooks are called even if the expansion
+//! produced no fields. Each field's [`origin`](Field::origin) tells the
+//! location of the word the field resulted from.
+//!
+//! Hooks are not user-defined traps. They are not affected by the `trap`
+//! built-in and not run for commands that are not executed because of the
+//! `Exec` option being off.
+//!
+//! ```
+//! # use futures_util::FutureExt as _;
+//! # use std::cell::RefCell;
+//! # use std::rc::Rc;
+//! # use yash_env::Env;
+//! # use yash_semantics::command::Command as _;
+//! # use yash_semantics::command::simple_command::hook::add_hook;
+//! # use yash_syntax::syntax::SimpleCommand;
+//! let mut env = Env::new_virtual();
+//! let names = Rc::new(RefCell::new(Vec::new()));
+//! let names_2 = Rc::clone(&names);
+//! add_hook(&mut env, move |_env, fields| {
+//!     let fields = fields.iter().map(|field| field.value.clone());
+//!     names_2.borrow_mut().push(fields.collect::<Vec<_>>());
+//! });
+//!
+//! let command: SimpleCommand = "x=1 ${x-foo} bar".parse().unwrap();
+//! let _ = command.execute(&mut env).now_or_never().unwrap();
+//! assert_eq!(*names.borrow(), [["foo", "bar"]]);
+//! ```
+
+use std::rc::Rc;
+use yash_env::semantics::Field;
+use yash_env::Env;
+
+/// Function called before executing a simple command
+///
+/// The function receives the environment and the expanded fields of the
+/// command.
+pub type Hook = Rc<dyn Fn(&mut Env, &[Field])>;
+
+/// Collection of hooks
+///
+/// An instance of this type is stored in [`env.any`](Env::any) to hold the
+/// registered hooks. See the [module documentation](self) for details.
+#[derive(Clone, Default)]
+pub struct Hooks(pub Vec<Hook>);
+
+impl std::fmt::Debug for Hooks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Hooks").field(&self.0.len()).finish()
+    }
+}
+
+/// Registers a hook in the environment.
+///
+/// The hook is appended to the [`Hooks`] in [`env.any`](Env::any), which is
+/// inserted if there is none.
+pub fn add_hook<F>(env: &mut Env, hook: F)
+where
+    F: Fn(&mut Env, &[Field]) + 'static,
+{
+    env.any
+        .get_or_insert_with(|| Box::new(Hooks::default()))
+        .0
+        .push(Rc::new(hook));
+}
+
+/// Calls the hooks registered in the environment.
+///
+/// The hooks are called in the order of registration. A hook may register or
+/// remove hooks, but the change does not affect the hooks called in the
+/// current invocation of this function.
+pub fn run_hooks(env: &mut Env, fields: &[Field]) {
+    let Some(hooks) = env.any.get::<Hooks>() else {
+        return;
+    };
+    for hook in hooks.0.clone() {
+        hook(env, fields);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::Command as _;
+    use crate::tests::echo_builtin;
+    use futures_util::FutureExt as _;
+    use std::cell::RefCell;
+    use std::ops::ControlFlow::Continue;
+    use yash_syntax::syntax::List;
+
+    fn recording_hook(env: &mut Env) -> Rc<RefCell<Vec<Vec<String>>>> {
+        let record = Rc::new(RefCell::new(Vec::new()));
+        let record_2 = Rc::clone(&record);
+        add_hook(env, move |_env, fields| {
+            let values = fields.iter().map(|field| field.value.clone()).collect();
+            record_2.borrow_mut().push(values);
+        });
+        record
+    }
+
+    #[test]
+    fn hook_receives_expanded_fields_of_each_command() {
+        let mut env = Env::new_virtual();
+        env.builtins.insert("echo", echo_builtin());
+        let record = recording_hook(&mut env);
+
+        let list: List = "echo a 'b c'; x=1; echo $x >/dev/null; \"\""
+            .parse()
+            .unwrap();
+        let result = list.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+
+        assert_eq!(
+            *record.borrow(),
+            [
+                vec!["echo".to_string(), "a".to_string(), "b c".to_string()],
+                vec![],
+                vec!["echo".to_string(), "1".to_string()],
+                vec!["".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn hooks_are_called_in_order_of_registration() {
+        let mut env = Env::new_virtual();
+        let order = Rc::new(RefCell::new(Vec::new()));
+        for i in 0..3 {
+            let order = Rc::clone(&order);
+            add_hook(&mut env, move |_env, _fields| order.borrow_mut().push(i));
+        }
+
+        run_hooks(&mut env, &[]);
+        assert_eq!(*order.borrow(), [0, 1, 2]);
+    }
+
+    #[test]
+    fn hook_can_modify_environment() {
+        let mut env = Env::new_virtual();
+        add_hook(&mut env, |env, fields| {
+            env.exit_status = yash_env::semantics::ExitStatus(fields.len() as _);
+        });
+
+        run_hooks(&mut env, &Field::dummies(["a", "b"]));
+        assert_eq!(env.exit_status, yash_env::semantics::ExitStatus(2));
+    }
+
+    #[test]
+    fn no_hooks_registered() {
+        let mut env = Env::new_virtual();
+        run_hooks(&mut env, &Field::dummies(["a"]));
+        assert!(env.any.get::<Hooks>().is_none());
+    }
+}
@@ -0,0 +1,252 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2026 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Handler called when a command is not found
+//!
+//! When the [command search](crate::command_search) finds no target for a
+//! simple command, the [execution of the simple
+//! command](crate::command::Command) consults the handler registered in
+//! [`env.any`](Env::any) before reporting the error. The handler receives the
+//! expanded fields of the command and returns an [`Action`] that tells what to
+//! do next. For example, a handler can suggest a package that provides the
+//! command or correct a typo in the command name.
+//!
+//! Use [`set_handler`] to register a handler. Only one handler can be
+//! registered at a time. Without a handler, the command fails with the
+//! default error message and exit status 127.
+//!
+//! ```
+//! # use futures_util::FutureExt as _;
+//! # use yash_env::Env;
+//! # use yash_env::semantics::ExitStatus;
+//! # use yash_semantics::command::Command as _;
+//! # use yash_semantics::command::simple_command::not_found::{set_handler, Action};
+//! # use yash_syntax::syntax::SimpleCommand;
+//! let mut env = Env::new_virtual();
+//! set_handler(&mut env, |_env, _fields| {
+//!     Box::pin(async { Action::ExitStatus(ExitStatus(42)) })
+//! });
+//!
+//! let command: SimpleCommand = "no_such_command".parse().unwrap();
+//! let _ = command.execute(&mut env).now_or_never().unwrap();
+//! assert_eq!(env.exit_status, ExitStatus(42));
+//! ```
+
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use yash_env::semantics::ExitStatus;
+use yash_env::semantics::Field;
+use yash_env::Env;
+
+/// Result of a [`Handler`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Action {
+    /// Report the error as if no handler were registered.
+    Default,
+
+    /// Finish the command with the given exit status.
+    ///
+    /// The default error message is not printed. The redirections and
+    /// assignments of the command are not performed.
+    ExitStatus(ExitStatus),
+
+    /// Execute the given fields as the command instead.
+    ///
+    /// The first field is searched for as the command name, and the
+    /// redirections and assignments of the original command are applied to the
+    /// new command. The handler is not consulted again if the new command is
+    /// not found either. If the fields are empty, this action is equivalent to
+    /// [`Default`](Self::Default).
+    Substitute(Vec<Field>),
+}
+
+/// Function called when a command is not found
+///
+/// The function receives the environment and the expanded fields of the
+/// command. The first field is the name of the command that was not found.
+pub type Handler =
+    Rc<dyn for<'a> Fn(&'a mut Env, &'a [Field]) -> Pin<Box<dyn Future<Output = Action> + 'a>>>;
+
+/// Registered handler
+///
+/// An instance of this type is stored in [`env.any`](Env::any) to hold the
+/// handler registered by [`set_handler`].
+#[derive(Clone)]
+pub struct RegisteredHandler(pub Handler);
+
+impl std::fmt::Debug for RegisteredHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("RegisteredHandler(..)")
+    }
+}
+
+/// Registers a handler in the environment.
+///
+/// The handler replaces the previously registered one, if any.
+pub fn set_handler<F>(env: &mut Env, handler: F)
+where
+    F: for<'a> Fn(&'a mut Env, &'a [Field]) -> Pin<Box<dyn Future<Output = Action> + 'a>> + 'static,
+{
+    env.any
+        .insert(Box::new(RegisteredHandler(Rc::new(handler))));
+}
+
+/// Removes the handler from the environment.
+pub fn remove_handler(env: &mut Env) {
+    env.any.remove::<RegisteredHandler>();
+}
+
+/// Calls the handler registered in the environment.
+///
+/// Returns [`Action::Default`] if no handler is registered.
+pub async fn run_handler(env: &mut Env, fields: &[Field]) -> Action {
+    let Some(RegisteredHandler(handler)) = env.any.get::<RegisteredHandler>().cloned() else {
+        return Action::Default;
+    };
+    handler(env, fields).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::Command as _;
+    use crate::tests::echo_builtin;
+    use futures_util::FutureExt as _;
+    use std::cell::RefCell;
+    use std::ops::ControlFlow::Continue;
+    use yash_env_test_helper::assert_stderr;
+    use yash_env_test_helper::assert_stdout;
+    use yash_env_test_helper::in_virtual_system;
+    use yash_syntax::syntax::SimpleCommand;
+
+    #[test]
+    fn no_handler_registered() {
+        let mut env = Env::new_virtual();
+        let action = run_handler(&mut env, &Field::dummies(["foo"]))
+            .now_or_never()
+            .unwrap();
+        assert_eq!(action, Action::Default);
+    }
+
+    #[test]
+    fn handler_receives_fields() {
+        let mut env = Env::new_virtual();
+        let record = Rc::new(RefCell::new(Vec::new()));
+        let record_2 = Rc::clone(&record);
+        set_handler(&mut env, move |_env, fields| {
+            let values = fields.iter().map(|field| field.value.clone());
+            record_2.borrow_mut().extend(values);
+            Box::pin(async { Action::Default })
+        });
+
+        let command: SimpleCommand = "no_such_command a b".parse().unwrap();
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus::NOT_FOUND);
+        assert_eq!(*record.borrow(), ["no_such_command", "a", "b"]);
+    }
+
+    #[test]
+    fn handler_customizing_exit_status() {
+        in_virtual_system(|mut env, state| async move {
+            set_handler(&mut env, |_env, _fields| {
+                Box::pin(async { Action::ExitStatus(ExitStatus(3)) })
+            });
+
+            let command: SimpleCommand = "no_such_command".parse().unwrap();
+            let result = command.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus(3));
+            assert_stderr(&state, |stderr| assert_eq!(stderr, ""));
+        })
+    }
+
+    #[test]
+    fn handler_substituting_builtin() {
+        in_virtual_system(|mut env, state| async move {
+            env.builtins.insert("echo", echo_builtin());
+            set_handler(&mut env, |_env, fields| {
+                let mut fields = fields.to_vec();
+                fields[0].value = "echo".to_string();
+                Box::pin(async { Action::Substitute(fields) })
+            });
+
+            let command: SimpleCommand = "ehco hello >/tmp/out".parse().unwrap();
+            let result = command.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+            assert_stdout(&state, |stdout| assert_eq!(stdout, ""));
+
+            let file = state.borrow().file_system.get("/tmp/out").unwrap();
+            let file = file.borrow();
+            assert_matches::assert_matches!(
+                &file.body,
+                yash_env::system::r#virtual::FileBody::Regular { content, .. } => {
+                    assert_eq!(std::str::from_utf8(content), Ok("hello\n"));
+                }
+            );
+        })
+    }
+
+    #[test]
+    fn handler_is_not_called_again_for_missing_substitute() {
+        in_virtual_system(|mut env, state| async move {
+            let count = Rc::new(RefCell::new(0));
+            let count_2 = Rc::clone(&count);
+            set_handler(&mut env, move |_env, _fields| {
+                *count_2.borrow_mut() += 1;
+                let fields = Field::dummies(["still_missing"]);
+                Box::pin(async { Action::Substitute(fields) })
+            });
+
+            let command: SimpleCommand = "no_such_command".parse().unwrap();
+            let result = command.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::NOT_FOUND);
+            assert_eq!(*count.borrow(), 1);
+            assert_stderr(&state, |stderr| {
+                assert!(stderr.contains("still_missing"), "{stderr}")
+            });
+        })
+    }
+
+    #[test]
+    fn handler_is_not_called_for_found_command() {
+        let mut env = Env::new_virtual();
+        env.builtins.insert("echo", echo_builtin());
+        set_handler(&mut env, |_env, _fields| unreachable!());
+
+        let command: SimpleCommand = "echo".parse().unwrap();
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+    }
+
+    #[test]
+    fn removing_handler() {
+        let mut env = Env::new_virtual();
+        set_handler(&mut env, |_env, _fields| {
+            Box::pin(async { Action::ExitStatus(ExitStatus(3)) })
+        });
+        remove_handler(&mut env);
+
+        let command: SimpleCommand = "no_such_command".parse().unwrap();
+        let result = command.execute(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_eq!(env.exit_status, ExitStatus::NOT_FOUND);
+    }
+}
@@ -0,0 +1,198 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Reporting the execution time of a timed pipeline
+//!
+//! When a pipeline is prefixed with the `time` reserved word, the shell takes
+//! a [`Stopwatch`] before executing the pipeline and prints the elapsed real
+//! time and the CPU time consumed by the shell and its children to the
+//! standard error after the pipeline finishes.
+//!
+//! The report is printed in one of the following formats, depending on the
+//! [`TimeFormat`] of the pipeline. In the first format, the labels and the
+//! times are separated by a tab, which is shown as spaces here.
+//!
+//! ```text
+//! real    0m1.500s
+//! user    0m0.250s
+//! sys     0m0.010s
+//! ```
+//!
+//! ```text
+//! real 1.50
+//! user 0.25
+//! sys 0.01
+//! ```
+//!
+//! The latter is the format of the `time -p` utility defined in POSIX. It is
+//! always used if the [`PosixlyCorrect`] option is on.
+
+use std::time::Instant;
+use yash_env::option::Option::PosixlyCorrect;
+use yash_env::option::State::On;
+use yash_env::system::Times;
+use yash_env::Env;
+use yash_env::System as _;
+use yash_syntax::syntax::TimeFormat;
+
+/// Snapshot of the clock and the CPU times
+#[derive(Clone, Copy, Debug)]
+pub struct Stopwatch {
+    start: Instant,
+    times: Times,
+}
+
+impl Stopwatch {
+    /// Takes a snapshot of the current time and the consumed CPU times.
+    #[must_use]
+    pub fn start(env: &Env) -> Self {
+        let start = env.system.now();
+        let times = env.system.times().unwrap_or_default();
+        Stopwatch { start, times }
+    }
+
+    /// Prints the time elapsed since the snapshot was taken.
+    ///
+    /// The `format` is overridden with [`TimeFormat::Posix`] if the
+    /// [`PosixlyCorrect`] option is on.
+    pub async fn report(&self, env: &mut Env, format: TimeFormat) {
+        let now = env.system.now();
+        let times = env.system.times().unwrap_or_default();
+        let real = now.saturating_duration_since(self.start).as_secs_f64();
+        let user = (times.self_user + times.children_user)
+            - (self.times.self_user + self.times.children_user);
+        let sys = (times.self_system + times.children_system)
+            - (self.times.self_system + self.times.children_system);
+
+        let format = if env.options.get(PosixlyCorrect) == On {
+            TimeFormat::Posix
+        } else {
+            format
+        };
+        let report = format_report(format, real, user.max(0.0), sys.max(0.0));
+        env.system.print_error(&report).await;
+    }
+}
+
+/// Formats the report of the elapsed times given in seconds.
+fn format_report(format: TimeFormat, real: f64, user: f64, sys: f64) -> String {
+    match format {
+        TimeFormat::Default => format!(
+            "real\t{}\nuser\t{}\nsys\t{}\n",
+            minutes_and_seconds(real),
+            minutes_and_seconds(user),
+            minutes_and_seconds(sys),
+        ),
+        TimeFormat::Posix => format!("real {real:.2}\nuser {user:.2}\nsys {sys:.2}\n"),
+    }
+}
+
+/// Formats seconds like `1m2.345s`.
+fn minutes_and_seconds(seconds: f64) -> String {
+    // Round to milliseconds first so that the seconds part never shows 60.000
+    let millis = (seconds * 1000.0).round() as u64;
+    let minutes = millis / 60_000;
+    let millis = millis % 60_000;
+    format!("{}m{}.{:03}s", minutes, millis / 1000, millis % 1000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt as _;
+    use std::rc::Rc;
+    use std::time::Duration;
+    use yash_env::VirtualSystem;
+    use yash_env_test_helper::assert_stderr;
+
+    #[test]
+    fn minutes_and_seconds_formatting() {
+        assert_eq!(minutes_and_seconds(0.0), "0m0.000s");
+        assert_eq!(minutes_and_seconds(1.5), "0m1.500s");
+        assert_eq!(minutes_and_seconds(59.9999), "1m0.000s");
+        assert_eq!(minutes_and_seconds(123.456), "2m3.456s");
+    }
+
+    #[test]
+    fn default_report() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        state.borrow_mut().now = Some(Instant::now());
+        state.borrow_mut().times.self_user = 1.0;
+        let mut env = Env::with_system(Box::new(system));
+        let stopwatch = Stopwatch::start(&env);
+
+        {
+            let mut state = state.borrow_mut();
+            state.advance_time(Duration::from_millis(61_250));
+            state.times.self_user += 0.5;
+            state.times.children_user += 0.25;
+            state.times.children_system += 0.125;
+        }
+        stopwatch
+            .report(&mut env, TimeFormat::Default)
+            .now_or_never()
+            .unwrap();
+
+        assert_stderr(&state, |stderr| {
+            assert_eq!(stderr, "real\t1m1.250s\nuser\t0m0.750s\nsys\t0m0.125s\n")
+        });
+    }
+
+    #[test]
+    fn posix_report() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        state.borrow_mut().now = Some(Instant::now());
+        let mut env = Env::with_system(Box::new(system));
+        let stopwatch = Stopwatch::start(&env);
+
+        {
+            let mut state = state.borrow_mut();
+            state.advance_time(Duration::from_millis(2_500));
+            state.times.self_system += 0.5;
+            state.times.children_user += 1.25;
+        }
+        stopwatch
+            .report(&mut env, TimeFormat::Posix)
+            .now_or_never()
+            .unwrap();
+
+        assert_stderr(&state, |stderr| {
+            assert_eq!(stderr, "real 2.50\nuser 1.25\nsys 0.50\n")
+        });
+    }
+
+    #[test]
+    fn posix_report_forced_by_posixly_correct_option() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        state.borrow_mut().now = Some(Instant::now());
+        let mut env = Env::with_system(Box::new(system));
+        env.options.set(PosixlyCorrect, On);
+        let stopwatch = Stopwatch::start(&env);
+
+        state.borrow_mut().advance_time(Duration::from_secs(3));
+        stopwatch
+            .report(&mut env, TimeFormat::Default)
+            .now_or_never()
+            .unwrap();
+
+        assert_stderr(&state, |stderr| {
+            assert_eq!(stderr, "real 3.00\nuser 0.00\nsys 0.00\n")
+        });
+    }
+}
@@ -33,6 +33,13 @@
 //! For a [substitutive](Substitutive) built-in or external utility to be chosen
 //! as a target, a corresponding executable file must be present in a directory
 //! specified in the `$PATH` variable.
+//!
+//! An empty component in `$PATH` (e.g., the middle of `/bin::/usr/bin`) names
+//! the current working directory. In that case, the candidate pathname is
+//! the bare command name, which the system resolves relative to the working
+//! directory. If `$PATH` is unset, no directory is searched, so neither
+//! substitutive built-ins nor external utilities are found unless the command
+//! name contains a slash.
 
 use assert_matches::assert_matches;
 use std::ffi::CStr;
@@ -234,10 +241,15 @@ pub fn search_path<E: PathEnv>(env: &mut E, name: &str) -> Option<CString> {
 mod tests {
     use super::*;
     use assert_matches::assert_matches;
+    use std::cell::RefCell;
     use std::collections::HashMap;
     use std::collections::HashSet;
     use yash_env::function::FunctionSet;
+    use yash_env::system::r#virtual::Inode;
+    use yash_env::system::Mode;
+    use yash_env::variable::Scope;
     use yash_env::variable::Value;
+    use yash_env::VirtualSystem;
     use yash_syntax::source::Location;
     use yash_syntax::syntax::CompoundCommand;
     use yash_syntax::syntax::FullCompoundCommand;
@@ -556,4 +568,47 @@ mod tests {
             assert_eq!(path.to_bytes(), "foo".as_bytes());
         });
     }
+
+    #[test]
+    fn empty_path_component_with_virtual_system() {
+        let mut system = VirtualSystem::new();
+        let mut content = Inode::default();
+        content.permissions.set(Mode::USER_EXEC, true);
+        let content = Rc::new(RefCell::new(content));
+        system
+            .state
+            .borrow_mut()
+            .file_system
+            .save("/dir/foo", content)
+            .unwrap();
+        system.current_process_mut().chdir("/dir".into());
+        let mut env = Env::with_system(Box::new(system));
+        let mut path = env.variables.get_or_new(PATH, Scope::Global);
+        path.assign("/bin:/usr/bin", None).unwrap();
+
+        assert_eq!(search_path(&mut env, "foo"), None);
+
+        let mut path = env.variables.get_or_new(PATH, Scope::Global);
+        path.assign("/bin::/usr/bin", None).unwrap();
+
+        assert_eq!(search_path(&mut env, "foo"), Some(c"foo".to_owned()));
+    }
+
+    #[test]
+    fn nothing_is_searched_with_unset_path() {
+        let mut system = VirtualSystem::new();
+        let mut content = Inode::default();
+        content.permissions.set(Mode::USER_EXEC, true);
+        let content = Rc::new(RefCell::new(content));
+        system
+            .state
+            .borrow_mut()
+            .file_system
+            .save("/dir/foo", content)
+            .unwrap();
+        system.current_process_mut().chdir("/dir".into());
+        let mut env = Env::with_system(Box::new(system));
+
+        assert_eq!(search_path(&mut env, "foo"), None);
+    }
 }
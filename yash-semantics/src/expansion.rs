@@ -43,8 +43,9 @@
 //!
 //! ## Brace expansion
 //!
-//! The brace expansion produces copies of a field containing a pair of braces.
-//! (TODO: This feature is not yet implemented.)
+//! The [brace expansion](brace) produces copies of a field containing a pair
+//! of braces. This non-POSIX extension is performed only if the
+//! [`BraceExpand`](yash_env::option::BraceExpand) option is on.
 //!
 //! ## Field splitting
 //!
@@ -67,11 +68,36 @@
 //! the attribute stripping is part of the pathname expansion. In
 //! [`expand_word`], they are carried out as the last step of the whole
 //! expansion.
+//!
+//! # Expanding strings outside of command execution
+//!
+//! [`expand_text`] and [`expand_word_to_string`] perform the initial expansion
+//! followed by quote removal and attribute stripping, but neither field
+//! splitting nor pathname expansion. They are suitable for expanding a string
+//! that is not part of a command, such as a prompt string or a value read from
+//! a configuration file. Parse the string into a [`Text`] or [`Word`] with
+//! [`yash_syntax::parser::lex::Lexer`] or [`str::parse`] before expanding it.
+//!
+//! The expansion reads and may modify the [`Env`](yash_env::Env) as follows:
+//!
+//! - Parameter expansion reads `env.variables`, and the `:=` modifier assigns
+//!   to them. The `Unset` and `PosixlyCorrect` [options](yash_env::option)
+//!   affect the results.
+//! - Command substitution runs the command in a subshell, so `env.system` must
+//!   be able to [start a child process](yash_env::System::new_child_process).
+//!   Built-ins and functions used in the command must be registered in the
+//!   `Env`. A [`VirtualSystem`](yash_env::system::virtual::VirtualSystem)
+//!   needs an executor to run the subshell.
+//! - Tilde expansion reads `$HOME` and the user database via `env.system`.
+//! - Arithmetic expansion reads and may assign to `env.variables`.
 
 pub mod attr;
 pub mod attr_strip;
+pub mod brace;
 pub mod glob;
 pub mod initial;
+pub mod pattern_cache;
+pub mod pattern_options;
 pub mod phrase;
 pub mod quote_removal;
 pub mod split;
@@ -92,6 +118,8 @@ use self::quote_removal::skip_quotes;
 use self::split::Ifs;
 use std::borrow::Cow;
 use thiserror::Error;
+use yash_env::option::Option::BraceExpand;
+use yash_env::option::State::On;
 use yash_env::semantics::ExitStatus;
 use yash_env::system::Errno;
 use yash_env::variable::Value;
@@ -103,6 +131,7 @@ use yash_syntax::source::pretty::MessageBase;
 use yash_syntax::source::Location;
 use yash_syntax::syntax::ExpansionMode;
 use yash_syntax::syntax::Param;
+use yash_syntax::syntax::ParamType;
 use yash_syntax::syntax::Text;
 use yash_syntax::syntax::Word;
 
@@ -135,6 +164,13 @@ pub enum ErrorCause {
     #[error("error in command substitution: {0}")]
     CommandSubstError(Errno),
 
+    /// Output of a command substitution exceeding the limit.
+    ///
+    /// The value is the limit in bytes.
+    /// See [`yash_env::Env::command_subst_output_limit`].
+    #[error("command substitution output exceeded {0} bytes")]
+    CommandSubstOutputTooLarge(usize),
+
     /// Error while evaluating an arithmetic expansion.
     #[error(transparent)]
     ArithError(#[from] ArithError),
@@ -154,6 +190,27 @@ pub enum ErrorCause {
     /// Assignment to a nonassignable parameter
     #[error(transparent)]
     NonassignableParameter(#[from] NonassignableError),
+
+    /// Expansion with a side effect in an environment that cannot be modified
+    ///
+    /// This error occurs when a command substitution or an assignment is
+    /// attempted in an expansion performed with a shared reference to the
+    /// environment. See [`initial::ExpandEnv`].
+    #[error("the environment cannot be modified in this expansion")]
+    ImmutableEnv,
+
+    /// Pattern that matched no pathnames with the `FailGlob` option
+    #[error("no pathname matches the pattern `{pattern}`")]
+    UnmatchedPattern { pattern: String },
+}
+
+/// Describes a parameter by its type and name for use in error messages.
+fn describe_param(param: &Param) -> String {
+    match param.r#type {
+        ParamType::Variable => format!("variable `{param}`"),
+        ParamType::Special(_) => format!("special parameter `{param}`"),
+        ParamType::Positional(_) => format!("positional parameter {param}"),
+    }
 }
 
 impl ErrorCause {
@@ -164,11 +221,14 @@ impl ErrorCause {
         use ErrorCause::*;
         match self {
             CommandSubstError(_) => "error performing the command substitution",
+            CommandSubstOutputTooLarge(_) => "command substitution output is too large",
             ArithError(_) => "error evaluating the arithmetic expansion",
             AssignReadOnly(_) => "error assigning to variable",
             UnsetParameter { .. } => "cannot expand unset parameter",
             VacantExpansion(error) => error.message_or_default(),
             NonassignableParameter(_) => "cannot assign to parameter",
+            ImmutableEnv => "cannot perform an expansion with side effects",
+            UnmatchedPattern { .. } => "no pathname matches the pattern",
         }
     }
 
@@ -179,9 +239,12 @@ impl ErrorCause {
         use ErrorCause::*;
         match self {
             CommandSubstError(e) => e.to_string(),
+            CommandSubstOutputTooLarge(limit) => {
+                format!("output exceeded the limit of {limit} bytes")
+            }
             ArithError(e) => e.to_string(),
             AssignReadOnly(e) => e.to_string(),
-            UnsetParameter { param } => format!("parameter `{param}` is not set"),
+            UnsetParameter { param } => format!("{} is not set", describe_param(param)),
             VacantExpansion(e) => match e.vacancy {
                 Vacancy::Unset => format!("parameter `{}` is not set", e.param),
                 Vacancy::EmptyScalar => format!("parameter `{}` is an empty string", e.param),
@@ -191,6 +254,8 @@ impl ErrorCause {
                 }
             },
             NonassignableParameter(e) => e.to_string(),
+            ImmutableEnv => "the environment cannot be modified here".to_string(),
+            UnmatchedPattern { pattern } => format!("pattern `{pattern}` matched no files"),
         }
         .into()
     }
@@ -203,6 +268,7 @@ impl ErrorCause {
         use ErrorCause::*;
         match self {
             CommandSubstError(_) => None,
+            CommandSubstOutputTooLarge(_) => None,
             ArithError(e) => e.related_location(),
             AssignReadOnly(e) => Some((
                 &e.read_only_location,
@@ -211,6 +277,8 @@ impl ErrorCause {
             UnsetParameter { .. } => None,
             VacantExpansion(_) => None,
             NonassignableParameter(_) => None,
+            ImmutableEnv => None,
+            UnmatchedPattern { .. } => None,
         }
     }
 
@@ -220,12 +288,17 @@ impl ErrorCause {
         use ErrorCause::*;
         match self {
             CommandSubstError(_)
+            | CommandSubstOutputTooLarge(_)
             | ArithError(_)
             | AssignReadOnly(_)
             | VacantExpansion(_)
-            | NonassignableParameter(_) => None,
+            | NonassignableParameter(_)
+            | ImmutableEnv => None,
 
             UnsetParameter { .. } => Some("unset parameters are disallowed by the nounset option"),
+            UnmatchedPattern { .. } => {
+                Some("unmatched patterns are disallowed by the failglob option")
+            }
         }
     }
 }
@@ -260,11 +333,14 @@ impl MessageBase for Error {
         // Report the vacancy that caused the assignment that led to the error.
         let vacancy = match &self.cause {
             ErrorCause::CommandSubstError(_) => None,
+            ErrorCause::CommandSubstOutputTooLarge(_) => None,
             ErrorCause::ArithError(_) => None,
             ErrorCause::AssignReadOnly(e) => e.vacancy,
             ErrorCause::UnsetParameter { .. } => None,
             ErrorCause::VacantExpansion(_) => None,
             ErrorCause::NonassignableParameter(e) => Some(e.vacancy),
+            ErrorCause::ImmutableEnv => None,
+            ErrorCause::UnmatchedPattern { .. } => None,
         };
         if let Some(vacancy) = vacancy {
             let message = match vacancy {
@@ -309,6 +385,43 @@ pub type Result<T> = std::result::Result<T, Error>;
 /// stripping.
 /// The second field of the result tuple is the exit status of the last command
 /// substitution performed during the expansion, if any.
+///
+/// The example below expands a parameter and a command substitution in a
+/// virtual environment. See the [module documentation](self#expanding-strings-outside-of-command-execution)
+/// for the capabilities of the environment the expansion depends on.
+///
+/// ```
+/// # use std::future::Future;
+/// # use std::pin::Pin;
+/// # use yash_env::builtin::{Builtin, Type};
+/// # use yash_env::io::Fd;
+/// # use yash_env::semantics::{ExitStatus, Field};
+/// # use yash_env::variable::Scope;
+/// # use yash_env::{Env, System as _};
+/// # use yash_env_test_helper::in_virtual_system;
+/// # use yash_semantics::expansion::expand_text;
+/// # use yash_syntax::syntax::Text;
+/// # fn echo(env: &mut Env, args: Vec<Field>)
+/// #     -> Pin<Box<dyn Future<Output = yash_env::builtin::Result> + '_>> {
+/// #     Box::pin(async move {
+/// #         let mut line = args.iter().map(|f| f.value.as_str()).collect::<Vec<_>>().join(" ");
+/// #         line.push('\n');
+/// #         env.system.write_all(Fd::STDOUT, line.as_bytes()).await.unwrap();
+/// #         ExitStatus::SUCCESS.into()
+/// #     })
+/// # }
+/// in_virtual_system(|mut env, _state| async move {
+///     env.builtins.insert("echo", Builtin::new(Type::Mandatory, echo));
+///     env.get_or_create_variable("name", Scope::Global)
+///         .assign("world", None)
+///         .unwrap();
+///
+///     let text: Text = "Hello, $name! $(echo It works.)".parse().unwrap();
+///     let (result, exit_status) = expand_text(&mut env, &text).await.unwrap();
+///     assert_eq!(result, "Hello, world! It works.");
+///     assert_eq!(exit_status, Some(ExitStatus::SUCCESS));
+/// })
+/// ```
 pub async fn expand_text(
     env: &mut yash_env::Env,
     text: &Text,
@@ -366,6 +479,36 @@ pub async fn expand_word(
     Ok((field, exit_status))
 }
 
+/// Expands a word to a string.
+///
+/// This function is a shorthand for [`expand_word`] that returns only the value
+/// of the resulting field, discarding its origin and the exit status of command
+/// substitutions. Like `expand_word`, it performs neither field splitting nor
+/// pathname expansion.
+///
+/// See the [module documentation](self#expanding-strings-outside-of-command-execution)
+/// for the capabilities of the environment the expansion depends on.
+///
+/// ```
+/// # use yash_env::variable::Scope;
+/// # use yash_env_test_helper::in_virtual_system;
+/// # use yash_semantics::expansion::expand_word_to_string;
+/// # use yash_syntax::syntax::Word;
+/// in_virtual_system(|mut env, _state| async move {
+///     env.get_or_create_variable("dir", Scope::Global)
+///         .assign("/tmp/my files", None)
+///         .unwrap();
+///
+///     let word: Word = r#"$dir/*.txt' and '"$(exit 3)"x"#.parse().unwrap();
+///     let result = expand_word_to_string(&mut env, &word).await.unwrap();
+///     assert_eq!(result, "/tmp/my files/*.txt and x");
+/// })
+/// ```
+pub async fn expand_word_to_string(env: &mut yash_env::Env, word: &Word) -> Result<String> {
+    let (field, _exit_status) = expand_word(env, word).await?;
+    Ok(field.value)
+}
+
 /// Expands a word to fields.
 ///
 /// This function performs the initial expansion and multi-field expansion,
@@ -388,7 +531,18 @@ where
     // initial expansion //
     let phrase = word.expand(&mut env).await?;
 
-    // TODO brace expansion //
+    // brace expansion //
+    let mut fields = Vec::with_capacity(phrase.field_count());
+    let brace_expand = env.inner.options.get(BraceExpand) == On;
+    for chars in phrase {
+        let origin = word.location.clone();
+        let attr_field = AttrField { chars, origin };
+        if brace_expand {
+            brace::expand_into(attr_field, &mut fields);
+        } else {
+            fields.push(attr_field);
+        }
+    }
 
     // field splitting //
     let ifs = env
@@ -397,17 +551,15 @@ where
         .get_scalar(IFS)
         .map(Ifs::new)
         .unwrap_or_default();
-    let mut split_fields = Vec::with_capacity(phrase.field_count());
-    for chars in phrase {
-        let origin = word.location.clone();
-        let attr_field = AttrField { chars, origin };
+    let mut split_fields = Vec::with_capacity(fields.len());
+    for attr_field in fields {
         split::split_into(attr_field, &ifs, &mut split_fields);
     }
     drop(ifs);
 
     // pathname expansion (including quote removal and attribute stripping) //
     for field in split_fields {
-        results.extend(glob(env.inner, field));
+        results.extend(glob(env.inner, field)?);
     }
 
     Ok(env.last_command_subst_exit_status)
@@ -502,9 +654,14 @@ mod tests {
     use crate::tests::return_builtin;
     use assert_matches::assert_matches;
     use futures_util::FutureExt;
+    use yash_env::option::Option::Unset;
+    use yash_env::option::State::Off;
     use yash_env::variable::Scope;
+    use yash_env::variable::IFS;
     use yash_env_test_helper::in_virtual_system;
     use yash_syntax::source::pretty::Message;
+    use yash_syntax::source::Source;
+    use yash_syntax::syntax::SpecialParam;
 
     #[test]
     fn from_error_for_message() {
@@ -565,6 +722,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn labels_of_unset_parameters() {
+        let cause = ErrorCause::UnsetParameter {
+            param: Param::variable("foo"),
+        };
+        assert_eq!(cause.label(), "variable `foo` is not set");
+
+        let cause = ErrorCause::UnsetParameter {
+            param: Param::from(3),
+        };
+        assert_eq!(cause.label(), "positional parameter 3 is not set");
+
+        let cause = ErrorCause::UnsetParameter {
+            param: Param::from(SpecialParam::Exclamation),
+        };
+        assert_eq!(cause.label(), "special parameter `!` is not set");
+    }
+
+    #[test]
+    fn nounset_error_locates_parameter_in_word() {
+        let mut env = yash_env::Env::new_virtual();
+        env.options.set(Unset, Off);
+        env.variables.positional_params_mut().values = vec!["1".to_string()];
+
+        let word = "a${foo}b".parse().unwrap();
+        let e = expand_word(&mut env, &word)
+            .now_or_never()
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(e.cause.label(), "variable `foo` is not set");
+        assert_eq!(*e.location.code.value.borrow(), "a${foo}b");
+        assert_eq!(e.location.range, 1..7);
+
+        let word = "a$3b".parse().unwrap();
+        let e = expand_word(&mut env, &word)
+            .now_or_never()
+            .unwrap()
+            .unwrap_err();
+        assert_matches!(&e.cause, ErrorCause::UnsetParameter { param } => {
+            assert_eq!(param.r#type, ParamType::Positional(3));
+        });
+        assert_eq!(e.cause.label(), "positional parameter 3 is not set");
+        assert_eq!(e.location.range, 1..3);
+
+        env.variables.positional_params_mut().values.clear();
+        let word = "a$@b".parse().unwrap();
+        let mut fields = Vec::new();
+        expand_word_multiple(&mut env, &word, &mut fields)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_matches!(fields.as_slice(), [f] => assert_eq!(f.value, "ab"));
+    }
+
     #[test]
     fn expand_word_multiple_performs_initial_expansion() {
         in_virtual_system(|mut env, _state| async move {
@@ -582,6 +793,36 @@ mod tests {
         })
     }
 
+    #[test]
+    fn expand_word_multiple_performs_brace_expansion_if_enabled() {
+        let mut env = yash_env::Env::new_virtual();
+        env.options.set(BraceExpand, On);
+        env.variables
+            .get_or_new("v", Scope::Global)
+            .assign("1 2", None)
+            .unwrap();
+        let word = "a{b,$v,'{c,d}'}".parse().unwrap();
+        let mut fields = Vec::new();
+        expand_word_multiple(&mut env, &word, &mut fields)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        let values = fields.iter().map(|f| f.value.as_str()).collect::<Vec<_>>();
+        assert_eq!(values, ["ab", "a1", "2", "a{c,d}"]);
+    }
+
+    #[test]
+    fn expand_word_multiple_skips_brace_expansion_if_disabled() {
+        let mut env = yash_env::Env::new_virtual();
+        let word = "a{b,c}".parse().unwrap();
+        let mut fields = Vec::new();
+        expand_word_multiple(&mut env, &word, &mut fields)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_matches!(fields.as_slice(), [f] => assert_eq!(f.value, "a{b,c}"));
+    }
+
     #[test]
     fn expand_word_multiple_performs_field_splitting_possibly_with_default_ifs() {
         let mut env = yash_env::Env::new_virtual();
@@ -642,6 +883,87 @@ mod tests {
         });
     }
 
+    #[test]
+    fn expand_word_multiple_with_nested_default_values() {
+        let mut env = yash_env::Env::new_virtual();
+        env.variables
+            .get_or_new("a", Scope::Global)
+            .assign("", None)
+            .unwrap();
+        let word = "${a:-${b:-c}}".parse().unwrap();
+        let mut fields = Vec::new();
+        expand_word_multiple(&mut env, &word, &mut fields)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_matches!(fields.as_slice(), [f] => assert_eq!(f.value, "c"));
+
+        env.variables
+            .get_or_new("b", Scope::Global)
+            .assign("x y", None)
+            .unwrap();
+        let mut fields = Vec::new();
+        expand_word_multiple(&mut env, &word, &mut fields)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_matches!(fields.as_slice(), [f1, f2] => {
+            assert_eq!(f1.value, "x");
+            assert_eq!(f2.value, "y");
+        });
+    }
+
+    #[test]
+    fn expand_word_multiple_with_default_value_in_double_quotes() {
+        let mut env = yash_env::Env::new_virtual();
+        let word = "\"${x:-a  b}\"".parse().unwrap();
+        let mut fields = Vec::new();
+        expand_word_multiple(&mut env, &word, &mut fields)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_matches!(fields.as_slice(), [f] => assert_eq!(f.value, "a  b"));
+
+        let word = "${x:-a  b}".parse().unwrap();
+        let mut fields = Vec::new();
+        expand_word_multiple(&mut env, &word, &mut fields)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_matches!(fields.as_slice(), [f1, f2] => {
+            assert_eq!(f1.value, "a");
+            assert_eq!(f2.value, "b");
+        });
+
+        let word = "${x:-\"a  b\"}".parse().unwrap();
+        let mut fields = Vec::new();
+        expand_word_multiple(&mut env, &word, &mut fields)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_matches!(fields.as_slice(), [f] => assert_eq!(f.value, "a  b"));
+    }
+
+    #[test]
+    fn expand_word_multiple_does_not_expand_unused_default_value() {
+        let mut env = yash_env::Env::new_virtual();
+        env.variables
+            .get_or_new("a", Scope::Global)
+            .assign("set", None)
+            .unwrap();
+        for word in ["${a-${b=x}}", "${a:-${b=x}}"] {
+            let word = word.parse().unwrap();
+            let mut fields = Vec::new();
+            let exit_status = expand_word_multiple(&mut env, &word, &mut fields)
+                .now_or_never()
+                .unwrap()
+                .unwrap();
+            assert_eq!(exit_status, None);
+            assert_matches!(fields.as_slice(), [f] => assert_eq!(f.value, "set"));
+            assert_eq!(env.variables.get("b"), None);
+        }
+    }
+
     #[test]
     fn expand_words_returns_exit_status_of_last_command_substitution() {
         in_virtual_system(|mut env, _state| async move {
@@ -671,6 +993,221 @@ mod tests {
         });
     }
 
+    fn values(fields: &[Field]) -> Vec<&str> {
+        fields.iter().map(|field| field.value.as_str()).collect()
+    }
+
+    #[test]
+    fn expand_words_joins_quoted_at_with_adjacent_text() {
+        let mut env = yash_env::Env::new_virtual();
+        env.variables.positional_params_mut().values = ["1", "", "3  4"].map(String::from).into();
+        let word = r#""a$@b""#.parse().unwrap();
+        let (fields, _) = expand_words(&mut env, &[word])
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_eq!(values(&fields), ["a1", "", "3  4b"]);
+    }
+
+    #[test]
+    fn expand_words_quoted_at_without_positional_parameters() {
+        let mut env = yash_env::Env::new_virtual();
+        let words = [r#""$@""#.parse().unwrap(), r#""a$@b""#.parse().unwrap()];
+        let (fields, _) = expand_words(&mut env, &words)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_eq!(values(&fields), ["ab"]);
+    }
+
+    #[test]
+    fn expand_words_quoted_asterisk_joins_with_first_ifs_char() {
+        let mut env = yash_env::Env::new_virtual();
+        env.variables.positional_params_mut().values = ["1", "", "3  4"].map(String::from).into();
+        let words: [Word; 1] = [r#""$*""#.parse().unwrap()];
+        let (fields, _) = expand_words(&mut env, &words)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_eq!(values(&fields), ["1  3  4"]);
+
+        env.variables
+            .get_or_new(IFS, Scope::Global)
+            .assign("-+", None)
+            .unwrap();
+        let (fields, _) = expand_words(&mut env, &words)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_eq!(values(&fields), ["1--3  4"]);
+    }
+
+    #[test]
+    fn expand_words_splits_unquoted_at() {
+        let mut env = yash_env::Env::new_virtual();
+        env.variables.positional_params_mut().values = ["1", "", "3  4"].map(String::from).into();
+        let words = ["$@".parse().unwrap(), "$*".parse().unwrap()];
+        let (fields, _) = expand_words(&mut env, &words)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_eq!(values(&fields), ["1", "3", "4", "1", "3", "4"]);
+    }
+
+    fn env_with_home_dir(user: &str, home: &str) -> yash_env::Env {
+        let system = yash_env::VirtualSystem::new();
+        system
+            .state
+            .borrow_mut()
+            .home_dirs
+            .insert(user.to_string(), home.into());
+        yash_env::Env::with_system(Box::new(system))
+    }
+
+    fn word_with_tilde(s: &str) -> Word {
+        let mut word: Word = s.parse().unwrap();
+        word.parse_tilde_front();
+        word
+    }
+
+    #[test]
+    fn expand_words_performs_named_tilde_expansion() {
+        let mut env = env_with_home_dir("root", "/root");
+        let words = [word_with_tilde("~root/x"), word_with_tilde("~root")];
+        let (fields, _) = expand_words(&mut env, &words)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_matches!(fields.as_slice(), [f1, f2] => {
+            assert_eq!(f1.value, "/root/x");
+            assert_eq!(f2.value, "/root");
+        });
+    }
+
+    #[test]
+    fn expand_words_keeps_tilde_with_quoted_name() {
+        let mut env = env_with_home_dir("root", "/root");
+        let words = [
+            word_with_tilde(r#"~"root"/x"#),
+            word_with_tilde(r"~\root/x"),
+        ];
+        let (fields, _) = expand_words(&mut env, &words)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_matches!(fields.as_slice(), [f1, f2] => {
+            assert_eq!(f1.value, "~root/x");
+            assert_eq!(f2.value, "~root/x");
+        });
+    }
+
+    #[test]
+    fn expand_words_keeps_tilde_with_unknown_user() {
+        let mut env = env_with_home_dir("root", "/root");
+        let words = [word_with_tilde("~nobody/x")];
+        let (fields, _) = expand_words(&mut env, &words)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_matches!(fields.as_slice(), [f] => {
+            assert_eq!(f.value, "~nobody/x");
+        });
+    }
+
+    #[test]
+    fn origins_of_fields_resulting_from_field_splitting() {
+        let mut env = yash_env::Env::new_virtual();
+        env.variables
+            .get_or_new("v", Scope::Global)
+            .assign("foo bar  baz", None)
+            .unwrap();
+        let word: Word = "x$v".parse().unwrap();
+        let mut fields = Vec::new();
+        expand_word_multiple(&mut env, &word, &mut fields)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_matches!(fields.as_slice(), [f1, f2, f3] => {
+            assert_eq!(f1.value, "xfoo");
+            assert_eq!(f2.value, "bar");
+            assert_eq!(f3.value, "baz");
+
+            let code = &f1.origin.code;
+            assert_eq!(*code.value.borrow(), "xfoo bar  baz");
+            assert_eq!(
+                *code.source,
+                Source::Expansion { original: word.location.clone() }
+            );
+            assert_eq!(f1.origin.range, 0..4);
+            assert_eq!(f2.origin.code, *code);
+            assert_eq!(f2.origin.range, 5..8);
+            assert_eq!(f3.origin.code, *code);
+            assert_eq!(f3.origin.range, 10..13);
+        });
+    }
+
+    #[test]
+    fn origins_of_pathnames_resulting_from_split_fields() {
+        let system = yash_env::VirtualSystem::new();
+        let mut state = system.state.borrow_mut();
+        state.file_system.save("/a1", Default::default()).unwrap();
+        state.file_system.save("/a2", Default::default()).unwrap();
+        drop(state);
+        let mut env = yash_env::Env::with_system(Box::new(system));
+        env.variables
+            .get_or_new("v", Scope::Global)
+            .assign("/b /a*", None)
+            .unwrap();
+        let word: Word = "$v".parse().unwrap();
+        let mut fields = Vec::new();
+        expand_word_multiple(&mut env, &word, &mut fields)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_matches!(fields.as_slice(), [f1, f2, f3] => {
+            assert_eq!(f1.value, "/b");
+            assert_eq!(f1.origin.range, 0..2);
+            assert_eq!(f2.value, "/a1");
+            assert_eq!(f2.origin.range, 3..6);
+            assert_eq!(f3.value, "/a2");
+            assert_eq!(f3.origin.range, 3..6);
+            assert_eq!(*f3.origin.code.value.borrow(), "/b /a*");
+        });
+    }
+
+    #[test]
+    fn expand_words_does_not_glob_home_directory() {
+        let system = yash_env::VirtualSystem::new();
+        let mut state = system.state.borrow_mut();
+        state.home_dirs.insert("root".to_string(), "/*".into());
+        state.file_system.save("/foo", Default::default()).unwrap();
+        drop(state);
+        let mut env = yash_env::Env::with_system(Box::new(system));
+        let words = [word_with_tilde("~root")];
+        let (fields, _) = expand_words(&mut env, &words)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_matches!(fields.as_slice(), [f] => {
+            assert_eq!(f.value, "/*");
+        });
+    }
+
+    #[test]
+    fn expand_words_fails_on_unmatched_pattern_with_failglob() {
+        let mut env = yash_env::Env::new_virtual();
+        env.options.set(yash_env::option::Option::FailGlob, On);
+        let words = ["a".parse().unwrap(), "/no_such_dir/*".parse().unwrap()];
+        let e = expand_words(&mut env, &words)
+            .now_or_never()
+            .unwrap()
+            .unwrap_err();
+        assert_matches!(&e.cause, ErrorCause::UnmatchedPattern { pattern } => {
+            assert_eq!(pattern, "/no_such_dir/*");
+        });
+        assert_eq!(e.location.range, 0..14);
+    }
+
     #[test]
     fn expand_value_scalar() {
         let mut env = yash_env::Env::new_virtual();
@@ -71,6 +71,9 @@ pub struct AttrField {
     /// Value of the field.
     pub chars: Vec<AttrChar>,
     /// Location of the word this field resulted from.
+    ///
+    /// See [`split_into`](super::split::split_into) for the location of a
+    /// field that resulted from field splitting.
     pub origin: Location,
 }
 
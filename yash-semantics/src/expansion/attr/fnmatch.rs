@@ -17,6 +17,7 @@
 //! Utility for parsing `AttrChar` strings as a fnmatch pattern
 
 use super::AttrChar;
+use super::Origin;
 use yash_fnmatch::PatternChar;
 
 /// Converts unquoted backslashes to quoting characters.
@@ -33,15 +34,99 @@ pub fn apply_escapes(chars: &mut [AttrChar]) {
     }
 }
 
+/// Converts an `AttrChar` to a `PatternChar`.
+///
+/// Returns `None` for a quoting character, which does not take part in the
+/// pattern. A quoted character and a character resulting from a
+/// [hard expansion](super::Origin::HardExpansion) become a
+/// [literal](PatternChar::Literal) so that it does not act as a glob
+/// metacharacter. Other characters are [normal](PatternChar::Normal).
+pub fn to_pattern_char(c: &AttrChar) -> Option<PatternChar> {
+    if c.is_quoting {
+        None
+    } else if c.is_quoted || c.origin == Origin::HardExpansion {
+        Some(PatternChar::Literal(c.value))
+    } else {
+        Some(PatternChar::Normal(c.value))
+    }
+}
+
 /// Returns an iterator of `PatternChar`s from an `AttrChar` slice.
+///
+/// Each character is converted by [`to_pattern_char`]. Unquoted backslashes
+/// are not treated specially; use [`apply_escapes`] beforehand to make them
+/// escape the following characters.
 pub fn to_pattern_chars(chars: &[AttrChar]) -> impl Iterator<Item = PatternChar> + Clone + '_ {
-    chars.iter().filter_map(|c| {
-        if c.is_quoting {
-            None
-        } else if c.is_quoted {
-            Some(PatternChar::Literal(c.value))
-        } else {
-            Some(PatternChar::Normal(c.value))
+    chars.iter().filter_map(to_pattern_char)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attr_char(value: char, origin: Origin, is_quoted: bool, is_quoting: bool) -> AttrChar {
+        AttrChar {
+            value,
+            origin,
+            is_quoted,
+            is_quoting,
         }
-    })
+    }
+
+    #[test]
+    fn unquoted_asterisk() {
+        let chars = [attr_char('*', Origin::Literal, false, false)];
+        let result = to_pattern_chars(&chars).collect::<Vec<_>>();
+        assert_eq!(result, [PatternChar::Normal('*')]);
+
+        let chars = [attr_char('*', Origin::SoftExpansion, false, false)];
+        let result = to_pattern_chars(&chars).collect::<Vec<_>>();
+        assert_eq!(result, [PatternChar::Normal('*')]);
+    }
+
+    #[test]
+    fn quoted_asterisk() {
+        let chars = [
+            attr_char('"', Origin::Literal, false, true),
+            attr_char('*', Origin::SoftExpansion, true, false),
+            attr_char('"', Origin::Literal, false, true),
+        ];
+        let result = to_pattern_chars(&chars).collect::<Vec<_>>();
+        assert_eq!(result, [PatternChar::Literal('*')]);
+    }
+
+    #[test]
+    fn hard_expansion_bracket() {
+        let chars = [
+            attr_char('[', Origin::HardExpansion, false, false),
+            attr_char('a', Origin::HardExpansion, false, false),
+            attr_char(']', Origin::HardExpansion, false, false),
+            attr_char('*', Origin::Literal, false, false),
+        ];
+        let result = to_pattern_chars(&chars).collect::<Vec<_>>();
+        assert_eq!(
+            result,
+            [
+                PatternChar::Literal('['),
+                PatternChar::Literal('a'),
+                PatternChar::Literal(']'),
+                PatternChar::Normal('*'),
+            ]
+        );
+    }
+
+    #[test]
+    fn escapes_applied() {
+        let mut chars = [
+            attr_char('\\', Origin::SoftExpansion, false, false),
+            attr_char('?', Origin::SoftExpansion, false, false),
+            attr_char('?', Origin::SoftExpansion, false, false),
+        ];
+        apply_escapes(&mut chars);
+        let result = to_pattern_chars(&chars).collect::<Vec<_>>();
+        assert_eq!(
+            result,
+            [PatternChar::Literal('?'), PatternChar::Normal('?')]
+        );
+    }
 }
@@ -0,0 +1,554 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2026 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Brace expansion
+//!
+//! The brace expansion produces copies of a field containing a pair of braces
+//! that enclose comma-separated alternatives. Each copy has one of the
+//! alternatives in place of the braces. For example, `a{b,c}d` expands to
+//! `abd` and `acd`. Braces may be nested, as in `a{b,c{d,e}}f`, which expands
+//! to `abf`, `acdf`, and `acef`. An alternative may be empty, so `a{,b}`
+//! expands to `a` and `ab`.
+//!
+//! Braces may also enclose a sequence expression of the form `start..end` or
+//! `start..end..step`, where `start` and `end` are both integers or both
+//! single ASCII letters. The expression expands to the values from `start` to
+//! `end`, which may be in descending order, incremented or decremented by
+//! `step` (1 by default). For example, `{1..10..3}` expands to `1`, `4`, `7`,
+//! and `10`, and `{c..a}` to `c`, `b`, and `a`. If either integer has a
+//! leading zero, as in `{01..10}`, the values are padded with zeros to the
+//! width of the longer of the two. Characters resulting from a sequence
+//! expression have the `HardExpansion` origin, so they are subject to neither
+//! field splitting nor pathname expansion.
+//!
+//! Only unquoted braces, commas, and sequence expressions having a `Literal`
+//! [origin](Origin) are recognized. A brace that has no matching brace or
+//! encloses neither a comma nor a valid sequence expression is left intact.
+//!
+//! The brace expansion is a non-POSIX extension that is performed only if the
+//! [`BraceExpand`](yash_env::option::BraceExpand) option is on.
+//!
+//! # Example
+//!
+//! ```
+//! use yash_syntax::source::Location;
+//! use yash_semantics::expansion::attr::{AttrChar, AttrField, Origin};
+//! use yash_semantics::expansion::brace::expand;
+//!
+//! let chars = "a{b,c{d,e}}f"
+//!     .chars()
+//!     .map(|c| AttrChar {
+//!         value: c,
+//!         origin: Origin::Literal,
+//!         is_quoted: false,
+//!         is_quoting: false,
+//!     })
+//!     .collect();
+//! let origin = Location::dummy("");
+//! let fields: Vec<AttrField> = expand(AttrField { chars, origin });
+//! let values: Vec<String> = fields
+//!     .iter()
+//!     .map(|field| field.chars.iter().map(|c| c.value).collect())
+//!     .collect();
+//! assert_eq!(values, ["abf", "acdf", "acef"]);
+//! ```
+
+use super::attr::AttrChar;
+use super::attr::AttrField;
+use super::attr::Origin;
+
+/// Tests whether the character is an unquoted literal `value`.
+fn is_special(c: &AttrChar, value: char) -> bool {
+    c.value == value && c.origin == Origin::Literal && !c.is_quoted && !c.is_quoting
+}
+
+/// Content of a pair of braces subject to expansion
+#[derive(Debug)]
+enum Content {
+    /// Comma-separated alternatives, represented by the positions of the
+    /// commas
+    Alternatives(Vec<usize>),
+    /// Sequence of values resulting from a range like `1..10`
+    Sequence(Vec<String>),
+}
+
+/// Pair of braces subject to expansion
+#[derive(Debug)]
+struct Braces {
+    open: usize,
+    close: usize,
+    content: Content,
+}
+
+/// Tests whether the string has a redundant leading zero, as in `01` or `-01`.
+fn has_leading_zero(s: &str) -> bool {
+    let digits = s.strip_prefix(['-', '+']).unwrap_or(s);
+    digits.len() > 1 && digits.starts_with('0')
+}
+
+/// Computes the values from `start` to `end` (inclusive) by `step`.
+///
+/// The sequence is descending if `start` is greater than `end`. The `step` must
+/// be positive.
+fn range(start: i64, end: i64, step: i64) -> Vec<i64> {
+    let mut values = Vec::new();
+    let mut value = start;
+    loop {
+        values.push(value);
+        let next = if start <= end {
+            value.checked_add(step).filter(|&next| next <= end)
+        } else {
+            value.checked_sub(step).filter(|&next| next >= end)
+        };
+        match next {
+            Some(next) => value = next,
+            None => break values,
+        }
+    }
+}
+
+/// Parses the content of braces as a sequence expression.
+///
+/// The content must be of the form `start..end` or `start..end..step` where
+/// `start` and `end` are both integers or both single ASCII letters and `step`
+/// is a non-zero integer. The function returns the resulting values or `None`
+/// if the content is not a valid sequence expression.
+fn parse_sequence(content: &str) -> Option<Vec<String>> {
+    let mut operands = content.split("..");
+    let start = operands.next()?;
+    let end = operands.next()?;
+    let step = match operands.next() {
+        None => 1,
+        Some(step) => step.parse::<i64>().ok()?.checked_abs()?,
+    };
+    if operands.next().is_some() || step == 0 {
+        return None;
+    }
+
+    if let (Ok(start_value), Ok(end_value)) = (start.parse::<i64>(), end.parse::<i64>()) {
+        let width = if has_leading_zero(start) || has_leading_zero(end) {
+            start.len().max(end.len())
+        } else {
+            0
+        };
+        let values = range(start_value, end_value, step);
+        return Some(
+            values
+                .into_iter()
+                .map(|value| format!("{value:0width$}"))
+                .collect(),
+        );
+    }
+
+    let single_letter = |s: &str| match s.as_bytes() {
+        &[c] if c.is_ascii_alphabetic() => Some(c),
+        _ => None,
+    };
+    let start = single_letter(start)?;
+    let end = single_letter(end)?;
+    let values = range(start.into(), end.into(), step);
+    Some(
+        values
+            .into_iter()
+            .map(|value| char::from(value as u8).to_string())
+            .collect(),
+    )
+}
+
+/// Tries to interpret the characters between braces as a sequence expression.
+fn sequence(chars: &[AttrChar]) -> Option<Vec<String>> {
+    if !chars
+        .iter()
+        .all(|c| c.origin == Origin::Literal && !c.is_quoted && !c.is_quoting)
+    {
+        return None;
+    }
+    parse_sequence(&chars.iter().map(|c| c.value).collect::<String>())
+}
+
+/// Finds the first pair of braces that is subject to expansion.
+fn find_braces(chars: &[AttrChar]) -> Option<Braces> {
+    let opens = chars.iter().enumerate().filter(|(_, c)| is_special(c, '{'));
+    for (open, _) in opens {
+        let mut depth = 0_usize;
+        let mut commas = Vec::new();
+        for (index, c) in chars.iter().enumerate().skip(open + 1) {
+            if is_special(c, '{') {
+                depth += 1;
+            } else if is_special(c, '}') {
+                if depth == 0 {
+                    let close = index;
+                    let content = if !commas.is_empty() {
+                        Content::Alternatives(commas)
+                    } else if let Some(values) = sequence(&chars[open + 1..close]) {
+                        Content::Sequence(values)
+                    } else {
+                        break;
+                    };
+                    return Some(Braces {
+                        open,
+                        close,
+                        content,
+                    });
+                }
+                depth -= 1;
+            } else if depth == 0 && is_special(c, ',') {
+                commas.push(index);
+            }
+        }
+    }
+    None
+}
+
+/// Performs brace expansion and appends the result to a collection.
+///
+/// This function applies brace expansion to the given field and extends the
+/// given collection with the results. If the field contains no braces subject
+/// to expansion, the field is added to the collection intact. All the results
+/// have the same origin as the input field.
+///
+/// See also [`expand`], which returns the results in a new collection rather
+/// than extending an existing one.
+pub fn expand_into<R>(field: AttrField, results: &mut R)
+where
+    R: Extend<AttrField>,
+{
+    let Some(braces) = find_braces(&field.chars) else {
+        // TODO Use Extend::extend_one when stabilized (rust#72631)
+        results.extend(std::iter::once(field));
+        return;
+    };
+
+    let prefix = &field.chars[..braces.open];
+    let suffix = &field.chars[braces.close + 1..];
+    let mut expand_with = |infix: &[AttrChar]| {
+        let chars = [prefix, infix, suffix].concat();
+        let origin = field.origin.clone();
+        expand_into(AttrField { chars, origin }, results);
+    };
+
+    match braces.content {
+        Content::Alternatives(commas) => {
+            let starts = std::iter::once(braces.open).chain(commas.iter().copied());
+            let ends = commas.iter().copied().chain(std::iter::once(braces.close));
+            for (start, end) in starts.zip(ends) {
+                expand_with(&field.chars[start + 1..end]);
+            }
+        }
+        Content::Sequence(values) => {
+            for value in values {
+                let infix = value
+                    .chars()
+                    .map(|c| AttrChar {
+                        value: c,
+                        origin: Origin::HardExpansion,
+                        is_quoted: false,
+                        is_quoting: false,
+                    })
+                    .collect::<Vec<_>>();
+                expand_with(&infix);
+            }
+        }
+    }
+}
+
+/// Performs brace expansion and returns the result in a new collection.
+///
+/// This function works similarly to [`expand_into`], but returns the results
+/// in a new collection.
+pub fn expand<R>(field: AttrField) -> R
+where
+    R: Default + Extend<AttrField>,
+{
+    let mut results = R::default();
+    expand_into(field, &mut results);
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use yash_syntax::source::Location;
+
+    fn dummy_attr_field(s: &str) -> AttrField {
+        let chars = s
+            .chars()
+            .map(|c| AttrChar {
+                value: c,
+                origin: Origin::Literal,
+                is_quoted: false,
+                is_quoting: false,
+            })
+            .collect();
+        let origin = Location::dummy("");
+        AttrField { chars, origin }
+    }
+
+    fn values(fields: &[AttrField]) -> Vec<String> {
+        fields
+            .iter()
+            .map(|field| field.chars.iter().map(|c| c.value).collect())
+            .collect()
+    }
+
+    fn expand_str(s: &str) -> Vec<String> {
+        let fields: Vec<AttrField> = expand(dummy_attr_field(s));
+        values(&fields)
+    }
+
+    #[test]
+    fn no_braces() {
+        assert_eq!(expand_str(""), [""]);
+        assert_eq!(expand_str("abc"), ["abc"]);
+    }
+
+    #[test]
+    fn simple_alternatives() {
+        assert_eq!(expand_str("{a,b,c}"), ["a", "b", "c"]);
+        assert_eq!(expand_str("x{a,b}y"), ["xay", "xby"]);
+    }
+
+    #[test]
+    fn many_braces() {
+        assert_eq!(expand_str("{a,b}{1,2}"), ["a1", "a2", "b1", "b2"]);
+    }
+
+    #[test]
+    fn nested_braces() {
+        assert_eq!(expand_str("a{b,c{d,e}}f"), ["abf", "acdf", "acef"]);
+    }
+
+    #[test]
+    fn empty_alternatives() {
+        assert_eq!(expand_str("a{,x}"), ["a", "ax"]);
+        assert_eq!(expand_str("{,}"), ["", ""]);
+    }
+
+    #[test]
+    fn unmatched_braces_are_intact() {
+        assert_eq!(expand_str("{a,b"), ["{a,b"]);
+        assert_eq!(expand_str("a,b}"), ["a,b}"]);
+        assert_eq!(expand_str("{a{b,c}"), ["{ab", "{ac"]);
+        assert_eq!(expand_str("{a,b}}"), ["a}", "b}"]);
+    }
+
+    #[test]
+    fn braces_without_commas_are_intact() {
+        assert_eq!(expand_str("{}"), ["{}"]);
+        assert_eq!(expand_str("{a}"), ["{a}"]);
+        assert_eq!(expand_str("{a}{b,c}"), ["{a}b", "{a}c"]);
+        assert_eq!(expand_str("{a{b,c}d}"), ["{abd}", "{acd}"]);
+    }
+
+    #[test]
+    fn numeric_sequences() {
+        assert_eq!(expand_str("{1..5}"), ["1", "2", "3", "4", "5"]);
+        assert_eq!(expand_str("{-2..1}"), ["-2", "-1", "0", "1"]);
+        assert_eq!(expand_str("{3..3}"), ["3"]);
+        assert_eq!(expand_str("a{1..3}b"), ["a1b", "a2b", "a3b"]);
+    }
+
+    #[test]
+    fn numeric_sequences_with_step() {
+        assert_eq!(expand_str("{1..10..3}"), ["1", "4", "7", "10"]);
+        assert_eq!(expand_str("{1..10..4}"), ["1", "5", "9"]);
+        assert_eq!(expand_str("{1..3..-1}"), ["1", "2", "3"]);
+    }
+
+    #[test]
+    fn descending_sequences() {
+        assert_eq!(expand_str("{3..1}"), ["3", "2", "1"]);
+        assert_eq!(expand_str("{10..1..3}"), ["10", "7", "4", "1"]);
+        assert_eq!(expand_str("{c..a}"), ["c", "b", "a"]);
+    }
+
+    #[test]
+    fn zero_padded_sequences() {
+        assert_eq!(expand_str("{01..03}"), ["01", "02", "03"]);
+        assert_eq!(expand_str("{8..010}"), ["008", "009", "010"]);
+        assert_eq!(expand_str("{-01..1}"), ["-01", "000", "001"]);
+    }
+
+    #[test]
+    fn character_sequences() {
+        assert_eq!(expand_str("{a..e}"), ["a", "b", "c", "d", "e"]);
+        assert_eq!(expand_str("{a..e..2}"), ["a", "c", "e"]);
+        assert_eq!(expand_str("{X..Z}"), ["X", "Y", "Z"]);
+    }
+
+    #[test]
+    fn invalid_sequences_are_intact() {
+        assert_eq!(expand_str("{1..}"), ["{1..}"]);
+        assert_eq!(expand_str("{..1}"), ["{..1}"]);
+        assert_eq!(expand_str("{1...3}"), ["{1...3}"]);
+        assert_eq!(expand_str("{1..a}"), ["{1..a}"]);
+        assert_eq!(expand_str("{ab..c}"), ["{ab..c}"]);
+        assert_eq!(expand_str("{1..3..0}"), ["{1..3..0}"]);
+        assert_eq!(expand_str("{1..3..x}"), ["{1..3..x}"]);
+        assert_eq!(expand_str("{1..2..3..4}"), ["{1..2..3..4}"]);
+    }
+
+    #[test]
+    fn sequences_with_alternatives() {
+        assert_eq!(expand_str("{a,{1..2}}"), ["a", "1", "2"]);
+        assert_eq!(expand_str("{1..2}{a,b}"), ["1a", "1b", "2a", "2b"]);
+    }
+
+    #[test]
+    fn characters_resulting_from_sequences_are_hard_expansions() {
+        let fields: Vec<AttrField> = expand(dummy_attr_field("x{8..10}"));
+        assert_eq!(values(&fields), ["x8", "x9", "x10"]);
+        let field = &fields[2];
+        assert_eq!(field.chars[0].origin, Origin::Literal);
+        assert_eq!(field.chars[1].origin, Origin::HardExpansion);
+        assert_eq!(field.chars[2].origin, Origin::HardExpansion);
+        assert!(field.chars.iter().all(|c| !c.is_quoted && !c.is_quoting));
+    }
+
+    #[test]
+    fn quoted_sequences_are_intact() {
+        let mut field = dummy_attr_field("{1..3}");
+        field.chars[2].is_quoted = true;
+        let fields: Vec<AttrField> = expand(field);
+        assert_eq!(values(&fields), ["{1..3}"]);
+    }
+
+    #[test]
+    fn quoted_characters_are_not_special() {
+        for index in [0, 2, 4] {
+            let mut field = dummy_attr_field("{a,b}");
+            field.chars[index].is_quoted = true;
+            let fields: Vec<AttrField> = expand(field);
+            assert_eq!(values(&fields), ["{a,b}"], "index={index}");
+        }
+    }
+
+    #[test]
+    fn expanded_characters_are_not_special() {
+        for origin in [Origin::SoftExpansion, Origin::HardExpansion] {
+            let mut field = dummy_attr_field("{a,b}");
+            field.chars[0].origin = origin;
+            let fields: Vec<AttrField> = expand(field);
+            assert_eq!(values(&fields), ["{a,b}"], "origin={origin:?}");
+        }
+    }
+
+    #[test]
+    fn origins_are_kept() {
+        let mut field = dummy_attr_field("{a,b}");
+        field.origin = Location::dummy("{a,b}");
+        let fields: Vec<AttrField> = expand(field);
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0].origin, Location::dummy("{a,b}"));
+        assert_eq!(fields[1].origin, Location::dummy("{a,b}"));
+    }
+
+    /// Simplified model of a word containing braces
+    #[derive(Clone, Debug)]
+    enum Part {
+        Literal(String),
+        Braces(Vec<Vec<Part>>),
+    }
+
+    fn render(parts: &[Part]) -> String {
+        parts
+            .iter()
+            .map(|part| match part {
+                Part::Literal(s) => s.clone(),
+                Part::Braces(alternatives) => {
+                    let alternatives = alternatives.iter().map(|a| render(a));
+                    format!("{{{}}}", alternatives.collect::<Vec<_>>().join(","))
+                }
+            })
+            .collect()
+    }
+
+    /// Computes the expected results of brace expansion from the model.
+    fn model_expand(parts: &[Part]) -> Vec<String> {
+        parts.iter().fold(vec![String::new()], |results, part| {
+            let expansions = match part {
+                Part::Literal(s) => vec![s.clone()],
+                Part::Braces(alternatives) => {
+                    alternatives.iter().flat_map(|a| model_expand(a)).collect()
+                }
+            };
+            results
+                .iter()
+                .flat_map(|r| expansions.iter().map(move |e| format!("{r}{e}")))
+                .collect()
+        })
+    }
+
+    fn parts() -> impl Strategy<Value = Vec<Part>> {
+        let literal = "[a-c]{0,2}".prop_map(Part::Literal);
+        let part = literal.prop_recursive(4, 32, 3, |inner| {
+            prop_oneof![
+                "[a-c]{0,2}".prop_map(Part::Literal),
+                prop::collection::vec(prop::collection::vec(inner, 0..3), 2..4)
+                    .prop_map(Part::Braces),
+            ]
+        });
+        prop::collection::vec(part, 0..4)
+    }
+
+    fn nested(depth: usize) -> String {
+        (0..depth).fold("z".to_owned(), |inner, _| format!("{{a,{inner}}}"))
+    }
+
+    proptest! {
+        #[test]
+        fn results_agree_with_model(parts in parts()) {
+            let word = render(&parts);
+            prop_assert_eq!(expand_str(&word), model_expand(&parts), "word={}", word);
+        }
+
+        #[test]
+        fn numeric_sequences_agree_with_ranges(
+            start in -50_i64..50,
+            end in -50_i64..50,
+            step in 1_i64..10,
+        ) {
+            let expected: Vec<String> = if start <= end {
+                (start..=end).step_by(step as usize).map(|n| n.to_string()).collect()
+            } else {
+                (end..=start).rev().step_by(step as usize).map(|n| n.to_string()).collect()
+            };
+            let word = format!("{{{start}..{end}..{step}}}");
+            prop_assert_eq!(expand_str(&word), expected);
+        }
+
+        #[test]
+        fn nesting_depth_determines_result_count(depth in 0_usize..20) {
+            let results = expand_str(&nested(depth));
+            prop_assert_eq!(results.len(), depth + 1);
+            prop_assert_eq!(results.last().unwrap(), "z");
+        }
+
+        #[test]
+        fn empty_alternatives_yield_bare_affixes(
+            prefix in "[a-c]{0,3}",
+            suffix in "[a-c]{0,3}",
+            empty_count in 1_usize..5,
+        ) {
+            let alternatives = vec![""; empty_count].join(",");
+            let word = format!("{prefix}{{{alternatives},x}}{suffix}");
+            let mut expected = vec![format!("{prefix}{suffix}"); empty_count];
+            expected.push(format!("{prefix}x{suffix}"));
+            prop_assert_eq!(expand_str(&word), expected);
+        }
+    }
+}
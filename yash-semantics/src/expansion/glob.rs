@@ -43,31 +43,42 @@
 //! # Results
 //!
 //! Pathname expansion returns pathnames that have matched the input pattern,
-//! sorted alphabetically. Any errors are silently ignored. If directory
-//! scanning produces no pathnames, the input pattern is returned intact. (TODO:
-//! the null-glob option)
+//! sorted alphabetically. The sort compares whole pathnames, so pathnames in
+//! different directories may be interleaved, and a pathname appears only once
+//! in the results. Any errors are silently ignored. If directory
+//! scanning produces no pathnames, the result depends on the shell options:
+//!
+//! - If the `FailGlob` option is on, the expansion fails with
+//!   [`ErrorCause::UnmatchedPattern`].
+//! - Otherwise, if the `NullGlob` option is on, the result is empty.
+//! - Otherwise, the input pattern is returned intact.
 //!
 //! If the input field contains no non-literal elements subject to pattern
 //! matching at all, the result is the input intact.
 
+use super::attr::fnmatch::to_pattern_char;
 use super::attr::AttrChar;
 use super::attr::AttrField;
 use super::attr::Origin;
+use super::pattern_options::PatternOptions;
+use super::Error;
+use super::ErrorCause;
 use std::ffi::CString;
 use std::iter::Once;
 use std::marker::PhantomData;
-use yash_env::option::State::Off;
+use yash_env::option::Option::{FailGlob, NullGlob};
+use yash_env::option::State::{Off, On};
 use yash_env::semantics::Field;
 use yash_env::system::AT_FDCWD;
 use yash_env::Env;
 use yash_env::System;
-use yash_fnmatch::Config;
 use yash_fnmatch::Pattern;
 use yash_fnmatch::PatternChar;
 use yash_syntax::source::Location;
 
 #[derive(Debug)]
 enum Inner {
+    Empty,
     One(Once<Field>),
     Many(std::vec::IntoIter<Field>),
 }
@@ -108,6 +119,7 @@ impl Iterator for Glob<'_> {
     type Item = Field;
     fn next(&mut self) -> Option<Field> {
         match &mut self.inner {
+            Inner::Empty => None,
             Inner::One(once) => once.next(),
             Inner::Many(many) => many.next(),
         }
@@ -115,7 +127,7 @@ impl Iterator for Glob<'_> {
 }
 
 /// Converts a field to a glob pattern.
-fn to_pattern(field: &[AttrChar]) -> Option<Pattern> {
+fn to_pattern(env: &mut Env, field: &[AttrChar]) -> Option<Pattern> {
     #[derive(Clone, Debug)]
     struct Chars<'a> {
         inner: std::slice::Iter<'a, AttrChar>,
@@ -126,13 +138,15 @@ fn to_pattern(field: &[AttrChar]) -> Option<Pattern> {
         fn next(&mut self) -> Option<PatternChar> {
             for c in &mut self.inner {
                 let quoted = std::mem::replace(&mut self.next_quoted, false);
-                if c.is_quoting {
-                    continue;
-                } else if quoted || c.is_quoted || c.origin == Origin::HardExpansion {
-                    return Some(PatternChar::Literal(c.value));
-                } else {
-                    self.next_quoted = c.value == '\\';
-                    return Some(PatternChar::Normal(c.value));
+                match to_pattern_char(c) {
+                    None => continue,
+                    Some(PatternChar::Normal(value)) if quoted => {
+                        return Some(PatternChar::Literal(value))
+                    }
+                    Some(pc) => {
+                        self.next_quoted = pc == PatternChar::Normal('\\');
+                        return Some(pc);
+                    }
                 }
             }
             None
@@ -143,11 +157,19 @@ fn to_pattern(field: &[AttrChar]) -> Option<Pattern> {
         inner: field.iter(),
         next_quoted: false,
     };
-    let mut config = Config::default();
-    config.anchor_begin = true;
-    config.anchor_end = true;
-    config.literal_period = true;
-    Pattern::parse_with_config(chars, config).ok()
+    let config = PatternOptions::from_options(&env.options).glob_config();
+    super::pattern_cache::parse(env, chars, config)
+}
+
+/// Tests whether the field contains any unquoted character that may make the
+/// field a non-literal pattern.
+fn has_pattern_chars(field: &[AttrChar]) -> bool {
+    field.iter().any(|c| {
+        matches!(c.value, '*' | '?' | '[')
+            && !c.is_quoted
+            && !c.is_quoting
+            && c.origin != Origin::HardExpansion
+    })
 }
 
 fn remove_quotes_and_strip(chars: &[AttrChar]) -> impl Iterator<Item = char> + '_ {
@@ -172,7 +194,7 @@ impl SearchEnv<'_> {
             Some(index) => (&suffix[..index], Some(&suffix[index + 1..])),
         };
 
-        match to_pattern(this).map(Pattern::into_literal) {
+        match to_pattern(self.env, this).map(Pattern::into_literal) {
             None => {
                 self.push_component(new_suffix, false, |prefix| {
                     prefix.extend(remove_quotes_and_strip(this))
@@ -255,12 +277,21 @@ impl SearchEnv<'_> {
 /// expansion.
 ///
 /// If the `Glob` option is `Off` in `env.options`, the expansion is skipped.
-pub fn glob(env: &mut Env, field: AttrField) -> Glob {
+///
+/// If the pattern matches no pathnames and the `FailGlob` option is `On`, this
+/// function returns an error. See the [module documentation](self) for other
+/// cases.
+pub fn glob(env: &mut Env, field: AttrField) -> Result<Glob, Error> {
     if env.options.get(yash_env::option::Option::Glob) == Off {
-        return Glob::from(Inner::from(field.remove_quotes_and_strip()));
+        return Ok(Glob::from(Inner::from(field.remove_quotes_and_strip())));
     }
 
-    // TODO Quick check for *, ?, [ containment
+    // Without special characters, the field cannot match anything but itself.
+    // This check also prevents the case-insensitive pattern from replacing the
+    // field with the name of an existing file that differs only in case.
+    if !has_pattern_chars(&field.chars) {
+        return Ok(Glob::from(Inner::from(field.remove_quotes_and_strip())));
+    }
 
     let mut search_env = SearchEnv {
         env,
@@ -271,16 +302,28 @@ pub fn glob(env: &mut Env, field: AttrField) -> Glob {
     search_env.search_dir(&field.chars);
 
     let mut results = search_env.results;
-    Glob::from(if results.is_empty() {
+    if results.is_empty() {
         let field = AttrField {
             chars: field.chars,
             origin: search_env.origin,
         };
-        Inner::from(field.remove_quotes_and_strip())
-    } else {
-        results.sort_unstable_by(|a, b| a.value.cmp(&b.value));
-        Inner::Many(results.into_iter())
-    })
+        let field = field.remove_quotes_and_strip();
+        if env.options.get(FailGlob) == On {
+            let cause = ErrorCause::UnmatchedPattern {
+                pattern: field.value,
+            };
+            let location = field.origin;
+            return Err(Error { cause, location });
+        }
+        if env.options.get(NullGlob) == On {
+            return Ok(Glob::from(Inner::Empty));
+        }
+        return Ok(Glob::from(Inner::from(field)));
+    }
+
+    results.sort_unstable_by(|a, b| a.value.cmp(&b.value));
+    results.dedup_by(|a, b| a.value == b.value);
+    Ok(Glob::from(Inner::Many(results.into_iter())))
 }
 
 #[cfg(test)]
@@ -291,6 +334,7 @@ mod tests {
     use std::rc::Rc;
     use yash_env::path::Path;
     use yash_env::str::UnixStr;
+    use yash_env::system::r#virtual::FileBody;
     use yash_env::system::Mode;
     use yash_env::VirtualSystem;
     use yash_syntax::source::Location;
@@ -327,7 +371,7 @@ mod tests {
     fn literal_field() {
         let mut env = Env::new_virtual();
         let f = dummy_attr_field("abc");
-        let mut i = glob(&mut env, f);
+        let mut i = glob(&mut env, f).unwrap();
         assert_eq!(i.next().unwrap().value, "abc");
         assert_eq!(i.next(), None);
     }
@@ -337,7 +381,7 @@ mod tests {
         let mut env = env_with_dummy_files(["a", r"\a"]);
         // The backslash escapes the '?', so this is not a pattern.
         let f = dummy_attr_field(r"\?");
-        let mut i = glob(&mut env, f);
+        let mut i = glob(&mut env, f).unwrap();
         assert_eq!(i.next().unwrap().value, r"\?");
         assert_eq!(i.next(), None);
     }
@@ -348,7 +392,7 @@ mod tests {
         let mut f = dummy_attr_field("aXbcYde");
         f.chars[1].is_quoting = true;
         f.chars[4].is_quoting = true;
-        let mut i = glob(&mut env, f);
+        let mut i = glob(&mut env, f).unwrap();
         assert_eq!(i.next().unwrap().value, "abcde");
         assert_eq!(i.next(), None);
     }
@@ -358,7 +402,7 @@ mod tests {
         let mut env = env_with_dummy_files(["foo.exe"]);
         let mut f = dummy_attr_field("foo.*");
         f.chars[4].is_quoted = true;
-        let mut i = glob(&mut env, f);
+        let mut i = glob(&mut env, f).unwrap();
         assert_eq!(i.next().unwrap().value, "foo.*");
         assert_eq!(i.next(), None);
     }
@@ -368,7 +412,7 @@ mod tests {
         let mut env = env_with_dummy_files(["foo.exe"]);
         let mut f = dummy_attr_field("foo.*");
         f.chars[4].origin = Origin::HardExpansion;
-        let mut i = glob(&mut env, f);
+        let mut i = glob(&mut env, f).unwrap();
         assert_eq!(i.next().unwrap().value, "foo.*");
         assert_eq!(i.next(), None);
     }
@@ -377,7 +421,7 @@ mod tests {
     fn single_component_pattern_no_match() {
         let mut env = env_with_dummy_files(["foo.exe"]);
         let f = dummy_attr_field("*.txt");
-        let mut i = glob(&mut env, f);
+        let mut i = glob(&mut env, f).unwrap();
         assert_eq!(i.next().unwrap().value, "*.txt");
         assert_eq!(i.next(), None);
     }
@@ -386,7 +430,7 @@ mod tests {
     fn single_component_pattern_single_match() {
         let mut env = env_with_dummy_files(["foo.exe", "foo.txt"]);
         let f = dummy_attr_field("*.txt");
-        let mut i = glob(&mut env, f);
+        let mut i = glob(&mut env, f).unwrap();
         assert_eq!(i.next().unwrap().value, "foo.txt");
         assert_eq!(i.next(), None);
     }
@@ -395,7 +439,7 @@ mod tests {
     fn single_component_pattern_many_matches() {
         let mut env = env_with_dummy_files(["foo.exe", "foo.txt"]);
         let f = dummy_attr_field("foo.*");
-        let mut i = glob(&mut env, f);
+        let mut i = glob(&mut env, f).unwrap();
         assert_eq!(i.next().unwrap().value, "foo.exe");
         assert_eq!(i.next().unwrap().value, "foo.txt");
         assert_eq!(i.next(), None);
@@ -405,7 +449,7 @@ mod tests {
     fn absolute_path_single_component_pattern_many_matches() {
         let mut env = env_with_dummy_files(["/foo.exe", "/foo.txt"]);
         let f = dummy_attr_field("/foo.*");
-        let mut i = glob(&mut env, f);
+        let mut i = glob(&mut env, f).unwrap();
         assert_eq!(i.next().unwrap().value, "/foo.exe");
         assert_eq!(i.next().unwrap().value, "/foo.txt");
         assert_eq!(i.next(), None);
@@ -418,7 +462,7 @@ mod tests {
             "a/no/a/a", "b/a/a/a",
         ]);
         let f = dummy_attr_field("a/?/a/?");
-        let mut i = glob(&mut env, f);
+        let mut i = glob(&mut env, f).unwrap();
         assert_eq!(i.next().unwrap().value, "a/a/a/a");
         assert_eq!(i.next().unwrap().value, "a/a/a/b");
         assert_eq!(i.next().unwrap().value, "a/b/a/a");
@@ -426,6 +470,72 @@ mod tests {
         assert_eq!(i.next(), None);
     }
 
+    #[test]
+    fn multi_component_results_are_sorted_as_whole_pathnames() {
+        // Sorting the results per directory would yield "a/x1" before
+        // "a.b/x0", but '.' precedes '/' in the whole pathnames.
+        let mut env = env_with_dummy_files(["a/x1", "a/x2", "a.b/x0", "a.b/x3"]);
+        let f = dummy_attr_field("a*/x?");
+        let i = glob(&mut env, f).unwrap();
+        let values = i.map(|field| field.value).collect::<Vec<_>>();
+        assert_eq!(values, ["a.b/x0", "a.b/x3", "a/x1", "a/x2"]);
+    }
+
+    #[test]
+    fn relative_pattern_is_resolved_against_working_directory() {
+        let mut env = env_with_dummy_files([
+            "/work/src/main.rs",
+            "/work/src/lib.rs",
+            "/work/src/README",
+            "/src/other.rs",
+        ]);
+        env.system.chdir(c"/work").unwrap();
+        let f = dummy_attr_field("src/*.rs");
+        let i = glob(&mut env, f).unwrap();
+        let values = i.map(|field| field.value).collect::<Vec<_>>();
+        assert_eq!(values, ["src/lib.rs", "src/main.rs"]);
+    }
+
+    #[test]
+    fn duplicate_pathnames_are_removed() {
+        // A directory listing may yield the same name more than once, for
+        // example if the directory is modified while being read. Here, the
+        // directory contains an extra entry named ".", so the search reaches
+        // "a/." through two different entries.
+        let system = VirtualSystem::new();
+        let mut state = system.state.borrow_mut();
+        state.file_system.save("/a/x", Rc::default()).unwrap();
+        let dir = state.file_system.get("/a").unwrap();
+        let mut dir = dir.borrow_mut();
+        let FileBody::Directory { files } = &mut dir.body else {
+            panic!("not a directory: {:?}", dir.body);
+        };
+        files.insert(Rc::from(UnixStr::new(".")), Rc::default());
+        drop(dir);
+        drop(state);
+        let mut env = Env::with_system(Box::new(system));
+        let f = dummy_attr_field("a/.*");
+        let i = glob(&mut env, f).unwrap();
+        let values = i.map(|field| field.value).collect::<Vec<_>>();
+        assert_eq!(values, ["a/.", "a/.."]);
+    }
+
+    #[test]
+    fn absolute_pattern_scans_each_matching_directory() {
+        let mut env = env_with_dummy_files([
+            "/usr/bin/x",
+            "/usr/local/bin/y",
+            "/usr/local/lib/z",
+            "/usr/share/doc/w",
+            "/opt/tool/bin/v",
+        ]);
+        env.system.chdir(c"/usr/local").unwrap();
+        let f = dummy_attr_field("/usr/*/bin");
+        let i = glob(&mut env, f).unwrap();
+        let values = i.map(|field| field.value).collect::<Vec<_>>();
+        assert_eq!(values, ["/usr/local/bin"]);
+    }
+
     #[test]
     fn multi_component_pattern_ending_with_literal() {
         let mut env = env_with_dummy_files([
@@ -442,7 +552,7 @@ mod tests {
             "/no/a",
         ]);
         let f = dummy_attr_field("/?/a/?/a");
-        let mut i = glob(&mut env, f);
+        let mut i = glob(&mut env, f).unwrap();
         assert_eq!(i.next().unwrap().value, "/a/a/a/a");
         assert_eq!(i.next().unwrap().value, "/a/a/b/a");
         assert_eq!(i.next().unwrap().value, "/b/a/a/a");
@@ -453,17 +563,44 @@ mod tests {
     fn multi_component_pattern_ending_with_slash() {
         let mut env = env_with_dummy_files(["a/a/_", "a/b/_", "a/c"]);
         let f = dummy_attr_field("a/*/");
-        let mut i = glob(&mut env, f);
+        let mut i = glob(&mut env, f).unwrap();
         assert_eq!(i.next().unwrap().value, "a/a/");
         assert_eq!(i.next().unwrap().value, "a/b/");
         assert_eq!(i.next(), None);
     }
 
+    #[test]
+    fn trailing_slash_pattern_matches_only_directories() {
+        let mut env = env_with_dummy_files(["dir1/_", "dir2/_", "dir3", "file"]);
+        let f = dummy_attr_field("di*/");
+        let i = glob(&mut env, f).unwrap();
+        let values = i.map(|field| field.value).collect::<Vec<_>>();
+        assert_eq!(values, ["dir1/", "dir2/"]);
+    }
+
+    #[test]
+    fn two_pattern_components() {
+        let mut env = env_with_dummy_files(["x1/a.txt", "x1/b.rs", "x2/c.txt", "y1/d.txt", "x3"]);
+        let f = dummy_attr_field("x?/*.txt");
+        let i = glob(&mut env, f).unwrap();
+        let values = i.map(|field| field.value).collect::<Vec<_>>();
+        assert_eq!(values, ["x1/a.txt", "x2/c.txt"]);
+    }
+
+    #[test]
+    fn intermediate_matches_must_be_directories() {
+        let mut env = env_with_dummy_files(["a/b/c", "a/file", "a/d/c"]);
+        let f = dummy_attr_field("a/*/*");
+        let i = glob(&mut env, f).unwrap();
+        let values = i.map(|field| field.value).collect::<Vec<_>>();
+        assert_eq!(values, ["a/b/c", "a/d/c"]);
+    }
+
     #[test]
     fn multi_component_pattern_with_adjacent_slashes() {
         let mut env = env_with_dummy_files(["a/b", "b/a"]);
         let f = dummy_attr_field("?//?");
-        let mut i = glob(&mut env, f);
+        let mut i = glob(&mut env, f).unwrap();
         assert_eq!(i.next().unwrap().value, "a//b");
         assert_eq!(i.next().unwrap().value, "b//a");
         assert_eq!(i.next(), None);
@@ -483,25 +620,64 @@ mod tests {
         }
         let mut env = Env::with_system(Box::new(system));
         let f = dummy_attr_field("foo/*");
-        let mut i = glob(&mut env, f);
+        let mut i = glob(&mut env, f).unwrap();
         assert_eq!(i.next().unwrap().value, "foo/bar");
         assert_eq!(i.next(), None);
     }
 
+    #[test]
+    fn unreadable_directory_yields_no_matches() {
+        let system = VirtualSystem::new();
+        {
+            let mut state = system.state.borrow_mut();
+            state
+                .file_system
+                .save("foo/bar", Default::default())
+                .unwrap();
+            let dir = state.file_system.get("foo").unwrap();
+            dir.borrow_mut().permissions = Mode::USER_WRITE | Mode::USER_EXEC;
+        }
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        let f = dummy_attr_field("foo/*");
+        let mut i = glob(&mut env, f).unwrap();
+        assert_eq!(i.next().unwrap().value, "foo/*");
+        assert_eq!(i.next(), None);
+        yash_env_test_helper::assert_stderr(&state, |stderr| assert_eq!(stderr, ""));
+    }
+
     #[test]
     fn invalid_pattern_remains_intact() {
         let mut env = env_with_dummy_files(["foo.txt"]);
         let f = dummy_attr_field("*[[:wrong:]]*");
-        let mut i = glob(&mut env, f);
+        let mut i = glob(&mut env, f).unwrap();
         assert_eq!(i.next().unwrap().value, "*[[:wrong:]]*");
         assert_eq!(i.next(), None);
     }
 
+    #[test]
+    fn invalid_pattern_component_is_treated_as_literal_child() {
+        let mut env = env_with_dummy_files(["a/x[[:wrong:]]", "b/x", "c/x[[:wrong:]]"]);
+        let f = dummy_attr_field("?/x[[:wrong:]]");
+        let i = glob(&mut env, f).unwrap();
+        let values = i.map(|field| field.value).collect::<Vec<_>>();
+        assert_eq!(values, ["a/x[[:wrong:]]", "c/x[[:wrong:]]"]);
+    }
+
+    #[test]
+    fn missing_literal_child_of_matched_directories() {
+        let mut env = env_with_dummy_files(["a/x", "b/y"]);
+        let f = dummy_attr_field("?/z");
+        let mut i = glob(&mut env, f).unwrap();
+        assert_eq!(i.next().unwrap().value, "?/z");
+        assert_eq!(i.next(), None);
+    }
+
     #[test]
     fn slash_between_brackets() {
         let mut env = env_with_dummy_files(["abd", "a/d"]);
         let f = dummy_attr_field("a[b/c]d");
-        let mut i = glob(&mut env, f);
+        let mut i = glob(&mut env, f).unwrap();
         assert_eq!(i.next().unwrap().value, "a[b/c]d");
         assert_eq!(i.next(), None);
     }
@@ -510,7 +686,7 @@ mod tests {
     fn nul_byte_in_literal_followed_by_pattern() {
         let mut env = env_with_dummy_files(["x", "y/y"]);
         let f = dummy_attr_field("\0/*");
-        let mut i = glob(&mut env, f);
+        let mut i = glob(&mut env, f).unwrap();
         assert_eq!(i.next().unwrap().value, "\0/*");
         assert_eq!(i.next(), None);
     }
@@ -519,7 +695,7 @@ mod tests {
     fn broken_utf8_byte_in_directory_entry_name() {
         let mut env = env_with_dummy_files([UnixStr::from_bytes(b"foo/\xFF")]);
         let f = dummy_attr_field("foo/*");
-        let mut i = glob(&mut env, f);
+        let mut i = glob(&mut env, f).unwrap();
         assert_eq!(i.next().unwrap().value, "foo/*");
         assert_eq!(i.next(), None);
     }
@@ -529,8 +705,100 @@ mod tests {
         let mut env = env_with_dummy_files(["foo.exe"]);
         env.options.set(yash_env::option::Option::Glob, Off);
         let f = dummy_attr_field("foo.*");
-        let mut i = glob(&mut env, f);
+        let mut i = glob(&mut env, f).unwrap();
         assert_eq!(i.next().unwrap().value, "foo.*");
         assert_eq!(i.next(), None);
     }
+
+    #[test]
+    fn nullglob_option_removes_unmatched_pattern() {
+        let mut env = env_with_dummy_files(["foo.exe"]);
+        env.options.set(NullGlob, On);
+        let f = dummy_attr_field("*.txt");
+        let mut i = glob(&mut env, f).unwrap();
+        assert_eq!(i.next(), None);
+    }
+
+    #[test]
+    fn nullglob_option_keeps_matches_and_literal_fields() {
+        let mut env = env_with_dummy_files(["foo.exe"]);
+        env.options.set(NullGlob, On);
+        let mut i = glob(&mut env, dummy_attr_field("*.exe")).unwrap();
+        assert_eq!(i.next().unwrap().value, "foo.exe");
+        assert_eq!(i.next(), None);
+
+        let mut i = glob(&mut env, dummy_attr_field("bar.txt")).unwrap();
+        assert_eq!(i.next().unwrap().value, "bar.txt");
+        assert_eq!(i.next(), None);
+    }
+
+    #[test]
+    fn failglob_option_rejects_unmatched_pattern() {
+        let mut env = env_with_dummy_files(["foo.exe"]);
+        env.options.set(FailGlob, On);
+        let mut f = dummy_attr_field("*.txt");
+        f.origin = Location::dummy("echo *.txt");
+        let e = glob(&mut env, f).unwrap_err();
+        assert_eq!(
+            e.cause,
+            ErrorCause::UnmatchedPattern {
+                pattern: "*.txt".to_string()
+            }
+        );
+        assert_eq!(*e.location.code.value.borrow(), "echo *.txt");
+    }
+
+    #[test]
+    fn failglob_option_takes_precedence_over_nullglob() {
+        let mut env = env_with_dummy_files(["foo.exe"]);
+        env.options.set(FailGlob, On);
+        env.options.set(NullGlob, On);
+        let f = dummy_attr_field("*.txt");
+        let e = glob(&mut env, f).unwrap_err();
+        assert_matches::assert_matches!(e.cause, ErrorCause::UnmatchedPattern { .. });
+
+        let f = dummy_attr_field("*.exe");
+        let mut i = glob(&mut env, f).unwrap();
+        assert_eq!(i.next().unwrap().value, "foo.exe");
+        assert_eq!(i.next(), None);
+    }
+
+    #[test]
+    fn case_insensitive_without_caseglob() {
+        let mut env = env_with_dummy_files(["README.TXT", "dir/File.txt", "foo.exe"]);
+        env.options.set(yash_env::option::Option::CaseGlob, Off);
+        let f = dummy_attr_field("*.txt");
+        let mut i = glob(&mut env, f).unwrap();
+        assert_eq!(i.next().unwrap().value, "README.TXT");
+        assert_eq!(i.next(), None);
+
+        let f = dummy_attr_field("DIR/file.*");
+        let mut i = glob(&mut env, f).unwrap();
+        assert_eq!(i.next().unwrap().value, "dir/File.txt");
+        assert_eq!(i.next(), None);
+    }
+
+    #[test]
+    fn literal_field_intact_without_caseglob() {
+        let mut env = env_with_dummy_files(["README"]);
+        env.options.set(yash_env::option::Option::CaseGlob, Off);
+        let f = dummy_attr_field("readme");
+        let mut i = glob(&mut env, f).unwrap();
+        assert_eq!(i.next().unwrap().value, "readme");
+        assert_eq!(i.next(), None);
+    }
+
+    #[test]
+    fn compiled_pattern_is_cached() {
+        let mut env = env_with_dummy_files(["foo.txt"]);
+        let i = glob(&mut env, dummy_attr_field("*.txt")).unwrap();
+        assert_eq!(i.count(), 1);
+        let i = glob(&mut env, dummy_attr_field("*.txt")).unwrap();
+        assert_eq!(i.count(), 1);
+
+        let cache = env.any.get::<super::super::pattern_cache::PatternCache>();
+        let cache = cache.unwrap();
+        assert_eq!(cache.misses(), 1);
+        assert_eq!(cache.hits(), 1);
+    }
 }
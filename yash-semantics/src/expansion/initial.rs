@@ -18,19 +18,248 @@
 //!
 //! The initial expansion evaluates a word to a phrase. This module defines the
 //! [`Expand`] trait and its implementors that perform the expansion.
+//!
+//! The expansion accesses the shell execution environment through the
+//! [`ReadEnv`] and [`ExpandEnv`] traits. Most parts of the expansion only need
+//! [`ReadEnv`], which provides read-only access to the environment. Only
+//! command substitutions and assignments in parameter and arithmetic
+//! expansions require the mutating methods of [`ExpandEnv`].
+//! [`yash_env::Env`] implements both traits.
+//!
+//! A shared reference to a [`ReadEnv`] implementor also implements
+//! [`ExpandEnv`], so you can expand a word with an environment you cannot
+//! borrow mutably. In that case, an expansion that would modify the
+//! environment fails with [`ErrorCause::ImmutableEnv`].
+//!
+//! ```
+//! # use futures_util::FutureExt as _;
+//! # use yash_env::variable::Scope;
+//! use yash_semantics::expansion::attr_strip::Strip as _;
+//! use yash_semantics::expansion::initial::{Env, Expand as _};
+//! use yash_semantics::expansion::quote_removal::skip_quotes;
+//! use yash_semantics::expansion::ErrorCause;
+//! use yash_syntax::syntax::Word;
+//!
+//! let mut env = yash_env::Env::new_virtual();
+//! env.variables
+//!     .get_or_new("greeting", Scope::Global)
+//!     .assign("Hello", None)
+//!     .unwrap();
+//!
+//! // Expand a word with only a shared reference to the environment
+//! let snapshot = &env;
+//! let mut snapshot_ref = snapshot;
+//! let mut expansion_env = Env::new(&mut snapshot_ref);
+//! let word: Word = r#""$greeting, world""#.parse().unwrap();
+//! let phrase = word.expand(&mut expansion_env).now_or_never().unwrap().unwrap();
+//! let chars = phrase.ifs_join(&snapshot.variables);
+//! let value: String = skip_quotes(chars).strip().collect();
+//! assert_eq!(value, "Hello, world");
+//!
+//! // Expansions with side effects are rejected
+//! let word: Word = "${unset=value}".parse().unwrap();
+//! let error = word.expand(&mut expansion_env).now_or_never().unwrap().unwrap_err();
+//! assert_eq!(error.cause, ErrorCause::ImmutableEnv);
+//! ```
 
 use super::phrase::Phrase;
 use super::Error;
+use super::ErrorCause;
+use std::ffi::CString;
 use std::fmt::Debug;
+use std::rc::Rc;
+use yash_env::job::Pid;
+use yash_env::option::OptionSet;
 use yash_env::semantics::ExitStatus;
+use yash_env::variable::Scope::Global;
+use yash_env::variable::VariableSet;
+use yash_env::System as _;
+use yash_syntax::source::Location;
+
+/// Read-only part of the environment the initial expansion depends on
+pub trait ReadEnv {
+    /// Returns the variables in the environment.
+    #[must_use]
+    fn variables(&self) -> &VariableSet;
+
+    /// Returns the current states of the shell options.
+    #[must_use]
+    fn options(&self) -> &OptionSet;
+
+    /// Returns the exit status of the last command.
+    ///
+    /// This is the value of the `$?` special parameter.
+    #[must_use]
+    fn exit_status(&self) -> ExitStatus;
+
+    /// Returns the process ID of the main shell process.
+    ///
+    /// This is the value of the `$$` special parameter.
+    #[must_use]
+    fn main_pid(&self) -> Pid;
+
+    /// Returns the process ID of the last asynchronous command.
+    ///
+    /// This is the value of the `$!` special parameter.
+    #[must_use]
+    fn last_async_pid(&self) -> Pid;
+
+    /// Returns the name of the shell or the script being executed.
+    ///
+    /// This is the value of the `$0` special parameter.
+    #[must_use]
+    fn arg0(&self) -> &str;
+
+    /// Returns the home directory of the specified user.
+    ///
+    /// This function is used in the tilde expansion of the form `~name`.
+    /// The result is `None` if the user is not found or the home directory is
+    /// not a valid UTF-8 string.
+    #[must_use]
+    fn home_directory(&self, user_name: &str) -> Option<String>;
+}
+
+/// Part of the environment the initial expansion may modify
+///
+/// The methods of this trait perform the side effects of the expansion.
+pub trait ExpandEnv: ReadEnv {
+    /// Assigns a value to a global variable.
+    ///
+    /// This function is used in parameter expansions like `${foo=bar}` and in
+    /// arithmetic expansions like `$((foo=1))`. The `location` is where the
+    /// value is assigned.
+    ///
+    /// If the variable is read-only, this function returns
+    /// [`ErrorCause::AssignReadOnly`] whose `vacancy` is `None`.
+    fn assign_variable(
+        &mut self,
+        name: &str,
+        value: String,
+        location: Location,
+    ) -> Result<(), ErrorCause>;
+
+    /// Performs a command substitution.
+    ///
+    /// This function executes the command in a subshell and returns the
+    /// standard output of the command and its exit status. Trailing newlines
+    /// are not yet removed from the output.
+    ///
+    /// The exit status is `Some` if the subshell has finished, even if the
+    /// output is an error because it exceeded
+    /// [`command_subst_output_limit`](yash_env::Env::command_subst_output_limit).
+    #[allow(async_fn_in_trait)] // We don't support Send
+    async fn run_command_subst(
+        &mut self,
+        command: Rc<str>,
+        location: Location,
+    ) -> (Result<String, Error>, Option<ExitStatus>);
+}
+
+impl ReadEnv for yash_env::Env {
+    fn variables(&self) -> &VariableSet {
+        &self.variables
+    }
+
+    fn options(&self) -> &OptionSet {
+        &self.options
+    }
+
+    fn exit_status(&self) -> ExitStatus {
+        self.exit_status
+    }
+
+    fn main_pid(&self) -> Pid {
+        self.main_pid
+    }
+
+    fn last_async_pid(&self) -> Pid {
+        self.jobs.last_async_pid()
+    }
+
+    fn arg0(&self) -> &str {
+        &self.arg0
+    }
+
+    fn home_directory(&self, user_name: &str) -> Option<String> {
+        let name = CString::new(user_name).ok()?;
+        let path = self.system.getpwnam_dir(&name).ok()??;
+        path.into_unix_string().into_string().ok()
+    }
+}
+
+impl ExpandEnv for yash_env::Env {
+    fn assign_variable(
+        &mut self,
+        name: &str,
+        value: String,
+        location: Location,
+    ) -> Result<(), ErrorCause> {
+        crate::assign::assign_variable(self, name.to_owned(), Global, value.into(), location)
+            .map(drop)
+            .map_err(|e| e.cause)
+    }
+
+    async fn run_command_subst(
+        &mut self,
+        command: Rc<str>,
+        location: Location,
+    ) -> (Result<String, Error>, Option<ExitStatus>) {
+        command_subst::run(self, command, location).await
+    }
+}
+
+impl<T: ReadEnv + ?Sized> ReadEnv for &T {
+    fn variables(&self) -> &VariableSet {
+        (**self).variables()
+    }
+    fn options(&self) -> &OptionSet {
+        (**self).options()
+    }
+    fn exit_status(&self) -> ExitStatus {
+        (**self).exit_status()
+    }
+    fn main_pid(&self) -> Pid {
+        (**self).main_pid()
+    }
+    fn last_async_pid(&self) -> Pid {
+        (**self).last_async_pid()
+    }
+    fn arg0(&self) -> &str {
+        (**self).arg0()
+    }
+    fn home_directory(&self, user_name: &str) -> Option<String> {
+        (**self).home_directory(user_name)
+    }
+}
+
+/// Expansion environment that cannot be modified
+///
+/// A shared reference does not allow modifying the environment, so the
+/// methods of this implementation always fail with
+/// [`ErrorCause::ImmutableEnv`].
+impl<T: ReadEnv + ?Sized> ExpandEnv for &T {
+    fn assign_variable(&mut self, _: &str, _: String, _: Location) -> Result<(), ErrorCause> {
+        Err(ErrorCause::ImmutableEnv)
+    }
+
+    async fn run_command_subst(
+        &mut self,
+        _: Rc<str>,
+        location: Location,
+    ) -> (Result<String, Error>, Option<ExitStatus>) {
+        let cause = ErrorCause::ImmutableEnv;
+        (Err(Error { cause, location }), None)
+    }
+}
 
 /// Environment in which initial expansion is performed
 ///
-/// This sturct extends [`yash_env::Env`] with some properties.
+/// This struct extends an [`ExpandEnv`] implementor, which is usually
+/// [`yash_env::Env`], with some properties.
 #[derive(Debug)]
-pub struct Env<'a> {
+pub struct Env<'a, E = yash_env::Env> {
     /// Main part of the environment
-    pub inner: &'a mut yash_env::Env,
+    pub inner: &'a mut E,
 
     /// Exit status of the last executed command substitution
     ///
@@ -45,12 +274,12 @@ pub struct Env<'a> {
     pub will_split: bool,
 }
 
-impl<'a> Env<'a> {
+impl<'a, E> Env<'a, E> {
     /// Creates a new `Env` instance.
     ///
     /// The `last_command_subst_exit_status` and `will_split` field are
     /// initialized to be `None` and `true`, respectively.
-    pub fn new(inner: &'a mut yash_env::Env) -> Self {
+    pub fn new(inner: &'a mut E) -> Self {
         Env {
             inner,
             last_command_subst_exit_status: None,
@@ -67,7 +296,7 @@ impl<'a> Env<'a> {
 pub trait Expand {
     /// Performs initial expansion.
     #[allow(async_fn_in_trait)] // We don't support Send
-    async fn expand(&self, env: &mut Env<'_>) -> Result<Phrase, Error>;
+    async fn expand<E: ExpandEnv>(&self, env: &mut Env<'_, E>) -> Result<Phrase, Error>;
 }
 
 mod arith;
@@ -82,3 +311,68 @@ pub use arith::ArithError;
 pub use param::NonassignableError;
 pub use param::Vacancy;
 pub use param::VacantError;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt as _;
+    use yash_env::variable::Scope;
+    use yash_syntax::syntax::Word;
+
+    #[test]
+    fn expanding_with_shared_reference() {
+        let mut env = yash_env::Env::new_virtual();
+        env.variables
+            .get_or_new("x", Scope::Global)
+            .assign("value", None)
+            .unwrap();
+        env.exit_status = ExitStatus(42);
+        let mut shared = &env;
+        let mut env = Env::new(&mut shared);
+        let word: Word = "${x}-$?-${y:-default}".parse().unwrap();
+
+        let phrase = word.expand(&mut env).now_or_never().unwrap().unwrap();
+        let value: String = phrase
+            .ifs_join(shared.variables())
+            .into_iter()
+            .map(|c| c.value)
+            .collect();
+        assert_eq!(value, "value-42-default");
+    }
+
+    #[test]
+    fn assignment_with_shared_reference() {
+        let env = yash_env::Env::new_virtual();
+        let mut shared = &env;
+        let mut env = Env::new(&mut shared);
+        let word: Word = "${x=value}".parse().unwrap();
+
+        let error = word.expand(&mut env).now_or_never().unwrap().unwrap_err();
+        assert_eq!(error.cause, ErrorCause::ImmutableEnv);
+        assert_eq!(shared.variables.get("x"), None);
+    }
+
+    #[test]
+    fn arithmetic_assignment_with_shared_reference() {
+        let env = yash_env::Env::new_virtual();
+        let mut shared = &env;
+        let mut env = Env::new(&mut shared);
+        let word: Word = "$((x=1))".parse().unwrap();
+
+        let error = word.expand(&mut env).now_or_never().unwrap().unwrap_err();
+        assert_eq!(error.cause, ErrorCause::ImmutableEnv);
+        assert_eq!(shared.variables.get("x"), None);
+    }
+
+    #[test]
+    fn command_substitution_with_shared_reference() {
+        let env = yash_env::Env::new_virtual();
+        let mut shared = &env;
+        let mut env = Env::new(&mut shared);
+        let word: Word = "$(echo foo)".parse().unwrap();
+
+        let error = word.expand(&mut env).now_or_never().unwrap().unwrap_err();
+        assert_eq!(error.cause, ErrorCause::ImmutableEnv);
+        assert_eq!(env.last_command_subst_exit_status, None);
+    }
+}
@@ -22,14 +22,16 @@ use super::super::phrase::Phrase;
 use super::super::ErrorCause;
 use super::Env;
 use super::Error;
-use crate::expansion::expand_text;
-use crate::expansion::AssignReadOnlyError;
+use super::Expand as _;
+use super::ExpandEnv;
+use crate::expansion::attr_strip::Strip as _;
+use crate::expansion::quote_removal::skip_quotes;
 use std::ops::Range;
 use std::rc::Rc;
 use yash_arith::eval;
+use yash_arith::Value;
 use yash_env::option::Option::Unset;
 use yash_env::option::State::{Off, On};
-use yash_env::variable::Scope::Global;
 use yash_syntax::source::Code;
 use yash_syntax::source::Location;
 use yash_syntax::source::Source;
@@ -139,13 +141,28 @@ struct UnsetVariable {
     param: Param,
 }
 
+/// Converts a byte range in the expression into a character range.
+///
+/// `yash_arith` reports locations as byte ranges in the expression string,
+/// whereas [`Location::range`] counts characters.
+#[must_use]
+fn to_char_range(expression: &str, range: Range<usize>) -> Range<usize> {
+    let char_index = |byte_index| {
+        expression
+            .char_indices()
+            .take_while(|&(index, _)| index < byte_index)
+            .count()
+    };
+    char_index(range.start)..char_index(range.end)
+}
+
 /// Converts `yash_arith::ErrorCause` into `initial::ErrorCause`.
 ///
 /// The `source` argument must be the arithmetic expression being expanded.
 /// It is used to reproduce a location contained in the error cause.
 #[must_use]
 fn convert_error_cause(
-    cause: yash_arith::ErrorCause<UnsetVariable, AssignReadOnlyError>,
+    cause: yash_arith::ErrorCause<UnsetVariable, ErrorCause>,
     source: &Rc<Code>,
 ) -> ErrorCause {
     use ArithError::*;
@@ -164,16 +181,18 @@ fn convert_error_cause(
             }
             yash_arith::SyntaxError::MissingOperator => ErrorCause::ArithError(MissingOperator),
             yash_arith::SyntaxError::UnclosedParenthesis { opening_location } => {
+                let range = to_char_range(&source.value.borrow(), opening_location);
                 let opening_location = Location {
                     code: Rc::clone(source),
-                    range: opening_location,
+                    range,
                 };
                 ErrorCause::ArithError(UnclosedParenthesis { opening_location })
             }
             yash_arith::SyntaxError::QuestionWithoutColon { question_location } => {
+                let range = to_char_range(&source.value.borrow(), question_location);
                 let question_location = Location {
                     code: Rc::clone(source),
-                    range: question_location,
+                    range,
                 };
                 ErrorCause::ArithError(QuestionWithoutColon { question_location })
             }
@@ -196,25 +215,25 @@ fn convert_error_cause(
             yash_arith::EvalError::GetVariableError(UnsetVariable { param }) => {
                 ErrorCause::UnsetParameter { param }
             }
-            yash_arith::EvalError::AssignVariableError(e) => ErrorCause::AssignReadOnly(e),
+            yash_arith::EvalError::AssignVariableError(cause) => cause,
         },
     }
 }
 
-struct VarEnv<'a> {
-    env: &'a mut yash_env::Env,
+struct VarEnv<'a, E> {
+    env: &'a mut E,
     expression: &'a str,
     expansion_location: &'a Location,
 }
 
-impl yash_arith::Env for VarEnv<'_> {
+impl<E: ExpandEnv> yash_arith::Env for VarEnv<'_, E> {
     type GetVariableError = UnsetVariable;
-    type AssignVariableError = AssignReadOnlyError;
+    type AssignVariableError = ErrorCause;
 
     fn get_variable(&self, name: &str) -> Result<Option<&str>, UnsetVariable> {
-        match self.env.variables.get_scalar(name) {
+        match self.env.variables().get_scalar(name) {
             Some(value) => Ok(Some(value)),
-            None => match self.env.options.get(Unset) {
+            None => match self.env.options().get(Unset) {
                 // TODO If the variable exists but is not scalar, UnsetVariable
                 // does not seem to be the right error.
                 Off => Err(UnsetVariable {
@@ -230,7 +249,7 @@ impl yash_arith::Env for VarEnv<'_> {
         name: &str,
         value: String,
         range: Range<usize>,
-    ) -> Result<(), AssignReadOnlyError> {
+    ) -> Result<(), ErrorCause> {
         let code = Rc::new(Code {
             value: self.expression.to_string().into(),
             start_line_number: 1.try_into().unwrap(),
@@ -239,67 +258,80 @@ impl yash_arith::Env for VarEnv<'_> {
             }
             .into(),
         });
+        let range = to_char_range(self.expression, range);
         self.env
-            .get_or_create_variable(name, Global)
-            .assign(value, Location { code, range })
-            .map(drop)
-            .map_err(|e| AssignReadOnlyError {
-                name: name.to_owned(),
-                new_value: e.new_value,
-                read_only_location: e.read_only_location,
-                vacancy: None,
-            })
+            .assign_variable(name, value, Location { code, range })
     }
 }
 
-pub async fn expand(text: &Text, location: &Location, env: &mut Env<'_>) -> Result<Phrase, Error> {
-    let (expression, exit_status) = expand_text(env.inner, text).await?;
-    if exit_status.is_some() {
-        env.last_command_subst_exit_status = exit_status;
-    }
+/// Expands the text into an arithmetic expression.
+///
+/// The text is expanded, and the result is joined and unquoted to be passed to
+/// [`evaluate`].
+pub(super) async fn expand_expression<E: ExpandEnv>(
+    text: &Text,
+    env: &mut Env<'_, E>,
+) -> Result<String, Error> {
+    let phrase = text.expand(env).await?;
+    let chars = phrase.ifs_join(env.inner.variables());
+    Ok(skip_quotes(chars).strip().collect())
+}
 
+/// Evaluates the arithmetic expression.
+///
+/// The `location` is the location of the whole expansion, which is used as the
+/// original location of errors in the expression.
+pub(super) fn evaluate<E: ExpandEnv>(
+    expression: &str,
+    location: &Location,
+    env: &mut E,
+) -> Result<Value, Error> {
     let result = eval(
-        &expression,
+        expression,
         &mut VarEnv {
-            env: env.inner,
-            expression: &expression,
+            env,
+            expression,
             expansion_location: location,
         },
     );
 
-    match result {
-        Ok(value) => {
-            let value = value.to_string();
-            let chars = value
-                .chars()
-                .map(|c| AttrChar {
-                    value: c,
-                    origin: Origin::SoftExpansion,
-                    is_quoted: false,
-                    is_quoting: false,
-                })
-                .collect();
-            Ok(Phrase::Field(chars))
-        }
-        Err(error) => {
-            let code = Rc::new(Code {
-                value: expression.into(),
-                start_line_number: 1.try_into().unwrap(),
-                source: Source::Arith {
-                    original: location.clone(),
-                }
-                .into(),
-            });
-            let cause = convert_error_cause(error.cause, &code);
-            Err(Error {
-                cause,
-                location: Location {
-                    code,
-                    range: error.location,
-                },
-            })
+    result.map_err(|error| {
+        let code = Rc::new(Code {
+            value: expression.to_owned().into(),
+            start_line_number: 1.try_into().unwrap(),
+            source: Source::Arith {
+                original: location.clone(),
+            }
+            .into(),
+        });
+        let cause = convert_error_cause(error.cause, &code);
+        Error {
+            cause,
+            location: Location {
+                code,
+                range: to_char_range(expression, error.location),
+            },
         }
-    }
+    })
+}
+
+pub async fn expand<E: ExpandEnv>(
+    text: &Text,
+    location: &Location,
+    env: &mut Env<'_, E>,
+) -> Result<Phrase, Error> {
+    let expression = expand_expression(text, env).await?;
+    let value = evaluate(&expression, location, env.inner)?.to_string();
+    let chars = value
+        .chars()
+        .map(|c| AttrChar {
+            value: c,
+            origin: Origin::SoftExpansion,
+            is_quoted: false,
+            is_quoting: false,
+        })
+        .collect();
+    Ok(Phrase::Field(chars))
 }
 
 #[cfg(test)]
@@ -307,6 +339,7 @@ mod tests {
     use super::*;
     use crate::tests::echo_builtin;
     use crate::tests::return_builtin;
+    use assert_matches::assert_matches;
     use futures_util::FutureExt;
     use yash_env::semantics::ExitStatus;
     use yash_env::system::Errno;
@@ -468,4 +501,43 @@ mod tests {
         );
         assert_eq!(e.location.range, 0..2);
     }
+
+    #[test]
+    fn byte_range_to_char_range() {
+        assert_eq!(to_char_range("abc", 1..3), 1..3);
+        assert_eq!(to_char_range("\u{3042}b\u{3044}", 3..7), 1..3);
+        assert_eq!(to_char_range("\u{3042}", 3..3), 1..1);
+    }
+
+    #[test]
+    fn locations_in_expression_with_non_ascii_characters() {
+        let text = "\u{3042}\u{3044} + (x = 2)".parse().unwrap();
+        let location = Location::dummy("my location");
+        let mut env = yash_env::Env::new_virtual();
+        let mut env2 = Env::new(&mut env);
+        let _ = expand(&text, &location, &mut env2).now_or_never().unwrap();
+        let v = env.variables.get("x").unwrap();
+        let location2 = v.last_assigned_location.as_ref().unwrap();
+        assert_eq!(location2.range, 6..7);
+
+        let text = "\u{3042} + 09".parse().unwrap();
+        let mut env = Env::new(&mut env);
+        let e = expand(&text, &location, &mut env)
+            .now_or_never()
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(e.location.range, 4..6);
+
+        let text = "\u{3042} * (1 + 2".parse().unwrap();
+        let e = expand(&text, &location, &mut env)
+            .now_or_never()
+            .unwrap()
+            .unwrap_err();
+        assert_matches!(
+            e.cause,
+            ErrorCause::ArithError(ArithError::UnclosedParenthesis { opening_location }) => {
+                assert_eq!(opening_location.range, 4..5);
+            }
+        );
+    }
 }
@@ -15,42 +15,86 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 //! Expansion of command substitution
+//!
+//! The result of command substitution is the standard output of the command
+//! with all trailing newline characters removed. Other characters, including
+//! carriage returns preceding the newlines, are kept intact. A command that
+//! outputs only newlines therefore expands to an empty string.
 
 use super::super::attr::AttrChar;
 use super::super::attr::Origin;
 use super::super::phrase::Phrase;
 use super::Env;
 use super::Error;
+use super::ExpandEnv;
 use crate::expansion::ErrorCause;
 use crate::read_eval_loop;
 use crate::trap::run_exit_trap;
 use crate::Handle;
 use std::cell::RefCell;
+use std::rc::Rc;
 use yash_env::io::Fd;
 use yash_env::job::Pid;
+use yash_env::semantics::ExitStatus;
 use yash_env::subshell::JobControl;
+use yash_env::subshell::StartError;
 use yash_env::subshell::Subshell;
-use yash_env::system::Errno;
 use yash_env::System;
 use yash_syntax::parser::lex::Lexer;
 use yash_syntax::source::Location;
 use yash_syntax::source::Source;
 
 /// Performs command substitution
-pub async fn expand<C>(command: C, location: Location, env: &mut Env<'_>) -> Result<Phrase, Error>
-where
-    C: AsRef<str> + 'static,
-{
+pub async fn expand<E: ExpandEnv>(
+    command: Rc<str>,
+    location: Location,
+    env: &mut Env<'_, E>,
+) -> Result<Phrase, Error> {
+    let (result, exit_status) = env.inner.run_command_subst(command, location).await;
+    if exit_status.is_some() {
+        env.last_command_subst_exit_status = exit_status;
+    }
+    let mut result = result?;
+
+    remove_trailing_newlines(&mut result);
+
+    let chars = result
+        .chars()
+        .map(|value| AttrChar {
+            value,
+            origin: Origin::SoftExpansion,
+            is_quoted: false,
+            is_quoting: false,
+        })
+        .collect();
+    Ok(Phrase::Field(chars))
+}
+
+/// Removes all trailing newline characters from the string.
+///
+/// Carriage returns are not removed, so `"a\r\n"` becomes `"a\r"`.
+fn remove_trailing_newlines(s: &mut String) {
+    let len = s.trim_end_matches('\n').len();
+    s.truncate(len);
+}
+
+/// Runs the command in a subshell and returns its output and exit status.
+///
+/// This is the implementation of [`ExpandEnv::run_command_subst`] for
+/// [`yash_env::Env`].
+pub(super) async fn run(
+    env: &mut yash_env::Env,
+    command: Rc<str>,
+    location: Location,
+) -> (Result<String, Error>, Option<ExitStatus>) {
     let original = location.clone();
 
     // Open a pipe to read the output from the command
-    let (reader, writer) = match env.inner.system.pipe() {
+    let (reader, writer) = match env.system.pipe() {
         Ok(pipes) => pipes,
         Err(errno) => {
-            return Err(Error {
-                cause: ErrorCause::CommandSubstError(errno),
-                location,
-            })
+            let cause = ErrorCause::CommandSubstError(errno);
+            return (Err(Error { cause, location }), None);
         }
     };
 
@@ -62,21 +106,18 @@ where
             run_exit_trap(env).await;
         })
     });
-    let subshell_result = subshell.start(env.inner).await;
+    let subshell_result = subshell.start(env).await;
 
-    expand_common(reader, writer, subshell_result, location, env).await
+    read_output(reader, writer, subshell_result, location, env).await
 }
 
-async fn subshell_body<C>(
+async fn subshell_body(
     env: &mut yash_env::Env,
     reader: Fd,
     writer: Fd,
     original: Location,
-    command: C,
-) -> yash_env::semantics::Result
-where
-    C: AsRef<str> + 'static,
-{
+    command: Rc<str>,
+) -> yash_env::semantics::Result {
     // Arrange the file descriptors
     env.system.close(reader).ok();
     if writer != Fd::STDOUT {
@@ -91,76 +132,71 @@ where
     }
 
     // Run the command
-    let mut lexer = Lexer::from_memory(command.as_ref(), Source::CommandSubst { original });
+    let mut lexer = Lexer::from_memory(&command, Source::CommandSubst { original });
     read_eval_loop(&RefCell::new(env), &mut lexer).await
 }
 
-/// The second half of [`expand`] that does not depend on type parameter `C`.
-async fn expand_common(
+/// Reads the output of the subshell started by [`run`] and waits for it.
+async fn read_output(
     reader: Fd,
     writer: Fd,
-    subshell_result: Result<(Pid, Option<JobControl>), Errno>,
+    subshell_result: Result<(Pid, Option<JobControl>), StartError>,
     location: Location,
-    env: &mut Env<'_>,
-) -> Result<Phrase, Error> {
+    env: &mut yash_env::Env,
+) -> (Result<String, Error>, Option<ExitStatus>) {
     // See if the subshell has successfully started
     let pid = match subshell_result {
         Ok((pid, job_control)) => {
             debug_assert_eq!(job_control, None);
             pid
         }
-        Err(errno) => {
-            env.inner.system.close(reader).ok();
-            env.inner.system.close(writer).ok();
-            return Err(Error {
-                cause: ErrorCause::CommandSubstError(errno),
-                location,
-            });
+        Err(error) => {
+            env.system.close(reader).ok();
+            env.system.close(writer).ok();
+            let cause = ErrorCause::CommandSubstError(error.errno());
+            return (Err(Error { cause, location }), None);
         }
     };
 
-    env.inner.system.close(writer).ok();
+    env.system.close(writer).ok();
 
     // Read the output from the subshell
+    let limit = env.command_subst_output_limit;
     let mut result = Vec::new();
     let mut buffer = [0; 4096];
-    while let Ok(count) = env.inner.system.read_async(reader, &mut buffer).await {
+    let mut exceeded = false;
+    while let Ok(count) = env.system.read_async(reader, &mut buffer).await {
         if count == 0 {
             break;
         }
+        if limit.is_some_and(|limit| result.len() + count > limit) {
+            exceeded = true;
+            break;
+        }
         result.extend(&buffer[..count]);
     }
-    env.inner.system.close(reader).ok();
+    // Closing the reader before waiting lets the subshell fail writing the
+    // rest of the output if the limit has been exceeded.
+    env.system.close(reader).ok();
 
     // Wait for the subshell
-    match env.inner.wait_for_subshell_to_finish(pid).await {
-        Ok((_pid, exit_status)) => env.last_command_subst_exit_status = Some(exit_status),
+    let exit_status = match env.wait_for_subshell_to_finish(pid).await {
+        Ok((_pid, result)) => result.into(),
         Err(errno) => {
-            return Err(Error {
-                cause: ErrorCause::CommandSubstError(errno),
-                location,
-            })
+            let cause = ErrorCause::CommandSubstError(errno);
+            return (Err(Error { cause, location }), None);
         }
+    };
+
+    if exceeded {
+        let cause = ErrorCause::CommandSubstOutputTooLarge(limit.unwrap());
+        return (Err(Error { cause, location }), Some(exit_status));
     }
 
     // TODO Reject invalid UTF-8 sequence if strict POSIX mode is on
-    let mut result = String::from_utf8(result)
+    let result = String::from_utf8(result)
         .unwrap_or_else(|e| String::from_utf8_lossy(&e.into_bytes()).into());
-
-    // Remove trailing newlines
-    let len = result.trim_end_matches('\n').len();
-    result.truncate(len);
-
-    let chars = result
-        .chars()
-        .map(|value| AttrChar {
-            value,
-            origin: Origin::SoftExpansion,
-            is_quoted: false,
-            is_quoting: false,
-        })
-        .collect();
-    Ok(Phrase::Field(chars))
+    (Ok(result), Some(exit_status))
 }
 
 #[cfg(test)]
@@ -173,10 +209,33 @@ mod tests {
     use yash_env::system::Errno;
     use yash_env_test_helper::in_virtual_system;
 
+    #[test]
+    fn removing_trailing_newlines() {
+        let mut s = String::from("a\n\n\n");
+        remove_trailing_newlines(&mut s);
+        assert_eq!(s, "a");
+
+        let mut s = String::from("a\r\n");
+        remove_trailing_newlines(&mut s);
+        assert_eq!(s, "a\r");
+
+        let mut s = String::from("\n\n");
+        remove_trailing_newlines(&mut s);
+        assert_eq!(s, "");
+
+        let mut s = String::new();
+        remove_trailing_newlines(&mut s);
+        assert_eq!(s, "");
+
+        let mut s = String::from("a\n\nb\n");
+        remove_trailing_newlines(&mut s);
+        assert_eq!(s, "a\n\nb");
+    }
+
     #[test]
     fn empty_substitution() {
         in_virtual_system(|mut env, _state| async move {
-            let command = "".to_string();
+            let command = "".into();
             let location = Location::dummy("");
             let mut env = Env::new(&mut env);
             let result = expand(command, location, &mut env).await;
@@ -188,7 +247,7 @@ mod tests {
     fn one_line_substitution() {
         in_virtual_system(|mut env, _state| async move {
             env.builtins.insert("echo", echo_builtin());
-            let command = "echo ok".to_string();
+            let command = "echo ok".into();
             let location = Location::dummy("");
             let mut env = Env::new(&mut env);
             let result = expand(command, location, &mut env).await;
@@ -208,7 +267,7 @@ mod tests {
     fn many_line_substitution() {
         in_virtual_system(|mut env, _state| async move {
             env.builtins.insert("echo", echo_builtin());
-            let command = "echo 1; echo 2; echo; echo 3; echo; echo".to_string();
+            let command = "echo 1; echo 2; echo; echo 3; echo; echo".into();
             let location = Location::dummy("");
             let mut env = Env::new(&mut env);
             let result = expand(command, location, &mut env).await;
@@ -225,11 +284,73 @@ mod tests {
         })
     }
 
+    #[test]
+    fn nested_substitution() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("echo", echo_builtin());
+            let command = "echo $(echo x)y".into();
+            let location = Location::dummy("");
+            let mut env = Env::new(&mut env);
+            let result = expand(command, location, &mut env).await;
+            let chars = "xy"
+                .chars()
+                .map(|value| AttrChar {
+                    value,
+                    origin: Origin::SoftExpansion,
+                    is_quoted: false,
+                    is_quoting: false,
+                })
+                .collect();
+            assert_eq!(result, Ok(Phrase::Field(chars)));
+            assert_eq!(env.last_command_subst_exit_status, Some(ExitStatus(0)));
+        })
+    }
+
+    #[test]
+    fn output_within_limit() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("echo", echo_builtin());
+            env.command_subst_output_limit = Some(3);
+            let command = "echo ok".into();
+            let location = Location::dummy("");
+            let mut env = Env::new(&mut env);
+            let result = expand(command, location, &mut env).await;
+
+            let o = AttrChar {
+                value: 'o',
+                origin: Origin::SoftExpansion,
+                is_quoted: false,
+                is_quoting: false,
+            };
+            let k = AttrChar { value: 'k', ..o };
+            assert_eq!(result, Ok(Phrase::Field(vec![o, k])));
+        })
+    }
+
+    #[test]
+    fn output_exceeding_limit() {
+        in_virtual_system(|mut env, _state| async move {
+            env.builtins.insert("echo", echo_builtin());
+            env.command_subst_output_limit = Some(2);
+            let command = "echo ok".into();
+            let location = Location::dummy("foo");
+            let mut env = Env::new(&mut env);
+            let result = expand(command, location.clone(), &mut env).await;
+
+            let cause = ErrorCause::CommandSubstOutputTooLarge(2);
+            assert_eq!(result, Err(Error { cause, location }));
+            assert_eq!(
+                env.last_command_subst_exit_status,
+                Some(ExitStatus::SUCCESS)
+            );
+        })
+    }
+
     #[test]
     fn exit_status_of_command_substitution() {
         in_virtual_system(|mut env, _state| async move {
             env.builtins.insert("return", return_builtin());
-            let command = "return -n 100".to_string();
+            let command = "return -n 100".into();
             let location = Location::dummy("");
             let mut env = Env::new(&mut env);
             let result = expand(command, location, &mut env).await;
@@ -240,7 +361,7 @@ mod tests {
 
     #[test]
     fn error_in_command_substitution() {
-        let command = "".to_string();
+        let command = "".into();
         let location = Location::dummy("foo");
         let mut env = yash_env::Env::new_virtual();
         let mut env = Env::new(&mut env);
@@ -23,6 +23,7 @@ use super::super::ErrorCause;
 use super::super::Origin;
 use super::Env;
 use super::Expand;
+use super::ExpandEnv;
 use yash_env::option::Option::Unset;
 use yash_env::option::State::Off;
 use yash_env::variable::Value;
@@ -52,6 +53,8 @@ impl<'a> From<&'a BracedParam> for ParamRef<'a> {
 
 // TODO Consider exporting these modules
 mod resolve;
+mod subst;
+mod substring;
 mod switch;
 mod trim;
 
@@ -61,11 +64,11 @@ pub use switch::VacantError;
 
 impl Expand for ParamRef<'_> {
     /// Performs parameter expansion.
-    async fn expand(&self, env: &mut Env<'_>) -> Result<Phrase, Error> {
+    async fn expand<E: ExpandEnv>(&self, env: &mut Env<'_, E>) -> Result<Phrase, Error> {
         // TODO Expand and parse Index
 
         // Lookup //
-        let resolve = resolve::resolve(env.inner, self.param, self.location);
+        let resolve = resolve::resolve(&*env.inner, self.param, self.location);
 
         // TODO Apply Index
 
@@ -80,7 +83,7 @@ impl Expand for ParamRef<'_> {
             }
         } else {
             // Check for nounset option error //
-            if value.is_none() && env.inner.options.get(Unset) == Off {
+            if value.is_none() && env.inner.options().get(Unset) == Off {
                 return Err(Error {
                     cause: ErrorCause::UnsetParameter {
                         param: self.param.clone(),
@@ -101,6 +104,9 @@ impl Expand for ParamRef<'_> {
                 match &mut value {
                     None => value = Some(Value::scalar("0")),
                     Some(Value::Scalar(v)) => to_length(v),
+                    Some(Value::Array(vs)) if is_all_positional(self.param) => {
+                        value = Some(Value::scalar(vs.len().to_string()))
+                    }
                     Some(Value::Array(vs)) => vs.iter_mut().for_each(to_length),
                 }
             }
@@ -110,17 +116,48 @@ impl Expand for ParamRef<'_> {
                     trim::apply(env, trim, value).await?
                 }
             }
+
+            Modifier::Substring(substring) => {
+                if let Some(value) = &mut value {
+                    if let Value::Array(values) = value {
+                        if is_all_positional(self.param) {
+                            // The offset counts from `$0`, not `$1`.
+                            values.insert(0, env.inner.arg0().to_owned());
+                        }
+                    }
+                    substring::apply(env, substring, value, self.location).await?
+                }
+            }
+
+            Modifier::Subst(subst) => {
+                if let Some(value) = &mut value {
+                    subst::apply(env, subst, value).await?
+                }
+            }
         }
 
         let mut phrase = into_phrase(value);
         if !env.will_split && self.param.r#type == ParamType::Special(SpecialParam::Asterisk) {
-            phrase = Phrase::Field(phrase.ifs_join(&env.inner.variables));
+            phrase = Phrase::Field(phrase.ifs_join(env.inner.variables()));
         }
         Ok(phrase)
     }
 }
 
+/// Tests whether the parameter is `*` or `@`.
+///
+/// The length of these parameters is the number of positional parameters
+/// rather than the lengths of the individual parameters.
+fn is_all_positional(param: &Param) -> bool {
+    matches!(
+        param.r#type,
+        ParamType::Special(SpecialParam::Asterisk | SpecialParam::At)
+    )
+}
+
 /// Modifies a string to its length.
+///
+/// The length is counted in characters, not bytes.
 fn to_length(v: &mut String) {
     *v = v.chars().count().to_string()
 }
@@ -154,7 +191,7 @@ pub mod tests {
     use super::*;
     use futures_util::FutureExt;
     use yash_env::variable::{Scope, IFS};
-    use yash_syntax::syntax::{Switch, SwitchCondition, SwitchType};
+    use yash_syntax::syntax::{Substring, Switch, SwitchCondition, SwitchType};
 
     pub fn env_with_positional_params_and_ifs() -> yash_env::Env {
         let mut env = yash_env::Env::new_virtual();
@@ -221,6 +258,81 @@ pub mod tests {
         assert_eq!(phrase, Phrase::Field(to_field("3")));
     }
 
+    #[test]
+    fn length_of_positional_parameter() {
+        let mut env = yash_env::Env::new_virtual();
+        env.variables.positional_params_mut().values =
+            ["a", "bb", "ccc", "dd", "e\u{30A4}\u{30A4}"]
+                .map(String::from)
+                .into();
+        let mut env = Env::new(&mut env);
+        let mut param = braced_param(Param::from(5));
+        param.modifier = Modifier::Length;
+        let param = ParamRef::from(&param);
+
+        let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+        assert_eq!(phrase, Phrase::Field(to_field("3")));
+    }
+
+    #[test]
+    fn length_of_all_positional_parameters() {
+        let mut env = yash_env::Env::new_virtual();
+        env.variables.positional_params_mut().values = ["a", "bb", "ccc"].map(String::from).into();
+        let mut env = Env::new(&mut env);
+
+        for special in [SpecialParam::Asterisk, SpecialParam::At] {
+            let mut param = braced_param(Param::from(special));
+            param.modifier = Modifier::Length;
+            let param = ParamRef::from(&param);
+
+            let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+            assert_eq!(phrase, Phrase::Field(to_field("3")), "{special:?}");
+        }
+    }
+
+    #[test]
+    fn substring_of_all_positional_parameters() {
+        let mut env = yash_env::Env::new_virtual();
+        env.arg0 = "yash".to_string();
+        env.variables.positional_params_mut().values = ["a", "b", "c"].map(String::from).into();
+        let mut env = Env::new(&mut env);
+
+        let mut param = braced_param(Param::from(SpecialParam::At));
+        for (offset, length, expected) in [
+            ("1", None, &["a", "b", "c"][..]),
+            ("0", Some("2"), &["yash", "a"]),
+            ("-1", None, &["c"]),
+            ("3", None, &["c"]),
+            ("4", None, &[]),
+        ] {
+            param.modifier = Modifier::Substring(Substring {
+                offset: offset.parse().unwrap(),
+                length: length.map(|length| length.parse().unwrap()),
+            });
+            let param = ParamRef::from(&param);
+
+            let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+            let expected = into_phrase(Some(Value::array(expected.iter().copied())));
+            assert_eq!(phrase, expected, "{offset:?} {length:?}");
+        }
+    }
+
+    #[test]
+    fn length_of_no_positional_parameters() {
+        let mut env = yash_env::Env::new_virtual();
+        let mut env = Env::new(&mut env);
+        env.will_split = false;
+
+        for special in [SpecialParam::Asterisk, SpecialParam::At] {
+            let mut param = braced_param(Param::from(special));
+            param.modifier = Modifier::Length;
+            let param = ParamRef::from(&param);
+
+            let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+            assert_eq!(phrase, Phrase::Field(to_field("0")), "{special:?}");
+        }
+    }
+
     #[test]
     fn length_of_array() {
         let mut env = yash_env::Env::new_virtual();
@@ -383,6 +495,35 @@ pub mod tests {
         assert_eq!(phrase, Phrase::Field(to_field("a&c")));
     }
 
+    #[test]
+    fn expand_asterisk_join_with_unset_ifs() {
+        let mut env = env_with_positional_params_and_ifs();
+        env.variables.unset(IFS, Scope::Global).unwrap();
+        let param = braced_param(SpecialParam::Asterisk);
+        let param = ParamRef::from(&param);
+        let mut env = Env::new(&mut env);
+        env.will_split = false;
+
+        let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+        assert_eq!(phrase, Phrase::Field(to_field("a c")));
+    }
+
+    #[test]
+    fn expand_asterisk_join_with_empty_ifs() {
+        let mut env = env_with_positional_params_and_ifs();
+        env.variables
+            .get_or_new(IFS, Scope::Global)
+            .assign("", None)
+            .unwrap();
+        let param = braced_param(SpecialParam::Asterisk);
+        let param = ParamRef::from(&param);
+        let mut env = Env::new(&mut env);
+        env.will_split = false;
+
+        let phrase = param.expand(&mut env).now_or_never().unwrap().unwrap();
+        assert_eq!(phrase, Phrase::Field(to_field("ac")));
+    }
+
     #[test]
     fn none_into_phrase() {
         assert_eq!(into_phrase(None), Phrase::one_empty_field());
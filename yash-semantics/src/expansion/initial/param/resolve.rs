@@ -16,44 +16,52 @@
 
 //! Resolving parameter names to values
 
+use crate::expansion::initial::ReadEnv;
 use yash_env::variable::Expansion;
-use yash_env::Env;
 use yash_syntax::source::Location;
 use yash_syntax::syntax::Param;
 use yash_syntax::syntax::ParamType::*;
 use yash_syntax::syntax::SpecialParam::*;
 
 /// Resolves a parameter name to its value.
-pub fn resolve<'a>(env: &'a Env, param: &Param, location: &Location) -> Expansion<'a> {
-    fn variable<'a>(env: &'a Env, name: &str, location: &Location) -> Expansion<'a> {
-        env.variables
+pub fn resolve<'a, E: ReadEnv + ?Sized>(
+    env: &'a E,
+    param: &Param,
+    location: &Location,
+) -> Expansion<'a> {
+    fn variable<'a, E: ReadEnv + ?Sized>(
+        env: &'a E,
+        name: &str,
+        location: &Location,
+    ) -> Expansion<'a> {
+        env.variables()
             .get(name)
             .map_or(Expansion::Unset, |v| v.expand(location))
     }
-    fn options(env: &Env) -> Expansion {
+    fn options<E: ReadEnv + ?Sized>(env: &E) -> Expansion<'_> {
         let mut value = String::new();
         for option in yash_env::option::Option::iter() {
             if let Some((name, state)) = option.short_name() {
-                if state == env.options.get(option) {
+                if state == env.options().get(option) {
                     value.push(name);
                 }
             }
         }
         value.into()
     }
-    fn positional(env: &Env) -> &[String] {
-        &env.variables.positional_params().values
+    fn positional<E: ReadEnv + ?Sized>(env: &E) -> &[String] {
+        &env.variables().positional_params().values
     }
 
     match param.r#type {
         Variable => variable(env, &param.id, location),
         Special(At | Asterisk) => positional(env).into(),
         Special(Number) => positional(env).len().to_string().into(),
-        Special(Question) => env.exit_status.to_string().into(),
+        Special(Question) => env.exit_status().to_string().into(),
         Special(Hyphen) => options(env),
-        Special(Dollar) => env.main_pid.to_string().into(),
-        Special(Exclamation) => env.jobs.last_async_pid().to_string().into(),
-        Special(Zero) => env.arg0.as_str().into(),
+        Special(Dollar) => env.main_pid().to_string().into(),
+        Special(Exclamation) => env.last_async_pid().to_string().into(),
+        Special(Zero) => env.arg0().into(),
         Positional(0) => Expansion::Unset,
         Positional(index) => positional(env).get(index - 1).into(),
     }
@@ -63,9 +71,12 @@ pub fn resolve<'a>(env: &'a Env, param: &Param, location: &Location) -> Expansio
 mod tests {
     use super::*;
     use yash_env::job::Pid;
+    use yash_env::variable::Context;
+    use yash_env::variable::PositionalParams;
     use yash_env::variable::Scope;
     use yash_env::variable::Value;
     use yash_env::variable::PATH;
+    use yash_env::Env;
     use yash_syntax::source::Location;
     use yash_syntax::syntax::SpecialParam;
 
@@ -265,4 +276,40 @@ mod tests {
         let result = resolve(&env, &Param::from(2), &loc);
         assert_eq!(result, Expansion::Scalar("b".into()));
     }
+
+    #[test]
+    fn positional_with_multi_digit_index() {
+        let mut env = Env::new_virtual();
+        env.variables.positional_params_mut().values = ('a'..='k').map(String::from).collect();
+        let loc = Location::dummy("");
+
+        let result = resolve(&env, &Param::from(10), &loc);
+        assert_eq!(result, Expansion::Scalar("j".into()));
+        let result = resolve(&env, &Param::from(11), &loc);
+        assert_eq!(result, Expansion::Scalar("k".into()));
+        assert_eq!(resolve(&env, &Param::from(12), &loc), Expansion::Unset);
+    }
+
+    #[test]
+    fn positional_in_function_context() {
+        let mut env = Env::new_virtual();
+        env.variables.positional_params_mut().values = vec!["a".to_string(), "b".to_string()];
+        let guard = env.push_context(Context::Regular {
+            positional_params: PositionalParams {
+                values: vec!["x".to_string()],
+                last_modified_location: None,
+            },
+        });
+        let loc = Location::dummy("");
+
+        let result = resolve(&*guard, &Param::from(SpecialParam::Number), &loc);
+        assert_eq!(result, Expansion::Scalar("1".into()));
+        let result = resolve(&*guard, &Param::from(1), &loc);
+        assert_eq!(result, Expansion::Scalar("x".into()));
+        assert_eq!(resolve(&*guard, &Param::from(2), &loc), Expansion::Unset);
+
+        Env::pop_context(guard);
+        let result = resolve(&env, &Param::from(SpecialParam::Number), &loc);
+        assert_eq!(result, Expansion::Scalar("2".into()));
+    }
 }
@@ -0,0 +1,255 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Parameter expansion substitution semantics
+//!
+//! The pattern of a [`Subst`] is expanded and matched against the value in the
+//! same way as the pattern of a trim. The pattern may match anywhere in the
+//! value, and the longest match is replaced. Matches of an empty string are
+//! ignored, so an empty pattern leaves the value intact. The replacement is
+//! expanded without field splitting or pathname expansion and inserted into
+//! the value literally.
+//!
+//! If the value is an array, the substitution is applied to each element.
+
+use super::Env;
+use super::Error;
+use crate::expansion::attr::fnmatch::apply_escapes;
+use crate::expansion::attr::fnmatch::to_pattern_chars;
+use crate::expansion::attr_strip::Strip as _;
+use crate::expansion::initial::Expand as _;
+use crate::expansion::initial::ExpandEnv;
+use crate::expansion::pattern_options::PatternOptions;
+use crate::expansion::quote_removal::skip_quotes;
+use yash_env::variable::Value::{self, Array, Scalar};
+use yash_fnmatch::Pattern;
+use yash_syntax::syntax::Subst;
+use yash_syntax::syntax::SubstCount;
+
+fn subst_value(pattern: &Pattern, replacement: &str, count: SubstCount, value: &mut String) {
+    let mut result = String::new();
+    let mut pos = 0;
+    while let Some(range) = pattern.find(&value[pos..]) {
+        let start = pos + range.start;
+        let end = pos + range.end;
+
+        if start == end {
+            // Skip the empty match and look for a non-empty match after it
+            let Some(c) = value[start..].chars().next() else {
+                break;
+            };
+            let next = start + c.len_utf8();
+            result.push_str(&value[pos..next]);
+            pos = next;
+            continue;
+        }
+
+        result.push_str(&value[pos..start]);
+        result.push_str(replacement);
+        pos = end;
+
+        if count == SubstCount::First {
+            break;
+        }
+    }
+    result.push_str(&value[pos..]);
+    *value = result;
+}
+
+/// Applies the substitution modifier to the value.
+pub async fn apply<E: ExpandEnv>(
+    env: &mut Env<'_, E>,
+    subst: &Subst,
+    value: &mut Value,
+) -> Result<(), Error> {
+    let expansion = subst.pattern.expand(env).await?;
+    let mut pattern = expansion.ifs_join(env.inner.variables());
+    apply_escapes(&mut pattern);
+
+    let expansion = subst.replacement.expand(env).await?;
+    let replacement = expansion.ifs_join(env.inner.variables());
+    let replacement: String = skip_quotes(replacement).strip().collect();
+
+    let config = PatternOptions::from_options(env.inner.options()).subst_config();
+    let pattern = match Pattern::parse_with_config(to_pattern_chars(&pattern), config) {
+        Ok(parse) => parse,
+        Err(_error) => {
+            // Treat the broken pattern as a valid pattern that does not match anything
+            return Ok(());
+        }
+    };
+
+    match value {
+        Scalar(value) => subst_value(&pattern, &replacement, subst.count, value),
+        Array(array) => {
+            for value in array {
+                subst_value(&pattern, &replacement, subst.count, value);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt;
+    use yash_env::option::Option::CaseGlob;
+    use yash_env::option::State::Off;
+    use yash_env::variable::Scope::Global;
+    use yash_syntax::syntax::SubstCount::{All, First};
+
+    fn apply_to_scalar(value: &str, count: SubstCount, pattern: &str, replacement: &str) -> String {
+        let mut env = yash_env::Env::new_virtual();
+        let mut env = Env::new(&mut env);
+        let subst = Subst {
+            count,
+            pattern: pattern.parse().unwrap(),
+            replacement: replacement.parse().unwrap(),
+        };
+        let mut value = Value::scalar(value);
+        let result = apply(&mut env, &subst, &mut value).now_or_never().unwrap();
+        assert_eq!(result, Ok(()));
+        match value {
+            Scalar(value) => value,
+            Array(_) => panic!("unexpected array: {value:?}"),
+        }
+    }
+
+    #[test]
+    fn first_match() {
+        assert_eq!(apply_to_scalar("abcabc", First, "b", "X"), "aXcabc");
+        assert_eq!(apply_to_scalar("abcabc", First, "abc", "X"), "Xabc");
+        assert_eq!(apply_to_scalar("abcabc", First, "c", "X"), "abXabc");
+    }
+
+    #[test]
+    fn all_matches() {
+        assert_eq!(apply_to_scalar("abcabc", All, "b", "X"), "aXcaXc");
+        assert_eq!(apply_to_scalar("aaaa", All, "aa", "b"), "bb");
+        assert_eq!(apply_to_scalar("aaa", All, "aa", "b"), "ba");
+    }
+
+    #[test]
+    fn unmatched_pattern() {
+        assert_eq!(apply_to_scalar("abc", First, "x", "X"), "abc");
+        assert_eq!(apply_to_scalar("abc", All, "x", "X"), "abc");
+        assert_eq!(apply_to_scalar("", All, "x", "X"), "");
+    }
+
+    #[test]
+    fn longest_match() {
+        assert_eq!(apply_to_scalar("abcabc", First, "b*", "X"), "aX");
+        assert_eq!(apply_to_scalar("abcabc", All, "?", "X"), "XXXXXX");
+        assert_eq!(apply_to_scalar("abcabc", All, "[ab]", ""), "cc");
+    }
+
+    #[test]
+    fn empty_pattern() {
+        assert_eq!(apply_to_scalar("abc", First, "", "X"), "abc");
+        assert_eq!(apply_to_scalar("abc", All, "", "X"), "abc");
+        assert_eq!(apply_to_scalar("", All, "", "X"), "");
+    }
+
+    #[test]
+    fn empty_matches_are_skipped() {
+        assert_eq!(apply_to_scalar("", First, "*", "X"), "");
+        assert_eq!(apply_to_scalar("", All, "*", "X"), "");
+        assert_eq!(apply_to_scalar("bab", First, "a*", "X"), "bX");
+    }
+
+    #[test]
+    fn empty_replacement() {
+        assert_eq!(apply_to_scalar("abcabc", First, "b", ""), "acabc");
+        assert_eq!(apply_to_scalar("abcabc", All, "b", ""), "acac");
+        assert_eq!(apply_to_scalar("abc", All, "*", ""), "");
+    }
+
+    #[test]
+    fn replacement_is_literal() {
+        assert_eq!(apply_to_scalar("abc", First, "b", "*"), "a*c");
+        assert_eq!(apply_to_scalar("abc", First, "b", "&"), "a&c");
+        assert_eq!(apply_to_scalar("abc", First, "b", r"'\'"), r"a\c");
+        assert_eq!(apply_to_scalar("abc", First, "b", r#""x y""#), "ax yc");
+    }
+
+    #[test]
+    fn quoted_pattern_is_literal() {
+        assert_eq!(apply_to_scalar("a*b*c", All, r"\*", "-"), "a-b-c");
+        assert_eq!(apply_to_scalar("a*b?c", All, "'?'", "-"), "a*b-c");
+    }
+
+    #[test]
+    fn multibyte_characters() {
+        assert_eq!(apply_to_scalar("αβγβ", All, "β", "b"), "αbγb");
+        assert_eq!(apply_to_scalar("αβγ", All, "x*", "b"), "αβγ");
+    }
+
+    #[test]
+    fn expanded_pattern_and_replacement() {
+        let mut env = yash_env::Env::new_virtual();
+        env.variables
+            .get_or_new("p", Global)
+            .assign("b?", None)
+            .unwrap();
+        env.variables
+            .get_or_new("r", Global)
+            .assign("1 2", None)
+            .unwrap();
+        let mut env = Env::new(&mut env);
+        let subst = Subst {
+            count: All,
+            pattern: "$p".parse().unwrap(),
+            replacement: "$r".parse().unwrap(),
+        };
+        let mut value = Value::scalar("abcabc");
+        let result = apply(&mut env, &subst, &mut value).now_or_never().unwrap();
+        assert_eq!(result, Ok(()));
+        assert_eq!(value, Value::scalar("a1 2a1 2"));
+    }
+
+    #[test]
+    fn array_value() {
+        let mut env = yash_env::Env::new_virtual();
+        let mut env = Env::new(&mut env);
+        let subst = Subst {
+            count: First,
+            pattern: "a".parse().unwrap(),
+            replacement: "x".parse().unwrap(),
+        };
+        let mut value = Value::array(["aa", "ba", "c"]);
+        let result = apply(&mut env, &subst, &mut value).now_or_never().unwrap();
+        assert_eq!(result, Ok(()));
+        assert_eq!(value, Value::array(["xa", "bx", "c"]));
+    }
+
+    #[test]
+    fn case_insensitive_with_caseglob_off() {
+        let mut env = yash_env::Env::new_virtual();
+        env.options.set(CaseGlob, Off);
+        let mut env = Env::new(&mut env);
+        let subst = Subst {
+            count: All,
+            pattern: "a".parse().unwrap(),
+            replacement: "x".parse().unwrap(),
+        };
+        let mut value = Value::scalar("aAbA");
+        let result = apply(&mut env, &subst, &mut value).now_or_never().unwrap();
+        assert_eq!(result, Ok(()));
+        assert_eq!(value, Value::scalar("xxbx"));
+    }
+}
@@ -0,0 +1,274 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Parameter expansion substring semantics
+//!
+//! The offset and length of a [`Substring`] are evaluated as arithmetic
+//! expressions. A negative offset counts from the end of the value. A negative
+//! length specifies the number of characters to be left at the end of the
+//! value. The resulting range is clamped to the value, so an offset beyond the
+//! end of the value yields an empty string rather than an error. An empty
+//! offset or length is regarded as zero.
+//!
+//! For a scalar value, the offset and length count characters. For an array
+//! value, they count elements from zero. The value of `${@:offset:length}` and
+//! `${*:offset:length}` is the positional parameters preceded by `$0`, so that
+//! `${@:1:3}` expands to `$1`, `$2`, and `$3` and `${@:0:1}` expands to `$0`,
+//! as in other shells. The `$0` is added by the caller of [`apply`].
+
+use super::super::arith::evaluate;
+use super::super::arith::expand_expression;
+use super::Env;
+use super::Error;
+use crate::expansion::initial::ExpandEnv;
+use std::ops::Range;
+use yash_arith::Value::Integer;
+use yash_env::variable::Value::{self, Array, Scalar};
+use yash_syntax::source::Location;
+use yash_syntax::syntax::Substring;
+use yash_syntax::syntax::Text;
+
+/// Expands and evaluates the text as an arithmetic expression.
+///
+/// An empty expression evaluates to zero.
+async fn evaluate_number<E: ExpandEnv>(
+    text: &Text,
+    location: &Location,
+    env: &mut Env<'_, E>,
+) -> Result<i64, Error> {
+    let expression = expand_expression(text, env).await?;
+    if expression.trim().is_empty() {
+        return Ok(0);
+    }
+    let Integer(value) = evaluate(&expression, location, env.inner)?;
+    Ok(value)
+}
+
+/// Computes the range of the substring in a sequence of `len` items.
+fn range(len: usize, offset: i64, length: Option<i64>) -> Range<usize> {
+    let len_i64 = i64::try_from(len).unwrap_or(i64::MAX);
+    let resolve = |index: i64| {
+        let index = if index < 0 {
+            len_i64.saturating_add(index)
+        } else {
+            index
+        };
+        index.clamp(0, len_i64) as usize
+    };
+
+    let start = resolve(offset);
+    let end = match length {
+        None => len,
+        Some(length) if length < 0 => resolve(length),
+        Some(length) => start.saturating_add(length.try_into().unwrap_or(usize::MAX)),
+    };
+    let end = end.clamp(start, len);
+    start..end
+}
+
+/// Applies the substring modifier to the value.
+pub async fn apply<E: ExpandEnv>(
+    env: &mut Env<'_, E>,
+    substring: &Substring,
+    value: &mut Value,
+    location: &Location,
+) -> Result<(), Error> {
+    let offset = evaluate_number(&substring.offset, location, env).await?;
+    let length = match &substring.length {
+        None => None,
+        Some(length) => Some(evaluate_number(length, location, env).await?),
+    };
+
+    match value {
+        Scalar(value) => {
+            let range = range(value.chars().count(), offset, length);
+            *value = value.chars().skip(range.start).take(range.len()).collect();
+        }
+        Array(values) => {
+            let range = range(values.len(), offset, length);
+            values.truncate(range.end);
+            values.drain(..range.start);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expansion::ErrorCause;
+    use assert_matches::assert_matches;
+    use futures_util::FutureExt;
+    use yash_env::option::Option::Unset;
+    use yash_env::option::State::Off;
+    use yash_env::variable::Scope::Global;
+
+    fn substring(offset: &str, length: Option<&str>) -> Substring {
+        Substring {
+            offset: offset.parse().unwrap(),
+            length: length.map(|length| length.parse().unwrap()),
+        }
+    }
+
+    fn apply_to_scalar(value: &str, offset: &str, length: Option<&str>) -> String {
+        let mut env = yash_env::Env::new_virtual();
+        let mut env = Env::new(&mut env);
+        let substring = substring(offset, length);
+        let mut value = Value::scalar(value);
+        let location = Location::dummy("");
+        let result = apply(&mut env, &substring, &mut value, &location)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, Ok(()));
+        match value {
+            Scalar(value) => value,
+            Array(_) => panic!("unexpected array: {value:?}"),
+        }
+    }
+
+    #[test]
+    fn range_with_offset_only() {
+        assert_eq!(range(5, 0, None), 0..5);
+        assert_eq!(range(5, 2, None), 2..5);
+        assert_eq!(range(5, 5, None), 5..5);
+        assert_eq!(range(5, 6, None), 5..5);
+        assert_eq!(range(5, i64::MAX, None), 5..5);
+        assert_eq!(range(0, 1, None), 0..0);
+    }
+
+    #[test]
+    fn range_with_negative_offset() {
+        assert_eq!(range(5, -1, None), 4..5);
+        assert_eq!(range(5, -5, None), 0..5);
+        assert_eq!(range(5, -6, None), 0..5);
+        assert_eq!(range(5, i64::MIN, None), 0..5);
+    }
+
+    #[test]
+    fn range_with_positive_length() {
+        assert_eq!(range(5, 1, Some(0)), 1..1);
+        assert_eq!(range(5, 1, Some(2)), 1..3);
+        assert_eq!(range(5, 1, Some(4)), 1..5);
+        assert_eq!(range(5, 1, Some(10)), 1..5);
+        assert_eq!(range(5, -2, Some(1)), 3..4);
+        assert_eq!(range(5, 7, Some(1)), 5..5);
+        assert_eq!(range(5, 1, Some(i64::MAX)), 1..5);
+    }
+
+    #[test]
+    fn range_with_negative_length() {
+        assert_eq!(range(5, 1, Some(-1)), 1..4);
+        assert_eq!(range(5, 1, Some(-4)), 1..1);
+        assert_eq!(range(5, 1, Some(-5)), 1..1);
+        assert_eq!(range(5, 3, Some(-3)), 3..3);
+        assert_eq!(range(5, -3, Some(-1)), 2..4);
+        assert_eq!(range(5, 1, Some(i64::MIN)), 1..1);
+    }
+
+    #[test]
+    fn scalar_substrings() {
+        assert_eq!(apply_to_scalar("abcde", "1", None), "bcde");
+        assert_eq!(apply_to_scalar("abcde", "1", Some("2")), "bc");
+        assert_eq!(apply_to_scalar("abcde", "-2", None), "de");
+        assert_eq!(apply_to_scalar("abcde", "-2", Some("1")), "d");
+        assert_eq!(apply_to_scalar("abcde", "1", Some("-1")), "bcd");
+        assert_eq!(apply_to_scalar("abcde", "9", None), "");
+        assert_eq!(apply_to_scalar("abcde", "-9", Some("2")), "ab");
+        assert_eq!(apply_to_scalar("abcde", "4", Some("-2")), "");
+        assert_eq!(apply_to_scalar("", "0", Some("1")), "");
+    }
+
+    #[test]
+    fn scalar_substrings_count_characters() {
+        assert_eq!(apply_to_scalar("αβγδ", "1", Some("2")), "βγ");
+        assert_eq!(apply_to_scalar("αβγδ", "-1", None), "δ");
+    }
+
+    #[test]
+    fn empty_offset_and_length() {
+        assert_eq!(apply_to_scalar("abcde", "", None), "abcde");
+        assert_eq!(apply_to_scalar("abcde", "", Some("2")), "ab");
+        assert_eq!(apply_to_scalar("abcde", "1", Some("")), "");
+    }
+
+    #[test]
+    fn arithmetic_offset_and_length() {
+        assert_eq!(apply_to_scalar("abcdef", "1+1", Some("2*2-1")), "cde");
+        assert_eq!(apply_to_scalar("abcdef", " -(1+1)", None), "ef");
+    }
+
+    #[test]
+    fn offset_and_length_with_variables() {
+        let mut env = yash_env::Env::new_virtual();
+        env.variables
+            .get_or_new("i", Global)
+            .assign("2", None)
+            .unwrap();
+        let mut env = Env::new(&mut env);
+        let substring = substring("$i", Some("i"));
+        let mut value = Value::scalar("0123456");
+        let location = Location::dummy("");
+        let result = apply(&mut env, &substring, &mut value, &location)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, Ok(()));
+        assert_eq!(value, Value::scalar("23"));
+    }
+
+    #[test]
+    fn array_substrings() {
+        let mut env = yash_env::Env::new_virtual();
+        let mut env = Env::new(&mut env);
+        let location = Location::dummy("");
+
+        let mut value = Value::array(["a", "b", "c", "d"]);
+        let result = apply(&mut env, &substring("1", Some("2")), &mut value, &location)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, Ok(()));
+        assert_eq!(value, Value::array(["b", "c"]));
+
+        let mut value = Value::array(["a", "b", "c", "d"]);
+        let result = apply(&mut env, &substring("-1", None), &mut value, &location)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, Ok(()));
+        assert_eq!(value, Value::array(["d"]));
+
+        let mut value = Value::array(["a", "b", "c", "d"]);
+        let result = apply(&mut env, &substring("5", None), &mut value, &location)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, Ok(()));
+        assert_eq!(value, Value::Array(vec![]));
+    }
+
+    #[test]
+    fn arithmetic_error() {
+        let mut env = yash_env::Env::new_virtual();
+        env.options.set(Unset, Off);
+        let mut env = Env::new(&mut env);
+        let mut value = Value::scalar("abc");
+        let location = Location::dummy("");
+        let result = apply(&mut env, &substring("x", None), &mut value, &location)
+            .now_or_never()
+            .unwrap();
+        let error = result.unwrap_err();
+        assert_matches!(error.cause, ErrorCause::UnsetParameter { .. });
+        assert_eq!(value, Value::scalar("abc"));
+    }
+}
@@ -21,12 +21,10 @@ use super::Error;
 use super::Phrase;
 use crate::expansion::attr::Origin;
 use crate::expansion::attr_strip::Strip;
-use crate::expansion::expand_word;
 use crate::expansion::initial::Expand as _;
+use crate::expansion::initial::ExpandEnv;
 use crate::expansion::quote_removal::skip_quotes;
-use crate::expansion::AssignReadOnlyError;
 use crate::expansion::ErrorCause;
-use yash_env::variable::Scope;
 use yash_env::variable::Value;
 use yash_syntax::source::Location;
 use yash_syntax::syntax::Param;
@@ -187,8 +185,8 @@ fn attribute(mut phrase: Phrase) -> Phrase {
 }
 
 /// Assigns the expansion of `value` to variable `name`.
-async fn assign(
-    env: &mut Env<'_>,
+async fn assign<E: ExpandEnv>(
+    env: &mut Env<'_, E>,
     param: &Param,
     vacancy: Vacancy,
     value: &Word,
@@ -202,43 +200,36 @@ async fn assign(
         return Err(Error { cause, location });
     }
     let value_phrase = attribute(value.expand(env).await?);
-    let joined_value = value_phrase.clone().ifs_join(&env.inner.variables);
+    let joined_value = value_phrase.clone().ifs_join(env.inner.variables());
     let final_value = skip_quotes(joined_value).strip().collect::<String>();
     env.inner
-        .get_or_create_variable(&param.id, Scope::Global)
-        .assign(final_value, location)
-        .map_err(|e| {
-            let location = e.assigned_location.unwrap();
-            let cause = ErrorCause::AssignReadOnly(AssignReadOnlyError {
-                name: param.id.to_owned(),
-                new_value: e.new_value,
-                read_only_location: e.read_only_location,
-                vacancy: Some(vacancy),
-            });
+        .assign_variable(&param.id, final_value, location.clone())
+        .map_err(|mut cause| {
+            if let ErrorCause::AssignReadOnly(e) = &mut cause {
+                e.vacancy = Some(vacancy);
+            }
             Error { cause, location }
         })?;
     Ok(value_phrase)
 }
 
 /// Expands a word to be used as a vacant expansion error message.
-async fn vacant_expansion_error_message(
-    env: &mut Env<'_>,
+async fn vacant_expansion_error_message<E: ExpandEnv>(
+    env: &mut Env<'_, E>,
     message_word: &Word,
 ) -> Result<Option<String>, Error> {
     if message_word.units.is_empty() {
         return Ok(None);
     }
 
-    let (message_field, exit_status) = expand_word(env.inner, message_word).await?;
-    if exit_status.is_some() {
-        env.last_command_subst_exit_status = exit_status;
-    }
-    Ok(Some(message_field.value))
+    let phrase = message_word.expand(env).await?;
+    let chars = phrase.ifs_join(env.inner.variables());
+    Ok(Some(skip_quotes(chars).strip().collect()))
 }
 
 /// Constructs a vacant expansion error.
-async fn vacant_expansion_error(
-    env: &mut Env<'_>,
+async fn vacant_expansion_error<E: ExpandEnv>(
+    env: &mut Env<'_, E>,
     param: &Param,
     vacancy: Vacancy,
     message_word: &Word,
@@ -261,8 +252,8 @@ async fn vacant_expansion_error(
 /// If this function returns `Some(_)`, that should be the result of the whole
 /// parameter expansion containing the switch. Otherwise, the parameter
 /// expansion should continue processing other modifiers.
-pub async fn apply(
-    env: &mut Env<'_>,
+pub async fn apply<E: ExpandEnv>(
+    env: &mut Env<'_, E>,
     switch: &Switch,
     param: &Param,
     value: Option<&Value>,
@@ -300,6 +291,7 @@ mod tests {
     use crate::expansion::attr::AttrChar;
     use assert_matches::assert_matches;
     use futures_util::FutureExt;
+    use yash_env::variable::Scope;
     use yash_env::variable::IFS;
     use yash_syntax::syntax::SpecialParam;
     use yash_syntax::syntax::SwitchCondition::*;
@@ -407,6 +399,70 @@ mod tests {
         assert_eq!(result, Some(Ok(Phrase::Field(to_field("foo")))));
     }
 
+    #[test]
+    fn alter_with_empty_scalar() {
+        let mut env = yash_env::Env::new_virtual();
+        let mut env = Env::new(&mut env);
+        let param = Param::variable("var");
+        let value = Value::scalar("");
+        let location = Location::dummy("somewhere");
+
+        let switch = Switch {
+            r#type: Alter,
+            condition: Unset,
+            word: "foo".parse().unwrap(),
+        };
+        let result = apply(&mut env, &switch, &param, Some(&value), &location)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, Some(Ok(Phrase::Field(to_field("foo")))));
+
+        let switch = Switch {
+            r#type: Alter,
+            condition: UnsetOrEmpty,
+            word: "foo".parse().unwrap(),
+        };
+        let result = apply(&mut env, &switch, &param, Some(&value), &location)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn alter_with_quoted_word() {
+        let mut env = yash_env::Env::new_virtual();
+        let mut env = Env::new(&mut env);
+        let switch = Switch {
+            r#type: Alter,
+            condition: UnsetOrEmpty,
+            word: r#""q""#.parse().unwrap(),
+        };
+        let param = Param::variable("var");
+        let value = Value::scalar("bar");
+        let location = Location::dummy("somewhere");
+
+        let result = apply(&mut env, &switch, &param, Some(&value), &location)
+            .now_or_never()
+            .unwrap();
+
+        let quoting = AttrChar {
+            value: '"',
+            origin: Origin::SoftExpansion,
+            is_quoted: false,
+            is_quoting: true,
+        };
+        let quoted = AttrChar {
+            value: 'q',
+            origin: Origin::SoftExpansion,
+            is_quoted: true,
+            is_quoting: false,
+        };
+        assert_eq!(
+            result,
+            Some(Ok(Phrase::Field(vec![quoting, quoted, quoting])))
+        );
+    }
+
     #[test]
     fn default_with_vacant_value() {
         let mut env = yash_env::Env::new_virtual();
@@ -606,6 +662,33 @@ mod tests {
         assert_eq!(error.location, location);
     }
 
+    #[test]
+    fn assign_to_positional_parameter() {
+        let mut env = yash_env::Env::new_virtual();
+        let mut env = Env::new(&mut env);
+        let switch = Switch {
+            r#type: Assign,
+            condition: UnsetOrEmpty,
+            word: "foo".parse().unwrap(),
+        };
+        let param = Param::from(1);
+        let location = Location::dummy("somewhere");
+
+        let result = apply(&mut env, &switch, &param, None, &location)
+            .now_or_never()
+            .unwrap();
+        let error = result.unwrap().unwrap_err();
+        assert_matches!(
+            error.cause,
+            ErrorCause::NonassignableParameter(error) => {
+                assert_eq!(error.cause, NonassignableErrorCause::NotVariable { param });
+                assert_eq!(error.vacancy, Vacancy::Unset);
+            }
+        );
+        assert_eq!(error.location, location);
+        assert_eq!(env.inner.variables.get("1"), None);
+    }
+
     #[test]
     fn error_with_vacant_value_and_non_empty_word() {
         let mut env = yash_env::Env::new_virtual();
@@ -692,4 +775,48 @@ mod tests {
             .unwrap();
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn error_with_set_value_does_not_expand_word() {
+        let mut env = yash_env::Env::new_virtual();
+        let mut env = Env::new(&mut env);
+        let switch = Switch {
+            r#type: Error,
+            condition: UnsetOrEmpty,
+            word: "${x=assigned}".parse().unwrap(),
+        };
+        let param = Param::variable("var");
+        let value = Value::scalar("value");
+        let location = Location::dummy("somewhere");
+        let result = apply(&mut env, &switch, &param, Some(&value), &location)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, None);
+        assert_eq!(env.inner.variables.get("x"), None);
+    }
+
+    #[test]
+    fn error_with_vacant_value_expands_word() {
+        let mut env = yash_env::Env::new_virtual();
+        let mut env = Env::new(&mut env);
+        let switch = Switch {
+            r#type: Error,
+            condition: Unset,
+            word: "${x=assigned} message".parse().unwrap(),
+        };
+        let param = Param::variable("var");
+        let location = Location::dummy("somewhere");
+        let result = apply(&mut env, &switch, &param, None, &location)
+            .now_or_never()
+            .unwrap();
+        let error = result.unwrap().unwrap_err();
+        assert_eq!(error.location, location);
+        assert_matches!(error.cause, ErrorCause::VacantExpansion(e) => {
+            assert_eq!(e.message, Some("assigned message".to_string()));
+        });
+        assert_eq!(
+            env.inner.variables.get("x").unwrap().value,
+            Some(Value::scalar("assigned"))
+        );
+    }
 }
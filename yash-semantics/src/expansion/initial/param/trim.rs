@@ -21,12 +21,11 @@ use super::Error;
 use crate::expansion::attr::fnmatch::apply_escapes;
 use crate::expansion::attr::fnmatch::to_pattern_chars;
 use crate::expansion::initial::Expand as _;
+use crate::expansion::initial::ExpandEnv;
+use crate::expansion::pattern_options::PatternOptions;
 use yash_env::variable::Value::{self, Array, Scalar};
-use yash_fnmatch::Config;
 use yash_fnmatch::Pattern;
 use yash_syntax::syntax::Trim;
-use yash_syntax::syntax::TrimLength::{Longest, Shortest};
-use yash_syntax::syntax::TrimSide::{Prefix, Suffix};
 
 fn trim_value(pattern: &Pattern, value: &mut String) {
     let config = pattern.config();
@@ -41,20 +40,17 @@ fn trim_value(pattern: &Pattern, value: &mut String) {
 }
 
 /// Applies the trim modifier to the value.
-pub async fn apply(env: &mut Env<'_>, trim: &Trim, value: &mut Value) -> Result<(), Error> {
+pub async fn apply<E: ExpandEnv>(
+    env: &mut Env<'_, E>,
+    trim: &Trim,
+    value: &mut Value,
+) -> Result<(), Error> {
     let expansion = trim.pattern.expand(env).await?;
-    let mut pattern = expansion.ifs_join(&env.inner.variables);
+    let mut pattern = expansion.ifs_join(env.inner.variables());
     apply_escapes(&mut pattern);
 
-    let mut config = Config::default();
-    match trim.side {
-        Prefix => config.anchor_begin = true,
-        Suffix => config.anchor_end = true,
-    }
-    match trim.length {
-        Shortest => config.shortest_match = true,
-        Longest => (),
-    }
+    let config =
+        PatternOptions::from_options(env.inner.options()).trim_config(trim.side, trim.length);
     let pattern = match Pattern::parse_with_config(to_pattern_chars(&pattern), config) {
         Ok(parse) => parse,
         Err(_error) => {
@@ -79,6 +75,10 @@ pub async fn apply(env: &mut Env<'_>, trim: &Trim, value: &mut Value) -> Result<
 mod tests {
     use super::*;
     use futures_util::FutureExt;
+    use yash_env::option::Option::CaseGlob;
+    use yash_env::option::State::Off;
+    use yash_syntax::syntax::TrimLength::{Longest, Shortest};
+    use yash_syntax::syntax::TrimSide::{Prefix, Suffix};
 
     #[test]
     fn shortest_prefix_with_scalar() {
@@ -184,4 +184,81 @@ mod tests {
         assert_eq!(result, Ok(()));
         assert_eq!(value, Value::scalar("123123123"));
     }
+
+    #[test]
+    fn case_insensitive_without_caseglob() {
+        let mut env = yash_env::Env::new_virtual();
+        env.options.set(CaseGlob, Off);
+        let mut env = Env::new(&mut env);
+        let trim = Trim {
+            side: Suffix,
+            length: Shortest,
+            pattern: "txt".parse().unwrap(),
+        };
+        let mut value = Value::scalar("file.TXT");
+        let result = apply(&mut env, &trim, &mut value).now_or_never().unwrap();
+        assert_eq!(result, Ok(()));
+        assert_eq!(value, Value::scalar("file."));
+    }
+
+    #[test]
+    fn path_components() {
+        let mut env = yash_env::Env::new_virtual();
+        let mut env = Env::new(&mut env);
+        let cases = [
+            (Suffix, Shortest, "a/b"),
+            (Suffix, Longest, "a"),
+            (Prefix, Shortest, "b/c"),
+            (Prefix, Longest, "c"),
+        ];
+        for (side, length, expected) in cases {
+            let pattern = match side {
+                Prefix => "*/",
+                Suffix => "/*",
+            };
+            let trim = Trim {
+                side,
+                length,
+                pattern: pattern.parse().unwrap(),
+            };
+            let mut value = Value::scalar("a/b/c");
+            let result = apply(&mut env, &trim, &mut value).now_or_never().unwrap();
+            assert_eq!(result, Ok(()));
+            assert_eq!(value, Value::scalar(expected), "{side:?} {length:?}");
+        }
+    }
+
+    #[test]
+    fn quoted_pattern_characters_are_literal() {
+        let mut env = yash_env::Env::new_virtual();
+        let mut env = Env::new(&mut env);
+        let trim = Trim {
+            side: Suffix,
+            length: Longest,
+            pattern: r#""*"\?"#.parse().unwrap(),
+        };
+        let mut value = Value::scalar("a*?b*?");
+        let result = apply(&mut env, &trim, &mut value).now_or_never().unwrap();
+        assert_eq!(result, Ok(()));
+        assert_eq!(value, Value::scalar("a*?b"));
+    }
+
+    #[test]
+    fn pattern_is_expanded() {
+        let mut env = yash_env::Env::new_virtual();
+        env.variables
+            .get_or_new("p", yash_env::variable::Scope::Global)
+            .assign("?", None)
+            .unwrap();
+        let mut env = Env::new(&mut env);
+        let trim = Trim {
+            side: Prefix,
+            length: Shortest,
+            pattern: "$p$p".parse().unwrap(),
+        };
+        let mut value = Value::scalar("abc");
+        let result = apply(&mut env, &trim, &mut value).now_or_never().unwrap();
+        assert_eq!(result, Ok(()));
+        assert_eq!(value, Value::scalar("c"));
+    }
 }
@@ -19,6 +19,7 @@
 use super::super::Error;
 use super::Env;
 use super::Expand;
+use super::ExpandEnv;
 use super::Phrase;
 
 /// Expands a slice of expandable items.
@@ -30,7 +31,7 @@ use super::Phrase;
 /// If the slice has no item, the result is [one empty
 /// field](Phrase::one_empty_field).
 impl<T: Expand> Expand for [T] {
-    async fn expand(&self, env: &mut Env<'_>) -> Result<Phrase, Error> {
+    async fn expand<E: ExpandEnv>(&self, env: &mut Env<'_, E>) -> Result<Phrase, Error> {
         if self.is_empty() {
             return Ok(Phrase::one_empty_field());
         }
@@ -70,7 +71,7 @@ mod tests {
     }
 
     impl Expand for Stub {
-        async fn expand(&self, _: &mut Env<'_>) -> Result<Phrase, Error> {
+        async fn expand<E: ExpandEnv>(&self, _: &mut Env<'_, E>) -> Result<Phrase, Error> {
             self.0.take().expect("expand should be called only once")
         }
     }
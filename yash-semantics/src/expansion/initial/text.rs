@@ -22,6 +22,7 @@ use super::super::Error;
 use super::param::ParamRef;
 use super::Env;
 use super::Expand;
+use super::ExpandEnv;
 use super::Phrase;
 use yash_syntax::syntax::Text;
 use yash_syntax::syntax::TextUnit::{self, *};
@@ -76,7 +77,7 @@ use yash_syntax::syntax::Unquote;
 ///
 /// TODO Elaborate on index and modifiers
 impl Expand for TextUnit {
-    async fn expand(&self, env: &mut Env<'_>) -> Result<Phrase, Error> {
+    async fn expand<E: ExpandEnv>(&self, env: &mut Env<'_, E>) -> Result<Phrase, Error> {
         match self {
             &Literal(value) => Ok(Phrase::Char(AttrChar {
                 value,
@@ -120,7 +121,7 @@ impl Expand for TextUnit {
             }
 
             Backquote { content, location } => {
-                let command = content.unquote().0;
+                let command = content.unquote().0.into();
                 let location = location.clone();
                 super::command_subst::expand(command, location, env).await
             }
@@ -137,7 +138,7 @@ impl Expand for TextUnit {
 ///
 /// This implementation delegates to `[TextUnit] as Expand`.
 impl Expand for Text {
-    async fn expand(&self, env: &mut Env<'_>) -> Result<Phrase, Error> {
+    async fn expand<E: ExpandEnv>(&self, env: &mut Env<'_, E>) -> Result<Phrase, Error> {
         self.0.expand(env).await
     }
 }
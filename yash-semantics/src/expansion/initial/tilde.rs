@@ -18,10 +18,8 @@
 
 use crate::expansion::attr::AttrChar;
 use crate::expansion::attr::Origin;
-use std::ffi::CString;
+use crate::expansion::initial::ReadEnv;
 use yash_env::variable::HOME;
-use yash_env::Env;
-use yash_env::System;
 
 fn into_attr_chars<I>(i: I) -> Vec<AttrChar>
 where
@@ -38,18 +36,20 @@ where
 }
 
 /// Performs tilde expansion.
-pub fn expand(name: &str, env: &Env) -> Vec<AttrChar> {
+///
+/// An empty `name` expands to the value of `$HOME`. A non-empty `name` expands
+/// to the [home directory](ReadEnv::home_directory) of the user. If the
+/// expansion fails, the tilde prefix is left unexpanded.
+///
+/// The result is marked [`Origin::HardExpansion`] so that it is not subject to
+/// field splitting or pathname expansion.
+pub fn expand<E: ReadEnv + ?Sized>(name: &str, env: &E) -> Vec<AttrChar> {
     if name.is_empty() {
-        let result = env.variables.get_scalar(HOME).unwrap_or("~");
+        let result = env.variables().get_scalar(HOME).unwrap_or("~");
         into_attr_chars(result.chars())
+    } else if let Some(path) = env.home_directory(name) {
+        into_attr_chars(path.chars())
     } else {
-        if let Ok(name) = CString::new(name) {
-            if let Ok(Some(path)) = env.system.getpwnam_dir(&name) {
-                if let Ok(path) = path.into_unix_string().into_string() {
-                    return into_attr_chars(path.chars());
-                }
-            }
-        }
         into_attr_chars(std::iter::once('~').chain(name.chars()))
     }
 }
@@ -60,6 +60,7 @@ mod tests {
     use yash_env::path::PathBuf;
     use yash_env::variable::Scope;
     use yash_env::variable::Value;
+    use yash_env::Env;
     use yash_env::VirtualSystem;
 
     #[test]
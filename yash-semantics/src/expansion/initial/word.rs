@@ -21,6 +21,7 @@ use super::super::attr::Origin;
 use super::super::Error;
 use super::Env;
 use super::Expand;
+use super::ExpandEnv;
 use super::Phrase;
 use yash_syntax::syntax::Unquote as _;
 use yash_syntax::syntax::Word;
@@ -127,7 +128,7 @@ fn double_quote(phrase: &mut Phrase) {
 ///
 /// TODO: `~+`, `~-`, `~+n`, `~-n`
 impl Expand for WordUnit {
-    async fn expand(&self, env: &mut Env<'_>) -> Result<Phrase, Error> {
+    async fn expand<E: ExpandEnv>(&self, env: &mut Env<'_, E>) -> Result<Phrase, Error> {
         match self {
             Unquoted(text_unit) => text_unit.expand(env).await,
             SingleQuote(value) => Ok(single_quote(value)),
@@ -141,7 +142,7 @@ impl Expand for WordUnit {
                 Ok(phrase)
             }
             DollarSingleQuote(string) => Ok(dollar_single_quote(&string.unquote().0)),
-            Tilde(name) => Ok(super::tilde::expand(name, env.inner).into()),
+            Tilde(name) => Ok(super::tilde::expand(name, &*env.inner).into()),
         }
     }
 }
@@ -151,7 +152,7 @@ impl Expand for WordUnit {
 /// This implementation delegates to `[WordUnit] as Expand`.
 impl Expand for Word {
     #[inline]
-    async fn expand(&self, env: &mut Env<'_>) -> Result<Phrase, Error> {
+    async fn expand<E: ExpandEnv>(&self, env: &mut Env<'_, E>) -> Result<Phrase, Error> {
         self.units.expand(env).await
     }
 }
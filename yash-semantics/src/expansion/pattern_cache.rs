@@ -0,0 +1,298 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Cache of compiled patterns
+//!
+//! Compiling a [`Pattern`] may involve building a regular expression, which
+//! is much more expensive than matching a string against it. Scripts often
+//! match the same pattern many times, as in a `case` command or pathname
+//! expansion inside a loop, so compiled patterns are cached in a
+//! [`PatternCache`] stored in [`Env::any`].
+//!
+//! The cache is keyed by the pattern characters and the [`Config`] used to
+//! compile them. It holds a bounded number of entries and evicts the least
+//! recently used one when it is full. Patterns that contain no special
+//! characters are not cached since they do not need a regular expression.
+
+use std::collections::HashMap;
+use yash_env::Env;
+use yash_fnmatch::Config;
+use yash_fnmatch::Pattern;
+use yash_fnmatch::PatternChar;
+
+type Key = (Vec<PatternChar>, Config);
+
+#[derive(Clone, Debug)]
+struct Entry {
+    /// Compiled pattern, or `None` if the pattern is invalid
+    pattern: Option<Pattern>,
+    /// Value of [`PatternCache::clock`] when this entry was last used
+    last_used: u64,
+}
+
+/// Bounded LRU cache of compiled patterns
+///
+/// See the [module documentation](self) for details.
+#[derive(Clone, Debug)]
+pub struct PatternCache {
+    entries: HashMap<Key, Entry>,
+    capacity: usize,
+    clock: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl PatternCache {
+    /// Default maximum number of entries
+    pub const DEFAULT_CAPACITY: usize = 64;
+
+    /// Creates an empty cache with the [default capacity](Self::DEFAULT_CAPACITY).
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+
+    /// Creates an empty cache that holds at most `capacity` entries.
+    ///
+    /// If `capacity` is zero, nothing is cached.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        PatternCache {
+            entries: HashMap::new(),
+            capacity,
+            clock: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Returns the maximum number of entries.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of cached entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether the cache is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of lookups that found a cached pattern.
+    #[must_use]
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Returns the number of lookups that had to compile the pattern.
+    #[must_use]
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Returns the compiled pattern for the given characters and config.
+    ///
+    /// If the pattern is not in the cache, this function compiles it with
+    /// [`Pattern::parse_with_config`] and caches the result, evicting the
+    /// least recently used entry if the cache is full. Invalid patterns are
+    /// cached as well, and this function returns `None` for them.
+    ///
+    /// Patterns that compile to a literal string are cheap to build, so they
+    /// are neither cached nor counted as hits or misses.
+    pub fn get_or_parse<I>(&mut self, chars: I, config: Config) -> Option<Pattern>
+    where
+        I: IntoIterator<Item = PatternChar>,
+    {
+        self.clock += 1;
+        let key = (chars.into_iter().collect::<Vec<_>>(), config);
+
+        if let Some(entry) = self.entries.get_mut(&key) {
+            entry.last_used = self.clock;
+            self.hits += 1;
+            return entry.pattern.clone();
+        }
+
+        let pattern = Pattern::parse_with_config(key.0.iter().copied(), config).ok();
+        if pattern.as_ref().is_some_and(|p| p.as_literal().is_some()) {
+            return pattern;
+        }
+        self.misses += 1;
+        if self.capacity == 0 {
+            return pattern;
+        }
+        if self.entries.len() >= self.capacity {
+            self.evict_least_recently_used();
+        }
+        let entry = Entry {
+            pattern: pattern.clone(),
+            last_used: self.clock,
+        };
+        self.entries.insert(key, entry);
+        pattern
+    }
+
+    fn evict_least_recently_used(&mut self) {
+        let oldest = self
+            .entries
+            .iter()
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(key, _)| key.clone());
+        if let Some(key) = oldest {
+            self.entries.remove(&key);
+        }
+    }
+}
+
+impl Default for PatternCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the compiled pattern using the cache in the environment.
+///
+/// This function calls [`PatternCache::get_or_parse`] on the `PatternCache`
+/// stored in [`env.any`](Env::any), inserting a new cache if there is none.
+pub fn parse<I>(env: &mut Env, chars: I, config: Config) -> Option<Pattern>
+where
+    I: IntoIterator<Item = PatternChar>,
+{
+    env.any
+        .get_or_insert_with(|| Box::new(PatternCache::new()))
+        .get_or_parse(chars, config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normal(s: &str) -> impl Iterator<Item = PatternChar> + '_ {
+        s.chars().map(PatternChar::Normal)
+    }
+
+    #[test]
+    fn second_lookup_hits() {
+        let mut cache = PatternCache::new();
+        let pattern = cache.get_or_parse(normal("a*"), Config::default());
+        assert!(pattern.unwrap().is_match("abc"));
+        assert_eq!(cache.hits(), 0);
+        assert_eq!(cache.misses(), 1);
+
+        let pattern = cache.get_or_parse(normal("a*"), Config::default());
+        assert!(pattern.unwrap().is_match("abc"));
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.misses(), 1);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn config_is_part_of_key() {
+        let mut cache = PatternCache::new();
+        let mut config = Config::default();
+        _ = cache.get_or_parse(normal("a?"), config);
+        config.anchor_begin = true;
+        let pattern = cache.get_or_parse(normal("a?"), config).unwrap();
+        assert!(!pattern.is_match("bab"));
+        assert_eq!(cache.hits(), 0);
+        assert_eq!(cache.misses(), 2);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn literal_and_normal_chars_are_distinguished() {
+        let mut cache = PatternCache::new();
+        _ = cache.get_or_parse(normal("**"), Config::default());
+        let chars = [PatternChar::Literal('*'), PatternChar::Normal('*')];
+        let pattern = cache.get_or_parse(chars, Config::default()).unwrap();
+        assert!(!pattern.is_match("a"));
+        assert!(pattern.is_match("*a"));
+        assert_eq!(cache.misses(), 2);
+    }
+
+    #[test]
+    fn invalid_pattern_is_cached() {
+        let mut cache = PatternCache::new();
+        assert!(cache
+            .get_or_parse(normal("[[:foo:]]"), Config::default())
+            .is_none());
+        assert!(cache
+            .get_or_parse(normal("[[:foo:]]"), Config::default())
+            .is_none());
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.misses(), 1);
+    }
+
+    #[test]
+    fn least_recently_used_entry_is_evicted() {
+        let mut cache = PatternCache::with_capacity(2);
+        _ = cache.get_or_parse(normal("a*"), Config::default());
+        _ = cache.get_or_parse(normal("b*"), Config::default());
+        _ = cache.get_or_parse(normal("a*"), Config::default());
+        _ = cache.get_or_parse(normal("c*"), Config::default());
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.misses(), 3);
+
+        // "a*" is still cached but "b*" has been evicted.
+        _ = cache.get_or_parse(normal("a*"), Config::default());
+        assert_eq!(cache.hits(), 2);
+        _ = cache.get_or_parse(normal("b*"), Config::default());
+        assert_eq!(cache.misses(), 4);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn literal_pattern_is_not_cached() {
+        let mut cache = PatternCache::new();
+        let pattern = cache.get_or_parse(normal("abc"), Config::default());
+        assert_eq!(pattern.unwrap().as_literal(), Some("abc"));
+        let pattern = cache.get_or_parse(normal("abc"), Config::default());
+        assert_eq!(pattern.unwrap().as_literal(), Some("abc"));
+        assert_eq!(cache.hits(), 0);
+        assert_eq!(cache.misses(), 0);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn zero_capacity_caches_nothing() {
+        let mut cache = PatternCache::with_capacity(0);
+        assert!(cache
+            .get_or_parse(normal("a*"), Config::default())
+            .is_some());
+        assert!(cache
+            .get_or_parse(normal("a*"), Config::default())
+            .is_some());
+        assert_eq!(cache.hits(), 0);
+        assert_eq!(cache.misses(), 2);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn cache_in_env() {
+        let mut env = Env::new_virtual();
+        _ = parse(&mut env, normal("x*"), Config::default());
+        _ = parse(&mut env, normal("x*"), Config::default());
+        let cache = env.any.get::<PatternCache>().unwrap();
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.misses(), 1);
+    }
+}
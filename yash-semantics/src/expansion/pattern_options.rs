@@ -0,0 +1,160 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Shell options affecting pattern matching
+//!
+//! Pathname expansion, the case command, and the trimming and substitution
+//! modifiers of parameter expansion all compile patterns with [`yash_fnmatch`]. The
+//! [`PatternOptions`] struct collects the shell options that affect how the
+//! patterns match, so that all of them behave consistently. Each consumer
+//! obtains its [`Config`] from one of the methods of `PatternOptions`.
+
+use yash_env::option::Option::CaseGlob;
+use yash_env::option::OptionSet;
+use yash_env::option::State::Off;
+use yash_fnmatch::Config;
+use yash_syntax::syntax::TrimLength;
+use yash_syntax::syntax::TrimSide;
+
+/// Options that affect pattern matching
+///
+/// See the [module documentation](self) for details.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct PatternOptions {
+    /// Whether patterns match case-insensitively
+    ///
+    /// This is `true` if the [`CaseGlob`] option is off.
+    pub case_insensitive: bool,
+}
+
+impl PatternOptions {
+    /// Creates pattern options from the shell option set.
+    #[must_use]
+    pub fn from_options(options: &OptionSet) -> Self {
+        PatternOptions {
+            case_insensitive: options.get(CaseGlob) == Off,
+        }
+    }
+
+    fn base_config(&self) -> Config {
+        let mut config = Config::default();
+        config.case_insensitive = self.case_insensitive;
+        config
+    }
+
+    /// Returns the configuration for a pathname component in pathname
+    /// expansion.
+    ///
+    /// The pattern must match the whole component, and a leading period must
+    /// be matched explicitly.
+    #[must_use]
+    pub fn glob_config(&self) -> Config {
+        let mut config = self.base_config();
+        config.anchor_begin = true;
+        config.anchor_end = true;
+        config.literal_period = true;
+        config
+    }
+
+    /// Returns the configuration for a pattern of the case command.
+    ///
+    /// The pattern must match the whole subject.
+    #[must_use]
+    pub fn case_config(&self) -> Config {
+        let mut config = self.base_config();
+        config.anchor_begin = true;
+        config.anchor_end = true;
+        config
+    }
+
+    /// Returns the configuration for a pattern of the trimming modifier in
+    /// parameter expansion.
+    ///
+    /// The pattern is anchored to the side of the value to be removed, and
+    /// matches the shortest or longest part depending on `length`.
+    #[must_use]
+    pub fn trim_config(&self, side: TrimSide, length: TrimLength) -> Config {
+        let mut config = self.base_config();
+        match side {
+            TrimSide::Prefix => config.anchor_begin = true,
+            TrimSide::Suffix => config.anchor_end = true,
+        }
+        match length {
+            TrimLength::Shortest => config.shortest_match = true,
+            TrimLength::Longest => (),
+        }
+        config
+    }
+
+    /// Returns the configuration for a pattern of the substitution modifier
+    /// in parameter expansion.
+    ///
+    /// The pattern may match anywhere in the value, and matches the longest
+    /// part.
+    #[must_use]
+    pub fn subst_config(&self) -> Config {
+        self.base_config()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_options_are_case_sensitive() {
+        let options = PatternOptions::from_options(&OptionSet::default());
+        assert!(!options.case_insensitive);
+        assert!(!options.glob_config().case_insensitive);
+        assert!(!options.case_config().case_insensitive);
+    }
+
+    #[test]
+    fn caseglob_off_makes_all_configs_case_insensitive() {
+        let mut option_set = OptionSet::default();
+        option_set.set(CaseGlob, Off);
+        let options = PatternOptions::from_options(&option_set);
+        assert!(options.case_insensitive);
+        assert!(options.glob_config().case_insensitive);
+        assert!(options.case_config().case_insensitive);
+        let config = options.trim_config(TrimSide::Suffix, TrimLength::Longest);
+        assert!(config.case_insensitive);
+        assert!(options.subst_config().case_insensitive);
+    }
+
+    #[test]
+    fn trim_config_anchors_and_length() {
+        let options = PatternOptions::default();
+        let config = options.trim_config(TrimSide::Prefix, TrimLength::Shortest);
+        assert!(config.anchor_begin);
+        assert!(!config.anchor_end);
+        assert!(config.shortest_match);
+
+        let config = options.trim_config(TrimSide::Suffix, TrimLength::Longest);
+        assert!(!config.anchor_begin);
+        assert!(config.anchor_end);
+        assert!(!config.shortest_match);
+    }
+
+    #[test]
+    fn subst_config_is_unanchored_longest_match() {
+        let config = PatternOptions::default().subst_config();
+        assert!(!config.anchor_begin);
+        assert!(!config.anchor_end);
+        assert!(!config.shortest_match);
+    }
+}
@@ -48,39 +48,47 @@
 //!     AttrField { chars, origin }
 //! }
 //!
+//! // and this utility to inspect the results, ignoring their origins:
+//! fn values(fields: Vec<AttrField>) -> Vec<String> {
+//!     fields
+//!         .into_iter()
+//!         .map(|field| field.chars.iter().map(|c| c.value).collect())
+//!         .collect()
+//! }
+//!
 //! let ifs = Ifs::new(" -");
 //!
 //! // When there are no separators in the input, the result is the input itself:
-//! let fields: Vec<AttrField> = split(field("abc"), &ifs);
-//! assert_eq!(fields, [field("abc")]);
+//! let fields = values(split(field("abc"), &ifs));
+//! assert_eq!(fields, ["abc"]);
 //!
 //! // Whitespace separators are removed:
-//! let fields: Vec<AttrField> = split(field("  abc   "), &ifs);
-//! assert_eq!(fields, [field("abc")]);
+//! let fields = values(split(field("  abc   "), &ifs));
+//! assert_eq!(fields, ["abc"]);
 //!
 //! // An empty input yields no fields rather than an empty field:
-//! let fields: Vec<AttrField> = split(field(""), &ifs);
-//! assert_eq!(fields, []);
+//! let fields = values(split(field(""), &ifs));
+//! assert!(fields.is_empty());
 //!
 //! // Whitespace separators split fields:
-//! let fields: Vec<AttrField> = split(field("foo bar  baz"), &ifs);
-//! assert_eq!(fields, [field("foo"), field("bar"), field("baz")]);
+//! let fields = values(split(field("foo bar  baz"), &ifs));
+//! assert_eq!(fields, ["foo", "bar", "baz"]);
 //!
 //! // Non-whitespace separators each split fields, which may produce empty fields:
-//! let fields: Vec<AttrField> = split(field("foo-bar--baz"), &ifs);
-//! assert_eq!(fields, [field("foo"), field("bar"), field(""), field("baz")]);
+//! let fields = values(split(field("foo-bar--baz"), &ifs));
+//! assert_eq!(fields, ["foo", "bar", "", "baz"]);
 //!
 //! // Whitespace separators around non-whitespace separators are ignored:
-//! let fields: Vec<AttrField> = split(field("foo - bar -  - baz"), &ifs);
-//! assert_eq!(fields, [field("foo"), field("bar"), field(""), field("baz")]);
+//! let fields = values(split(field("foo - bar -  - baz"), &ifs));
+//! assert_eq!(fields, ["foo", "bar", "", "baz"]);
 //!
 //! // Trailing non-whitespace separators may seem special:
-//! let fields: Vec<AttrField> = split(field("foo-bar"), &ifs);
-//! assert_eq!(fields, [field("foo"), field("bar")]);
-//! let fields: Vec<AttrField> = split(field("foo-bar-"), &ifs);
-//! assert_eq!(fields, [field("foo"), field("bar")]);
-//! let fields: Vec<AttrField> = split(field("foo-bar--"), &ifs);
-//! assert_eq!(fields, [field("foo"), field("bar"), field("")]);
+//! let fields = values(split(field("foo-bar"), &ifs));
+//! assert_eq!(fields, ["foo", "bar"]);
+//! let fields = values(split(field("foo-bar-"), &ifs));
+//! assert_eq!(fields, ["foo", "bar"]);
+//! let fields = values(split(field("foo-bar--"), &ifs));
+//! assert_eq!(fields, ["foo", "bar", ""]);
 //! ```
 //!
 //! # The empty-last-field option
@@ -96,12 +104,22 @@ pub use self::ranges::Ranges;
 use super::attr::AttrField;
 #[cfg(doc)]
 use super::attr::{AttrChar, Origin};
+use std::cell::RefCell;
+use std::num::NonZeroU64;
+use std::rc::Rc;
+use yash_syntax::source::{Code, Location, Source};
 
 /// Performs field splitting and appends the result to a collection.
 ///
 /// This function applies field splitting to the given field using the given IFS
-/// and extends the given collection with the results. The resultant fields
-/// share the same origin as the input field.
+/// and extends the given collection with the results.
+///
+/// If the field is not divided, the result has the same origin as the input
+/// field. If the field is divided into more than one field, the origin of each
+/// result is a location in a new [`Code`] whose value is the input field
+/// (including quoting characters) and whose source is [`Source::Expansion`]
+/// referring to the origin of the input field. The range of the location is
+/// the range of the characters of the result in the input field.
 ///
 /// See also [`split`], which returns the results in a new collection rather
 /// than extending an existing one.
@@ -109,33 +127,35 @@ pub fn split_into<R>(field: AttrField, ifs: &Ifs, results: &mut R)
 where
     R: Extend<AttrField>,
 {
-    /*
-    results.extend(
-        ifs.ranges(field.chars.iter().copied())
-            .map(|range| AttrField {
-                chars: field.chars[range].to_vec(),
-                origin: field.origin.clone(),
-            }),
-    );
-    */
-
-    // Optimize by reusing the original field for the last one.
     let mut ranges = ifs.ranges(field.chars.iter().copied()).peekable();
-    while let Some(range) = ranges.next() {
+    let Some(first) = ranges.next() else {
+        return;
+    };
+
+    if ranges.peek().is_none() {
+        // Optimize by reusing the original field for the only result.
+        let mut field = field;
+        field.chars.truncate(first.end);
+        field.chars.drain(..first.start);
         // TODO Use Extend::extend_one when stabilized (rust#72631)
-        if ranges.peek().is_some() {
-            results.extend(std::iter::once(AttrField {
-                chars: field.chars[range].to_vec(),
-                origin: field.origin.clone(),
-            }));
-        } else {
-            let mut field = field;
-            field.chars.truncate(range.end);
-            field.chars.drain(..range.start);
-            results.extend(std::iter::once(field));
-            break;
-        }
+        results.extend(std::iter::once(field));
+        return;
     }
+
+    let code = Rc::new(Code {
+        value: RefCell::new(field.chars.iter().map(|c| c.value).collect()),
+        start_line_number: NonZeroU64::MIN,
+        source: Rc::new(Source::Expansion {
+            original: field.origin,
+        }),
+    });
+    results.extend(std::iter::once(first).chain(ranges).map(|range| AttrField {
+        chars: field.chars[range.clone()].to_vec(),
+        origin: Location {
+            code: Rc::clone(&code),
+            range,
+        },
+    }));
 }
 
 /// Performs field splitting and returns the result in a new collection.
@@ -155,7 +175,6 @@ where
 mod tests {
     use super::super::attr::{AttrChar, Origin};
     use super::*;
-    use yash_syntax::source::Location;
 
     fn dummy_attr_field(s: &str) -> AttrField {
         let chars = s
@@ -195,12 +214,19 @@ mod tests {
         assert_eq!(fields, [dummy_attr_field("foo")]);
     }
 
+    fn values(fields: &[AttrField]) -> Vec<String> {
+        fields
+            .iter()
+            .map(|field| field.chars.iter().map(|c| c.value).collect())
+            .collect()
+    }
+
     #[test]
     fn split_into_two_fields() {
         let field = dummy_attr_field("foo  bar");
         let ifs = Ifs::default();
         let fields: Vec<AttrField> = split(field, &ifs);
-        assert_eq!(fields, [dummy_attr_field("foo"), dummy_attr_field("bar")]);
+        assert_eq!(values(&fields), ["foo", "bar"]);
     }
 
     #[test]
@@ -208,14 +234,67 @@ mod tests {
         let field = dummy_attr_field(" one two  three four  ");
         let ifs = Ifs::default();
         let fields: Vec<AttrField> = split(field, &ifs);
+        assert_eq!(values(&fields), ["one", "two", "three", "four"]);
+    }
+
+    #[test]
+    fn split_with_non_whitespace_separators() {
+        let field = dummy_attr_field("a::b:");
+        let ifs = Ifs::new(":");
+        let fields: Vec<AttrField> = split(field, &ifs);
+        assert_eq!(values(&fields), ["a", "", "b"]);
+    }
+
+    #[test]
+    fn whitespace_around_non_whitespace_separator_is_collapsed() {
+        let field = dummy_attr_field(" a : b  :: c ");
+        let ifs = Ifs::new(" :");
+        let fields: Vec<AttrField> = split(field, &ifs);
+        assert_eq!(values(&fields), ["a", "b", "", "c"]);
+    }
+
+    #[test]
+    fn no_split_at_quoted_or_literal_characters() {
+        let mut field = dummy_attr_field("a b c d");
+        field.chars[1].is_quoted = true;
+        field.chars[3].origin = Origin::Literal;
+        field.chars[5].origin = Origin::HardExpansion;
+        let ifs = Ifs::default();
+        let fields: Vec<AttrField> = split(field, &ifs);
+        assert_eq!(values(&fields), ["a b c d"]);
+    }
+
+    #[test]
+    fn origin_of_unsplit_field_is_kept() {
+        let mut field = dummy_attr_field(" foo ");
+        field.origin = Location::dummy("$x");
+        let ifs = Ifs::default();
+        let fields: Vec<AttrField> = split(field, &ifs);
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].origin, Location::dummy("$x"));
+    }
+
+    #[test]
+    fn origins_of_split_fields() {
+        let mut field = dummy_attr_field("foo bar-baz");
+        field.origin = Location::dummy("$x");
+        let ifs = Ifs::new(" -");
+        let fields: Vec<AttrField> = split(field, &ifs);
+        assert_eq!(values(&fields), ["foo", "bar", "baz"]);
+
+        let code = &fields[0].origin.code;
+        assert_eq!(*code.value.borrow(), "foo bar-baz");
+        assert_eq!(code.start_line_number.get(), 1);
         assert_eq!(
-            fields,
-            [
-                dummy_attr_field("one"),
-                dummy_attr_field("two"),
-                dummy_attr_field("three"),
-                dummy_attr_field("four")
-            ]
+            *code.source,
+            Source::Expansion {
+                original: Location::dummy("$x")
+            }
         );
+        assert_eq!(fields[0].origin.range, 0..3);
+        assert!(Rc::ptr_eq(&fields[1].origin.code, code));
+        assert_eq!(fields[1].origin.range, 4..7);
+        assert!(Rc::ptr_eq(&fields[2].origin.code, code));
+        assert_eq!(fields[2].origin.range, 8..11);
     }
 }
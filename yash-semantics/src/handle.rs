@@ -21,6 +21,7 @@ use std::ops::ControlFlow::{Break, Continue};
 use yash_env::io::print_message;
 use yash_env::semantics::Divert;
 use yash_env::Env;
+use yash_syntax::parser::ErrorCause;
 
 /// Error handler.
 ///
@@ -36,13 +37,24 @@ pub trait Handle {
 /// Prints an error message.
 ///
 /// This implementation handles the error by printing an error message to the
-/// standard error and returning `Divert::Interrupt(Some(ExitStatus::ERROR))`.
+/// standard error and returning `Divert::Interrupt(Some(exit_status))`.
+///
+/// For a syntax error, the exit status is [`ExitStatus::ERROR`]. For an I/O
+/// error in reading the input, the exit status is [`ExitStatus::NOEXEC`],
+/// which is the same as the exit status of the shell when it cannot open the
+/// script at all. The message of an I/O error includes the description of the
+/// underlying error and points to the location up to which the input was read.
+///
 /// Note that other POSIX-compliant implementations may use different non-zero
 /// exit statuses.
 impl Handle for yash_syntax::parser::Error {
     async fn handle(&self, env: &mut Env) -> super::Result {
         print_message(env, self).await;
-        Break(Divert::Interrupt(Some(ExitStatus::ERROR)))
+        let exit_status = match self.cause {
+            ErrorCause::Syntax(_) => ExitStatus::ERROR,
+            ErrorCause::Io(_) => ExitStatus::NOEXEC,
+        };
+        Break(Divert::Interrupt(Some(exit_status)))
     }
 }
 
@@ -89,3 +101,90 @@ impl Handle for crate::redir::Error {
         Continue(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expansion::ErrorCause;
+    use crate::read_eval_loop;
+    use crate::tests::echo_builtin;
+    use futures_util::FutureExt as _;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use yash_env::option::Option::{ErrExit, FailGlob, Unset};
+    use yash_env::option::State::{Off, On};
+    use yash_env::system::r#virtual::VirtualSystem;
+    use yash_env_test_helper::assert_stderr;
+    use yash_env_test_helper::assert_stdout;
+    use yash_syntax::parser::lex::Lexer;
+    use yash_syntax::source::Location;
+    use yash_syntax::syntax::Param;
+
+    #[test]
+    fn expansion_error_message_points_to_failing_parameter() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        env.builtins.insert("echo", echo_builtin());
+        env.options.set(Unset, Off);
+        let mut lexer = Lexer::with_code("echo ok\necho $foo\necho not reached\n");
+        let ref_env = RefCell::new(&mut env);
+
+        let result = read_eval_loop(&ref_env, &mut lexer).now_or_never().unwrap();
+        assert_eq!(result, Break(Divert::Interrupt(Some(ExitStatus::ERROR))));
+        assert_stderr(&state, |stderr| {
+            assert!(stderr.contains("variable `foo` is not set"), "{stderr}");
+            assert!(stderr.contains(":2:6"), "{stderr}");
+            assert!(stderr.contains("echo $foo"), "{stderr}");
+            assert!(!stderr.contains("not reached"), "{stderr}");
+        });
+    }
+
+    #[test]
+    fn unmatched_pattern_with_failglob_aborts_command() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        env.builtins.insert("echo", echo_builtin());
+        env.options.set(FailGlob, On);
+        let mut lexer = Lexer::with_code("echo /no_such_dir/*\n");
+        let ref_env = RefCell::new(&mut env);
+
+        let result = read_eval_loop(&ref_env, &mut lexer).now_or_never().unwrap();
+        assert_eq!(result, Break(Divert::Interrupt(Some(ExitStatus::ERROR))));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, ""));
+        assert_stderr(&state, |stderr| {
+            assert!(stderr.contains("pattern `/no_such_dir/*`"), "{stderr}");
+            assert!(stderr.contains(":1:6"), "{stderr}");
+        });
+    }
+
+    #[test]
+    fn expansion_error_interrupts_with_error_exit_status() {
+        let mut env = Env::new_virtual();
+        let error = crate::expansion::Error {
+            cause: ErrorCause::UnsetParameter {
+                param: Param::variable("foo"),
+            },
+            location: Location::dummy("$foo"),
+        };
+
+        let result = error.handle(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Break(Divert::Interrupt(Some(ExitStatus::ERROR))));
+    }
+
+    #[test]
+    fn expansion_error_exits_with_errexit() {
+        let mut env = Env::new_virtual();
+        env.options.set(ErrExit, On);
+        let error = crate::expansion::Error {
+            cause: ErrorCause::UnsetParameter {
+                param: Param::variable("foo"),
+            },
+            location: Location::dummy("$foo"),
+        };
+
+        let result = error.handle(&mut env).now_or_never().unwrap();
+        assert_eq!(result, Break(Divert::Exit(Some(ExitStatus::ERROR))));
+    }
+}
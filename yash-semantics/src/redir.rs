@@ -35,7 +35,10 @@
 //! - `FdIn`: Copies a file descriptor, regarding the expanded field as a
 //!   non-negative decimal integer denoting a readable file descriptor to copy
 //!   from. Closes the target file descriptor if the field is a single hyphen
-//!   (`-`) instead.
+//!   (`-`) instead. The operand is expanded to a single field before being
+//!   interpreted, so it may contain parameter expansions as in `>&$fd`. If the
+//!   field is neither an integer nor a hyphen, the redirection fails with
+//!   [`MalformedFd`](ErrorCause::MalformedFd).
 //! - `FdOut`: Likewise, copies or closes a file descriptor, but the source file
 //!   descriptor must be writable instead of readable.
 //! - `Pipe`: Opens a pipe, regarding the expanded field as a
@@ -48,6 +51,12 @@
 //! If the `Clobber` [shell option](yash_env::option::Option) is off and a
 //! regular file exists at the target pathname, then `FileOut` will fail.
 //!
+//! If the expanded field is empty for `FileIn`, `FileInOut`, `FileOut`,
+//! `FileClobber`, or `FileAppend`, the redirection fails with
+//! [`EmptyPathname`](ErrorCause::EmptyPathname) without trying to open any
+//! file. An error opening the file, such as `EISDIR` for a directory opened
+//! for writing, is reported as [`OpenFile`](ErrorCause::OpenFile).
+//!
 //! If the body is `HereDoc`, the redirection opens a readable file descriptor
 //! that yields [expansion](crate::expansion) of the content. The current
 //! implementation uses an unnamed temporary file for the file descriptor, but
@@ -94,6 +103,8 @@ use yash_env::option::Option::Clobber;
 use yash_env::option::State::Off;
 use yash_env::semantics::ExitStatus;
 use yash_env::semantics::Field;
+use yash_env::system::resource::Resource;
+use yash_env::system::resource::INFINITY;
 use yash_env::system::Errno;
 use yash_env::system::FdFlag;
 use yash_env::system::FileType;
@@ -146,6 +157,10 @@ pub enum ErrorCause {
     #[error("file descriptor {0} is reserved by the shell")]
     ReservedFd(Fd),
 
+    /// Empty pathname as the operand of a file redirection
+    #[error("empty pathname")]
+    EmptyPathname,
+
     /// Error while opening a file.
     ///
     /// The `CString` is the pathname of the file that could not be opened.
@@ -178,6 +193,7 @@ impl ErrorCause {
         match self {
             Expansion(e) => e.message(),
             NulByte(_) => "nul byte found in the pathname",
+            EmptyPathname => "cannot open the file",
             FdNotOverwritten(_, _) | ReservedFd(_) => "cannot redirect the file descriptor",
             OpenFile(_, _) => "cannot open the file",
             MalformedFd(_, _) => "not a valid file descriptor",
@@ -194,6 +210,7 @@ impl ErrorCause {
         match self {
             Expansion(e) => e.label(),
             NulByte(_) => "pathname should not contain a nul byte".into(),
+            EmptyPathname => "pathname is empty".into(),
             FdNotOverwritten(_, errno) => errno.to_string().into(),
             ReservedFd(fd) => format!("file descriptor {fd} reserved by shell").into(),
             OpenFile(path, errno) => format!("{}: {}", path.to_string_lossy(), errno).into(),
@@ -267,7 +284,25 @@ fn is_cloexec(env: &Env, fd: Fd) -> bool {
     matches!(env.system.fcntl_getfd(fd), Ok(flags) if flags.contains(FdFlag::CloseOnExec))
 }
 
+/// Tests whether the FD is less than the limit of the number of open files.
+///
+/// Redirecting an FD beyond the limit would always fail, so we check the limit
+/// before opening the file to avoid creating it in vain.
+#[allow(clippy::unnecessary_cast)]
+fn is_fd_within_limit(env: &Env, fd: Fd) -> bool {
+    match env.system.getrlimit(Resource::NOFILE) {
+        Ok(limits) => limits.soft == INFINITY || (fd.0 as u64) < limits.soft as u64,
+        Err(_) => true,
+    }
+}
+
 fn into_c_string_value_and_origin(field: Field) -> Result<(CString, Location), Error> {
+    if field.value.is_empty() {
+        return Err(Error {
+            cause: ErrorCause::EmptyPathname,
+            location: field.origin,
+        });
+    }
     match CString::new(field.value) {
         Ok(value) => Ok((value, field.origin)),
         Err(e) => Err(Error {
@@ -478,6 +513,13 @@ async fn perform(
         });
     }
 
+    if !is_fd_within_limit(env, target_fd) {
+        return Err(Error {
+            cause: ErrorCause::FdNotOverwritten(target_fd, Errno::EBADF),
+            location: redir.body.operand().location.clone(),
+        });
+    }
+
     // Save the current open file description at target_fd to a new FD
     let save = match env
         .system
@@ -670,14 +712,20 @@ mod tests {
     use assert_matches::assert_matches;
     use futures_util::FutureExt;
     use std::cell::RefCell;
+    use std::ops::ControlFlow::Continue;
     use std::rc::Rc;
     use yash_env::system::r#virtual::FileBody;
     use yash_env::system::r#virtual::Inode;
     use yash_env::system::resource::LimitPair;
     use yash_env::system::resource::Resource;
+    use yash_env::variable::Scope;
     use yash_env::Env;
     use yash_env::VirtualSystem;
+    use yash_env_test_helper::assert_stderr;
+    use yash_env_test_helper::assert_stdout;
     use yash_env_test_helper::in_virtual_system;
+    use yash_syntax::syntax::Command;
+    use yash_syntax::syntax::List;
     use yash_syntax::syntax::Text;
 
     /// Returns a virtual system with a file descriptor limit.
@@ -923,6 +971,27 @@ mod tests {
         assert_eq!(e.location, redir.body.operand().location);
     }
 
+    #[test]
+    fn target_exceeding_nofile_limit() {
+        let system = system_with_nofile_limit();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        let mut env = RedirGuard::new(&mut env);
+        let redir = "1024> foo".parse().unwrap();
+        let e = env
+            .perform_redir(&redir, None)
+            .now_or_never()
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(
+            e.cause,
+            ErrorCause::FdNotOverwritten(Fd(1024), Errno::EBADF)
+        );
+        assert_eq!(e.location, redir.body.operand().location);
+        // The file should not have been created.
+        assert!(state.borrow().file_system.get("foo").is_err());
+    }
+
     #[test]
     fn exit_status_of_command_substitution_in_normal() {
         in_virtual_system(|mut env, state| async move {
@@ -964,6 +1033,25 @@ mod tests {
         })
     }
 
+    #[test]
+    fn expansion_error_location_in_here_doc() {
+        let mut env = Env::new_virtual();
+        let mut env = RedirGuard::new(&mut env);
+        let list: List = "cat <<END\nfoo\n${X?}\nEND\n".parse().unwrap();
+        let Command::Simple(command) = &*list.0[0].and_or.first.commands[0] else {
+            panic!("not a simple command: {list}");
+        };
+
+        let e = env
+            .perform_redir(&command.redirs[0], None)
+            .now_or_never()
+            .unwrap()
+            .unwrap_err();
+        assert_matches!(e.cause, ErrorCause::Expansion(_));
+        assert_eq!(e.location.range, 14..19);
+        assert_eq!(e.location.code.line_number(e.location.range.start).get(), 3);
+    }
+
     #[test]
     fn xtrace_normal() {
         let mut xtrace = XTrace::new();
@@ -1058,6 +1146,48 @@ mod tests {
         });
     }
 
+    #[test]
+    fn file_out_with_empty_pathname() {
+        let system = system_with_nofile_limit();
+        let mut env = Env::with_system(Box::new(system));
+        let mut env = RedirGuard::new(&mut env);
+        let redir = "3> \"$unset\"".parse().unwrap();
+        let e = env
+            .perform_redir(&redir, None)
+            .now_or_never()
+            .unwrap()
+            .unwrap_err();
+
+        assert_eq!(e.cause, ErrorCause::EmptyPathname);
+        assert_eq!(e.location, redir.body.operand().location);
+        assert_eq!(env.system.fcntl_getfd(Fd(3)), Err(Errno::EBADF));
+    }
+
+    #[test]
+    fn file_out_to_directory() {
+        let system = system_with_nofile_limit();
+        let mut state = system.state.borrow_mut();
+        let dir = Rc::new(RefCell::new(Inode {
+            body: FileBody::Directory {
+                files: Default::default(),
+            },
+            permissions: Mode::ALL_9,
+        }));
+        state.file_system.save("/dir", dir).unwrap();
+        drop(state);
+        let mut env = Env::with_system(Box::new(system));
+        let mut env = RedirGuard::new(&mut env);
+        let redir = "> /dir".parse().unwrap();
+        let e = env
+            .perform_redir(&redir, None)
+            .now_or_never()
+            .unwrap()
+            .unwrap_err();
+
+        assert_eq!(e.cause, ErrorCause::OpenFile(c"/dir".into(), Errno::EISDIR));
+        assert_eq!(e.location, redir.body.operand().location);
+    }
+
     #[test]
     fn file_out_truncates_existing_file() {
         let file = Rc::new(RefCell::new(Inode::new([42, 123, 254])));
@@ -1262,6 +1392,28 @@ mod tests {
         });
     }
 
+    #[test]
+    fn file_append_from_sequential_redirections() {
+        let system = system_with_nofile_limit();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        for line in ["one\n", "two\n"] {
+            let mut env = RedirGuard::new(&mut env);
+            let redir = ">> foo".parse().unwrap();
+            env.perform_redir(&redir, None)
+                .now_or_never()
+                .unwrap()
+                .unwrap();
+            env.system.write(Fd::STDOUT, line.as_bytes()).unwrap();
+        }
+
+        let file = state.borrow().file_system.get("foo").unwrap();
+        let file = file.borrow();
+        assert_matches!(&file.body, FileBody::Regular { content, .. } => {
+            assert_eq!(std::str::from_utf8(content), Ok("one\ntwo\n"));
+        });
+    }
+
     #[test]
     fn file_append_closes_opened_file_on_error() {
         let mut env = Env::with_system(Box::new(system_with_nofile_limit()));
@@ -1323,6 +1475,33 @@ mod tests {
         assert_eq!(buffer, [132, 79, 210, 0]);
     }
 
+    #[test]
+    fn file_in_out_reads_and_writes_same_fd() {
+        let system = system_with_nofile_limit();
+        let file = Rc::new(RefCell::new(Inode::new(*b"abc\n")));
+        let mut state = system.state.borrow_mut();
+        state.file_system.save("foo", Rc::clone(&file)).unwrap();
+        drop(state);
+        let mut env = Env::with_system(Box::new(system));
+        let mut env = RedirGuard::new(&mut env);
+        let redir = "<> foo".parse().unwrap();
+        env.perform_redir(&redir, None)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        let mut buffer = [0; 2];
+        let read_count = env.system.read(Fd::STDIN, &mut buffer).unwrap();
+        assert_eq!(read_count, 2);
+        assert_eq!(buffer, *b"ab");
+        env.system.write(Fd::STDIN, b"X").unwrap();
+
+        let file = file.borrow();
+        assert_matches!(&file.body, FileBody::Regular { content, .. } => {
+            assert_eq!(std::str::from_utf8(content), Ok("abX\n"));
+        });
+    }
+
     #[test]
     fn file_in_out_closes_opened_file_on_error() {
         let mut env = Env::with_system(Box::new(system_with_nofile_limit()));
@@ -1568,4 +1747,73 @@ mod tests {
         let write_count = env.system.write(Fd(1), &[0x20]).unwrap();
         assert_eq!(write_count, 1);
     }
+
+    #[test]
+    fn fd_out_copies_fd_from_expanded_operand() {
+        let system = system_with_nofile_limit();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        let fd = &mut env.variables.get_or_new("fd", Scope::Global);
+        fd.assign("2", None).unwrap();
+        let mut env = RedirGuard::new(&mut env);
+        let redir = ">&$fd".parse().unwrap();
+        env.perform_redir(&redir, None)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        env.system.write(Fd::STDOUT, &[42]).unwrap();
+        assert_stderr(&state, |stderr| assert_eq!(stderr, "*"));
+    }
+
+    #[test]
+    fn fd_in_closes_fd_with_expanded_hyphen() {
+        let mut env = Env::with_system(Box::new(system_with_nofile_limit()));
+        let v = &mut env.variables.get_or_new("v", Scope::Global);
+        v.assign("-", None).unwrap();
+        let mut env = RedirGuard::new(&mut env);
+        let redir = "<&\"$v\"".parse().unwrap();
+        env.perform_redir(&redir, None)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        let mut buffer = [0; 1];
+        let e = env.system.read(Fd::STDIN, &mut buffer).unwrap_err();
+        assert_eq!(e, Errno::EBADF);
+    }
+
+    #[test]
+    fn fd_out_rejects_non_numeric_expanded_operand() {
+        let mut env = Env::with_system(Box::new(system_with_nofile_limit()));
+        let fd = &mut env.variables.get_or_new("fd", Scope::Global);
+        fd.assign("1x", None).unwrap();
+        let mut env = RedirGuard::new(&mut env);
+        let redir = ">&$fd".parse().unwrap();
+        let e = env
+            .perform_redir(&redir, None)
+            .now_or_never()
+            .unwrap()
+            .unwrap_err();
+        assert_matches!(&e.cause, ErrorCause::MalformedFd(value, _) => {
+            assert_eq!(value, "1x");
+        });
+        assert_eq!(e.location, redir.body.operand().location);
+    }
+
+    #[test]
+    fn simple_command_with_fd_out_to_expanded_operand() {
+        use crate::command::Command as _;
+        in_virtual_system(|mut env, state| async move {
+            env.builtins.insert("echo", echo_builtin());
+            let fd = &mut env.variables.get_or_new("fd", Scope::Global);
+            fd.assign("2", None).unwrap();
+            let list: List = "echo x >&$fd".parse().unwrap();
+            let result = list.execute(&mut env).await;
+            assert_eq!(result, Continue(()));
+            assert_eq!(env.exit_status, ExitStatus::SUCCESS);
+            assert_stdout(&state, |stdout| assert_eq!(stdout, ""));
+            assert_stderr(&state, |stderr| assert_eq!(stderr, "x\n"));
+        })
+    }
 }
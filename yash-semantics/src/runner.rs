@@ -21,12 +21,19 @@ use crate::trap::run_traps_for_caught_signals;
 use crate::Handle;
 use std::cell::RefCell;
 use std::ops::ControlFlow::{Break, Continue};
+use std::rc::Rc;
+use yash_env::io::print_message;
+use yash_env::option::Option::{PosixlyCorrect, Verbose};
+use yash_env::option::State::On;
 use yash_env::semantics::Divert;
 use yash_env::semantics::ExitStatus;
 use yash_env::semantics::Result;
 use yash_env::Env;
+use yash_env::Shadowed;
+use yash_syntax::alias::{Alias, Glossary};
 use yash_syntax::parser::lex::Lexer;
 use yash_syntax::parser::{ErrorCause, Parser};
+use yash_syntax::source::pretty::{Annotation, AnnotationType, Message};
 use yash_syntax::syntax::List;
 
 /// Reads input, parses it, and executes commands in a loop.
@@ -46,10 +53,25 @@ use yash_syntax::syntax::List;
 /// zero. Otherwise, the exit status reflects the result of the last executed
 /// command.
 ///
+/// If the input function fails with an I/O error, the loop [handles](Handle)
+/// the error and stops. Since each command line is executed before the next
+/// one is read, all the complete command lines read before the error have
+/// been executed. The result is `Break(Divert::Interrupt(Some(exit_status)))`
+/// where `exit_status` is [`ExitStatus::NOEXEC`], which distinguishes an
+/// input error from a syntax error.
+///
 /// [Pending traps are run](run_traps_for_caught_signals) and [subshell statuses
 /// are updated](Env::update_all_subshell_statuses) between parsing input and
 /// running commands.
 ///
+/// Before parsing each command line, the loop makes the lexer
+/// [POSIXly-correct](Lexer::set_posixly_correct) if the
+/// [`PosixlyCorrect`] option is on, so that non-POSIX syntax is rejected.
+///
+/// If the shell is interactive and the [`Verbose`] option is on, the loop
+/// prints a warning when an alias that [shadows a function or
+/// built-in](Env::shadowed_by_alias) is substituted in a command line.
+///
 /// For the top-level read-eval loop of an interactive shell, see
 /// [`interactive_read_eval_loop`].
 ///
@@ -142,9 +164,17 @@ async fn read_eval_loop_impl(
         if !lexer.pending() {
             lexer.flush();
         }
+        lexer.set_posixly_correct(env.borrow().options.get(PosixlyCorrect) == On);
+
+        let warns_shadowing = is_interactive && env.borrow().options.get(Verbose) == On;
+        let recorder = ShadowingRecorder {
+            env,
+            shadowing: RefCell::default(),
+        };
+        let aliases: &dyn Glossary = if warns_shadowing { &recorder } else { env };
 
         let command = Parser::config()
-            .aliases(env)
+            .aliases(aliases)
             .declaration_utilities(env)
             .input(lexer)
             .command_line()
@@ -152,6 +182,10 @@ async fn read_eval_loop_impl(
 
         let env = &mut **env.borrow_mut();
 
+        for alias in recorder.shadowing.into_inner() {
+            warn_shadowing(env, &alias).await;
+        }
+
         let (mut result, error_recoverable) = match command {
             // No more commands
             Ok(None) => {
@@ -190,6 +224,62 @@ async fn read_eval_loop_impl(
     }
 }
 
+/// Glossary that records aliases shadowing a function or built-in
+///
+/// This glossary delegates to the environment and remembers the aliases that
+/// are looked up and found to [shadow](Env::shadowed_by_alias) another command.
+#[derive(Debug)]
+struct ShadowingRecorder<'a, 'b> {
+    env: &'a RefCell<&'b mut Env>,
+    shadowing: RefCell<Vec<Rc<Alias>>>,
+}
+
+impl Glossary for ShadowingRecorder<'_, '_> {
+    fn look_up(&self, name: &str) -> Option<Rc<Alias>> {
+        let env = self.env.borrow();
+        let alias = env.look_up(name)?;
+        if env.shadowed_by_alias(name).is_some() {
+            let mut shadowing = self.shadowing.borrow_mut();
+            if !shadowing.iter().any(|a| a.name == alias.name) {
+                shadowing.push(Rc::clone(&alias));
+            }
+        }
+        Some(alias)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.env.borrow().is_empty()
+    }
+}
+
+/// Prints a warning about an alias shadowing a function or built-in.
+async fn warn_shadowing(env: &mut Env, alias: &Alias) {
+    let (kind, origin) = match env.shadowed_by_alias(&alias.name) {
+        None => return,
+        Some(Shadowed::Function(function)) => ("function", Some(function.origin.clone())),
+        Some(Shadowed::Builtin(_)) => ("built-in", None),
+    };
+    let mut annotations = vec![Annotation::new(
+        AnnotationType::Info,
+        "the alias was defined here".into(),
+        &alias.origin,
+    )];
+    if let Some(origin) = &origin {
+        annotations.push(Annotation::new(
+            AnnotationType::Info,
+            "the function was defined here".into(),
+            origin,
+        ));
+    }
+    let message = Message {
+        r#type: AnnotationType::Warning,
+        title: format!("alias `{}` hides the {} of the same name", alias.name, kind).into(),
+        annotations,
+        footers: vec![],
+    };
+    print_message(env, message).await;
+}
+
 async fn run_command(env: &mut Env, command: &List) -> Result {
     run_traps_for_caught_signals(env).await?;
     env.update_all_subshell_statuses();
@@ -206,10 +296,11 @@ mod tests {
     use yash_env::input::Echo;
     use yash_env::input::Memory;
     use yash_env::option::Option::Verbose;
-    use yash_env::option::State::On;
     use yash_env::system::r#virtual::VirtualSystem;
     use yash_env::system::r#virtual::SIGUSR1;
+    use yash_env::system::Errno;
     use yash_env::trap::Action;
+    use yash_env::variable::Scope;
     use yash_env_test_helper::assert_stderr;
     use yash_env_test_helper::assert_stdout;
     use yash_syntax::input::Context;
@@ -244,6 +335,21 @@ mod tests {
         assert_stdout(&state, |stdout| assert_eq!(stdout, "42\n"));
     }
 
+    #[test]
+    fn exit_status_updated_between_commands() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        env.builtins.insert("echo", echo_builtin());
+        env.builtins.insert("return", return_builtin());
+        let mut lexer = Lexer::with_code("return -n 3; echo $?; echo $?\nreturn -n 5\necho $?");
+        let ref_env = RefCell::new(&mut env);
+
+        let result = read_eval_loop(&ref_env, &mut lexer).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "3\n0\n5\n"));
+    }
+
     #[test]
     fn executing_many_lines_of_code() {
         let system = VirtualSystem::new();
@@ -297,6 +403,103 @@ mod tests {
         assert_stderr(&state, |stderr| assert_eq!(stderr, "case _ in esac"));
     }
 
+    fn env_with_alias_shadowing_builtin(system: VirtualSystem) -> Env {
+        use yash_syntax::alias::HashEntry;
+        let mut env = Env::with_system(Box::new(system));
+        env.aliases.insert(HashEntry::new(
+            "echo".to_string(),
+            "echo alias".to_string(),
+            false,
+            Location::dummy("alias"),
+        ));
+        env.builtins.insert("echo", echo_builtin());
+        env
+    }
+
+    #[test]
+    fn warning_alias_shadowing_builtin_in_interactive_verbose_mode() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = env_with_alias_shadowing_builtin(system);
+        env.options.set(Verbose, On);
+        let mut lexer = Lexer::with_code("echo 1; echo 2");
+        let ref_env = RefCell::new(&mut env);
+
+        let result = interactive_read_eval_loop(&ref_env, &mut lexer)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "alias 1\nalias 2\n"));
+        assert_stderr(&state, |stderr| {
+            assert_eq!(
+                stderr.matches("alias `echo` hides the built-in").count(),
+                1,
+                "{stderr:?}"
+            );
+        });
+    }
+
+    #[test]
+    fn warning_alias_shadowing_function_in_interactive_verbose_mode() {
+        use yash_env::function::Function;
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = env_with_alias_shadowing_builtin(system);
+        env.options.set(Verbose, On);
+        let body = "{ :; }".parse::<yash_syntax::syntax::FullCompoundCommand>();
+        let origin = Location::dummy("function origin");
+        let function = Function::new("echo", body.unwrap(), origin);
+        env.functions.define(function).unwrap();
+        let mut lexer = Lexer::with_code("echo 1");
+        let ref_env = RefCell::new(&mut env);
+
+        let result = interactive_read_eval_loop(&ref_env, &mut lexer)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stderr(&state, |stderr| {
+            assert!(
+                stderr.contains("alias `echo` hides the function"),
+                "{stderr:?}"
+            );
+            assert!(stderr.contains("the alias was defined here"), "{stderr:?}");
+            assert!(
+                stderr.contains("the function was defined here"),
+                "{stderr:?}"
+            );
+        });
+    }
+
+    #[test]
+    fn no_warning_alias_shadowing_builtin_in_non_verbose_mode() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = env_with_alias_shadowing_builtin(system);
+        let mut lexer = Lexer::with_code("echo 1");
+        let ref_env = RefCell::new(&mut env);
+
+        let result = interactive_read_eval_loop(&ref_env, &mut lexer)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "alias 1\n"));
+        assert_stderr(&state, |stderr| assert_eq!(stderr, ""));
+    }
+
+    #[test]
+    fn no_warning_alias_shadowing_builtin_in_non_interactive_mode() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = env_with_alias_shadowing_builtin(system);
+        env.options.set(Verbose, On);
+        let mut lexer = Lexer::with_code("echo 1");
+        let ref_env = RefCell::new(&mut env);
+
+        let result = read_eval_loop(&ref_env, &mut lexer).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stderr(&state, |stderr| assert_eq!(stderr, ""));
+    }
+
     #[test]
     fn command_interrupt_interactive() {
         // If the command execution results in an interrupt in interactive mode,
@@ -350,6 +553,90 @@ mod tests {
         assert_stdout(&state, |stdout| assert_eq!(stdout, ""));
     }
 
+    fn env_with_read_only_variable(system: VirtualSystem) -> Env {
+        let mut env = Env::with_system(Box::new(system));
+        env.builtins.insert("echo", echo_builtin());
+        let mut x = env.variables.get_or_new("x", Scope::Global);
+        x.assign("0", None).unwrap();
+        x.make_read_only(Location::dummy("readonly"));
+        env
+    }
+
+    #[test]
+    fn assignment_error_aborts_non_interactive_loop() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = env_with_read_only_variable(system);
+        let mut lexer = Lexer::with_code("x=1\necho reached\n");
+        let ref_env = RefCell::new(&mut env);
+
+        let result = read_eval_loop(&ref_env, &mut lexer).now_or_never().unwrap();
+        assert_eq!(result, Break(Divert::Interrupt(Some(ExitStatus::ERROR))));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, ""));
+        assert_stderr(&state, |stderr| assert_ne!(stderr, ""));
+    }
+
+    #[test]
+    fn assignment_error_with_special_builtin_aborts_non_interactive_loop() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = env_with_read_only_variable(system);
+        env.builtins.insert("return", return_builtin());
+        let mut lexer = Lexer::with_code("x=1 return -n 0\necho reached\n");
+        let ref_env = RefCell::new(&mut env);
+
+        let result = read_eval_loop(&ref_env, &mut lexer).now_or_never().unwrap();
+        assert_eq!(result, Break(Divert::Interrupt(Some(ExitStatus::ERROR))));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, ""));
+    }
+
+    #[test]
+    fn assignment_error_continues_interactive_loop() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = env_with_read_only_variable(system);
+        let mut lexer = Lexer::with_code("x=1\necho $? $x\n");
+        let ref_env = RefCell::new(&mut env);
+
+        let result = interactive_read_eval_loop(&ref_env, &mut lexer)
+            .now_or_never()
+            .unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "2 0\n"));
+    }
+
+    #[test]
+    fn non_posix_modifier_in_posix_mode() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        env.builtins.insert("echo", echo_builtin());
+        env.options.set(PosixlyCorrect, On);
+        let mut lexer = Lexer::with_code("echo ${x:1}\n");
+        let ref_env = RefCell::new(&mut env);
+
+        let result = read_eval_loop(&ref_env, &mut lexer).now_or_never().unwrap();
+        assert_eq!(result, Break(Divert::Interrupt(Some(ExitStatus::ERROR))));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, ""));
+        assert_stderr(&state, |stderr| {
+            assert!(stderr.contains("POSIX"), "{stderr:?}")
+        });
+    }
+
+    #[test]
+    fn non_posix_modifier_in_non_posix_mode() {
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        env.builtins.insert("echo", echo_builtin());
+        let mut lexer = Lexer::with_code("x=abc\necho ${x:1} ${x/b/-}\n");
+        let ref_env = RefCell::new(&mut env);
+
+        let result = read_eval_loop(&ref_env, &mut lexer).now_or_never().unwrap();
+        assert_eq!(result, Continue(()));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "bc a-c\n"));
+    }
+
     #[test]
     fn handling_syntax_error() {
         let system = VirtualSystem::new();
@@ -410,7 +697,37 @@ mod tests {
         let result = interactive_read_eval_loop(&ref_env, &mut lexer)
             .now_or_never()
             .unwrap();
-        assert_eq!(result, Break(Divert::Interrupt(Some(ExitStatus::ERROR))));
+        assert_eq!(result, Break(Divert::Interrupt(Some(ExitStatus::NOEXEC))));
+    }
+
+    #[test]
+    fn input_error_after_first_line() {
+        struct FailingInput(Option<&'static str>);
+        impl yash_syntax::input::Input for FailingInput {
+            async fn next_line(&mut self, _context: &Context) -> std::io::Result<String> {
+                match self.0.take() {
+                    Some(line) => Ok(line.to_string()),
+                    None => Err(std::io::Error::from_raw_os_error(Errno::EIO.0)),
+                }
+            }
+        }
+
+        let system = VirtualSystem::new();
+        let state = Rc::clone(&system.state);
+        let mut env = Env::with_system(Box::new(system));
+        env.builtins.insert("echo", echo_builtin());
+        let input = FailingInput(Some("echo first; echo second\n"));
+        let mut lexer = Lexer::new(Box::new(input));
+        let ref_env = RefCell::new(&mut env);
+
+        let result = read_eval_loop(&ref_env, &mut lexer).now_or_never().unwrap();
+        assert_eq!(result, Break(Divert::Interrupt(Some(ExitStatus::NOEXEC))));
+        assert_stdout(&state, |stdout| assert_eq!(stdout, "first\nsecond\n"));
+        assert_stderr(&state, |stderr| {
+            assert!(stderr.contains("cannot read commands"), "{stderr:?}");
+            let description = std::io::Error::from_raw_os_error(Errno::EIO.0).to_string();
+            assert!(stderr.contains(&description), "{stderr:?}");
+        });
     }
 
     #[test]
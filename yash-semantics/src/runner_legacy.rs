@@ -22,6 +22,7 @@ use crate::Handle;
 use std::cell::Cell;
 use std::ops::ControlFlow::Continue;
 use std::rc::Rc;
+use yash_env::option::Option::PosixlyCorrect;
 use yash_env::option::Option::Verbose;
 use yash_env::option::State;
 use yash_env::semantics::ExitStatus;
@@ -137,6 +138,8 @@ impl<'a, 'b> ReadEvalLoop<'a, 'b> {
             if let Some(verbose) = &self.verbose {
                 verbose.set(self.env.options.get(Verbose));
             }
+            self.lexer
+                .set_posixly_correct(self.env.options.get(PosixlyCorrect) == State::On);
 
             let mut parser = Parser::config()
                 .aliases(&self.env)
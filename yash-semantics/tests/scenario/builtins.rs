@@ -0,0 +1,110 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Minimal built-ins available in scenarios
+//!
+//! The real built-ins are defined in the `yash-builtin` crate, which depends on
+//! this crate, so scenarios use these simplified implementations instead.
+
+use itertools::Itertools as _;
+use std::future::ready;
+use std::future::Future;
+use std::ops::ControlFlow::Break;
+use std::pin::Pin;
+use yash_env::builtin::Builtin;
+use yash_env::builtin::Type::{Mandatory, Special};
+use yash_env::io::Fd;
+use yash_env::semantics::Divert;
+use yash_env::semantics::ExitStatus;
+use yash_env::semantics::Field;
+use yash_env::system::Errno;
+use yash_env::Env;
+
+type MainResult<'a> = Pin<Box<dyn Future<Output = yash_env::builtin::Result> + 'a>>;
+
+fn echo_main(env: &mut Env, args: Vec<Field>) -> MainResult<'_> {
+    Box::pin(async move {
+        let message = format!("{}\n", args.iter().map(|f| &f.value).format(" "));
+        match env.system.write_all(Fd::STDOUT, message.as_bytes()).await {
+            Ok(_) => ExitStatus::SUCCESS.into(),
+            Err(_) => ExitStatus::FAILURE.into(),
+        }
+    })
+}
+
+fn cat_main(env: &mut Env, _args: Vec<Field>) -> MainResult<'_> {
+    async fn inner(env: &mut Env) -> Result<(), Errno> {
+        let mut buffer = [0; 1024];
+        loop {
+            let count = env.system.read_async(Fd::STDIN, &mut buffer).await?;
+            if count == 0 {
+                break Ok(());
+            }
+            env.system.write_all(Fd::STDOUT, &buffer[..count]).await?;
+        }
+    }
+
+    Box::pin(async move {
+        match inner(env).await {
+            Ok(_) => ExitStatus::SUCCESS.into(),
+            Err(_) => ExitStatus::FAILURE.into(),
+        }
+    })
+}
+
+fn return_main(env: &mut Env, args: Vec<Field>) -> MainResult<'_> {
+    let mut i = args.iter().peekable();
+    let no_return = i.next_if(|field| field.value == "-n").is_some();
+    let exit_status = i.next().map(|arg| ExitStatus(arg.value.parse().unwrap()));
+    let result = if no_return {
+        yash_env::builtin::Result::new(exit_status.unwrap_or(env.exit_status))
+    } else {
+        yash_env::builtin::Result::with_exit_status_and_divert(
+            env.exit_status,
+            Break(Divert::Return(exit_status)),
+        )
+    };
+    Box::pin(ready(result))
+}
+
+fn exit_main(env: &mut Env, args: Vec<Field>) -> MainResult<'_> {
+    let exit_status = args
+        .first()
+        .map(|field| ExitStatus(field.value.parse().unwrap_or(2)));
+    let result = yash_env::builtin::Result::with_exit_status_and_divert(
+        env.exit_status,
+        Break(Divert::Exit(exit_status)),
+    );
+    Box::pin(ready(result))
+}
+
+/// Installs the built-ins into the environment.
+///
+/// - `echo` prints its operands separated by spaces.
+/// - `cat` copies the standard input to the standard output.
+/// - `return [-n] [status]` returns from a function, or just sets the exit
+///   status if `-n` is given.
+/// - `exit [status]` exits the shell.
+pub fn install(env: &mut Env) {
+    env.builtins
+        .insert("echo", Builtin::new(Mandatory, echo_main));
+    env.builtins
+        .insert("cat", Builtin::new(Mandatory, cat_main));
+    env.builtins
+        .insert("return", Builtin::new(Special, return_main));
+    env.builtins
+        .insert("exit", Builtin::new(Special, exit_main));
+}
@@ -0,0 +1,326 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Parser and runner for scenario files
+
+use std::cell::RefCell;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::rc::Rc;
+use yash_env::system::r#virtual::{FileBody, Inode, SystemState};
+use yash_env_test_helper::in_virtual_system;
+use yash_semantics::read_eval_loop;
+use yash_semantics::ExitStatus;
+use yash_syntax::parser::lex::Lexer;
+
+/// Expected content of an output
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Expected {
+    /// The output must be exactly this string.
+    Exactly(String),
+    /// The output is not checked.
+    Any,
+}
+
+/// Single test case read from a scenario file
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Scenario {
+    /// Name of the scenario
+    pub name: String,
+    /// Line number where the scenario starts in the file
+    pub line_number: usize,
+    /// Files created in the virtual file system before running the script
+    pub files: Vec<(String, String)>,
+    /// Script to run
+    pub script: String,
+    /// Expected content of the standard output
+    pub stdout: Expected,
+    /// Expected content of the standard error
+    pub stderr: Expected,
+    /// Expected content of files after running the script
+    pub files_after: Vec<(String, String)>,
+    /// Expected exit status of the script
+    pub exit_status: ExitStatus,
+}
+
+impl Scenario {
+    fn new(name: String, line_number: usize) -> Self {
+        Scenario {
+            name,
+            line_number,
+            files: Vec::new(),
+            script: String::new(),
+            stdout: Expected::Exactly(String::new()),
+            stderr: Expected::Exactly(String::new()),
+            files_after: Vec::new(),
+            exit_status: ExitStatus::SUCCESS,
+        }
+    }
+}
+
+/// Section of a scenario whose body is being read
+enum Section {
+    File,
+    Script,
+    Stdout,
+    Stderr,
+    FileAfter,
+    /// Section that does not take a body
+    None,
+}
+
+fn expected_body(expected: &mut Expected) -> &mut String {
+    match expected {
+        Expected::Exactly(body) => body,
+        Expected::Any => unreachable!("a section marked `any` has no body"),
+    }
+}
+
+/// Parses the content of a scenario file.
+///
+/// Panics if the content is malformed.
+pub fn parse(content: &str) -> Vec<Scenario> {
+    let mut scenarios = Vec::<Scenario>::new();
+    let mut section = Section::None;
+
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+
+        if let Some(name) = line.strip_prefix("=== ") {
+            scenarios.push(Scenario::new(name.to_owned(), line_number));
+            section = Section::None;
+            continue;
+        }
+
+        let Some(scenario) = scenarios.last_mut() else {
+            assert!(
+                line.is_empty() || line.starts_with('#'),
+                "line {line_number}: text before the first scenario",
+            );
+            continue;
+        };
+
+        if let Some(header) = line.strip_prefix("--- ") {
+            let (keyword, argument) = match header.split_once(' ') {
+                Some((keyword, argument)) => (keyword, argument.trim()),
+                None => (header, ""),
+            };
+            section = match (keyword, argument) {
+                ("file", path) if !path.is_empty() => {
+                    scenario.files.push((path.to_owned(), String::new()));
+                    Section::File
+                }
+                ("script", "") => Section::Script,
+                ("stdout", "") => Section::Stdout,
+                ("stdout", "any") => {
+                    scenario.stdout = Expected::Any;
+                    Section::None
+                }
+                ("stderr", "") => Section::Stderr,
+                ("stderr", "any") => {
+                    scenario.stderr = Expected::Any;
+                    Section::None
+                }
+                ("file-after", path) if !path.is_empty() => {
+                    scenario.files_after.push((path.to_owned(), String::new()));
+                    Section::FileAfter
+                }
+                ("exit", status) => {
+                    let status = status.parse().unwrap_or_else(|_| {
+                        panic!("line {line_number}: invalid exit status {status:?}")
+                    });
+                    scenario.exit_status = ExitStatus(status);
+                    Section::None
+                }
+                _ => panic!("line {line_number}: invalid section header {line:?}"),
+            };
+            continue;
+        }
+
+        let body = match section {
+            Section::File => &mut scenario.files.last_mut().unwrap().1,
+            Section::Script => &mut scenario.script,
+            Section::Stdout => expected_body(&mut scenario.stdout),
+            Section::Stderr => expected_body(&mut scenario.stderr),
+            Section::FileAfter => &mut scenario.files_after.last_mut().unwrap().1,
+            Section::None => {
+                assert!(
+                    line.is_empty(),
+                    "line {line_number}: unexpected text outside sections",
+                );
+                continue;
+            }
+        };
+        body.push_str(line);
+        body.push('\n');
+    }
+
+    scenarios
+}
+
+/// Returns the content of a regular file in the virtual file system.
+fn read_file(state: &RefCell<SystemState>, path: &str) -> Option<String> {
+    let file = state.borrow().file_system.get(path).ok()?;
+    let file = file.borrow();
+    match &file.body {
+        FileBody::Regular { content, .. } => Some(String::from_utf8_lossy(content).into_owned()),
+        _ => None,
+    }
+}
+
+/// Result of running a scenario
+struct Outcome {
+    stdout: String,
+    stderr: String,
+    files_after: Vec<Option<String>>,
+    exit_status: ExitStatus,
+}
+
+/// Runs the script of a scenario in a virtual system.
+fn run(scenario: &Scenario) -> Outcome {
+    let scenario = scenario.clone();
+    in_virtual_system(move |mut env, state| async move {
+        for (path, content) in &scenario.files {
+            let inode = Rc::new(RefCell::new(Inode::new(content.as_bytes())));
+            state.borrow_mut().file_system.save(path, inode).unwrap();
+        }
+        super::builtins::install(&mut env);
+
+        let mut lexer = Lexer::with_code(&scenario.script);
+        let ref_env = RefCell::new(&mut env);
+        let result = read_eval_loop(&ref_env, &mut lexer).await;
+        drop(lexer);
+        env.apply_result(result);
+
+        Outcome {
+            stdout: read_file(&state, "/dev/stdout").unwrap_or_default(),
+            stderr: read_file(&state, "/dev/stderr").unwrap_or_default(),
+            files_after: scenario
+                .files_after
+                .iter()
+                .map(|(path, _)| read_file(&state, path))
+                .collect(),
+            exit_status: env.exit_status,
+        }
+    })
+}
+
+/// Formats a line-by-line difference between the expected and actual texts.
+///
+/// Lines only in the expected text are prefixed with `-`, lines only in the
+/// actual text with `+`, and common lines with a space.
+pub fn diff(expected: &str, actual: &str) -> String {
+    let expected = expected.lines().collect::<Vec<_>>();
+    let actual = actual.lines().collect::<Vec<_>>();
+
+    // lengths[i][j] is the length of the longest common subsequence of
+    // expected[i..] and actual[j..].
+    let mut lengths = vec![vec![0; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lengths[i][j] = if expected[i] == actual[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut result = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            writeln!(result, " {}", expected[i]).unwrap();
+            i += 1;
+            j += 1;
+        } else if j == actual.len()
+            || (i < expected.len() && lengths[i + 1][j] >= lengths[i][j + 1])
+        {
+            writeln!(result, "-{}", expected[i]).unwrap();
+            i += 1;
+        } else {
+            writeln!(result, "+{}", actual[j]).unwrap();
+            j += 1;
+        }
+    }
+    result
+}
+
+/// Compares an output with the expected content and reports a mismatch.
+fn check(report: &mut String, what: &str, expected: &str, actual: &str) {
+    if expected != actual {
+        writeln!(report, "{what} differs (-expected +actual):").unwrap();
+        report.push_str(&diff(expected, actual));
+        if expected.trim_end_matches('\n') == actual.trim_end_matches('\n') {
+            writeln!(report, "(the difference is in trailing newlines)").unwrap();
+        }
+    }
+}
+
+/// Runs a scenario and returns a report of mismatches, if any.
+pub fn verify(scenario: &Scenario) -> Option<String> {
+    let outcome = run(scenario);
+    let mut report = String::new();
+
+    if let Expected::Exactly(expected) = &scenario.stdout {
+        check(&mut report, "stdout", expected, &outcome.stdout);
+    }
+    if let Expected::Exactly(expected) = &scenario.stderr {
+        check(&mut report, "stderr", expected, &outcome.stderr);
+    }
+    for ((path, expected), actual) in scenario.files_after.iter().zip(&outcome.files_after) {
+        match actual {
+            Some(actual) => check(&mut report, path, expected, actual),
+            None => writeln!(report, "{path} is not a regular file").unwrap(),
+        }
+    }
+    if outcome.exit_status != scenario.exit_status {
+        writeln!(
+            report,
+            "exit status: expected {}, actual {}",
+            scenario.exit_status, outcome.exit_status,
+        )
+        .unwrap();
+    }
+
+    (!report.is_empty()).then_some(report)
+}
+
+/// Runs all scenarios in a file and panics if any of them fails.
+///
+/// The `name` is a file name relative to the directory containing this file.
+pub fn run_file(name: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/scenario")
+        .join(name);
+    let content = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("cannot read {}: {e}", path.display()));
+    let scenarios = parse(&content);
+    assert!(!scenarios.is_empty(), "no scenarios in {name}");
+
+    let mut failures = String::new();
+    for scenario in &scenarios {
+        if let Some(report) = verify(scenario) {
+            writeln!(
+                failures,
+                "=== {} ({name}:{})\n{report}",
+                scenario.name, scenario.line_number,
+            )
+            .unwrap();
+        }
+    }
+    assert!(failures.is_empty(), "failed scenarios:\n\n{failures}");
+}
@@ -0,0 +1,96 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Scenario tests
+//!
+//! Each scenario parses and executes a script with [`read_eval_loop`] in a
+//! virtual system and compares the results with the expectation. Scenarios are
+//! written in `*.txt` files in this directory. A file contains any number of
+//! scenarios, each of which starts with a line of the form `=== <name>` and
+//! continues with the following sections:
+//!
+//! - `--- file <path>` creates a regular file in the virtual file system before
+//!   running the script. The following lines are the content of the file.
+//!   This section may appear more than once.
+//! - `--- script` is followed by the script to run.
+//! - `--- stdout` is followed by the expected standard output. If omitted, the
+//!   standard output is expected to be empty. Write `--- stdout any` to skip
+//!   checking it.
+//! - `--- stderr` is followed by the expected standard error, as with
+//!   `--- stdout`.
+//! - `--- file-after <path>` is followed by the expected content of a regular
+//!   file after running the script. This section may appear more than once.
+//! - `--- exit <status>` specifies the expected exit status of the script. If
+//!   omitted, the exit status is expected to be zero.
+//!
+//! Every line in the body of a section, including an empty line, is
+//! terminated by a newline, so scenarios must not be separated by blank lines.
+//! Lines before the first scenario that start with `#` are comments. The script can
+//! use the minimal built-ins defined in the [`builtins`] module.
+//!
+//! To add scenarios, append them to an existing file or create a new file and
+//! add a test function calling [`harness::run_file`] for it below. When a
+//! scenario fails, the test reports the differences between the expected and
+//! actual outputs.
+//!
+//! [`read_eval_loop`]: yash_semantics::read_eval_loop
+
+mod builtins;
+mod harness;
+
+use harness::run_file;
+
+#[test]
+fn glob() {
+    run_file("glob.txt")
+}
+
+#[test]
+fn runner() {
+    run_file("runner.txt")
+}
+
+#[test]
+fn simple_command() {
+    run_file("simple_command.txt")
+}
+
+#[test]
+fn parse_scenario() {
+    let scenarios = harness::parse(
+        "# comment\n=== one\n--- file a\nx\n--- script\necho\n--- stdout\n\n\
+         --- stderr any\n--- exit 3\n=== two\n--- script\n",
+    );
+    assert_eq!(scenarios.len(), 2);
+    assert_eq!(scenarios[0].name, "one");
+    assert_eq!(scenarios[0].line_number, 2);
+    assert_eq!(scenarios[0].files, [("a".to_owned(), "x\n".to_owned())]);
+    assert_eq!(scenarios[0].script, "echo\n");
+    assert_eq!(
+        scenarios[0].stdout,
+        harness::Expected::Exactly("\n".to_owned())
+    );
+    assert_eq!(scenarios[0].stderr, harness::Expected::Any);
+    assert_eq!(scenarios[0].exit_status, yash_semantics::ExitStatus(3));
+    assert_eq!(scenarios[1].name, "two");
+    assert_eq!(scenarios[1].script, "");
+}
+
+#[test]
+fn diff_marks_changed_lines() {
+    let diff = harness::diff("a\nb\nc\n", "a\nx\nc\nd\n");
+    assert_eq!(diff, " a\n-b\n+x\n c\n+d\n");
+}
@@ -0,0 +1,81 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Benchmarks for lexing and parsing representative scripts
+//!
+//! Run with `cargo bench --package yash-syntax`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::fmt::Write as _;
+use std::hint::black_box;
+use yash_syntax::syntax::List;
+
+/// Returns a script with a case command with many branches.
+fn big_case() -> String {
+    let mut script = String::from("case $1 in\n");
+    for i in 0..500 {
+        writeln!(
+            script,
+            "    (foo{i}|bar{i}|*baz{i}*) echo \"$i\" '{i}' ${{x:-{i}}};;"
+        )
+        .unwrap();
+    }
+    script.push_str("esac\n");
+    script
+}
+
+/// Returns a script with many here-documents.
+fn here_docs() -> String {
+    let mut script = String::new();
+    for i in 0..200 {
+        writeln!(script, "cat <<END{i} <<-'RAW{i}' >>/dev/null").unwrap();
+        writeln!(script, "line $i of ${{file}} $(echo {i}) `echo {i}`").unwrap();
+        writeln!(script, "another line with \\$ and \\\\ and $((i + {i}))").unwrap();
+        writeln!(script, "END{i}").unwrap();
+        writeln!(script, "\traw $text {i}").unwrap();
+        writeln!(script, "\tRAW{i}").unwrap();
+    }
+    script
+}
+
+/// Returns a script with a long pipeline.
+fn long_pipeline() -> String {
+    let mut script = String::from("cat input");
+    for i in 0..1000 {
+        write!(script, " |\n    sed -e 's/a{i}/b{i}/' 2>&1").unwrap();
+    }
+    script.push('\n');
+    script
+}
+
+fn parse(script: &str) -> List {
+    script.parse().unwrap()
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for (name, script) in [
+        ("big_case", big_case()),
+        ("here_docs", here_docs()),
+        ("long_pipeline", long_pipeline()),
+    ] {
+        group.bench_function(name, |b| b.iter(|| parse(black_box(&script))));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse);
+criterion_main!(benches);
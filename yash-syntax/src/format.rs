@@ -0,0 +1,113 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Formatting whole scripts
+//!
+//! This module provides the [`format_script`] function, which parses a shell
+//! script and re-prints it in a canonical format.
+//!
+//! The canonical format follows the conventions of the
+//! [`Display`](std::fmt::Display) implementations of the
+//! [syntax](crate::syntax) types:
+//!
+//! - Each top-level and-or list is printed on its own line. A `;` separator
+//!   between and-or lists is replaced with a newline. A `&` separator is
+//!   retained at the end of the line.
+//! - Compound commands are printed on a single line with their inner commands
+//!   separated by `; `.
+//! - Tokens are separated by a single space. Quotes are kept as they are.
+//! - Here-document contents are placed after the line containing the
+//!   here-document operators, followed by the delimiter.
+//! - Blank lines and comments are removed since they are not part of the
+//!   syntax tree.
+//!
+//! Parsing the formatted script results in a syntax tree equal to that of the
+//! original script except for source locations.
+//!
+//! ```
+//! # use yash_syntax::format::format_script;
+//! let script = "if  true ;then\n  echo  ok ;fi # comment\ncat <<END; echo done\nfoo\nEND\n";
+//! let formatted = format_script(script).unwrap();
+//! assert_eq!(formatted, "if true; then echo ok; fi\ncat <<END\nfoo\nEND\necho done\n");
+//! ```
+
+use crate::parser::lex::Lexer;
+use crate::parser::{Error, Parser};
+use futures_util::FutureExt as _;
+
+/// Parses the whole script and returns it in the canonical format.
+///
+/// See the [module documentation](self) for the format.
+///
+/// If the script contains a syntax error, this function returns the error
+/// without producing any output. The formatted script is returned only when
+/// the entire script has been parsed successfully.
+///
+/// Aliases are not substituted in the script.
+pub fn format_script(code: &str) -> Result<String, Error> {
+    let mut lexer = Lexer::with_code(code);
+    let mut parser = Parser::new(&mut lexer);
+    let mut commands = parser.commands();
+    let mut result = String::new();
+    while let Some(list) = commands
+        .next()
+        .now_or_never()
+        .expect("parsing a string should not block")
+    {
+        result.push_str(&list?.to_source_string());
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{ErrorCause, SyntaxError};
+    use crate::syntax::List;
+
+    #[test]
+    fn empty_script() {
+        assert_eq!(format_script("").unwrap(), "");
+        assert_eq!(format_script("\n  \n# comment\n").unwrap(), "");
+    }
+
+    #[test]
+    fn script_without_trailing_newline() {
+        assert_eq!(
+            format_script("echo  foo;echo bar").unwrap(),
+            "echo foo\necho bar\n"
+        );
+    }
+
+    #[test]
+    fn error_in_last_command() {
+        let e = format_script("echo ok\nfoo )\n").unwrap_err();
+        assert_eq!(e.cause, ErrorCause::Syntax(SyntaxError::UnopenedSubshell));
+        assert_eq!(e.location.range, 12..13);
+    }
+
+    #[test]
+    fn formatted_script_is_stable() {
+        let script = "for i in 1 2; do\n  cat <<-EOF &\n\t$i\n\tEOF\ndone\n\
+            case $x in a|b) ;; *) f() { :; } ;& esac\n";
+        let formatted = format_script(script).unwrap();
+        assert_eq!(format_script(&formatted).unwrap(), formatted);
+
+        let original: List = script.parse().unwrap();
+        let reparsed: List = formatted.parse().unwrap();
+        assert_eq!(reparsed.to_string(), original.to_string());
+    }
+}
@@ -37,9 +37,13 @@
 //!
 //! This crate also defines the [`alias`] module that can be used to define
 //! aliases that are recognized while parsing.
+//!
+//! The [`format`](mod@format) module provides a function that formats a whole
+//! script in a canonical style.
 
 pub mod alias;
 pub mod decl_util;
+pub mod format;
 pub mod input;
 pub mod parser;
 pub mod source;
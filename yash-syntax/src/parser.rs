@@ -91,3 +91,4 @@ pub use self::core::Result;
 pub use self::error::Error;
 pub use self::error::ErrorCause;
 pub use self::error::SyntaxError;
+pub use self::list::Commands;
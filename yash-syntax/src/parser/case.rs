@@ -156,7 +156,7 @@ impl Parser<'_, '_> {
             let next_token = self.take_token_auto(&[In]).await?;
             match next_token.id {
                 Token(Some(In)) => break,
-                Operator(Newline) => (),
+                Operator(Newline) => self.here_doc_contents().await?,
                 _ => {
                     let opening_location = open.word.location;
                     let cause = SyntaxError::MissingIn { opening_location }.into();
@@ -342,6 +342,39 @@ mod tests {
         assert_eq!(next.id, EndOfInput);
     }
 
+    #[test]
+    fn parser_case_item_with_semicolon_bar() {
+        let mut lexer = Lexer::with_code("foo);|");
+        let mut parser = Parser::new(&mut lexer);
+
+        let (item, continued) = parser.case_item().now_or_never().unwrap().unwrap().unwrap();
+        assert_eq!(item.patterns.len(), 1);
+        assert_eq!(item.patterns[0].to_string(), "foo");
+        assert_eq!(item.body.0, []);
+        assert_eq!(item.continuation, CaseContinuation::Continue);
+        assert!(continued);
+
+        let next = parser.peek_token().now_or_never().unwrap().unwrap();
+        assert_eq!(next.id, EndOfInput);
+    }
+
+    #[test]
+    fn parser_case_item_with_semicolon_semicolon_and() {
+        let mut lexer = Lexer::with_code("foo) :;;&");
+        let mut parser = Parser::new(&mut lexer);
+
+        let (item, continued) = parser.case_item().now_or_never().unwrap().unwrap().unwrap();
+        assert_eq!(item.patterns.len(), 1);
+        assert_eq!(item.patterns[0].to_string(), "foo");
+        assert_eq!(item.body.0.len(), 1);
+        assert_eq!(item.body.0[0].to_string(), ":");
+        assert_eq!(item.continuation, CaseContinuation::Continue);
+        assert!(continued);
+
+        let next = parser.peek_token().now_or_never().unwrap().unwrap();
+        assert_eq!(next.id, EndOfInput);
+    }
+
     #[test]
     fn parser_case_item_missing_pattern_without_open_paren() {
         let mut lexer = Lexer::with_code(")");
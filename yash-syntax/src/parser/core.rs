@@ -322,6 +322,14 @@ impl<'a, 'b> Parser<'a, 'b> {
     ///   substitution that ends with a blank character.
     /// - The token names a global alias.
     ///
+    /// Alias substitution is not performed on a token that comes from the
+    /// replacement string of the same alias, directly or indirectly through
+    /// other aliases (see [`Source::is_alias_for`]). This prevents infinite
+    /// substitution of recursive aliases like `alias a=b b=a`, regardless of
+    /// whether the aliases are global or not.
+    ///
+    /// [`Source::is_alias_for`]: crate::source::Source::is_alias_for
+    ///
     /// However, alias substitution should _not_ be performed on a reserved word
     /// in any case. It is your responsibility to check the token type and not to
     /// call this function on a reserved word. That is why this function is named
@@ -446,6 +454,7 @@ mod tests {
     use crate::alias::AliasSet;
     use crate::alias::HashEntry;
     use crate::source::Location;
+    use crate::source::Source;
     use assert_matches::assert_matches;
     use futures_util::FutureExt;
     use std::cell::OnceCell;
@@ -666,6 +675,65 @@ mod tests {
         assert_eq!(token.to_string(), "x");
     }
 
+    #[test]
+    fn parser_take_token_manual_mutually_recursive_global_aliases() {
+        let mut lexer = Lexer::with_code("a");
+        #[allow(clippy::mutable_key_type)]
+        let mut aliases = AliasSet::new();
+        aliases.insert(HashEntry::new(
+            "a".to_string(),
+            "b".to_string(),
+            true,
+            Location::dummy("?"),
+        ));
+        aliases.insert(HashEntry::new(
+            "b".to_string(),
+            "a".to_string(),
+            true,
+            Location::dummy("?"),
+        ));
+        let mut parser = Parser::config().aliases(&aliases).input(&mut lexer);
+
+        let result = parser.take_token_manual(false).now_or_never().unwrap();
+        assert_matches!(result, Ok(Rec::AliasSubstituted));
+
+        let result = parser.take_token_manual(false).now_or_never().unwrap();
+        assert_matches!(result, Ok(Rec::AliasSubstituted));
+
+        let result = parser.take_token_manual(false).now_or_never().unwrap();
+        let token = result.unwrap().unwrap();
+        assert_eq!(token.to_string(), "a");
+        assert_matches!(&*token.word.location.code.source, Source::Alias { alias, .. } => {
+            assert_eq!(alias.name, "b");
+        });
+    }
+
+    #[test]
+    fn parser_take_token_manual_self_recursive_global_alias() {
+        let mut lexer = Lexer::with_code("a");
+        #[allow(clippy::mutable_key_type)]
+        let mut aliases = AliasSet::new();
+        aliases.insert(HashEntry::new(
+            "a".to_string(),
+            "a a".to_string(),
+            true,
+            Location::dummy("?"),
+        ));
+        let mut parser = Parser::config().aliases(&aliases).input(&mut lexer);
+
+        let result = parser.take_token_manual(false).now_or_never().unwrap();
+        assert_matches!(result, Ok(Rec::AliasSubstituted));
+
+        for _ in 0..2 {
+            let result = parser.take_token_manual(false).now_or_never().unwrap();
+            let token = result.unwrap().unwrap();
+            assert_eq!(token.to_string(), "a");
+        }
+
+        let token = parser.take_token_raw().now_or_never().unwrap().unwrap();
+        assert_eq!(token.id, EndOfInput);
+    }
+
     #[test]
     fn parser_take_token_auto_non_keyword() {
         let mut lexer = Lexer::with_code("X");
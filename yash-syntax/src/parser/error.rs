@@ -52,6 +52,9 @@ pub enum SyntaxError {
     InvalidModifier,
     /// A braced parameter expansion has both a prefix and suffix modifier.
     MultipleModifier,
+    /// A modifier that is not defined in POSIX is used in the POSIXly-correct
+    /// mode.
+    NonPosixModifier,
     /// A command substitution started with `$(` but lacks a closing `)`.
     UnclosedCommandSubstitution { opening_location: Location },
     /// A command substitution started with `` ` `` but lacks a closing `` ` ``.
@@ -202,6 +205,9 @@ impl SyntaxError {
             InvalidParam => "the parameter name is invalid",
             InvalidModifier => "the parameter expansion contains a malformed modifier",
             MultipleModifier => "a suffix modifier cannot be used together with a prefix modifier",
+            NonPosixModifier => {
+                "the parameter expansion modifier is not supported in the POSIXly-correct mode"
+            }
             UnclosedCommandSubstitution { .. } => "the command substitution is not closed",
             UnclosedBackquote { .. } => "the backquote is not closed",
             UnclosedArith { .. } => "the arithmetic expansion is not closed",
@@ -305,6 +311,7 @@ impl SyntaxError {
             InvalidParam => "not a valid named or positional parameter",
             InvalidModifier => "broken modifier",
             MultipleModifier => "conflicting modifier",
+            NonPosixModifier => "non-POSIX modifier",
             UnclosedBackquote { .. } => "expected '`'",
             UnclosedArith { .. } => "expected `))`",
             InvalidCommandToken => "does not begin a valid command",
@@ -103,7 +103,11 @@ impl Parser<'_, '_> {
                 Token(_) | IoNumber => {
                     values.push(next.word);
                 }
-                Operator(Semicolon) | Operator(Newline) => {
+                Operator(Semicolon) => {
+                    return Ok((Some(values), opening_location));
+                }
+                Operator(Newline) => {
+                    self.here_doc_contents().await?;
                     return Ok((Some(values), opening_location));
                 }
                 _ => {
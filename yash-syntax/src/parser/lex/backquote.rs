@@ -34,8 +34,13 @@ impl WordLexer<'_, '_> {
             };
             let is_escapable =
                 |c| matches!(c, '$' | '`' | '\\') || c == '"' && double_quote_escapable;
-            if let Some(c) = self.consume_char_if(is_escapable).await? {
-                return Ok(Some(BackquoteUnit::Backslashed(c.value)));
+            let escaped = self
+                .disable_line_continuation()
+                .consume_char_if(is_escapable)
+                .await?
+                .map(|c| c.value);
+            if let Some(c) = escaped {
+                return Ok(Some(BackquoteUnit::Backslashed(c)));
             } else {
                 return Ok(Some(BackquoteUnit::Literal('\\')));
             }
@@ -219,6 +224,27 @@ mod tests {
         assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(None));
     }
 
+    #[test]
+    fn lexer_backquote_escaped_backslash_before_newline() {
+        // The second backslash is escaped, so it does not start a line
+        // continuation.
+        let mut lexer = Lexer::with_code("`\\\\\n`");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        let result = lexer.backquote().now_or_never().unwrap().unwrap().unwrap();
+        assert_matches!(result, TextUnit::Backquote { content, location } => {
+            assert_eq!(
+                content,
+                [BackquoteUnit::Backslashed('\\'), BackquoteUnit::Literal('\n')]
+            );
+            assert_eq!(location.range, 0..5);
+        });
+
+        assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(None));
+    }
+
     #[test]
     fn lexer_backquote_unclosed_empty() {
         let mut lexer = Lexer::with_code("`");
@@ -82,7 +82,7 @@ impl WordLexer<'_, '_> {
         // look ahead to see if it is okay to treat the `#` as a prefix.
         if let Some(c) = self.peek_char().await? {
             // Check characters that cannot be a special parameter.
-            if matches!(c, '}' | '+' | '=' | ':' | '%') {
+            if matches!(c, '}' | '+' | '=' | ':' | '%' | '/') {
                 return Ok(false);
             }
 
@@ -119,6 +119,14 @@ impl WordLexer<'_, '_> {
     /// including the closing brace. Otherwise, no characters are consumed and
     /// the return value is `Ok(None)`.
     ///
+    /// The word in the modifier is parsed recursively as a word, so a nested
+    /// expansion like `${x:-${y:-z}}` is parsed as a
+    /// [`TextUnit::BracedParam`](crate::syntax::TextUnit::BracedParam) in the
+    /// modifier. A `}` that is quoted or appears in a nested expansion or
+    /// command substitution does not close the parameter expansion. If the
+    /// closing brace is missing, the error is `SyntaxError::UnclosedParam`
+    /// with the location of the opening `${`.
+    ///
     /// The `start_index` parameter should be the index for the initial `$`. It is
     /// used to construct the result, but this function does not check if it
     /// actually points to the `$`.
@@ -133,7 +141,11 @@ impl WordLexer<'_, '_> {
 
         let param_start_index = self.index();
 
-        let c = self.peek_char().await?.unwrap();
+        let Some(c) = self.peek_char().await? else {
+            let cause = SyntaxError::EmptyParam.into();
+            let location = self.location().await?.clone();
+            return Err(Error { cause, location });
+        };
         let param = if is_name_char(c) {
             self.consume_char();
 
@@ -195,10 +207,14 @@ mod tests {
     use crate::parser::lex::Lexer;
     use crate::parser::lex::WordContext;
     use crate::source::Source;
+    use crate::syntax::SubstCount;
     use crate::syntax::SwitchCondition;
     use crate::syntax::SwitchType;
+    use crate::syntax::Text;
+    use crate::syntax::TextUnit;
     use crate::syntax::TrimLength;
     use crate::syntax::TrimSide;
+    use crate::syntax::WordUnit;
     use assert_matches::assert_matches;
     use futures_util::FutureExt;
 
@@ -432,6 +448,22 @@ mod tests {
         assert_eq!(e.location.range, 2..3);
     }
 
+    #[test]
+    fn lexer_braced_param_unclosed_at_end_of_input() {
+        let mut lexer = Lexer::with_code("${");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let e = lexer.braced_param(0).now_or_never().unwrap().unwrap_err();
+        assert_eq!(e.cause, ErrorCause::Syntax(SyntaxError::EmptyParam));
+        assert_eq!(*e.location.code.value.borrow(), "${");
+        assert_eq!(e.location.range, 2..2);
+    }
+
     #[test]
     fn lexer_braced_param_unclosed_with_name() {
         let mut lexer = Lexer::with_code("${_;");
@@ -791,6 +823,29 @@ mod tests {
         assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(Some(';')));
     }
 
+    #[test]
+    fn lexer_braced_param_hash_with_subst() {
+        let mut lexer = Lexer::with_code("${#/1/2};");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let result = lexer.braced_param(0).now_or_never().unwrap();
+        let param = result.unwrap().unwrap();
+        assert_eq!(param.param, Param::from(SpecialParam::Number));
+        assert_matches!(param.modifier, Modifier::Subst(subst) => {
+            assert_eq!(subst.count, SubstCount::First);
+            assert_eq!(subst.pattern.to_string(), "1");
+            assert_eq!(subst.replacement.to_string(), "2");
+        });
+        assert_eq!(param.location.range, 0..8);
+
+        assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(Some(';')));
+    }
+
     #[test]
     fn lexer_braced_param_multiple_modifier() {
         let mut lexer = Lexer::with_code("${#x+};");
@@ -855,4 +910,148 @@ mod tests {
 
         assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(Some('z')));
     }
+
+    #[test]
+    fn lexer_braced_param_nested_in_switch_word() {
+        let mut lexer = Lexer::with_code("${x:-${y:-z}};");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let result = lexer.braced_param(0).now_or_never().unwrap();
+        let param = result.unwrap().unwrap();
+        assert_eq!(param.param, Param::variable("x"));
+        assert_matches!(param.modifier, Modifier::Switch(switch) => {
+            assert_eq!(switch.r#type, SwitchType::Default);
+            assert_eq!(switch.condition, SwitchCondition::UnsetOrEmpty);
+            assert_matches!(&switch.word.units[..], [WordUnit::Unquoted(TextUnit::BracedParam(inner))] => {
+                assert_eq!(inner.param, Param::variable("y"));
+                assert_eq!(inner.to_string(), "${y:-z}");
+                assert_eq!(inner.location.range, 5..12);
+            });
+        });
+        assert_eq!(param.location.range, 0..13);
+
+        assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(Some(';')));
+    }
+
+    #[test]
+    fn lexer_braced_param_nested_in_trim_pattern() {
+        let mut lexer = Lexer::with_code("${x%${y#*}}");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Text,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let result = lexer.braced_param(0).now_or_never().unwrap();
+        let param = result.unwrap().unwrap();
+        assert_matches!(param.modifier, Modifier::Trim(trim) => {
+            assert_matches!(&trim.pattern.units[..], [WordUnit::Unquoted(TextUnit::BracedParam(inner))] => {
+                assert_eq!(inner.param, Param::variable("y"));
+                assert_eq!(inner.to_string(), "${y#*}");
+            });
+        });
+        assert_eq!(param.location.range, 0..11);
+        assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(None));
+    }
+
+    #[test]
+    fn lexer_braced_param_closing_brace_quoted_in_switch_word() {
+        let mut lexer = Lexer::with_code(r#"${x:-"}"'}'\}}"#);
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let result = lexer.braced_param(0).now_or_never().unwrap();
+        let param = result.unwrap().unwrap();
+        assert_matches!(param.modifier, Modifier::Switch(switch) => {
+            assert_eq!(switch.word.to_string(), r#""}"'}'\}"#);
+        });
+        assert_eq!(param.location.range, 0..14);
+        assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(None));
+    }
+
+    #[test]
+    fn lexer_braced_param_braces_in_command_substitution_in_switch_word() {
+        let mut lexer = Lexer::with_code("${x:-$(f() { echo }; }; f)}");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let result = lexer.braced_param(0).now_or_never().unwrap();
+        let param = result.unwrap().unwrap();
+        assert_matches!(param.modifier, Modifier::Switch(switch) => {
+            assert_matches!(&switch.word.units[..], [WordUnit::Unquoted(TextUnit::CommandSubst { content, .. })] => {
+                assert_eq!(&**content, "f() { echo }; }; f");
+            });
+        });
+        assert_eq!(param.location.range, 0..27);
+        assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(None));
+    }
+
+    #[test]
+    fn lexer_braced_param_nested_in_double_quotes() {
+        let mut lexer = Lexer::with_code(r#""${x:-"${y:-"}"}"}""#);
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+
+        let result = lexer.word_unit(|_| false).now_or_never().unwrap();
+        let unit = result.unwrap().unwrap();
+        assert_matches!(unit, WordUnit::DoubleQuote(Text(units)) => {
+            assert_matches!(&units[..], [TextUnit::BracedParam(outer)] => {
+                assert_eq!(outer.param, Param::variable("x"));
+                assert_eq!(outer.to_string(), r#"${x:-"${y:-"}"}"}"#);
+            });
+        });
+        assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(None));
+    }
+
+    #[test]
+    fn lexer_braced_param_unclosed_outer_expansion() {
+        let mut lexer = Lexer::with_code("${x:-${y:-z}");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let e = lexer.braced_param(0).now_or_never().unwrap().unwrap_err();
+        assert_matches!(e.cause,
+            ErrorCause::Syntax(SyntaxError::UnclosedParam { opening_location }) => {
+            assert_eq!(opening_location.range, 0..2);
+        });
+        assert_eq!(e.location.range, 12..12);
+    }
+
+    #[test]
+    fn lexer_braced_param_unclosed_inner_expansion() {
+        let mut lexer = Lexer::with_code("${x:-${y:-z");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+        lexer.peek_char().now_or_never().unwrap().unwrap();
+        lexer.consume_char();
+
+        let e = lexer.braced_param(0).now_or_never().unwrap().unwrap_err();
+        assert_matches!(e.cause,
+            ErrorCause::Syntax(SyntaxError::UnclosedParam { opening_location }) => {
+            assert_eq!(opening_location.range, 5..7);
+        });
+        assert_eq!(e.location.range, 11..11);
+    }
 }
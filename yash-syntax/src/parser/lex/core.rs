@@ -487,6 +487,7 @@ impl Config {
         Lexer {
             core: LexerCore::new(input, start_line_number, source),
             line_continuation_enabled: true,
+            posixly_correct: false,
         }
     }
 }
@@ -533,6 +534,7 @@ pub struct Lexer<'a> {
     // skipping to `LexerCore`.
     core: LexerCore<'a>,
     line_continuation_enabled: bool,
+    posixly_correct: bool,
 }
 
 impl<'a> Lexer<'a> {
@@ -593,6 +595,25 @@ impl<'a> Lexer<'a> {
         inner(code, source.into())
     }
 
+    /// Returns whether the lexer is in the POSIXly-correct mode.
+    ///
+    /// See [`set_posixly_correct`](Self::set_posixly_correct) for details.
+    #[must_use]
+    pub fn posixly_correct(&self) -> bool {
+        self.posixly_correct
+    }
+
+    /// Sets whether the lexer is in the POSIXly-correct mode.
+    ///
+    /// In the POSIXly-correct mode, the lexer rejects syntax extensions that
+    /// are not defined in POSIX, such as the [substring](crate::syntax::Substring)
+    /// and [substitution](crate::syntax::Subst) modifiers of parameter
+    /// expansion. The mode is off by default. Changing the mode affects only
+    /// the characters that are parsed after the change.
+    pub fn set_posixly_correct(&mut self, posixly_correct: bool) {
+        self.posixly_correct = posixly_correct;
+    }
+
     /// Disables line continuation recognition onward.
     ///
     /// By default, [`peek_char`](Self::peek_char) silently skips line
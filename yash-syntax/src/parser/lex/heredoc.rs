@@ -307,6 +307,74 @@ END
         assert_eq!(location.range, 12..13);
     }
 
+    #[test]
+    fn lexer_here_doc_content_with_tabs_removed_and_quoted_delimiter() {
+        let heredoc = here_doc_operator("'END'", true);
+
+        let mut lexer = Lexer::with_code("\t$foo\n\t\tEND\n");
+        lexer
+            .here_doc_content(&heredoc)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            heredoc.content.get().unwrap().0,
+            [
+                Literal('$'),
+                Literal('f'),
+                Literal('o'),
+                Literal('o'),
+                Literal('\n')
+            ]
+        );
+    }
+
+    #[test]
+    fn lexer_here_doc_content_leading_spaces_not_removed() {
+        let heredoc = here_doc_operator("END", true);
+
+        let mut lexer = Lexer::with_code(" \tfoo\n END\nEND\n");
+        lexer
+            .here_doc_content(&heredoc)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_eq!(heredoc.content.get().unwrap().to_string(), " \tfoo\n END\n",);
+    }
+
+    #[test]
+    fn lexer_here_doc_content_delimiter_with_trailing_blanks() {
+        let heredoc = here_doc_operator("END", false);
+
+        let mut lexer = Lexer::with_code("END \nEND\t\nEND\n");
+        lexer
+            .here_doc_content(&heredoc)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_eq!(heredoc.content.get().unwrap().to_string(), "END \nEND\t\n",);
+    }
+
+    #[test]
+    fn lexer_here_doc_content_locations_in_later_lines() {
+        let heredoc = here_doc_operator("END", false);
+
+        let mut lexer = Lexer::with_code("foo\nbar ${X?}\nEND\n");
+        lexer
+            .here_doc_content(&heredoc)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        let content = heredoc.content.get().unwrap();
+        assert_matches!(&content.0[8], BracedParam(param) => {
+            assert_eq!(param.param.id, "X");
+            assert_eq!(*param.location.code.value.borrow(), "foo\nbar ${X?}\nEND\n");
+            assert_eq!(param.location.range, 8..13);
+            assert_eq!(param.location.code.line_number(8).get(), 2);
+        });
+    }
+
     #[test]
     fn lexer_here_doc_content_unclosed() {
         let heredoc = here_doc_operator("END", false);
@@ -329,4 +397,22 @@ END
         assert_eq!(*e.location.code.source, Source::Unknown);
         assert_eq!(e.location.range, 0..0);
     }
+    #[test]
+    fn lexer_here_doc_content_delimiter_at_end_of_input_without_newline() {
+        let heredoc = here_doc_operator("END", false);
+
+        let mut lexer = Lexer::with_code("foo\nEND");
+        let e = lexer
+            .here_doc_content(&heredoc)
+            .now_or_never()
+            .unwrap()
+            .unwrap_err();
+        assert_matches!(e.cause,
+            ErrorCause::Syntax(SyntaxError::UnclosedHereDocContent { redir_op_location }) => {
+            assert_eq!(*redir_op_location.code.value.borrow(), "END");
+            assert_eq!(redir_op_location.range, 0..3);
+        });
+        assert_eq!(*e.location.code.value.borrow(), "foo\nEND");
+        assert_eq!(e.location.range, 7..7);
+    }
 }
@@ -23,12 +23,16 @@ use crate::parser::core::Result;
 use crate::parser::error::Error;
 use crate::parser::error::SyntaxError;
 use crate::syntax::Modifier;
+use crate::syntax::Subst;
+use crate::syntax::SubstCount;
+use crate::syntax::Substring;
 use crate::syntax::Switch;
 use crate::syntax::SwitchCondition;
 use crate::syntax::SwitchType;
 use crate::syntax::Trim;
 use crate::syntax::TrimLength;
 use crate::syntax::TrimSide;
+use crate::syntax::Word;
 
 impl Lexer<'_> {
     /// Returns an invalid modifier error.
@@ -86,6 +90,39 @@ impl Lexer<'_> {
     }
 }
 
+impl Lexer<'_> {
+    /// Returns an error if the lexer is in the POSIXly-correct mode.
+    ///
+    /// This function should be called after parsing a non-POSIX modifier.
+    /// The `start_index` must be the index of the first character of the
+    /// modifier.
+    fn reject_non_posix_modifier(&mut self, start_index: usize) -> Result<()> {
+        if self.posixly_correct() {
+            let cause = SyntaxError::NonPosixModifier.into();
+            let location = self.location_range(start_index..self.index());
+            return Err(Error { cause, location });
+        }
+        Ok(())
+    }
+
+    /// Parses a [substring](Substring) after the initial colon.
+    async fn substring(&mut self, start_index: usize) -> Result<Modifier> {
+        let is_escapable = |c| matches!(c, '$' | '`' | '\\' | '}');
+
+        // Boxing needed for recursion
+        let offset =
+            Box::pin(self.text_with_parentheses(|c| c == ':' || c == '}', is_escapable)).await?;
+        let length = if self.skip_if(|c| c == ':').await? {
+            Some(Box::pin(self.text_with_parentheses(|c| c == '}', is_escapable)).await?)
+        } else {
+            None
+        };
+
+        self.reject_non_posix_modifier(start_index)?;
+        Ok(Modifier::Substring(Substring { offset, length }))
+    }
+}
+
 impl WordLexer<'_, '_> {
     /// Parses a [switch](Switch), except the optional initial colon.
     ///
@@ -121,6 +158,49 @@ impl WordLexer<'_, '_> {
         }))
     }
 
+    /// Parses a [substitution](Subst).
+    ///
+    /// This function blindly consumes the current character, which must be
+    /// `/`.
+    async fn subst(&mut self, start_index: usize) -> Result<Modifier> {
+        self.consume_char();
+        let count = if self.skip_if(|c| c == '/').await? {
+            SubstCount::All
+        } else {
+            SubstCount::First
+        };
+
+        let mut lexer = WordLexer {
+            lexer: self.lexer,
+            context: WordContext::Word,
+        };
+        // Boxing needed for recursion
+        let mut pattern = Box::pin(lexer.word(|c| c == '/' || c == '}')).await?;
+        pattern.parse_tilde_front();
+
+        let replacement = if self.skip_if(|c| c == '/').await? {
+            let mut word = Box::pin(self.word(|c| c == '}')).await?;
+            match self.context {
+                WordContext::Text => (),
+                WordContext::Word => word.parse_tilde_front(),
+            }
+            word
+        } else {
+            let index = self.index();
+            Word {
+                units: vec![],
+                location: self.location_range(index..index),
+            }
+        };
+
+        self.reject_non_posix_modifier(start_index)?;
+        Ok(Modifier::Subst(Subst {
+            count,
+            pattern,
+            replacement,
+        }))
+    }
+
     /// Parses a suffix modifier, i.e., a modifier other than the length prefix.
     ///
     /// If there is a [switch](Switch), [`self.context`](Self::context) affects
@@ -128,7 +208,13 @@ impl WordLexer<'_, '_> {
     /// expansion is recognized at the beginning of the word and any character
     /// can be escaped by a backslash. If the context is `Text`, only `$`, `"`,
     /// `` ` ``, `\` and `}` can be escaped and single quotes are not recognized
-    /// in the word.
+    /// in the word. The same applies to the replacement of a
+    /// [substitution](Subst).
+    ///
+    /// A colon that is not followed by a switch starts a
+    /// [substring](Substring). The substring and substitution are non-POSIX
+    /// extensions, which are rejected if the lexer is in the
+    /// [POSIXly-correct mode](Lexer::set_posixly_correct).
     pub async fn suffix_modifier(&mut self) -> Result<Modifier> {
         let start_index = self.index();
         let colon = self.skip_if(|c| c == ':').await?;
@@ -137,6 +223,9 @@ impl WordLexer<'_, '_> {
             match symbol {
                 '+' | '-' | '=' | '?' => self.switch(colon, symbol).await,
                 '#' | '%' => self.trim(start_index, colon, symbol).await,
+                '}' => self.suffix_modifier_not_found(start_index, colon),
+                _ if colon => self.substring(start_index).await,
+                '/' => self.subst(start_index).await,
                 _ => self.suffix_modifier_not_found(start_index, colon),
             }
         } else {
@@ -559,8 +648,8 @@ mod tests {
     }
 
     #[test]
-    fn lexer_suffix_modifier_orphan_colon_followed_by_letter() {
-        let mut lexer = Lexer::with_code(r":x}");
+    fn lexer_suffix_modifier_orphan_colon_followed_by_symbol() {
+        let mut lexer = Lexer::with_code(r":#}");
         let mut lexer = WordLexer {
             lexer: &mut lexer,
             context: WordContext::Word,
@@ -568,21 +657,151 @@ mod tests {
 
         let e = lexer.suffix_modifier().now_or_never().unwrap().unwrap_err();
         assert_eq!(e.cause, ErrorCause::Syntax(SyntaxError::InvalidModifier));
-        assert_eq!(*e.location.code.value.borrow(), ":x}");
-        assert_eq!(e.location.range, 0..1);
+        assert_eq!(*e.location.code.value.borrow(), ":#}");
+        assert_eq!(e.location.range, 0..2);
     }
 
     #[test]
-    fn lexer_suffix_modifier_orphan_colon_followed_by_symbol() {
-        let mut lexer = Lexer::with_code(r":#}");
+    fn lexer_suffix_modifier_substring_offset_only() {
+        let mut lexer = Lexer::with_code(r":1+x}");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+
+        let result = lexer.suffix_modifier().now_or_never().unwrap().unwrap();
+        assert_matches!(result, Modifier::Substring(substring) => {
+            assert_eq!(substring.offset.to_string(), "1+x");
+            assert_eq!(substring.length, None);
+        });
+
+        assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(Some('}')));
+    }
+
+    #[test]
+    fn lexer_suffix_modifier_substring_offset_and_length() {
+        let mut lexer = Lexer::with_code(r":$i:(2):x}");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Text,
+        };
+
+        let result = lexer.suffix_modifier().now_or_never().unwrap().unwrap();
+        assert_matches!(result, Modifier::Substring(substring) => {
+            assert_eq!(substring.offset.to_string(), "$i");
+            assert_eq!(substring.length.unwrap().to_string(), "(2):x");
+        });
+
+        assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(Some('}')));
+    }
+
+    #[test]
+    fn lexer_suffix_modifier_substring_empty_length() {
+        let mut lexer = Lexer::with_code(r": -1:}");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+
+        let result = lexer.suffix_modifier().now_or_never().unwrap().unwrap();
+        assert_matches!(result, Modifier::Substring(substring) => {
+            assert_eq!(substring.offset.to_string(), " -1");
+            assert_eq!(substring.length, Some(Text(vec![])));
+        });
+    }
+
+    #[test]
+    fn lexer_suffix_modifier_subst_first() {
+        let mut lexer = Lexer::with_code(r"/a*/~b}");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+
+        let result = lexer.suffix_modifier().now_or_never().unwrap().unwrap();
+        assert_matches!(result, Modifier::Subst(subst) => {
+            assert_eq!(subst.count, SubstCount::First);
+            assert_eq!(subst.pattern.to_string(), "a*");
+            assert_eq!(subst.replacement.to_string(), "~b");
+            assert_eq!(subst.replacement.units[0], WordUnit::Tilde("b".to_string()));
+        });
+
+        assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(Some('}')));
+    }
+
+    #[test]
+    fn lexer_suffix_modifier_subst_all_without_replacement() {
+        let mut lexer = Lexer::with_code(r"//\/x}");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+
+        let result = lexer.suffix_modifier().now_or_never().unwrap().unwrap();
+        assert_matches!(result, Modifier::Subst(subst) => {
+            assert_eq!(subst.count, SubstCount::All);
+            assert_eq!(subst.pattern.to_string(), r"\/x");
+            assert_eq!(subst.replacement.units, []);
+            assert_eq!(subst.replacement.location.range, 5..5);
+        });
+
+        assert_eq!(lexer.peek_char().now_or_never().unwrap(), Ok(Some('}')));
+    }
+
+    #[test]
+    fn lexer_suffix_modifier_subst_replacement_with_slash() {
+        let mut lexer = Lexer::with_code(r"/a/b/c}");
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Text,
+        };
+
+        let result = lexer.suffix_modifier().now_or_never().unwrap().unwrap();
+        assert_matches!(result, Modifier::Subst(subst) => {
+            assert_eq!(subst.count, SubstCount::First);
+            assert_eq!(subst.pattern.to_string(), "a");
+            assert_eq!(subst.replacement.to_string(), "b/c");
+        });
+    }
+
+    #[test]
+    fn lexer_suffix_modifier_substring_in_posix_mode() {
+        let mut lexer = Lexer::with_code(r":1:2}");
+        lexer.set_posixly_correct(true);
         let mut lexer = WordLexer {
             lexer: &mut lexer,
             context: WordContext::Word,
         };
 
         let e = lexer.suffix_modifier().now_or_never().unwrap().unwrap_err();
-        assert_eq!(e.cause, ErrorCause::Syntax(SyntaxError::InvalidModifier));
-        assert_eq!(*e.location.code.value.borrow(), ":#}");
-        assert_eq!(e.location.range, 0..2);
+        assert_eq!(e.cause, ErrorCause::Syntax(SyntaxError::NonPosixModifier));
+        assert_eq!(e.location.range, 0..4);
+    }
+
+    #[test]
+    fn lexer_suffix_modifier_subst_in_posix_mode() {
+        let mut lexer = Lexer::with_code(r"//a/b}");
+        lexer.set_posixly_correct(true);
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+
+        let e = lexer.suffix_modifier().now_or_never().unwrap().unwrap_err();
+        assert_eq!(e.cause, ErrorCause::Syntax(SyntaxError::NonPosixModifier));
+        assert_eq!(e.location.range, 0..5);
+    }
+
+    #[test]
+    fn lexer_suffix_modifier_trim_in_posix_mode() {
+        let mut lexer = Lexer::with_code(r"%a}");
+        lexer.set_posixly_correct(true);
+        let mut lexer = WordLexer {
+            lexer: &mut lexer,
+            context: WordContext::Word,
+        };
+
+        let result = lexer.suffix_modifier().now_or_never().unwrap().unwrap();
+        assert_matches!(result, Modifier::Trim(_));
     }
 }
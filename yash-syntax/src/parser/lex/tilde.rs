@@ -53,6 +53,9 @@ where
         match unit {
             Unquoted(Literal('/')) => break,
             Unquoted(Literal(':')) if delimit_at_colon => break,
+            // A backslash at the end of input quotes nothing but is still
+            // not part of a user name.
+            Unquoted(Literal('\\')) => return None,
             Unquoted(Literal(c)) => {
                 name.push(*c);
                 count += 1;
@@ -281,6 +284,17 @@ mod tests {
         assert_eq!(result.units, [Tilde("bar:baz".to_string())]);
     }
 
+    #[test]
+    fn word_parse_tilde_front_ending_with_backslash() {
+        let input = Word::from_str("~\\").unwrap();
+        assert_eq!(
+            input.units,
+            [Unquoted(Literal('~')), Unquoted(Literal('\\'))]
+        );
+        let result = parse_tilde_front(&input);
+        assert_eq!(result, input);
+    }
+
     #[test]
     fn word_parse_tilde_front_interrupted_by_non_literal() {
         let input = Word::from_str(r"~foo\/").unwrap();
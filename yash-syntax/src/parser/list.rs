@@ -37,9 +37,8 @@ fn error_type_for_trailing_token_in_command_line(token_id: TokenId) -> Option<Sy
         EndOfInput => None,
         Token(None) | IoNumber => Some(MissingSeparator),
         Token(Some(keyword)) => match keyword {
-            Bang | OpenBracketBracket | Case | For | Function | If | Until | While | OpenBrace => {
-                Some(MissingSeparator)
-            }
+            Bang | OpenBracketBracket | Case | For | Function | If | Time | Until | While
+            | OpenBrace => Some(MissingSeparator),
             Do => Some(UnopenedLoop),
             Done => Some(UnopenedDoClause),
             Elif | Else | Fi | Then => Some(UnopenedIf),
@@ -61,7 +60,7 @@ fn error_type_for_trailing_token_in_command_line(token_id: TokenId) -> Option<Sy
     }
 }
 
-impl Parser<'_, '_> {
+impl<'a, 'b> Parser<'a, 'b> {
     // There is no function that parses a single item because it would not be
     // very useful for parsing a list. An item requires a separator operator
     // ('&' or ';') for it to be followed by another item. You cannot tell from
@@ -156,6 +155,18 @@ impl Parser<'_, '_> {
         Ok(Some(list))
     }
 
+    /// Returns an iterator over complete commands in the remaining input.
+    ///
+    /// The returned [`Commands`] repeatedly calls
+    /// [`command_line`](Self::command_line) to yield complete commands with
+    /// their here-document contents filled. See [`Commands`] for details.
+    pub fn commands(&mut self) -> Commands<'_, 'a, 'b> {
+        Commands {
+            parser: self,
+            done: false,
+        }
+    }
+
     /// Parses an optional compound list.
     ///
     /// A compound list is a sequence of one or more and-or lists that are
@@ -201,6 +212,46 @@ impl Parser<'_, '_> {
     }
 }
 
+/// Asynchronous iterator over complete commands
+///
+/// This struct is returned by [`Parser::commands`]. Each call to
+/// [`next`](Self::next) parses a [complete command](Parser::command_line)
+/// including the contents of its here-documents. Lines that contain no
+/// commands (that is, empty lines and lines with only comments) are skipped.
+///
+/// The iteration ends when the end of input is reached or after an error has
+/// been returned. Since the parser does not read beyond the newline that
+/// terminates the last command, the underlying lexer is left positioned just
+/// after the consumed input, so you can interleave other reads with the
+/// iteration.
+#[derive(Debug)]
+#[must_use = "Commands does nothing unless iterated"]
+pub struct Commands<'p, 'a, 'b> {
+    parser: &'p mut Parser<'a, 'b>,
+    done: bool,
+}
+
+impl Commands<'_, '_, '_> {
+    /// Parses the next complete command.
+    ///
+    /// Returns `None` if the end of input has been reached or an error has
+    /// been returned by a previous call.
+    pub async fn next(&mut self) -> Option<Result<List>> {
+        while !self.done {
+            match self.parser.command_line().await {
+                Ok(Some(list)) if list.0.is_empty() => continue,
+                Ok(Some(list)) => return Some(Ok(list)),
+                Ok(None) => self.done = true,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+        None
+    }
+}
+
 #[allow(clippy::bool_assert_comparison)]
 #[cfg(test)]
 mod tests {
@@ -210,6 +261,7 @@ mod tests {
     use crate::source::Source;
     use crate::syntax::AndOrList;
     use crate::syntax::Command;
+    use crate::syntax::CompoundCommand;
     use crate::syntax::Pipeline;
     use crate::syntax::RedirBody;
     use assert_matches::assert_matches;
@@ -293,7 +345,12 @@ mod tests {
         assert_eq!(item.async_flag, None);
         let AndOrList { first, rest } = &*item.and_or;
         assert!(rest.is_empty(), "expected empty rest: {rest:?}");
-        let Pipeline { commands, negation } = first;
+        let Pipeline {
+            commands,
+            negation,
+            time,
+        } = first;
+        assert_eq!(*time, None);
         assert_eq!(*negation, false);
         assert_eq!(commands.len(), 1);
         let cmd = assert_matches!(*commands[0], Command::Simple(ref c) => c);
@@ -343,6 +400,154 @@ mod tests {
         assert_eq!(e.location.range, 2..5);
     }
 
+    fn here_doc_content_of_simple_command(command: &Command) -> String {
+        assert_matches!(command, Command::Simple(c) => {
+            assert_matches!(&c.redirs[0].body, RedirBody::HereDoc(here_doc) => {
+                here_doc.content.get().unwrap().to_string()
+            })
+        })
+    }
+
+    #[test]
+    fn parser_command_line_here_doc_in_while_condition() {
+        let mut lexer = Lexer::with_code("while cat <<END\nhere\nEND\ndo :; done\n");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.command_line().now_or_never().unwrap();
+        let list = result.unwrap().unwrap();
+        assert_eq!(list.to_string(), "while cat <<END; do :; done");
+        let command = &list.0[0].and_or.first.commands[0];
+        assert_matches!(command.as_ref(), Command::Compound(c) => {
+            assert_matches!(&c.command, CompoundCommand::While { condition, .. } => {
+                let command = &condition.0[0].and_or.first.commands[0];
+                assert_eq!(here_doc_content_of_simple_command(command), "here\n");
+            });
+        });
+    }
+
+    #[test]
+    fn parser_command_line_here_doc_in_function_body() {
+        let mut lexer = Lexer::with_code("f() {\ncat <<END\nhere\nEND\n}\n");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.command_line().now_or_never().unwrap();
+        let list = result.unwrap().unwrap();
+        assert_eq!(list.to_string(), "f() { cat <<END; }");
+        let command = &list.0[0].and_or.first.commands[0];
+        assert_matches!(command.as_ref(), Command::Function(f) => {
+            assert_matches!(&f.body.command, CompoundCommand::Grouping(body) => {
+                let command = &body.0[0].and_or.first.commands[0];
+                assert_eq!(here_doc_content_of_simple_command(command), "here\n");
+            });
+        });
+    }
+
+    #[test]
+    fn parser_command_line_here_doc_before_newline_in_for_values() {
+        let mut lexer = Lexer::with_code("cat <<END; for i in a b\nhere\nEND\ndo :; done\n");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.command_line().now_or_never().unwrap();
+        let list = result.unwrap().unwrap();
+        assert_eq!(list.to_string(), "cat <<END; for i in a b; do :; done");
+        let command = &list.0[0].and_or.first.commands[0];
+        assert_eq!(here_doc_content_of_simple_command(command), "here\n");
+    }
+
+    #[test]
+    fn parser_command_line_here_doc_before_newline_in_array_assignment() {
+        let mut lexer = Lexer::with_code("cat <<END; a=(1 2\nhere\nEND\n3)\n");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.command_line().now_or_never().unwrap();
+        let list = result.unwrap().unwrap();
+        assert_eq!(list.to_string(), "cat <<END; a=(1 2 3)");
+        let command = &list.0[0].and_or.first.commands[0];
+        assert_eq!(here_doc_content_of_simple_command(command), "here\n");
+    }
+
+    #[test]
+    fn parser_command_line_here_doc_before_newline_in_case_subject() {
+        let mut lexer = Lexer::with_code("cat <<END; case x\nhere\nEND\nin esac\n");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.command_line().now_or_never().unwrap();
+        let list = result.unwrap().unwrap();
+        assert_eq!(list.to_string(), "cat <<END; case x in esac");
+        let command = &list.0[0].and_or.first.commands[0];
+        assert_eq!(here_doc_content_of_simple_command(command), "here\n");
+    }
+
+    #[test]
+    fn parser_command_line_unclosed_double_quote_in_multi_line_command() {
+        let mut lexer = Lexer::with_code("true &&\necho \"abc\ndef");
+        let mut parser = Parser::new(&mut lexer);
+
+        let e = parser.command_line().now_or_never().unwrap().unwrap_err();
+        assert_matches!(e.cause,
+            ErrorCause::Syntax(SyntaxError::UnclosedDoubleQuote { opening_location }) => {
+            assert_eq!(opening_location.range, 13..14);
+            assert_eq!(opening_location.code.line_number(13).get(), 2);
+        });
+        assert_eq!(e.location.range, 21..21);
+    }
+
+    #[test]
+    fn parser_command_line_unclosed_single_quote_in_multi_line_command() {
+        let mut lexer = Lexer::with_code("true &&\necho 'abc\ndef");
+        let mut parser = Parser::new(&mut lexer);
+
+        let e = parser.command_line().now_or_never().unwrap().unwrap_err();
+        assert_matches!(e.cause,
+            ErrorCause::Syntax(SyntaxError::UnclosedSingleQuote { opening_location }) => {
+            assert_eq!(opening_location.range, 13..14);
+            assert_eq!(opening_location.code.line_number(13).get(), 2);
+        });
+        assert_eq!(e.location.range, 21..21);
+    }
+
+    #[test]
+    fn parser_command_line_unclosed_backquote_in_multi_line_command() {
+        let mut lexer = Lexer::with_code("true &&\necho `abc\ndef");
+        let mut parser = Parser::new(&mut lexer);
+
+        let e = parser.command_line().now_or_never().unwrap().unwrap_err();
+        assert_matches!(e.cause,
+            ErrorCause::Syntax(SyntaxError::UnclosedBackquote { opening_location }) => {
+            assert_eq!(opening_location.range, 13..14);
+            assert_eq!(opening_location.code.line_number(13).get(), 2);
+        });
+        assert_eq!(e.location.range, 21..21);
+    }
+
+    #[test]
+    fn parser_command_line_comment_at_start_of_input() {
+        let mut lexer = Lexer::with_code("#comment\necho ok\n");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.command_line().now_or_never().unwrap();
+        let list = result.unwrap().unwrap();
+        assert_eq!(list.0, []);
+
+        let result = parser.command_line().now_or_never().unwrap();
+        let list = result.unwrap().unwrap();
+        assert_eq!(list.to_string(), "echo ok");
+    }
+
+    #[test]
+    fn parser_command_line_comment_containing_here_doc_operator() {
+        let mut lexer = Lexer::with_code("echo a # <<END\necho b\n");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.command_line().now_or_never().unwrap();
+        let list = result.unwrap().unwrap();
+        assert_eq!(list.to_string(), "echo a");
+
+        let result = parser.command_line().now_or_never().unwrap();
+        let list = result.unwrap().unwrap();
+        assert_eq!(list.to_string(), "echo b");
+    }
+
     #[test]
     fn parser_command_line_wrong_delimiter_1() {
         let mut lexer = Lexer::with_code("foo)");
@@ -462,4 +667,61 @@ mod tests {
         assert_eq!(*e.location.code.source, Source::Unknown);
         assert_eq!(e.location.range, 10..11);
     }
+
+    #[test]
+    fn parser_commands_until_error() {
+        let mut lexer = Lexer::with_code("foo\n\ncat <<END; bar\nhere\nEND\nbaz )\nqux\n");
+        let mut parser = Parser::new(&mut lexer);
+        let mut commands = parser.commands();
+
+        let list = commands.next().now_or_never().unwrap().unwrap().unwrap();
+        assert_eq!(list.to_string(), "foo");
+
+        let list = commands.next().now_or_never().unwrap().unwrap().unwrap();
+        assert_eq!(list.to_string(), "cat <<END; bar");
+        assert_matches!(list.0[0].and_or.first.commands[0].as_ref(), Command::Simple(c) => {
+            assert_matches!(&c.redirs[0].body, RedirBody::HereDoc(here_doc) => {
+                assert_eq!(here_doc.content.get().unwrap().to_string(), "here\n");
+            });
+        });
+
+        let e = commands
+            .next()
+            .now_or_never()
+            .unwrap()
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(e.cause, ErrorCause::Syntax(SyntaxError::UnopenedSubshell));
+        assert_eq!(e.location.range, 33..34);
+
+        let result = commands.next().now_or_never().unwrap();
+        assert!(result.is_none(), "{result:?}");
+    }
+
+    #[test]
+    fn parser_commands_until_end_of_input() {
+        let mut lexer = Lexer::with_code("foo\n# comment\nbar");
+        let mut parser = Parser::new(&mut lexer);
+        let mut commands = parser.commands();
+
+        let list = commands.next().now_or_never().unwrap().unwrap().unwrap();
+        assert_eq!(list.to_string(), "foo");
+        let list = commands.next().now_or_never().unwrap().unwrap().unwrap();
+        assert_eq!(list.to_string(), "bar");
+        let result = commands.next().now_or_never().unwrap();
+        assert!(result.is_none(), "{result:?}");
+        let result = commands.next().now_or_never().unwrap();
+        assert!(result.is_none(), "{result:?}");
+    }
+
+    #[test]
+    fn parser_commands_leave_lexer_after_consumed_input() {
+        let mut lexer = Lexer::with_code("foo\nbar\n");
+        let mut parser = Parser::new(&mut lexer);
+        let mut commands = parser.commands();
+
+        let list = commands.next().now_or_never().unwrap().unwrap().unwrap();
+        assert_eq!(list.to_string(), "foo");
+        assert_eq!(lexer.index(), 4);
+    }
 }
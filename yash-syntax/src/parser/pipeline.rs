@@ -21,48 +21,105 @@ use super::core::Rec;
 use super::core::Result;
 use super::error::Error;
 use super::error::SyntaxError;
-use super::lex::Keyword::Bang;
+use super::lex::Keyword::{Bang, Time};
 use super::lex::Operator::Bar;
+use super::lex::Token as LexToken;
 use super::lex::TokenId::{Operator, Token};
+use crate::syntax::ExpansionMode;
+use crate::syntax::MaybeLiteral as _;
 use crate::syntax::Pipeline;
+use crate::syntax::SimpleCommand;
+use crate::syntax::TimeFormat;
 use std::rc::Rc;
 
 impl Parser<'_, '_> {
+    /// Parses the `-p` option following the `time` reserved word.
+    ///
+    /// This function must be called just after the `time` reserved word has
+    /// been consumed. The option is consumed and returned only if it is an
+    /// unquoted `-p`.
+    async fn time_option(&mut self) -> Result<Option<LexToken>> {
+        let token = self.peek_token().await?;
+        if token.id == Token(None) && token.word.to_string_if_literal().as_deref() == Some("-p") {
+            Ok(Some(self.take_token_raw().await?))
+        } else {
+            Ok(None)
+        }
+    }
+
     /// Parses a pipeline.
     ///
     /// If there is no valid pipeline at the current position, this function
     /// returns `Ok(Rec::Parsed(None))`.
+    ///
+    /// The `time` reserved word is recognized only at the beginning of the
+    /// pipeline. If it is not followed by a command, it is parsed as a simple
+    /// command consisting of the `time` word (and the `-p` option, if any).
     pub async fn pipeline(&mut self) -> Result<Rec<Option<Pipeline>>> {
+        // Parse the `time` reserved word
+        let time_tokens = if self.peek_token().await?.id == Token(Some(Time)) {
+            let time = self.take_token_raw().await?;
+            let option = self.time_option().await?;
+            Some((time, option))
+        } else {
+            None
+        };
+        let mut time = time_tokens.as_ref().map(|(_, option)| match option {
+            Some(_) => TimeFormat::Posix,
+            None => TimeFormat::Default,
+        });
+
         // Parse the first command
-        let (first, negation) = match self.command().await? {
-            Rec::AliasSubstituted => return Ok(Rec::AliasSubstituted),
-            Rec::Parsed(Some(first)) => (first, false),
-            Rec::Parsed(None) => {
-                // Parse the `!` reserved word
-                if self.peek_token().await?.id != Token(Some(Bang)) {
-                    return Ok(Rec::Parsed(None));
-                }
-                self.take_token_raw().await?;
-                // TODO Warn if `!` is immediately followed by `(`, which is
-                // not POSIXly portable.
-
-                // Parse the command after the `!`
-                loop {
-                    match self.command().await? {
-                        Rec::AliasSubstituted => continue,
-                        Rec::Parsed(Some(first)) => break (first, true),
-                        Rec::Parsed(None) => {
-                            // Error: the command is missing
-                            let next = self.take_token_raw().await?;
-                            let cause = if next.id == Token(Some(Bang)) {
-                                SyntaxError::DoubleNegation.into()
-                            } else {
-                                SyntaxError::MissingCommandAfterBang.into()
-                            };
-                            let location = next.word.location;
-                            return Err(Error { cause, location });
-                        }
+        let (first, negation) = loop {
+            match self.command().await? {
+                // After `time`, the alias substitution cannot be propagated to
+                // the caller since the `time` token has already been consumed.
+                Rec::AliasSubstituted if time.is_some() => continue,
+                Rec::AliasSubstituted => return Ok(Rec::AliasSubstituted),
+                Rec::Parsed(Some(first)) => break (first, false),
+                Rec::Parsed(None) => {
+                    // Parse the `!` reserved word
+                    if self.peek_token().await?.id != Token(Some(Bang)) {
+                        let Some((time_token, option)) = time_tokens else {
+                            return Ok(Rec::Parsed(None));
+                        };
+                        // Without a command, `time` is not a reserved word
+                        // but the name of a simple command.
+                        let words = std::iter::once(time_token)
+                            .chain(option)
+                            .map(|token| (token.word, ExpansionMode::Multiple))
+                            .collect();
+                        let command = SimpleCommand {
+                            assigns: vec![],
+                            words,
+                            redirs: Rc::default(),
+                        };
+                        time = None;
+                        break (command.into(), false);
                     }
+                    self.take_token_raw().await?;
+                    // TODO Warn if `!` is immediately followed by `(`, which is
+                    // not POSIXly portable.
+
+                    // Parse the command after the `!`
+                    let first = loop {
+                        match self.command().await? {
+                            Rec::AliasSubstituted => continue,
+                            Rec::Parsed(Some(first)) => break first,
+                            Rec::Parsed(None) => {
+                                // Error: the command is missing
+                                let next = self.take_token_raw().await?;
+                                let cause = if next.id == Token(Some(Bang)) {
+                                    SyntaxError::DoubleNegation.into()
+                                } else {
+                                    SyntaxError::MissingCommandAfterBang.into()
+                                };
+                                let location = next.word.location;
+                                return Err(Error { cause, location });
+                            }
+                        }
+                    };
+                    break (first, true);
                 }
             }
         };
@@ -95,7 +152,11 @@ impl Parser<'_, '_> {
             commands.push(Rc::new(next));
         }
 
-        Ok(Rec::Parsed(Some(Pipeline { commands, negation })))
+        Ok(Rec::Parsed(Some(Pipeline {
+            commands,
+            negation,
+            time,
+        })))
     }
 }
 
@@ -157,6 +218,133 @@ mod tests {
         assert_eq!(p.commands[0].to_string(), "foo");
     }
 
+    #[test]
+    fn parser_pipeline_timed() {
+        let mut lexer = Lexer::with_code("time foo | bar");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.pipeline().now_or_never().unwrap();
+        let p = result.unwrap().unwrap().unwrap();
+        assert_eq!(p.time, Some(TimeFormat::Default));
+        assert_eq!(p.negation, false);
+        assert_eq!(p.commands.len(), 2);
+        assert_eq!(p.commands[0].to_string(), "foo");
+        assert_eq!(p.commands[1].to_string(), "bar");
+    }
+
+    #[test]
+    fn parser_pipeline_timed_with_posix_format() {
+        let mut lexer = Lexer::with_code("time -p foo");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.pipeline().now_or_never().unwrap();
+        let p = result.unwrap().unwrap().unwrap();
+        assert_eq!(p.time, Some(TimeFormat::Posix));
+        assert_eq!(p.commands.len(), 1);
+        assert_eq!(p.commands[0].to_string(), "foo");
+    }
+
+    #[test]
+    fn parser_pipeline_timed_with_quoted_option() {
+        let mut lexer = Lexer::with_code("time '-p' foo");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.pipeline().now_or_never().unwrap();
+        let p = result.unwrap().unwrap().unwrap();
+        assert_eq!(p.time, Some(TimeFormat::Default));
+        assert_eq!(p.commands.len(), 1);
+        assert_eq!(p.commands[0].to_string(), "'-p' foo");
+    }
+
+    #[test]
+    fn parser_pipeline_timed_and_negated() {
+        let mut lexer = Lexer::with_code("time ! foo | bar");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.pipeline().now_or_never().unwrap();
+        let p = result.unwrap().unwrap().unwrap();
+        assert_eq!(p.time, Some(TimeFormat::Default));
+        assert_eq!(p.negation, true);
+        assert_eq!(p.commands.len(), 2);
+        assert_eq!(p.commands[0].to_string(), "foo");
+        assert_eq!(p.commands[1].to_string(), "bar");
+    }
+
+    #[test]
+    fn parser_pipeline_time_not_at_command_name() {
+        let mut lexer = Lexer::with_code("foo time");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.pipeline().now_or_never().unwrap();
+        let p = result.unwrap().unwrap().unwrap();
+        assert_eq!(p.time, None);
+        assert_eq!(p.commands.len(), 1);
+        assert_eq!(p.commands[0].to_string(), "foo time");
+    }
+
+    #[test]
+    fn parser_pipeline_time_without_command() {
+        let mut lexer = Lexer::with_code("time;");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.pipeline().now_or_never().unwrap();
+        let p = result.unwrap().unwrap().unwrap();
+        assert_eq!(p.time, None);
+        assert_eq!(p.negation, false);
+        assert_eq!(p.commands.len(), 1);
+        assert_eq!(p.commands[0].to_string(), "time");
+
+        let next = parser.peek_token().now_or_never().unwrap().unwrap();
+        assert_eq!(next.id, Operator(super::super::lex::Operator::Semicolon));
+    }
+
+    #[test]
+    fn parser_pipeline_time_with_option_without_command() {
+        let mut lexer = Lexer::with_code("time -p | cat");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.pipeline().now_or_never().unwrap();
+        let p = result.unwrap().unwrap().unwrap();
+        assert_eq!(p.time, None);
+        assert_eq!(p.commands.len(), 2);
+        assert_eq!(p.commands[0].to_string(), "time -p");
+        assert_eq!(p.commands[1].to_string(), "cat");
+    }
+
+    #[test]
+    fn parser_pipeline_time_after_bar() {
+        let mut lexer = Lexer::with_code("foo | time bar");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.pipeline().now_or_never().unwrap();
+        let p = result.unwrap().unwrap().unwrap();
+        assert_eq!(p.time, None);
+        assert_eq!(p.commands.len(), 2);
+        assert_eq!(p.commands[0].to_string(), "foo");
+        assert_eq!(p.commands[1].to_string(), "time bar");
+    }
+
+    #[test]
+    fn parser_pipeline_alias_substitution_after_time() {
+        let mut lexer = Lexer::with_code("time ll");
+        #[allow(clippy::mutable_key_type)]
+        let mut aliases = AliasSet::new();
+        let origin = Location::dummy("");
+        aliases.insert(HashEntry::new(
+            "ll".to_string(),
+            "ls -l".to_string(),
+            false,
+            origin,
+        ));
+        let mut parser = Parser::config().aliases(&aliases).input(&mut lexer);
+
+        let result = parser.pipeline().now_or_never().unwrap();
+        let p = result.unwrap().unwrap().unwrap();
+        assert_eq!(p.time, Some(TimeFormat::Default));
+        assert_eq!(p.commands.len(), 1);
+        assert_eq!(p.commands[0].to_string(), "ls -l");
+    }
+
     #[test]
     fn parser_pipeline_double_negation() {
         let mut lexer = Lexer::with_code(" !  !");
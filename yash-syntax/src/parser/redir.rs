@@ -347,6 +347,45 @@ mod tests {
         assert_eq!(e.location.range, 0..40);
     }
 
+    #[test]
+    fn parser_redirection_max_fd() {
+        let mut lexer = Lexer::with_code("2147483647>x");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.redirection().now_or_never().unwrap();
+        let redir = result.unwrap().unwrap();
+        assert_eq!(redir.fd, Some(Fd(2147483647)));
+    }
+
+    #[test]
+    fn parser_redirection_fd_just_out_of_range() {
+        let mut lexer = Lexer::with_code("echo 2147483648>x");
+        let mut parser = Parser::new(&mut lexer);
+        parser.take_token_raw().now_or_never().unwrap().unwrap();
+
+        let e = parser.redirection().now_or_never().unwrap().unwrap_err();
+        assert_eq!(e.cause, ErrorCause::Syntax(SyntaxError::FdOutOfRange));
+        assert_eq!(e.location.range, 5..15);
+    }
+
+    #[test]
+    fn parser_redirection_negative_looking_fd() {
+        // "-1" is not an IO_NUMBER, so the redirection has no explicit FD.
+        let mut lexer = Lexer::with_code("-1>x");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.redirection().now_or_never().unwrap();
+        assert_eq!(result, Ok(None));
+        let token = parser.take_token_raw().now_or_never().unwrap().unwrap();
+        assert_eq!(token.id, Token(None));
+        assert_eq!(token.word.to_string(), "-1");
+
+        let result = parser.redirection().now_or_never().unwrap();
+        let redir = result.unwrap().unwrap();
+        assert_eq!(redir.fd, None);
+        assert_eq!(redir.fd_or_default(), Fd::STDOUT);
+    }
+
     #[test]
     fn parser_redirection_not_operator() {
         let mut lexer = Lexer::with_code("x");
@@ -21,6 +21,7 @@ use super::core::Rec;
 use super::core::Result;
 use super::error::Error;
 use super::error::SyntaxError;
+use super::lex::Keyword::Time;
 use super::lex::Operator::{CloseParen, Newline, OpenParen};
 use super::lex::TokenId::{Operator, Token};
 use crate::syntax::Array;
@@ -98,7 +99,7 @@ impl Parser<'_, '_> {
         loop {
             let next = self.take_token_auto(&[]).await?;
             match next.id {
-                Operator(Newline) => continue,
+                Operator(Newline) => self.here_doc_contents().await?,
                 Operator(CloseParen) => break,
                 Token(_keyword) => words.push(next.word),
                 _ => {
@@ -117,6 +118,9 @@ impl Parser<'_, '_> {
     ///
     /// If there is no valid command at the current position, this function
     /// returns `Ok(Rec::Parsed(None))`.
+    ///
+    /// Tilde expansions are parsed at the beginning of each word and, in
+    /// assignment values, also after every unquoted colon.
     pub async fn simple_command(&mut self) -> Result<Rec<Option<SimpleCommand>>> {
         let mut is_declaration_utility = None;
         let mut result = Builder::default();
@@ -129,8 +133,10 @@ impl Parser<'_, '_> {
             }
 
             // Filter token type
+            // (The `time` reserved word is recognized only at the beginning of
+            // a pipeline, which is handled by the pipeline parser.)
             match self.peek_token().await?.id {
-                Token(Some(_keyword)) if result.is_empty() => break,
+                Token(Some(keyword)) if result.is_empty() && keyword != Time => break,
                 Token(_) => (),
                 _ => break,
             }
@@ -361,6 +367,20 @@ mod tests {
         assert_eq!(result, Ok(Rec::Parsed(None)));
     }
 
+    #[test]
+    fn parser_simple_command_time_as_command_name() {
+        let mut lexer = Lexer::with_code("time foo");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.simple_command().now_or_never().unwrap();
+        let sc = result.unwrap().unwrap().unwrap();
+        assert_eq!(sc.assigns, []);
+        assert_eq!(sc.words.len(), 2);
+        assert_eq!(sc.words[0].0.to_string(), "time");
+        assert_eq!(sc.words[1].0.to_string(), "foo");
+        assert_eq!(*sc.redirs, []);
+    }
+
     #[test]
     fn parser_simple_command_one_assignment() {
         let mut lexer = Lexer::with_code("my=assignment");
@@ -507,6 +527,89 @@ mod tests {
         assert_eq!(sc.words[0].1, ExpansionMode::Multiple);
     }
 
+    #[test]
+    fn parser_simple_command_assignments_only_before_command_name() {
+        let mut lexer = Lexer::with_code("a=1 >f b=2 echo c=3 d=4");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.simple_command().now_or_never().unwrap();
+        let sc = result.unwrap().unwrap().unwrap();
+        assert_eq!(sc.redirs.len(), 1);
+        assert_eq!(sc.assigns.len(), 2);
+        assert_eq!(sc.assigns[0].name, "a");
+        assert_eq!(sc.assigns[1].name, "b");
+        assert_eq!(sc.words.len(), 3);
+        assert_eq!(sc.words[0].0.to_string(), "echo");
+        assert_eq!(sc.words[1].0.to_string(), "c=3");
+        assert_eq!(sc.words[1].1, ExpansionMode::Multiple);
+        assert_eq!(sc.words[2].0.to_string(), "d=4");
+        assert_eq!(sc.words[2].1, ExpansionMode::Multiple);
+    }
+
+    #[test]
+    fn parser_simple_command_hash_inside_word() {
+        let mut lexer = Lexer::with_code("echo a#b");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.simple_command().now_or_never().unwrap();
+        let sc = result.unwrap().unwrap().unwrap();
+        assert_eq!(sc.words.len(), 2);
+        assert_eq!(sc.words[1].0.to_string(), "a#b");
+    }
+
+    #[test]
+    fn parser_simple_command_comment_after_word() {
+        let mut lexer = Lexer::with_code("echo a #b c");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.simple_command().now_or_never().unwrap();
+        let sc = result.unwrap().unwrap().unwrap();
+        assert_eq!(sc.words.len(), 2);
+        assert_eq!(sc.words[1].0.to_string(), "a");
+        let next = parser.peek_token().now_or_never().unwrap().unwrap();
+        assert_eq!(next.id, EndOfInput);
+    }
+
+    #[test]
+    fn parser_simple_command_hash_in_parameter_expansions() {
+        let mut lexer = Lexer::with_code("echo $# ${#x} ${x#a} ${x##a}");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.simple_command().now_or_never().unwrap();
+        let sc = result.unwrap().unwrap().unwrap();
+        assert_eq!(sc.words.len(), 5);
+        assert_matches!(&sc.words[1].0.units[..], [WordUnit::Unquoted(TextUnit::RawParam { param, .. })] => {
+            assert_eq!(param.id, "#");
+        });
+        assert_eq!(sc.words[2].0.to_string(), "${#x}");
+        assert_eq!(sc.words[3].0.to_string(), "${x#a}");
+        assert_eq!(sc.words[4].0.to_string(), "${x##a}");
+    }
+
+    #[test]
+    fn parser_simple_command_tilde_expansions_in_assignment_and_word() {
+        let mut lexer = Lexer::with_code("PATH=~/bin:~/sbin ~/bin:~/tool");
+        let mut parser = Parser::new(&mut lexer);
+
+        let result = parser.simple_command().now_or_never().unwrap();
+        let sc = result.unwrap().unwrap().unwrap();
+        assert_eq!(sc.assigns.len(), 1);
+        assert_matches!(&sc.assigns[0].value, Scalar(value) => {
+            assert_eq!(value.units[0], WordUnit::Tilde("".to_string()));
+            assert_eq!(value.units[5], WordUnit::Unquoted(TextUnit::Literal(':')));
+            assert_eq!(value.units[6], WordUnit::Tilde("".to_string()));
+            assert_eq!(value.to_string(), "~/bin:~/sbin");
+        });
+        // Only the tilde at the beginning of a command word is parsed.
+        assert_eq!(sc.words.len(), 1);
+        let units = &sc.words[0].0.units;
+        assert_eq!(units[0], WordUnit::Tilde("".to_string()));
+        assert_eq!(units[5], WordUnit::Unquoted(TextUnit::Literal(':')));
+        assert_eq!(units[6], WordUnit::Unquoted(TextUnit::Literal('~')));
+        let tildes = units.iter().filter(|u| matches!(u, WordUnit::Tilde(_)));
+        assert_eq!(tildes.count(), 1);
+    }
+
     #[test]
     fn parser_simple_command_word_redirection() {
         let mut lexer = Lexer::with_code("word <redirection");
@@ -64,6 +64,16 @@ pub enum Source {
     /// Arithmetic expansion
     Arith { original: Location },
 
+    /// Result of expanding a word
+    ///
+    /// This applies to the value of a word after the initial expansion, which
+    /// may be divided into multiple fields by field splitting. The location of
+    /// each field points to the range of the field in the expanded value.
+    Expansion {
+        /// Position of the word that was expanded
+        original: Location,
+    },
+
     /// Command string executed by the `eval` built-in
     Eval { original: Location },
 
@@ -171,6 +181,7 @@ impl Source {
             Alias { .. } => "<alias>",
             CommandSubst { .. } => "<command_substitution>",
             Arith { .. } => "<arithmetic_expansion>",
+            Expansion { .. } => "<expansion>",
             Eval { .. } => "<eval>",
             DotScript { name, .. } => name,
             Trap { condition, .. } => condition,
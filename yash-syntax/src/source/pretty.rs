@@ -167,6 +167,14 @@ impl super::Source {
                     original,
                 )));
             }
+            Expansion { original } => {
+                // TODO Use Extend::extend_one
+                result.extend(std::iter::once(Annotation::new(
+                    AnnotationType::Info,
+                    "field resulted from the expansion of this word".into(),
+                    original,
+                )));
+            }
             Eval { original } => {
                 // TODO Use Extend::extend_one
                 result.extend(std::iter::once(Annotation::new(
@@ -285,6 +293,20 @@ mod annotate_snippets_support {
         }
     }
 
+    /// Converts a range of character indices into a range of byte indices.
+    ///
+    /// [`Location::range`] counts characters, but `annotate_snippets` expects
+    /// byte offsets into the source string. Indices past the end of the string
+    /// are clamped to its length.
+    fn byte_range(code: &str, range: &std::ops::Range<usize>) -> std::ops::Range<usize> {
+        let byte_index = |char_index| {
+            code.char_indices()
+                .nth(char_index)
+                .map_or(code.len(), |(byte_index, _)| byte_index)
+        };
+        byte_index(range.start)..byte_index(range.end)
+    }
+
     /// Converts `yash_syntax::source::pretty::Message` into
     /// `annotate_snippets::Message`.
     ///
@@ -302,7 +324,7 @@ mod annotate_snippets_support {
             // into a temporary vector, and then merge annotations with the same code into a single
             // snippet.
             for annotation in &message.annotations {
-                let range = annotation.location.range.clone();
+                let range = byte_range(&annotation.code, &annotation.location.range);
                 let level = annotate_snippets::Level::from(annotation.r#type);
                 let as_annotation = level.span(range).label(&annotation.label);
                 let code = &*annotation.location.code;
@@ -337,4 +359,44 @@ mod annotate_snippets_support {
                 }))
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::parser::lex::Lexer;
+        use crate::parser::{Error, Parser};
+        use futures_util::FutureExt;
+
+        fn render_parse_error(code: &str) -> String {
+            let mut lexer = Lexer::with_code(code);
+            let mut parser = Parser::new(&mut lexer);
+            let error: Error = parser.command_line().now_or_never().unwrap().unwrap_err();
+            let message = Message::from(&error);
+            let message = annotate_snippets::Message::from(&message);
+            let renderer = annotate_snippets::Renderer::plain();
+            format!("{}", renderer.render(message))
+        }
+
+        #[test]
+        fn byte_range_conversion() {
+            assert_eq!(byte_range("abc", &(1..3)), 1..3);
+            assert_eq!(byte_range("\u{3042}b\u{3044}", &(1..3)), 3..7);
+            assert_eq!(byte_range("\u{3042}", &(1..1)), 3..3);
+            assert_eq!(byte_range("\u{3042}", &(2..5)), 3..3);
+        }
+
+        #[test]
+        fn whole_token_is_underlined() {
+            let rendered = render_parse_error("foo() long_command_name");
+            let expected = "1 | foo() long_command_name\n  |       ^^^^^^^^^^^^^^^^^ ";
+            assert!(rendered.contains(expected), "{rendered}");
+        }
+
+        #[test]
+        fn whole_token_is_underlined_after_multibyte_characters() {
+            let rendered = render_parse_error("echo \u{3042}\u{3044}; foo() long_command_name");
+            let expected = "1 | echo \u{3042}\u{3044}; foo() long_command_name\n  |                  ^^^^^^^^^^^^^^^^^ ";
+            assert!(rendered.contains(expected), "{rendered}");
+        }
+    }
 }
@@ -65,13 +65,22 @@
 //! to parse the source code. See the [`parser`](crate::parser) module for
 //! details.
 //!
+//! ## Constructing
+//!
+//! You can also construct ASTs without parsing source code. In addition to
+//! filling in the public fields of the AST types, you can use constructors
+//! such as [`Word::from_literal`], [`Redir::file_out`], and
+//! [`SimpleCommand::new`], and the [`ListBuilder`]. These constructors produce
+//! ASTs whose `Display` output parses back to equivalent ASTs.
+//!
 //! ## Displaying
 //!
 //! Most AST types support the [`Display`](std::fmt::Display) trait, which
 //! allows you to convert an AST to a source code string. Note that the
 //! `Display` trait implementations always produce single-line source code with
-//! here-document contents omitted. To pretty-format an AST in multiple lines
-//! with here-document contents included, you can use ... TODO TBD.
+//! here-document contents omitted. To format an AST in multiple lines with
+//! here-document contents included, you can use the `to_source_string` method
+//! of [`List`], [`Item`], and [`HereDoc`].
 
 use crate::parser::lex::Keyword;
 use crate::parser::lex::Operator;
@@ -247,6 +256,53 @@ pub struct Trim {
     pub pattern: Word,
 }
 
+/// Parameter expansion [modifier](Modifier) that extracts part of the value
+/// being expanded
+///
+/// Examples of substrings include `:2`, `:1:3` and `: -2`.
+///
+/// A substring is composed of an offset and an optional length, both of which
+/// are arithmetic expressions. This is a non-POSIX extension.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Substring {
+    /// Arithmetic expression for the index of the first character to extract
+    ///
+    /// A negative offset counts from the end of the value.
+    pub offset: Text,
+    /// Arithmetic expression for the number of characters to extract
+    ///
+    /// A negative length specifies the number of characters to leave at the
+    /// end of the value. If `None`, the rest of the value is extracted.
+    pub length: Option<Text>,
+}
+
+/// Flag that specifies which matches are replaced in a
+/// [substitution](Subst)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SubstCount {
+    /// Replace the first match only. (`/`)
+    First,
+    /// Replace all matches. (`//`)
+    All,
+}
+
+/// Parameter expansion [modifier](Modifier) that replaces the parts of the
+/// value being expanded that match a pattern
+///
+/// Examples of substitutions include `/foo/bar` and `//x*/`.
+///
+/// A substitution is composed of a count, pattern and replacement. This is a
+/// non-POSIX extension.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Subst {
+    /// Which matches should be replaced?
+    pub count: SubstCount,
+    /// Pattern to be matched with the expanded value
+    pub pattern: Word,
+    /// Word that replaces the matched parts of the value
+    pub replacement: Word,
+}
+
 /// Attribute that modifies a parameter expansion
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Modifier {
@@ -258,7 +314,11 @@ pub enum Modifier {
     Switch(Switch),
     /// `#`, `##`, `%` or `%%` suffix
     Trim(Trim),
-    // TODO Subst
+    /// `:` suffix followed by an offset and optional length (`${foo:1:2}`)
+    Substring(Substring),
+    /// `/` or `//` suffix followed by a pattern and replacement
+    /// (`${foo/bar/baz}`)
+    Subst(Subst),
 }
 
 /// Parameter expansion enclosed in braces
@@ -470,6 +530,12 @@ pub struct Assign {
 ///
 /// This is the `newtype` pattern applied to [`RawFd`], which is merely a type
 /// alias.
+///
+/// When parsing a [redirection](Redir), the parser accepts a file descriptor
+/// between 0 and `RawFd::MAX` (inclusive). A larger number is rejected with a
+/// [`FdOutOfRange`](crate::parser::SyntaxError::FdOutOfRange) error. Whether
+/// the file descriptor is actually available is checked when the redirection
+/// is performed.
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Fd(pub RawFd);
 
@@ -728,6 +794,15 @@ pub enum Command {
     Function(FunctionDefinition),
 }
 
+/// Report format of a pipeline prefixed with the `time` reserved word
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TimeFormat {
+    /// `time` without an option
+    Default,
+    /// `time -p`
+    Posix,
+}
+
 /// Commands separated by `|`
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Pipeline {
@@ -740,6 +815,11 @@ pub struct Pipeline {
     pub commands: Vec<Rc<Command>>,
     /// Whether the pipeline begins with a `!`
     pub negation: bool,
+    /// Whether and how the execution time of the pipeline is reported
+    ///
+    /// This is `Some(_)` if the pipeline begins with the `time` reserved
+    /// word, which precedes the `!` if any.
+    pub time: Option<TimeFormat>,
 }
 
 /// Condition that decides if a [Pipeline] in an [and-or list](AndOrList) should be executed
@@ -776,10 +856,14 @@ pub struct Item {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct List(pub Vec<Item>);
 
+/// Builder for constructing lists programmatically
+mod builder;
 /// Definitions and implementations of the [Unquote] and [MaybeLiteral] traits,
 /// and other conversions between types
 mod conversions;
 /// Implementations of [std::fmt::Display] for the shell language syntax types
 mod impl_display;
+pub mod visit;
 
+pub use builder::ListBuilder;
 pub use conversions::{MaybeLiteral, NotLiteral, NotSpecialParam, Unquote};
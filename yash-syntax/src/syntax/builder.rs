@@ -0,0 +1,192 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+/// Builder for constructing a [`List`]
+///
+/// `ListBuilder` helps you construct a list of and-or lists without parsing
+/// source code. Start with [`new`](Self::new), add pipelines by chaining
+/// methods, and finally call [`build`](Self::build) to obtain the list.
+///
+/// The [`pipeline`](Self::pipeline) method starts a new and-or list, and the
+/// [`and_then`](Self::and_then) and [`or_else`](Self::or_else) methods extend
+/// the last and-or list with a pipeline conditionally executed by `&&` and
+/// `||`, respectively. The [`background`](Self::background) method makes the
+/// last and-or list asynchronous as if it were followed by `&`.
+///
+/// ```
+/// # use yash_syntax::syntax::{ListBuilder, Redir, SimpleCommand, Word};
+/// let echo = SimpleCommand::new([Word::from_literal("echo"), Word::from_literal("a b")]);
+/// let cat = SimpleCommand {
+///     redirs: vec![Redir::file_out(None, Word::from_literal("out"))].into(),
+///     ..SimpleCommand::new([Word::from_literal("cat")])
+/// };
+/// let list = ListBuilder::new()
+///     .pipeline([echo, cat].into_iter().collect::<yash_syntax::syntax::Pipeline>())
+///     .and_then(SimpleCommand::new([Word::from_literal("true")]))
+///     .background()
+///     .build();
+/// assert_eq!(list.to_string(), "echo 'a b' | cat >out && true&");
+/// ```
+#[derive(Clone, Debug, Default)]
+#[must_use = "ListBuilder must be used to build a list"]
+pub struct ListBuilder {
+    items: Vec<(AndOrList, Option<Location>)>,
+}
+
+impl ListBuilder {
+    /// Creates a new builder with no and-or lists.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a new and-or list that starts with the given pipeline.
+    pub fn pipeline<P: Into<Pipeline>>(mut self, pipeline: P) -> Self {
+        let and_or = AndOrList {
+            first: pipeline.into(),
+            rest: vec![],
+        };
+        self.items.push((and_or, None));
+        self
+    }
+
+    /// Appends a pipeline to the last and-or list with the `&&` operator.
+    ///
+    /// # Panics
+    ///
+    /// If no and-or list has been added to the builder.
+    pub fn and_then<P: Into<Pipeline>>(self, pipeline: P) -> Self {
+        self.condition(AndOr::AndThen, pipeline.into())
+    }
+
+    /// Appends a pipeline to the last and-or list with the `||` operator.
+    ///
+    /// # Panics
+    ///
+    /// If no and-or list has been added to the builder.
+    pub fn or_else<P: Into<Pipeline>>(self, pipeline: P) -> Self {
+        self.condition(AndOr::OrElse, pipeline.into())
+    }
+
+    fn condition(mut self, and_or: AndOr, pipeline: Pipeline) -> Self {
+        let (last, _) = self
+            .items
+            .last_mut()
+            .expect("no and-or list to append the pipeline to");
+        last.rest.push((and_or, pipeline));
+        self
+    }
+
+    /// Makes the last and-or list asynchronous.
+    ///
+    /// The `async_flag` of the resulting [`Item`] is a
+    /// [dummy](Location::dummy) location of the `&` operator.
+    ///
+    /// # Panics
+    ///
+    /// If no and-or list has been added to the builder.
+    pub fn background(mut self) -> Self {
+        let (_, async_flag) = self
+            .items
+            .last_mut()
+            .expect("no and-or list to make asynchronous");
+        *async_flag = Some(Location::dummy("&"));
+        self
+    }
+
+    /// Creates a list from the and-or lists added to the builder.
+    #[must_use]
+    pub fn build(self) -> List {
+        List(
+            self.items
+                .into_iter()
+                .map(|(and_or, async_flag)| Item {
+                    and_or: Rc::new(and_or),
+                    async_flag,
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_list() {
+        let list = ListBuilder::new().build();
+        assert_eq!(list.0, []);
+    }
+
+    #[test]
+    fn pipeline_with_redirection() {
+        let echo = SimpleCommand::new([Word::from_literal("echo"), Word::from_literal("$x")]);
+        let tee = SimpleCommand {
+            redirs: vec![Redir::file_out(Some(Fd(2)), Word::from_literal("a file"))].into(),
+            ..SimpleCommand::new([Word::from_literal("tee")])
+        };
+        let list = ListBuilder::new()
+            .pipeline([echo, tee].into_iter().collect::<Pipeline>())
+            .build();
+
+        let source = "echo '$x' | tee 2>'a file'";
+        let parsed: List = source.parse().unwrap();
+        assert_eq!(list.to_string(), source);
+        assert_eq!(list.to_string(), parsed.to_string());
+
+        let Command::Simple(built) = &*list.0[0].and_or.first.commands[1] else {
+            panic!("not a simple command: {list:?}");
+        };
+        let Command::Simple(parsed) = &*parsed.0[0].and_or.first.commands[1] else {
+            panic!("not a simple command: {parsed:?}");
+        };
+        assert_eq!(built.words[0].1, parsed.words[0].1);
+        assert_eq!(built.redirs[0].fd, parsed.redirs[0].fd);
+        assert_eq!(
+            built.redirs[0].body.operand().units,
+            parsed.redirs[0].body.operand().units,
+        );
+    }
+
+    #[test]
+    fn and_or_lists() {
+        let command = |name| SimpleCommand::new([Word::from_literal(name)]);
+        let list = ListBuilder::new()
+            .pipeline(command("a"))
+            .and_then(command("b"))
+            .or_else(command("c"))
+            .pipeline(command("d"))
+            .background()
+            .pipeline(command("e"))
+            .build();
+
+        assert_eq!(list.to_string(), "a && b || c; d& e");
+        assert_eq!(list.0[0].async_flag, None);
+        assert_eq!(list.0[1].async_flag, Some(Location::dummy("&")));
+        assert_eq!(list.0[2].async_flag, None);
+
+        let reparsed: List = list.to_string().parse().unwrap();
+        assert_eq!(reparsed.to_string(), list.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "no and-or list")]
+    fn and_then_without_pipeline() {
+        let _ = ListBuilder::new().and_then(SimpleCommand::new([Word::from_literal("a")]));
+    }
+}
@@ -231,6 +231,34 @@ impl Unquote for Trim {
     }
 }
 
+impl Unquote for Substring {
+    fn write_unquoted<W: fmt::Write>(&self, w: &mut W) -> UnquoteResult {
+        let mut offset = String::new();
+        let mut quoted = self.offset.write_unquoted(&mut offset)?;
+        if super::impl_display::needs_space_before_offset(&offset) {
+            w.write_str(": ")?;
+        } else {
+            w.write_char(':')?;
+        }
+        w.write_str(&offset)?;
+        if let Some(length) = &self.length {
+            w.write_char(':')?;
+            quoted |= length.write_unquoted(w)?;
+        }
+        Ok(quoted)
+    }
+}
+
+impl Unquote for Subst {
+    fn write_unquoted<W: fmt::Write>(&self, w: &mut W) -> UnquoteResult {
+        write!(w, "{}", self.count)?;
+        let pattern_quoted = self.pattern.write_unquoted(w)?;
+        w.write_char('/')?;
+        let replacement_quoted = self.replacement.write_unquoted(w)?;
+        Ok(pattern_quoted || replacement_quoted)
+    }
+}
+
 impl Unquote for BracedParam {
     fn write_unquoted<W: fmt::Write>(&self, w: &mut W) -> UnquoteResult {
         use Modifier::*;
@@ -255,6 +283,18 @@ impl Unquote for BracedParam {
                 w.write_char('}')?;
                 Ok(quoted)
             }
+            Substring(ref substring) => {
+                write!(w, "${{{}", self.param)?;
+                let quoted = substring.write_unquoted(w)?;
+                w.write_char('}')?;
+                Ok(quoted)
+            }
+            Subst(ref subst) => {
+                write!(w, "${{{}", self.param)?;
+                let quoted = subst.write_unquoted(w)?;
+                w.write_char('}')?;
+                Ok(quoted)
+            }
         }
     }
 }
@@ -519,6 +559,127 @@ impl TryFrom<Word> for Assign {
     }
 }
 
+impl Word {
+    /// Creates a word that expands to the given string.
+    ///
+    /// The word is quoted as needed so that it expands to a single field
+    /// having the same value as the argument. The quoting follows the rules of
+    /// the [`yash_quote`] crate, except that a word that would be recognized as
+    /// a [keyword](Keyword) is also single-quoted so that it is not mistaken
+    /// for a reserved word when used as a command name.
+    ///
+    /// The location of the returned word is a [dummy](Location::dummy)
+    /// location containing the [displayed](std::fmt::Display) word, so the
+    /// result equals the word parsed from its own `Display` output.
+    ///
+    /// ```
+    /// # use yash_syntax::syntax::Word;
+    /// let word = Word::from_literal("foo bar");
+    /// assert_eq!(word.to_string(), "'foo bar'");
+    /// assert_eq!(word, "'foo bar'".parse().unwrap());
+    /// ```
+    #[must_use]
+    pub fn from_literal(value: &str) -> Word {
+        let quoted = yash_quote::quoted(value);
+        let units = if quoted.needs_quoting() || value.parse::<Keyword>().is_ok() {
+            if !value.contains('\'') {
+                vec![SingleQuote(value.to_owned())]
+            } else {
+                let units = value.chars().map(|c| match c {
+                    '"' | '`' | '$' | '\\' => Backslashed(c),
+                    _ => Literal(c),
+                });
+                vec![DoubleQuote(Text(units.collect()))]
+            }
+        } else {
+            value.chars().map(|c| Unquoted(Literal(c))).collect()
+        };
+        let code = units.iter().map(ToString::to_string).collect::<String>();
+        let location = Location::dummy(code);
+        Word { units, location }
+    }
+}
+
+impl Redir {
+    /// Creates a `>` redirection that opens the given file for output.
+    ///
+    /// If `fd` is `None`, the redirection applies to the standard output.
+    /// Use [`Word::from_literal`] to create a `path` from a string.
+    #[must_use]
+    pub fn file_out(fd: Option<Fd>, path: Word) -> Redir {
+        let operator = RedirOp::FileOut;
+        let body = RedirBody::Normal {
+            operator,
+            operand: path,
+        };
+        Redir { fd, body }
+    }
+}
+
+impl SimpleCommand {
+    /// Creates a simple command with the given words.
+    ///
+    /// The returned command has no assignments or redirections. The first word
+    /// is the command name and the rest are the arguments. All the words are
+    /// expanded in [`ExpansionMode::Multiple`]. Note that this is different
+    /// from what the parser produces for assignment-like arguments of a
+    /// [declaration utility](crate::decl_util).
+    #[must_use]
+    pub fn new<I: IntoIterator<Item = Word>>(words: I) -> SimpleCommand {
+        SimpleCommand {
+            assigns: vec![],
+            words: words
+                .into_iter()
+                .map(|word| (word, ExpansionMode::Multiple))
+                .collect(),
+            redirs: Rc::new(vec![]),
+        }
+    }
+}
+
+impl From<SimpleCommand> for Command {
+    fn from(command: SimpleCommand) -> Command {
+        Command::Simple(command)
+    }
+}
+
+impl From<FullCompoundCommand> for Command {
+    fn from(command: FullCompoundCommand) -> Command {
+        Command::Compound(command)
+    }
+}
+
+impl From<FunctionDefinition> for Command {
+    fn from(definition: FunctionDefinition) -> Command {
+        Command::Function(definition)
+    }
+}
+
+/// Constructs a non-negated, untimed pipeline containing the single command.
+impl<T: Into<Command>> From<T> for Pipeline {
+    fn from(command: T) -> Pipeline {
+        Pipeline {
+            commands: vec![Rc::new(command.into())],
+            negation: false,
+            time: None,
+        }
+    }
+}
+
+/// Constructs a non-negated, untimed pipeline from the commands.
+impl<T: Into<Command>> FromIterator<T> for Pipeline {
+    fn from_iter<I: IntoIterator<Item = T>>(commands: I) -> Pipeline {
+        Pipeline {
+            commands: commands
+                .into_iter()
+                .map(|command| Rc::new(command.into()))
+                .collect(),
+            negation: false,
+            time: None,
+        }
+    }
+}
+
 impl From<RawFd> for Fd {
     fn from(raw_fd: RawFd) -> Fd {
         Fd(raw_fd)
@@ -707,6 +868,38 @@ mod tests {
         assert_eq!(is_quoted, false);
     }
 
+    #[test]
+    fn substring_unquote() {
+        let substring = Substring {
+            offset: "-1".parse().unwrap(),
+            length: Some("2".parse().unwrap()),
+        };
+        let (unquoted, is_quoted) = substring.unquote();
+        assert_eq!(unquoted, ": -1:2");
+        assert_eq!(is_quoted, false);
+    }
+
+    #[test]
+    fn subst_unquote() {
+        let subst = Subst {
+            count: SubstCount::First,
+            pattern: "'a'".parse().unwrap(),
+            replacement: "b".parse().unwrap(),
+        };
+        let (unquoted, is_quoted) = subst.unquote();
+        assert_eq!(unquoted, "/a/b");
+        assert_eq!(is_quoted, true);
+
+        let subst = Subst {
+            count: SubstCount::All,
+            pattern: "a".parse().unwrap(),
+            replacement: "".parse().unwrap(),
+        };
+        let (unquoted, is_quoted) = subst.unquote();
+        assert_eq!(unquoted, "//a/");
+        assert_eq!(is_quoted, false);
+    }
+
     #[test]
     fn braced_param_unquote() {
         let param = BracedParam {
@@ -984,6 +1177,79 @@ mod tests {
         });
     }
 
+    #[test]
+    fn word_from_literal_without_quoting() {
+        let word = Word::from_literal("foo");
+        assert_eq!(
+            word.units,
+            [
+                Unquoted(Literal('f')),
+                Unquoted(Literal('o')),
+                Unquoted(Literal('o'))
+            ]
+        );
+        assert_eq!(word, Word::from_str("foo").unwrap());
+    }
+
+    #[test]
+    fn word_from_literal_single_quoted() {
+        for value in ["", "a b", "$x", "~", "a=b", "*", "if", "!", "{"] {
+            let word = Word::from_literal(value);
+            assert_eq!(word.units, [SingleQuote(value.to_string())]);
+            assert_eq!(word, Word::from_str(&word.to_string()).unwrap());
+        }
+    }
+
+    #[test]
+    fn word_from_literal_double_quoted() {
+        let word = Word::from_literal(r#"'"`$\"#);
+        assert_eq!(word.to_string(), r#""'\"\`\$\\""#);
+        assert_eq!(word, Word::from_str(&word.to_string()).unwrap());
+        assert_eq!(word.unquote(), (r#"'"`$\"#.to_string(), true));
+    }
+
+    #[test]
+    fn redir_file_out() {
+        let redir = Redir::file_out(None, Word::from_literal("out"));
+        assert_eq!(redir.to_string(), ">out");
+        assert_eq!(redir.fd_or_default(), Fd::STDOUT);
+
+        let redir = Redir::file_out(Some(Fd(3)), Word::from_literal("a b"));
+        assert_eq!(redir.to_string(), "3>'a b'");
+        assert_eq!(redir.fd_or_default(), Fd(3));
+    }
+
+    #[test]
+    fn simple_command_new() {
+        let command = SimpleCommand::new([Word::from_literal("echo"), Word::from_literal("a=b")]);
+        assert_eq!(command.assigns, []);
+        assert_eq!(command.words.len(), 2);
+        assert_eq!(command.words[0].1, ExpansionMode::Multiple);
+        assert_eq!(command.words[1].1, ExpansionMode::Multiple);
+        assert_eq!(*command.redirs, []);
+        assert_eq!(command.to_string(), "echo 'a=b'");
+    }
+
+    #[test]
+    fn simple_command_new_with_keyword_name() {
+        let command = SimpleCommand::new([Word::from_literal("while")]);
+        let parsed = SimpleCommand::from_str(&command.to_string()).unwrap();
+        assert_eq!(parsed.words[0].0, command.words[0].0);
+    }
+
+    #[test]
+    fn pipeline_from_commands() {
+        let pipeline: Pipeline = SimpleCommand::new([Word::from_literal("a")]).into();
+        assert_eq!(pipeline.to_string(), "a");
+
+        let pipeline: Pipeline = ["a", "b", "c"]
+            .into_iter()
+            .map(|name| SimpleCommand::new([Word::from_literal(name)]))
+            .collect();
+        assert_eq!(pipeline.to_string(), "a | b | c");
+        assert!(!pipeline.negation);
+    }
+
     #[test]
     fn redir_op_conversions() {
         use RedirOp::*;
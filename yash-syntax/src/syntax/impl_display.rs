@@ -82,6 +82,46 @@ impl fmt::Display for Trim {
     }
 }
 
+/// Tests whether a space is needed between the colon and the offset of a
+/// substring.
+///
+/// Without the space, an offset starting with a character such as `-` would
+/// be parsed as part of a switch or trim.
+pub(super) fn needs_space_before_offset(offset: &str) -> bool {
+    offset.starts_with(['+', '-', '=', '?', '#', '%'])
+}
+
+impl fmt::Display for Substring {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let offset = self.offset.to_string();
+        if needs_space_before_offset(&offset) {
+            f.write_str(": ")?;
+        } else {
+            f.write_char(':')?;
+        }
+        f.write_str(&offset)?;
+        if let Some(length) = &self.length {
+            write!(f, ":{length}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SubstCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubstCount::First => f.write_char('/'),
+            SubstCount::All => f.write_str("//"),
+        }
+    }
+}
+
+impl fmt::Display for Subst {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}/{}", self.count, self.pattern, self.replacement)
+    }
+}
+
 impl fmt::Display for BracedParam {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use Modifier::*;
@@ -90,6 +130,8 @@ impl fmt::Display for BracedParam {
             Length => write!(f, "${{#{}}}", self.param),
             Switch(ref switch) => write!(f, "${{{}{}}}", self.param, switch),
             Trim(ref trim) => write!(f, "${{{}{}}}", self.param, trim),
+            Substring(ref substring) => write!(f, "${{{}{}}}", self.param, substring),
+            Subst(ref subst) => write!(f, "${{{}{}}}", self.param, subst),
         }
     }
 }
@@ -161,6 +203,9 @@ impl fmt::Display for EscapedString {
 impl fmt::Display for WordUnit {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            // An unquoted literal backslash only occurs at the end of input.
+            // Printing it as is would make it escape whatever follows it.
+            Unquoted(Literal('\\')) => f.write_str("\\\\"),
             Unquoted(dq) => dq.fmt(f),
             SingleQuote(s) => write!(f, "'{s}'"),
             DoubleQuote(content) => write!(f, "\"{content}\""),
@@ -353,8 +398,20 @@ impl fmt::Display for Command {
     }
 }
 
+impl fmt::Display for TimeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeFormat::Default => write!(f, "time"),
+            TimeFormat::Posix => write!(f, "time -p"),
+        }
+    }
+}
+
 impl fmt::Display for Pipeline {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(time) = self.time {
+            write!(f, "{time} ")?;
+        }
         if self.negation {
             write!(f, "! ")?;
         }
@@ -420,6 +477,93 @@ impl fmt::Display for List {
     }
 }
 
+/// Collects here-documents in the order their contents appear in the source
+#[derive(Default)]
+struct HereDocCollector<'a>(Vec<&'a HereDoc>);
+
+impl<'a> visit::Visitor<'a> for HereDocCollector<'a> {
+    fn visit_here_doc(&mut self, here_doc: &'a HereDoc) {
+        self.0.push(here_doc);
+    }
+}
+
+impl HereDoc {
+    /// Writes the content and the delimiter line of the here-document.
+    ///
+    /// If the content has not been filled yet, it is treated as empty.
+    fn write_body(&self, out: &mut String) {
+        if let Some(content) = self.content.get() {
+            write!(out, "{content}").unwrap();
+        }
+        let (delimiter, _) = self.delimiter.unquote();
+        out.push_str(&delimiter);
+        out.push('\n');
+    }
+
+    /// Converts the here-document to a multi-line source code string.
+    ///
+    /// Unlike the [`Display`](fmt::Display) implementation, which only
+    /// produces the here-document operator and delimiter, the result of this
+    /// method contains a newline after the operator, followed by the content
+    /// and the delimiter line. The result is terminated by a newline.
+    ///
+    /// ```
+    /// # use yash_syntax::syntax::HereDoc;
+    /// let here_doc = HereDoc {
+    ///     delimiter: "'END'".parse().unwrap(),
+    ///     remove_tabs: false,
+    ///     content: "foo\n".parse::<yash_syntax::syntax::Text>().unwrap().into(),
+    /// };
+    /// assert_eq!(here_doc.to_source_string(), "<<'END'\nfoo\nEND\n");
+    /// ```
+    #[must_use]
+    pub fn to_source_string(&self) -> String {
+        let mut result = format!("{self}\n");
+        self.write_body(&mut result);
+        result
+    }
+}
+
+impl Item {
+    /// Converts the item to a multi-line source code string.
+    ///
+    /// The result starts with the item formatted by the
+    /// [`Display`](fmt::Display) implementation, followed by a newline and the
+    /// contents of all here-documents contained in the item. The result is
+    /// terminated by a newline. Parsing the result yields an item that is
+    /// equal to `self` except for source locations.
+    #[must_use]
+    pub fn to_source_string(&self) -> String {
+        let mut result = format!("{self}\n");
+        let mut here_docs = HereDocCollector::default();
+        visit::Visitor::visit_item(&mut here_docs, self);
+        for here_doc in here_docs.0 {
+            here_doc.write_body(&mut result);
+        }
+        result
+    }
+}
+
+impl List {
+    /// Converts the list to a multi-line source code string.
+    ///
+    /// Each item of the list is formatted by [`Item::to_source_string`] and
+    /// the results are concatenated, so every item starts on a new line and
+    /// is followed by the contents of its here-documents. Parsing the result
+    /// yields a list that is equal to `self` except for source locations.
+    ///
+    /// ```
+    /// # use yash_syntax::syntax::List;
+    /// let list: List = "cat <<END; echo done\nfoo\nEND\n".parse().unwrap();
+    /// assert_eq!(list.to_string(), "cat <<END; echo done");
+    /// assert_eq!(list.to_source_string(), "cat <<END\nfoo\nEND\necho done\n");
+    /// ```
+    #[must_use]
+    pub fn to_source_string(&self) -> String {
+        self.0.iter().map(Item::to_source_string).collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -486,6 +630,44 @@ mod tests {
         assert_eq!(trim.to_string(), "%%*");
     }
 
+    #[test]
+    fn substring_display() {
+        let substring = Substring {
+            offset: "1".parse().unwrap(),
+            length: None,
+        };
+        assert_eq!(substring.to_string(), ":1");
+
+        let substring = Substring {
+            offset: "-1".parse().unwrap(),
+            length: Some("$n".parse().unwrap()),
+        };
+        assert_eq!(substring.to_string(), ": -1:$n");
+
+        let substring = Substring {
+            offset: "".parse().unwrap(),
+            length: Some("".parse().unwrap()),
+        };
+        assert_eq!(substring.to_string(), "::");
+    }
+
+    #[test]
+    fn subst_display() {
+        let subst = Subst {
+            count: SubstCount::First,
+            pattern: "a*".parse().unwrap(),
+            replacement: "b".parse().unwrap(),
+        };
+        assert_eq!(subst.to_string(), "/a*/b");
+
+        let subst = Subst {
+            count: SubstCount::All,
+            pattern: "x".parse().unwrap(),
+            replacement: "".parse().unwrap(),
+        };
+        assert_eq!(subst.to_string(), "//x/");
+    }
+
     #[test]
     fn braced_param_display() {
         let param = BracedParam {
@@ -522,6 +704,27 @@ mod tests {
             ..param
         };
         assert_eq!(param.to_string(), "${foo%baz' 'bar}");
+
+        let substring = Substring {
+            offset: "1".parse().unwrap(),
+            length: Some("2".parse().unwrap()),
+        };
+        let param = BracedParam {
+            modifier: Modifier::Substring(substring),
+            ..param
+        };
+        assert_eq!(param.to_string(), "${foo:1:2}");
+
+        let subst = Subst {
+            count: SubstCount::All,
+            pattern: "a".parse().unwrap(),
+            replacement: "b".parse().unwrap(),
+        };
+        let param = BracedParam {
+            modifier: Modifier::Subst(subst),
+            ..param
+        };
+        assert_eq!(param.to_string(), "${foo//a/b}");
     }
 
     #[test]
@@ -602,6 +805,8 @@ mod tests {
         assert_eq!(unquoted.to_string(), "A");
         let unquoted = Unquoted(Backslashed('B'));
         assert_eq!(unquoted.to_string(), "\\B");
+        let unquoted = Unquoted(Literal('\\'));
+        assert_eq!(unquoted.to_string(), "\\\\");
 
         let single_quote = SingleQuote("".to_string());
         assert_eq!(single_quote.to_string(), "''");
@@ -976,6 +1181,7 @@ mod tests {
         let mut p = Pipeline {
             commands: vec![Rc::new("first".parse::<Command>().unwrap())],
             negation: false,
+            time: None,
         };
         assert_eq!(p.to_string(), "first");
 
@@ -988,6 +1194,15 @@ mod tests {
         p.commands.push(Rc::new("third".parse().unwrap()));
         p.negation = false;
         assert_eq!(p.to_string(), "first | second | third");
+
+        p.time = Some(TimeFormat::Default);
+        assert_eq!(p.to_string(), "time first | second | third");
+
+        p.negation = true;
+        assert_eq!(p.to_string(), "time ! first | second | third");
+
+        p.time = Some(TimeFormat::Posix);
+        assert_eq!(p.to_string(), "time -p ! first | second | third");
     }
 
     #[test]
@@ -1061,4 +1276,81 @@ mod tests {
         list.0.push(item);
         assert_eq!(format!("{list:#}"), "first; second& third;");
     }
+
+    fn assert_source_round_trip(list: &List) {
+        let source = list.to_source_string();
+        let reparsed: List = source.parse().unwrap();
+        assert_eq!(reparsed.to_string(), list.to_string());
+        assert_eq!(reparsed.to_source_string(), source);
+    }
+
+    #[test]
+    fn here_doc_to_source_string() {
+        let heredoc = HereDoc {
+            delimiter: Word::from_str("END").unwrap(),
+            remove_tabs: false,
+            content: Text::from_str("foo\n$bar\n").unwrap().into(),
+        };
+        assert_eq!(heredoc.to_source_string(), "<<END\nfoo\n$bar\nEND\n");
+
+        let heredoc = HereDoc {
+            delimiter: Word::from_str("-\\E\"N\"D").unwrap(),
+            remove_tabs: true,
+            content: Text::from_str("").unwrap().into(),
+        };
+        assert_eq!(heredoc.to_source_string(), "<<- -\\E\"N\"D\n-END\n");
+
+        let heredoc = HereDoc {
+            delimiter: Word::from_str("X").unwrap(),
+            remove_tabs: false,
+            content: OnceCell::new(),
+        };
+        assert_eq!(heredoc.to_source_string(), "<<X\nX\n");
+    }
+
+    #[test]
+    fn item_to_source_string() {
+        let list: List = "cat <<A <<-'B' & \nfoo\nA\n\tbar\nB\n".parse().unwrap();
+        let item = &list.0[0];
+        assert_eq!(item.to_source_string(), "cat <<A <<-'B'&\nfoo\nA\nbar\nB\n");
+    }
+
+    #[test]
+    fn list_to_source_string_without_here_docs() {
+        let list: List = "".parse().unwrap();
+        assert_eq!(list.to_source_string(), "");
+
+        let list: List = "foo; bar& baz".parse().unwrap();
+        assert_eq!(list.to_source_string(), "foo\nbar&\nbaz\n");
+        assert_source_round_trip(&list);
+    }
+
+    #[test]
+    fn list_to_source_string_with_here_docs() {
+        let list: List = "cat <<END; cat <<-\\EOF\n$x \\$\nEND\n\t\t$y\nEOF\n"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            list.to_source_string(),
+            "cat <<END\n$x \\$\nEND\ncat <<-\\EOF\n$y\nEOF\n"
+        );
+        assert_source_round_trip(&list);
+    }
+
+    #[test]
+    fn list_to_source_string_with_nested_here_docs() {
+        let list: List = "{ cat <<A; } >/dev/null && f() (cat <<B) <<C\na\nA\nb\nB\nc\nC\n"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            list.to_source_string(),
+            "{ cat <<A; } >/dev/null && f() (cat <<B) <<C\na\nA\nb\nB\nc\nC\n"
+        );
+        assert_source_round_trip(&list);
+
+        let list: List = "if cat <<A; then case x in (x) cat <<B;; esac; fi\na\nA\nb\nB\n"
+            .parse()
+            .unwrap();
+        assert_source_round_trip(&list);
+    }
 }
@@ -0,0 +1,429 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Syntax tree traversal
+//!
+//! This module defines the [`Visitor`] trait, which allows you to traverse an
+//! abstract syntax tree without pattern-matching every node type by hand.
+//!
+//! Each `visit_*` method of the trait has a default implementation that calls
+//! the corresponding `walk_*` function, which in turn calls the visitor's
+//! methods for the child nodes. To process a certain kind of node, override
+//! the method for the node. If you still want to visit the children of the
+//! node, call the `walk_*` function from your implementation.
+//!
+//! ```
+//! use yash_syntax::syntax::{List, SimpleCommand};
+//! use yash_syntax::syntax::visit::{Visitor, walk_simple_command};
+//!
+//! #[derive(Default)]
+//! struct CommandCounter(usize);
+//!
+//! impl Visitor<'_> for CommandCounter {
+//!     fn visit_simple_command(&mut self, command: &SimpleCommand) {
+//!         self.0 += 1;
+//!         walk_simple_command(self, command);
+//!     }
+//! }
+//!
+//! let list: List = "foo | bar && { baz; }".parse().unwrap();
+//! let mut counter = CommandCounter::default();
+//! counter.visit_list(&list);
+//! assert_eq!(counter.0, 3);
+//! ```
+//!
+//! Note that the content of a command substitution is not parsed until it is
+//! executed, so commands in the substitution are not visited.
+
+use super::*;
+
+/// Syntax tree visitor
+///
+/// See the [module-level documentation](self) for details.
+///
+/// The lifetime parameter `'ast` is the lifetime of the syntax tree being
+/// visited. It allows the visitor to retain references to the nodes.
+pub trait Visitor<'ast> {
+    /// Visits a list.
+    fn visit_list(&mut self, list: &'ast List) {
+        walk_list(self, list)
+    }
+
+    /// Visits an item of a list.
+    fn visit_item(&mut self, item: &'ast Item) {
+        walk_item(self, item)
+    }
+
+    /// Visits an and-or list.
+    fn visit_and_or_list(&mut self, and_or: &'ast AndOrList) {
+        walk_and_or_list(self, and_or)
+    }
+
+    /// Visits a pipeline.
+    fn visit_pipeline(&mut self, pipeline: &'ast Pipeline) {
+        walk_pipeline(self, pipeline)
+    }
+
+    /// Visits a command.
+    fn visit_command(&mut self, command: &'ast Command) {
+        walk_command(self, command)
+    }
+
+    /// Visits a simple command.
+    fn visit_simple_command(&mut self, command: &'ast SimpleCommand) {
+        walk_simple_command(self, command)
+    }
+
+    /// Visits a compound command with its redirections.
+    fn visit_full_compound_command(&mut self, command: &'ast FullCompoundCommand) {
+        walk_full_compound_command(self, command)
+    }
+
+    /// Visits a compound command.
+    fn visit_compound_command(&mut self, command: &'ast CompoundCommand) {
+        walk_compound_command(self, command)
+    }
+
+    /// Visits a branch of a case command.
+    fn visit_case_item(&mut self, item: &'ast CaseItem) {
+        walk_case_item(self, item)
+    }
+
+    /// Visits a function definition command.
+    fn visit_function_definition(&mut self, definition: &'ast FunctionDefinition) {
+        walk_function_definition(self, definition)
+    }
+
+    /// Visits an assignment.
+    fn visit_assign(&mut self, assign: &'ast Assign) {
+        walk_assign(self, assign)
+    }
+
+    /// Visits a redirection.
+    fn visit_redir(&mut self, redir: &'ast Redir) {
+        walk_redir(self, redir)
+    }
+
+    /// Visits a here-document.
+    fn visit_here_doc(&mut self, here_doc: &'ast HereDoc) {
+        walk_here_doc(self, here_doc)
+    }
+
+    /// Visits a word.
+    fn visit_word(&mut self, word: &'ast Word) {
+        walk_word(self, word)
+    }
+
+    /// Visits a word unit.
+    fn visit_word_unit(&mut self, unit: &'ast WordUnit) {
+        walk_word_unit(self, unit)
+    }
+
+    /// Visits a text.
+    fn visit_text(&mut self, text: &'ast Text) {
+        walk_text(self, text)
+    }
+
+    /// Visits a text unit.
+    fn visit_text_unit(&mut self, unit: &'ast TextUnit) {
+        walk_text_unit(self, unit)
+    }
+}
+
+/// Visits the items of a list.
+pub fn walk_list<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, list: &'ast List) {
+    for item in &list.0 {
+        visitor.visit_item(item);
+    }
+}
+
+/// Visits the and-or list of an item.
+pub fn walk_item<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, item: &'ast Item) {
+    visitor.visit_and_or_list(&item.and_or);
+}
+
+/// Visits the pipelines of an and-or list.
+pub fn walk_and_or_list<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, and_or: &'ast AndOrList) {
+    visitor.visit_pipeline(&and_or.first);
+    for (_, pipeline) in &and_or.rest {
+        visitor.visit_pipeline(pipeline);
+    }
+}
+
+/// Visits the commands of a pipeline.
+pub fn walk_pipeline<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, pipeline: &'ast Pipeline) {
+    for command in &pipeline.commands {
+        visitor.visit_command(command);
+    }
+}
+
+/// Visits the content of a command.
+pub fn walk_command<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, command: &'ast Command) {
+    match command {
+        Command::Simple(command) => visitor.visit_simple_command(command),
+        Command::Compound(command) => visitor.visit_full_compound_command(command),
+        Command::Function(definition) => visitor.visit_function_definition(definition),
+    }
+}
+
+/// Visits the assignments, words, and redirections of a simple command.
+pub fn walk_simple_command<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    command: &'ast SimpleCommand,
+) {
+    for assign in &command.assigns {
+        visitor.visit_assign(assign);
+    }
+    for (word, _) in &command.words {
+        visitor.visit_word(word);
+    }
+    for redir in command.redirs.iter() {
+        visitor.visit_redir(redir);
+    }
+}
+
+/// Visits the compound command and redirections of a full compound command.
+pub fn walk_full_compound_command<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    command: &'ast FullCompoundCommand,
+) {
+    visitor.visit_compound_command(&command.command);
+    for redir in &command.redirs {
+        visitor.visit_redir(redir);
+    }
+}
+
+/// Visits the words and lists contained in a compound command.
+pub fn walk_compound_command<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    command: &'ast CompoundCommand,
+) {
+    use CompoundCommand::*;
+    match command {
+        Grouping(list) => visitor.visit_list(list),
+        Subshell { body, .. } => visitor.visit_list(body),
+        For { name, values, body } => {
+            visitor.visit_word(name);
+            for value in values.iter().flatten() {
+                visitor.visit_word(value);
+            }
+            visitor.visit_list(body);
+        }
+        While { condition, body } | Until { condition, body } => {
+            visitor.visit_list(condition);
+            visitor.visit_list(body);
+        }
+        If {
+            condition,
+            body,
+            elifs,
+            r#else,
+        } => {
+            visitor.visit_list(condition);
+            visitor.visit_list(body);
+            for elif in elifs {
+                visitor.visit_list(&elif.condition);
+                visitor.visit_list(&elif.body);
+            }
+            if let Some(r#else) = r#else {
+                visitor.visit_list(r#else);
+            }
+        }
+        Case { subject, items } => {
+            visitor.visit_word(subject);
+            for item in items {
+                visitor.visit_case_item(item);
+            }
+        }
+    }
+}
+
+/// Visits the patterns and body of a case item.
+pub fn walk_case_item<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, item: &'ast CaseItem) {
+    for pattern in &item.patterns {
+        visitor.visit_word(pattern);
+    }
+    visitor.visit_list(&item.body);
+}
+
+/// Visits the name and body of a function definition.
+pub fn walk_function_definition<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    definition: &'ast FunctionDefinition,
+) {
+    visitor.visit_word(&definition.name);
+    visitor.visit_full_compound_command(&definition.body);
+}
+
+/// Visits the value of an assignment.
+pub fn walk_assign<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, assign: &'ast Assign) {
+    match &assign.value {
+        Scalar(word) => visitor.visit_word(word),
+        Array(words) => {
+            for word in words {
+                visitor.visit_word(word);
+            }
+        }
+    }
+}
+
+/// Visits the operand or here-document of a redirection.
+pub fn walk_redir<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, redir: &'ast Redir) {
+    match &redir.body {
+        RedirBody::Normal { operand, .. } => visitor.visit_word(operand),
+        RedirBody::HereDoc(here_doc) => visitor.visit_here_doc(here_doc),
+    }
+}
+
+/// Visits the delimiter and content of a here-document.
+///
+/// The content is not visited if it has not been filled yet.
+pub fn walk_here_doc<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, here_doc: &'ast HereDoc) {
+    visitor.visit_word(&here_doc.delimiter);
+    if let Some(content) = here_doc.content.get() {
+        visitor.visit_text(content);
+    }
+}
+
+/// Visits the units of a word.
+pub fn walk_word<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, word: &'ast Word) {
+    for unit in &word.units {
+        visitor.visit_word_unit(unit);
+    }
+}
+
+/// Visits the text units contained in a word unit.
+pub fn walk_word_unit<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, unit: &'ast WordUnit) {
+    match unit {
+        Unquoted(unit) => visitor.visit_text_unit(unit),
+        DoubleQuote(text) => visitor.visit_text(text),
+        SingleQuote(_) | DollarSingleQuote(_) | Tilde(_) => {}
+    }
+}
+
+/// Visits the units of a text.
+pub fn walk_text<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, text: &'ast Text) {
+    for unit in &text.0 {
+        visitor.visit_text_unit(unit);
+    }
+}
+
+/// Visits the words and texts contained in a text unit.
+///
+/// The words and texts in a parameter expansion modifier and the expression of
+/// an arithmetic expansion are visited.
+pub fn walk_text_unit<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, unit: &'ast TextUnit) {
+    match unit {
+        BracedParam(param) => match &param.modifier {
+            Modifier::None | Modifier::Length => {}
+            Modifier::Switch(switch) => visitor.visit_word(&switch.word),
+            Modifier::Trim(trim) => visitor.visit_word(&trim.pattern),
+            Modifier::Substring(substring) => {
+                visitor.visit_text(&substring.offset);
+                if let Some(length) = &substring.length {
+                    visitor.visit_text(length);
+                }
+            }
+            Modifier::Subst(subst) => {
+                visitor.visit_word(&subst.pattern);
+                visitor.visit_word(&subst.replacement);
+            }
+        },
+        Arith { content, .. } => visitor.visit_text(content),
+        Literal(_) | Backslashed(_) | RawParam { .. } | CommandSubst { .. } | Backquote { .. } => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct CommandNames<'ast>(Vec<&'ast Word>);
+
+    impl<'ast> Visitor<'ast> for CommandNames<'ast> {
+        fn visit_simple_command(&mut self, command: &'ast SimpleCommand) {
+            if let Some((name, _)) = command.words.first() {
+                self.0.push(name);
+            }
+            walk_simple_command(self, command);
+        }
+    }
+
+    #[test]
+    fn collecting_command_names() {
+        let list: List = "a=1 foo | bar && ! baz; \
+            f() { if qux; then for i in 1; do :; done; elif x; then y; else z; fi; } >/dev/null; \
+            while w; do case $i in (p) q ;; esac; done & (s; until t; do u; done)"
+            .parse()
+            .unwrap();
+        let mut names = CommandNames::default();
+        names.visit_list(&list);
+        let names = names.0.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            ["foo", "bar", "baz", "qux", ":", "x", "y", "z", "w", "q", "s", "t", "u"]
+        );
+    }
+
+    #[derive(Default)]
+    struct Counts {
+        words: usize,
+        assigns: usize,
+        redirs: usize,
+        here_docs: usize,
+        text_units: usize,
+    }
+
+    impl Visitor<'_> for Counts {
+        fn visit_word(&mut self, word: &Word) {
+            self.words += 1;
+            walk_word(self, word);
+        }
+        fn visit_assign(&mut self, assign: &Assign) {
+            self.assigns += 1;
+            walk_assign(self, assign);
+        }
+        fn visit_redir(&mut self, redir: &Redir) {
+            self.redirs += 1;
+            walk_redir(self, redir);
+        }
+        fn visit_here_doc(&mut self, here_doc: &HereDoc) {
+            self.here_docs += 1;
+            walk_here_doc(self, here_doc);
+        }
+        fn visit_text_unit(&mut self, unit: &TextUnit) {
+            self.text_units += 1;
+            walk_text_unit(self, unit);
+        }
+    }
+
+    #[test]
+    fn visiting_nested_words() {
+        let list: List = "a=${b:-\"$c\"} cmd <<END >\"${d#e}\"\nx$y\nEND\n"
+            .parse()
+            .unwrap();
+        let mut counts = Counts::default();
+        counts.visit_list(&list);
+        // a=..., "$c", cmd, END, "${d#e}", e
+        assert_eq!(counts.words, 6);
+        assert_eq!(counts.assigns, 1);
+        assert_eq!(counts.redirs, 2);
+        assert_eq!(counts.here_docs, 1);
+        // ${b:-...}, $c, c, m, d, E, N, D, x, $y, newline, ${d#e}, e
+        assert_eq!(counts.text_units, 13);
+    }
+}
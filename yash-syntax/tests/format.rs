@@ -0,0 +1,65 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Golden-file tests for the script formatter
+//!
+//! Each test formats `tests/format/<name>.sh` and compares the result with
+//! `tests/format/<name>.out`. The formatted script is also checked to be
+//! stable under re-formatting and to parse to the same syntax tree as the
+//! original script.
+
+use std::path::PathBuf;
+use yash_syntax::format::format_script;
+use yash_syntax::syntax::List;
+
+fn run(name: &str) {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/format");
+    let script = std::fs::read_to_string(dir.join(format!("{name}.sh"))).unwrap();
+    let expected = std::fs::read_to_string(dir.join(format!("{name}.out"))).unwrap();
+
+    let formatted = format_script(&script).unwrap();
+    assert_eq!(formatted, expected);
+    assert_eq!(format_script(&formatted).unwrap(), formatted);
+
+    let original: List = script.parse().unwrap();
+    let reparsed: List = formatted.parse().unwrap();
+    assert_eq!(reparsed.to_source_string(), original.to_source_string());
+}
+
+#[test]
+fn simple() {
+    run("simple")
+}
+
+#[test]
+fn case() {
+    run("case")
+}
+
+#[test]
+fn function() {
+    run("function")
+}
+
+#[test]
+fn loops() {
+    run("loops")
+}
+
+#[test]
+fn here_doc() {
+    run("here_doc")
+}
@@ -0,0 +1,11 @@
+case $1 in
+  (start|run)
+    echo starting
+    ;;
+  stop) echo stopping ;;
+  restart)
+    echo restarting ;&
+  -*) echo option;;&
+  *) ;;
+esac
+case x in esac
@@ -0,0 +1,11 @@
+greet() {
+    local name="$1"
+    echo "Hello, $name"
+}
+
+cleanup() {
+    rm -f "$tmp"
+} >/dev/null 2>&1
+
+sub() ( cd /; pwd )
+greet world; cleanup
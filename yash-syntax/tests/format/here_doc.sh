@@ -0,0 +1,16 @@
+cat <<END; cat <<'RAW'
+Hello, $USER
+  $(date)
+END
+$not_expanded
+RAW
+	if true; then
+		cat <<-EOF
+		indented
+		EOF
+	fi
+{ cat <<A; cat <<B; } | tr a-z A-Z
+a
+A
+b
+B
@@ -0,0 +1,14 @@
+for i in 1 2 3
+do
+    if [ "$i" -eq 2 ]; then
+        continue
+    elif [ "$i" -eq 3 ]
+    then break
+    else
+        echo "$i"
+    fi
+done
+
+while read -r line; do echo "$line"; done <input
+until false; do :; done
+for arg do shift; done
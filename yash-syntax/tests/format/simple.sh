@@ -0,0 +1,8 @@
+#!/bin/sh
+# Simple commands, pipelines and and-or lists
+a=1   b="x y"  echo   $a "$b" >out  2>&1
+foo | bar|baz ; ! qux&&quux || corge
+sleep 1 &   wait
+echo `date` $(uname) $((1+2)) ${x:-default} ${#y} ${z%%.*}
+
+exit
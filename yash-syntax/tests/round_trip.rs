@@ -0,0 +1,271 @@
+// This file is part of yash, an extended POSIX shell.
+// Copyright (C) 2025 WATANABE Yuki
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Property tests for the parse-print-parse invariant
+//!
+//! Scripts are generated by concatenating random fragments of shell syntax.
+//! Most of the generated scripts are syntactically invalid, which is fine:
+//! the parser must reject them without panicking. For each script that parses
+//! successfully, the syntax tree is printed with
+//! [`List::to_source_string`] and the result is parsed again. The second
+//! parse must succeed and yield the same syntax tree as the first one, except
+//! for the locations, and the tree must print the same as the first one.
+
+use proptest::prelude::*;
+use yash_syntax::syntax::visit::{self, Visitor};
+use yash_syntax::syntax::{
+    AndOrList, Assign, CaseItem, Command, CompoundCommand, FullCompoundCommand, FunctionDefinition,
+    HereDoc, Item, List, Pipeline, Redir, SimpleCommand, Text, TextUnit, Word, WordUnit,
+};
+
+/// Fragments the generated scripts are made of
+const FRAGMENTS: &[&str] = &[
+    " ",
+    " ",
+    " ",
+    "\t",
+    "\n",
+    "\n",
+    ";",
+    "&",
+    "|",
+    "&&",
+    "||",
+    "!",
+    "(",
+    ")",
+    "{",
+    "}",
+    "<",
+    ">",
+    ">>",
+    ">|",
+    "<>",
+    "<&",
+    ">&",
+    "<<<",
+    "2>",
+    "#",
+    "=",
+    "echo",
+    "a",
+    "x",
+    "0",
+    "-",
+    "\\",
+    "\\\n",
+    "'",
+    "\"",
+    "'a b'",
+    "\"$x\"",
+    "$",
+    "$x",
+    "$1",
+    "$@",
+    "$#",
+    "${x}",
+    "${x-y}",
+    "${x:=y}",
+    "${x#*}",
+    "${x%%y}",
+    "${#x}",
+    "${x:1}",
+    "${x: -1:2}",
+    "${x/a/b}",
+    "${x//*/}",
+    "$(",
+    "$((",
+    "))",
+    "`",
+    "$'\\n'",
+    "*",
+    "?",
+    "[a]",
+    "~",
+    "~/",
+    "if",
+    "then",
+    "elif",
+    "else",
+    "fi",
+    "for",
+    "in",
+    "do",
+    "done",
+    "while",
+    "until",
+    "case",
+    "esac",
+    ";;",
+    ";&",
+    ";|",
+    "function",
+    "f()",
+    "<<END\n",
+    "<<-END\n",
+    "<<'END'\n",
+    "<<\\END\n",
+    "<<\"E\"ND\n",
+    "END\n",
+    "\tEND\n",
+    "E\"ND\n",
+    "$x\n",
+    "\\$x\n",
+    "line\n",
+    "\\\"",
+    "\"\\\"\"",
+    "\"\\$\"",
+    "\"`\"",
+    "'\\'",
+    "$\"",
+    "`echo`",
+    "$(cat <<END\n",
+    "\\\\\n",
+    "\\`",
+];
+
+fn script() -> impl Strategy<Value = String> {
+    prop::collection::vec(prop::sample::select(FRAGMENTS), 0..24).prop_map(|f| f.concat())
+}
+
+/// Visitor that records every node of a syntax tree without its location
+///
+/// Each visited node is recorded as its type name and its
+/// [`Display`](std::fmt::Display) output, in the order of traversal. Two trees
+/// yield the same record if they have the same shape and every node prints the
+/// same, regardless of the locations of the nodes. The locations of the
+/// reparsed tree refer to the printed script rather than the original one, so
+/// they must be ignored in the comparison.
+///
+/// An unquoted text unit is recorded only as the word unit containing it.
+/// This is because an unquoted literal backslash, which only appears at the
+/// end of input, is printed as an escaped backslash, so it is not
+/// distinguished from the latter at the word unit level.
+#[derive(Default)]
+struct TreeRecorder(Vec<String>);
+
+impl TreeRecorder {
+    fn record<T: std::fmt::Display>(&mut self, type_name: &str, node: &T) {
+        self.0.push(format!("{type_name}: {node}"));
+    }
+}
+
+macro_rules! record_nodes {
+    ($($visit:ident($type:ident) => $walk:ident,)*) => {
+        $(
+            fn $visit(&mut self, node: &'ast $type) {
+                self.record(stringify!($type), node);
+                visit::$walk(self, node);
+            }
+        )*
+    };
+}
+
+impl<'ast> Visitor<'ast> for TreeRecorder {
+    record_nodes! {
+        visit_list(List) => walk_list,
+        visit_item(Item) => walk_item,
+        visit_and_or_list(AndOrList) => walk_and_or_list,
+        visit_pipeline(Pipeline) => walk_pipeline,
+        visit_command(Command) => walk_command,
+        visit_simple_command(SimpleCommand) => walk_simple_command,
+        visit_full_compound_command(FullCompoundCommand) => walk_full_compound_command,
+        visit_compound_command(CompoundCommand) => walk_compound_command,
+        visit_case_item(CaseItem) => walk_case_item,
+        visit_function_definition(FunctionDefinition) => walk_function_definition,
+        visit_assign(Assign) => walk_assign,
+        visit_redir(Redir) => walk_redir,
+        visit_here_doc(HereDoc) => walk_here_doc,
+        visit_word(Word) => walk_word,
+        visit_text(Text) => walk_text,
+        visit_text_unit(TextUnit) => walk_text_unit,
+    }
+
+    fn visit_word_unit(&mut self, unit: &'ast WordUnit) {
+        self.record("WordUnit", unit);
+        match unit {
+            WordUnit::Unquoted(unit) => visit::walk_text_unit(self, unit),
+            _ => visit::walk_word_unit(self, unit),
+        }
+    }
+}
+
+/// Returns the record of the syntax tree made by [`TreeRecorder`].
+fn tree_without_locations(list: &List) -> Vec<String> {
+    let mut recorder = TreeRecorder::default();
+    recorder.visit_list(list);
+    recorder.0
+}
+
+/// Checks the parse-print-parse invariant for the given script.
+///
+/// Returns without checking anything if the script is not valid.
+fn check_round_trip(script: &str) {
+    let Ok(list) = script.parse::<List>() else {
+        return;
+    };
+    let printed = list.to_source_string();
+    let reparsed = match printed.parse::<List>() {
+        Ok(reparsed) => reparsed,
+        Err(error) => panic!(
+            "printed script failed to parse: {error}\n\
+             original: {script:?}\nprinted: {printed:?}"
+        ),
+    };
+    assert_eq!(
+        tree_without_locations(&reparsed),
+        tree_without_locations(&list),
+        "round trip changed the syntax tree\noriginal: {script:?}\nprinted: {printed:?}"
+    );
+    let reprinted = reparsed.to_source_string();
+    assert_eq!(
+        reprinted, printed,
+        "round trip changed the printed script\noriginal: {script:?}"
+    );
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(2048))]
+
+    #[test]
+    fn parse_print_parse(script in script()) {
+        check_round_trip(&script);
+    }
+}
+
+// Regression tests for discrepancies found by the property test above
+
+#[test]
+fn backslash_at_end_of_input() {
+    check_round_trip("echo \\");
+}
+
+#[test]
+fn tilde_followed_by_backslash_at_end_of_input() {
+    check_round_trip("echo ~\\");
+}
+
+#[test]
+fn escaped_backslash_before_newline_in_backquotes() {
+    check_round_trip("echo `\\\\\n\n`");
+    check_round_trip("echo \"`\\\\\n\n`\"");
+}
+
+#[test]
+fn unclosed_braced_param_at_end_of_input() {
+    assert!("${".parse::<List>().is_err());
+    assert!("echo ${#".parse::<List>().is_err());
+}